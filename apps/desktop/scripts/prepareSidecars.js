@@ -22,6 +22,11 @@ const FFMPEG_BINARIES = {
   },
 };
 
+const DOWNLOAD_ATTEMPTS = 3;
+const RETRY_DELAY_MS = 2000;
+
+const sleep = (ms) => new Promise((resolve) => setTimeout(resolve, ms));
+
 async function getRustupTarget() {
   const { stdout } = await exec("rustup show");
   const line = stdout
@@ -38,27 +43,72 @@ async function exists(path) {
     .catch(() => false);
 }
 
+async function downloadArchive(url) {
+  const response = await fetch(url);
+  if (!response.ok)
+    throw new Error(`request failed with status ${response.status}`);
+
+  const archive = Buffer.from(await response.arrayBuffer());
+
+  // fetch doesn't notice when the connection drops before the body is complete
+  const expectedSize = Number(response.headers.get("content-length"));
+  if (expectedSize && archive.length !== expectedSize)
+    throw new Error(
+      `received ${archive.length} of ${expectedSize} bytes of the archive`
+    );
+
+  return archive;
+}
+
+async function isValidArchive(path) {
+  return await exec(`unzip -tq ${path}`)
+    .then(() => true)
+    .catch(() => false);
+}
+
+// Downloads the archive, retrying with exponential backoff until it's intact
+async function downloadFFmpeg(url, downloadPath) {
+  for (let attempt = 1; ; attempt++) {
+    try {
+      console.log(
+        `downloading ffmpeg archive (attempt ${attempt}/${DOWNLOAD_ATTEMPTS})`
+      );
+      await fs.writeFile(downloadPath, await downloadArchive(url));
+
+      if (await isValidArchive(downloadPath)) return;
+      throw new Error("downloaded archive is corrupt");
+    } catch (e) {
+      await fs.rm(downloadPath, { force: true });
+      if (attempt >= DOWNLOAD_ATTEMPTS) throw e;
+
+      const delay = RETRY_DELAY_MS * 2 ** (attempt - 1);
+      console.log(`${e.message}, retrying in ${delay / 1000}s`);
+      await sleep(delay);
+    }
+  }
+}
+
 async function main() {
   const targetTriple = process.env.TARGET_TRIPLE ?? (await getRustupTarget());
   const binaries = FFMPEG_BINARIES[targetTriple];
   if (!binaries) return;
 
   const ffmpegDownloadPath = path.join(binariesDir, "ffmpeg-download");
-  if (!(await exists(ffmpegDownloadPath))) {
-    await fs.mkdir(binariesDir, { recursive: true });
-    console.log("downloading ffmpeg archive");
-    const archive = await fetch(binaries.url)
-      .then((r) => r.blob())
-      .then((b) => b.arrayBuffer())
-      .then((a) => Buffer.from(a));
+  const ffmpegUnzippedPath = path.join(binariesDir, "ffmpeg-unzipped");
 
-    await fs.writeFile(ffmpegDownloadPath, archive);
+  // An archive left over from an interrupted run is downloaded again
+  if (
+    !(await exists(ffmpegDownloadPath)) ||
+    !(await isValidArchive(ffmpegDownloadPath))
+  ) {
+    await fs.mkdir(binariesDir, { recursive: true });
+    await fs.rm(ffmpegUnzippedPath, { recursive: true, force: true });
+    await downloadFFmpeg(binaries.url, ffmpegDownloadPath);
   }
 
-  const ffmpegUnzippedPath = path.join(binariesDir, "ffmpeg-unzipped");
-  if (!(await exists(ffmpegUnzippedPath))) {
+  if (!(await exists(path.join(ffmpegUnzippedPath, binaries.path)))) {
     console.log("extracting ffmpeg archive");
-    await exec(`unzip ${ffmpegDownloadPath} -d ${ffmpegUnzippedPath}`);
+    await exec(`unzip -o ${ffmpegDownloadPath} -d ${ffmpegUnzippedPath}`);
   }
 
   const ffmpegSidecarName = `ffmpeg-${targetTriple}`;
@@ -0,0 +1,192 @@
+use crate::{
+    export::{raw_track_paths, RAW_TRACKS_REMOVED_FILE},
+    recording_path, CommandError,
+};
+use cap_project::RecordingMeta;
+use std::{
+    fs::File,
+    io::{Read, Seek},
+    path::{Path, PathBuf},
+};
+use tauri::AppHandle;
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+const META_FILE: &str = "recording-meta.json";
+
+/// Rendered outputs can be rendered again from the bundle, so they're left out to keep it small
+const EXCLUDED_DIRS: &[&str] = &["output"];
+
+/// Zips a recording's project, with its raw tracks, cursor data and editor configuration,
+/// so it can be opened and edited on another machine
+#[tauri::command]
+#[specta::specta]
+pub async fn export_project_bundle(
+    app: AppHandle,
+    video_id: String,
+    dest: PathBuf,
+) -> Result<PathBuf, CommandError> {
+    let project_path = recording_path(&app, &video_id);
+    let meta = RecordingMeta::load_for_project(&project_path)?;
+
+    if project_path.join(RAW_TRACKS_REMOVED_FILE).exists() {
+        return Err(CommandError::InvalidInput(format!(
+            "The raw tracks of '{}' were deleted after rendering, so it can't be edited anymore",
+            meta.pretty_name
+        )));
+    }
+
+    let dest = tokio::task::spawn_blocking(move || {
+        let result = write_bundle(&project_path, &dest);
+        if result.is_err() {
+            std::fs::remove_file(&dest).ok();
+        }
+        result.map(|_| dest)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    Ok(dest)
+}
+
+/// Creates a new recording from a bundle made by `export_project_bundle`,
+/// returning the new recording's id
+#[tauri::command]
+#[specta::specta]
+pub async fn import_project_bundle(
+    app: AppHandle,
+    source: PathBuf,
+) -> Result<String, CommandError> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let project_path = recording_path(&app, &id);
+
+    tokio::task::spawn_blocking(move || {
+        let result = read_bundle(&source, &project_path);
+        if result.is_err() {
+            std::fs::remove_dir_all(&project_path).ok();
+        }
+        result
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    Ok(id)
+}
+
+fn write_bundle(project_path: &Path, dest: &Path) -> Result<(), String> {
+    let file =
+        File::create(dest).map_err(|e| format!("Failed to create {}: {e}", dest.display()))?;
+    let mut zip = ZipWriter::new(file);
+
+    add_dir(&mut zip, project_path, project_path)?;
+
+    zip.finish()
+        .map_err(|e| format!("Failed to write {}: {e}", dest.display()))?;
+    Ok(())
+}
+
+fn add_dir(zip: &mut ZipWriter<File>, root: &Path, dir: &Path) -> Result<(), String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {e}", dir.display()))?;
+
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        let name = entry_name(root, &path)?;
+
+        if path.is_dir() {
+            if dir == root && EXCLUDED_DIRS.contains(&name.as_str()) {
+                continue;
+            }
+            add_dir(zip, root, &path)?;
+            continue;
+        }
+
+        // Videos and audio are already compressed, deflating them again only takes time
+        let method = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") | Some("txt") => CompressionMethod::Deflated,
+            _ => CompressionMethod::Stored,
+        };
+        let options = SimpleFileOptions::default()
+            .compression_method(method)
+            .large_file(true);
+
+        zip.start_file(name, options).map_err(|e| e.to_string())?;
+        let mut file =
+            File::open(&path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        std::io::copy(&mut file, zip)
+            .map_err(|e| format!("Failed to add {} to the bundle: {e}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Zip entries always use forward slashes
+fn entry_name(root: &Path, path: &Path) -> Result<String, String> {
+    let relative = path.strip_prefix(root).map_err(|e| e.to_string())?;
+    Ok(relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+fn read_bundle(source: &Path, project_path: &Path) -> Result<(), String> {
+    let file =
+        File::open(source).map_err(|e| format!("Failed to open {}: {e}", source.display()))?;
+    let mut zip = ZipArchive::new(file)
+        .map_err(|e| format!("{} isn't a project bundle: {e}", source.display()))?;
+
+    validate_bundle(&mut zip)?;
+    zip.extract(project_path)
+        .map_err(|e| format!("Failed to extract {}: {e}", source.display()))?;
+
+    // The recording is a new one here, so it shouldn't point at the original's shareable link
+    let meta_path = project_path.join(META_FILE);
+    let mut meta: serde_json::Value = std::fs::read_to_string(&meta_path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+        .map_err(|e| format!("Failed to read the bundle's recording meta: {e}"))?;
+    if let Some(meta) = meta.as_object_mut() {
+        meta.remove("sharing");
+    }
+    std::fs::write(
+        &meta_path,
+        serde_json::to_string_pretty(&meta).unwrap_or_default(),
+    )
+    .map_err(|e| e.to_string())?;
+
+    let meta = RecordingMeta::load_for_project(&project_path.to_path_buf())?;
+    if let Some(missing) = raw_track_paths(&meta)
+        .into_iter()
+        .find(|path| !path.exists())
+    {
+        let name = missing.strip_prefix(project_path).unwrap_or(&missing);
+        return Err(format!(
+            "The bundle is missing {}, which its recording meta refers to",
+            name.display()
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_bundle(zip: &mut ZipArchive<impl Read + Seek>) -> Result<(), String> {
+    for i in 0..zip.len() {
+        let entry = zip.by_index(i).map_err(|e| e.to_string())?;
+        if entry.enclosed_name().is_none() {
+            return Err(format!(
+                "The bundle contains a file outside of the project: {}",
+                entry.name()
+            ));
+        }
+    }
+
+    let mut meta = String::new();
+    zip.by_name(META_FILE)
+        .map_err(|_| format!("The bundle has no {META_FILE}"))?
+        .read_to_string(&mut meta)
+        .map_err(|e| format!("Failed to read the bundle's {META_FILE}: {e}"))?;
+    serde_json::from_str::<RecordingMeta>(&meta)
+        .map_err(|e| format!("The bundle's {META_FILE} is invalid: {e}"))?;
+
+    Ok(())
+}
@@ -1,6 +1,109 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use cap_media::feeds::CameraFrameReceiver;
+use tauri::{async_runtime::JoinHandle, AppHandle, Manager};
+
+/// Inline previews don't need the camera's full frame rate
+const PREVIEW_FRAME_INTERVAL: Duration = Duration::from_millis(1000 / 15);
+const DEFAULT_PREVIEW_WIDTH: u32 = 480;
+
+/// Streams JPEG encoded camera frames to a window that renders the preview itself.
+/// Frames are shared with the camera window's websocket, so only one should be shown at a time.
+struct CameraPreview {
+    frame_rx: CameraFrameReceiver,
+    task: std::sync::Mutex<Option<JoinHandle<()>>>,
+}
+
+pub fn init(app: &AppHandle, frame_rx: CameraFrameReceiver) {
+    app.manage(CameraPreview {
+        frame_rx,
+        task: Default::default(),
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn start_camera_preview(
+    app: AppHandle,
+    frames: tauri::ipc::Channel<Vec<u8>>,
+    max_width: Option<u32>,
+) {
+    let preview = app.state::<CameraPreview>();
+    let frame_rx = preview.frame_rx.clone();
+    let max_width = max_width.unwrap_or(DEFAULT_PREVIEW_WIDTH).max(16);
+
+    let task = tauri::async_runtime::spawn(async move {
+        let mut last_sent: Option<Instant> = None;
+
+        while let Ok(frame) = frame_rx.recv_async().await {
+            if last_sent.is_some_and(|sent| sent.elapsed() < PREVIEW_FRAME_INTERVAL) {
+                continue;
+            }
+            last_sent = Some(Instant::now());
+
+            let jpeg = tauri::async_runtime::spawn_blocking(move || {
+                encode_preview_frame(frame, max_width)
+            })
+            .await
+            .ok()
+            .flatten();
+            let Some(jpeg) = jpeg else {
+                continue;
+            };
+
+            // The window that started the preview is gone
+            if frames.send(jpeg).is_err() {
+                break;
+            }
+        }
+    });
+
+    if let Some(previous) = preview.task.lock().unwrap().replace(task) {
+        previous.abort();
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn stop_camera_preview(app: AppHandle) {
+    if let Some(task) = app.state::<CameraPreview>().task.lock().unwrap().take() {
+        task.abort();
+    }
+}
+
+/// Frames are RGBA data followed by the height and width as little endian u32s
+fn encode_preview_frame(mut frame: Vec<u8>, max_width: u32) -> Option<Vec<u8>> {
+    let dimensions = frame.split_off(frame.len().checked_sub(8)?);
+    let height = u32::from_le_bytes(dimensions[0..4].try_into().ok()?);
+    let width = u32::from_le_bytes(dimensions[4..8].try_into().ok()?);
+
+    frame.truncate((width * height * 4) as usize);
+    let image = image::RgbaImage::from_raw(width, height, frame)?;
+
+    let image = if width > max_width {
+        let scaled_height = (height as f64 * max_width as f64 / width as f64).round() as u32;
+        image::imageops::resize(
+            &image,
+            max_width,
+            scaled_height.max(1),
+            image::imageops::FilterType::Triangle,
+        )
+    } else {
+        image
+    };
+
+    let mut jpeg = vec![];
+    let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, 70);
+    image::DynamicImage::ImageRgba8(image)
+        .into_rgb8()
+        .write_with_encoder(encoder)
+        .ok()?;
+
+    Some(jpeg)
+}
 
 // TODO: Possibly replace this with ffmpeg's network outputs in the pipeline somehow?
 pub async fn create_camera_ws(frame_rx: CameraFrameReceiver) -> u16 {
@@ -25,7 +128,7 @@ pub async fn create_camera_ws(frame_rx: CameraFrameReceiver) -> u16 {
 
     async fn handle_socket(mut socket: WebSocket, state: RouterState) {
         let camera_rx = state.lock().await;
-        println!("socket connection established");
+        tracing::info!("socket connection established");
         tracing::info!("Socket connection established");
         let now = std::time::Instant::now();
 
@@ -54,7 +157,7 @@ pub async fn create_camera_ws(frame_rx: CameraFrameReceiver) -> u16 {
         }
 
         let elapsed = now.elapsed();
-        println!("Websocket closing after {elapsed:.2?}");
+        tracing::info!("Websocket closing after {elapsed:.2?}");
         tracing::info!("Websocket closing after {elapsed:.2?}");
     }
 
@@ -0,0 +1,72 @@
+use crate::export::{render_video, RenderError};
+use cap_export::ClipEncoding;
+use cap_project::{ExportFormat, ProjectConfiguration};
+use serde::Deserialize;
+use specta::Type;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+#[derive(Deserialize, Type, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum ClipMode {
+    /// Copies the streams, so the clip starts at the keyframe before `start_secs`
+    Fast,
+    /// Re-encodes the clip so it starts and ends exactly where requested
+    Accurate,
+}
+
+/// Cuts `start_secs` to `end_secs` of the timeline out of the recording rendered with
+/// `project`, rendering it first if it isn't up to date, and saves it to `dest`
+#[tauri::command]
+#[specta::specta]
+pub async fn export_clip(
+    app: AppHandle,
+    video_id: String,
+    project: ProjectConfiguration,
+    start_secs: f64,
+    end_secs: f64,
+    dest: PathBuf,
+    mode: ClipMode,
+) -> Result<PathBuf, RenderError> {
+    if !start_secs.is_finite() || !end_secs.is_finite() || start_secs < 0.0 {
+        return Err(RenderError::InvalidInput(format!(
+            "Invalid clip range {start_secs}-{end_secs}s"
+        )));
+    }
+    if end_secs <= start_secs {
+        return Err(RenderError::InvalidInput(
+            "The clip has to end after it starts".to_string(),
+        ));
+    }
+
+    let encoding = match mode {
+        ClipMode::Fast => ClipEncoding::Copy,
+        ClipMode::Accurate => ClipEncoding::Reencode {
+            encoder: project.encoder,
+            quality: project.quality,
+        },
+    };
+
+    let video_path = render_video(
+        &app,
+        video_id,
+        project,
+        |_| {},
+        false,
+        ExportFormat::Mp4H264,
+    )
+    .await?;
+
+    if dest == video_path {
+        return Err(RenderError::InvalidInput(
+            "The clip can't replace the video it's cut from".to_string(),
+        ));
+    }
+
+    cap_export::export_clip(&video_path, dest.clone(), start_secs, end_secs, encoding)
+        .await
+        .map_err(|e| {
+            std::fs::remove_file(&dest).ok();
+            e.into()
+        })
+}
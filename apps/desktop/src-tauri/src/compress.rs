@@ -0,0 +1,209 @@
+use crate::{display_path, recording_path, CommandError};
+use cap_project::RecordingMeta;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+#[derive(Deserialize, Type, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum CompressionPreset {
+    High,
+    Medium,
+    Low,
+}
+
+impl CompressionPreset {
+    /// x264 constant rate factor, higher values give smaller files
+    fn crf(&self) -> u32 {
+        match self {
+            Self::High => 23,
+            Self::Medium => 28,
+            Self::Low => 33,
+        }
+    }
+
+    /// Taller exports are scaled down to this height
+    fn max_height(&self) -> Option<u32> {
+        match self {
+            Self::High => None,
+            Self::Medium => Some(1080),
+            Self::Low => Some(720),
+        }
+    }
+
+    fn audio_bitrate(&self) -> &'static str {
+        match self {
+            Self::High => "160k",
+            Self::Medium => "128k",
+            Self::Low => "96k",
+        }
+    }
+}
+
+#[derive(Serialize, Type, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CompressionResult {
+    path: PathBuf,
+    /// The video as it was before being compressed for the first time
+    backup_path: PathBuf,
+    before_bytes: f64,
+    after_bytes: f64,
+}
+
+/// Re-encodes the exported video of a recording into a smaller file, or its display track
+/// if it hasn't been exported. The original is kept next to it as a backup.
+#[tauri::command]
+#[specta::specta]
+pub async fn compress_recording(
+    app: AppHandle,
+    video_id: String,
+    target: CompressionPreset,
+) -> Result<CompressionResult, CommandError> {
+    let meta = RecordingMeta::load_for_project(&recording_path(&app, &video_id))?;
+
+    let path = [meta.output_path(), display_path(&meta)]
+        .into_iter()
+        .find(|path| path.exists())
+        .ok_or_else(|| {
+            CommandError::InvalidInput(format!("'{}' has no video to compress", meta.pretty_name))
+        })?;
+    let backup_path = backup_path(&path);
+    let compressed_path = path.with_extension("compressed.mp4");
+    let source = compression_source(&path);
+
+    // The editor relies on the display track's size, so only exports are scaled down
+    let allow_scaling = path == meta.output_path();
+
+    let result = compress(&source, &compressed_path, target, allow_scaling).await;
+    if let Err(e) = result {
+        std::fs::remove_file(&compressed_path).ok();
+        return Err(CommandError::Ffmpeg(e));
+    }
+
+    let before_bytes = file_size(&path)?;
+    let after_bytes = file_size(&compressed_path)?;
+
+    if after_bytes >= before_bytes {
+        std::fs::remove_file(&compressed_path).ok();
+        return Err(CommandError::InvalidInput(format!(
+            "'{}' is already smaller than the {target:?} preset would make it",
+            meta.pretty_name
+        )));
+    }
+
+    if !backup_path.exists() {
+        std::fs::rename(&path, &backup_path)
+            .map_err(|e| CommandError::Io(format!("Failed to back up {}: {e}", path.display())))?;
+    }
+    std::fs::rename(&compressed_path, &path)
+        .map_err(|e| CommandError::Io(format!("Failed to replace {}: {e}", path.display())))?;
+
+    Ok(CompressionResult {
+        path,
+        backup_path,
+        before_bytes: before_bytes as f64,
+        after_bytes: after_bytes as f64,
+    })
+}
+
+/// Where a video is kept from before it was first compressed
+pub fn backup_path(path: &Path) -> PathBuf {
+    path.with_extension("original.mp4")
+}
+
+/// Called when a video is replaced by rendering it again, after which its backup
+/// would bring back the previous render when compressing
+pub fn remove_backup(path: &Path) {
+    let backup_path = backup_path(path);
+    if backup_path.exists() {
+        if let Err(e) = std::fs::remove_file(&backup_path) {
+            tracing::error!("Failed to remove {}: {e}", backup_path.display());
+        }
+    }
+}
+
+/// Compressing again starts from the backup so quality isn't lost twice
+fn compression_source(path: &Path) -> PathBuf {
+    let backup_path = backup_path(path);
+    if backup_path.exists() {
+        backup_path
+    } else {
+        path.to_path_buf()
+    }
+}
+
+async fn compress(
+    source: &Path,
+    output: &Path,
+    preset: CompressionPreset,
+    allow_scaling: bool,
+) -> Result<(), String> {
+    let mut ffmpeg = cap_ffmpeg_cli::FFmpeg::new();
+    ffmpeg
+        .command
+        .args(["-y", "-loglevel", "error", "-i"])
+        .arg(source)
+        .args([
+            "-c:v", "libx264", "-preset", "medium", "-pix_fmt", "yuv420p",
+        ])
+        .args(["-crf", &preset.crf().to_string()])
+        .args(["-c:a", "aac", "-b:a", preset.audio_bitrate()]);
+
+    if let Some(max_height) = preset.max_height().filter(|_| allow_scaling) {
+        ffmpeg
+            .command
+            .args(["-vf", &format!("scale=-2:'min({max_height},ih)'")]);
+    }
+
+    let output = ffmpeg
+        .command
+        .args(["-movflags", "+faststart"])
+        .arg(output)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to compress recording: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(())
+}
+
+fn file_size(path: &Path) -> Result<u64, String> {
+    std::fs::metadata(path)
+        .map(|metadata| metadata.len())
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compressing_after_a_re_export_uses_the_new_export() {
+        let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("result.mp4");
+
+        // First export, then compressed with the export kept as the backup
+        std::fs::write(backup_path(&path), "first export").unwrap();
+        std::fs::write(&path, "first export, compressed").unwrap();
+        assert_eq!(compression_source(&path), backup_path(&path));
+
+        // Exporting again replaces the video the backup was made from
+        remove_backup(&path);
+        std::fs::write(&path, "second export").unwrap();
+
+        let source = compression_source(&path);
+        assert_eq!(source, path);
+        assert_eq!(std::fs::read_to_string(source).unwrap(), "second export");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,269 @@
+use crate::{recording_path, CommandError};
+use cap_project::{AudioMeta, Content, Display, RecordingMeta, SingleSegment};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+struct VideoInfo {
+    width: u32,
+    height: u32,
+    fps: f64,
+    duration_secs: f64,
+    video_codec: String,
+    audio_codec: Option<String>,
+}
+
+fn probe_video(path: &Path) -> Result<VideoInfo, String> {
+    ffmpeg::init().map_err(|e| e.to_string())?;
+
+    let ictx = ffmpeg::format::input(&path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+
+    let video_stream = ictx
+        .streams()
+        .best(ffmpeg::media::Type::Video)
+        .ok_or_else(|| format!("No video stream found in {}", path.display()))?;
+    let video = ffmpeg::codec::context::Context::from_parameters(video_stream.parameters())
+        .and_then(|c| c.decoder().video())
+        .map_err(|e| format!("Failed to read video stream of {}: {}", path.display(), e))?;
+
+    let audio_codec = ictx
+        .streams()
+        .best(ffmpeg::media::Type::Audio)
+        .map(|s| format!("{:?}", s.parameters().id()));
+
+    Ok(VideoInfo {
+        width: video.width(),
+        height: video.height(),
+        fps: f64::from(video_stream.avg_frame_rate()),
+        duration_secs: ictx.duration().max(0) as f64 / f64::from(ffmpeg::ffi::AV_TIME_BASE),
+        video_codec: format!("{:?}", video_stream.parameters().id()),
+        audio_codec,
+    })
+}
+
+/// Joins the exported videos of several recordings into a new recording.
+/// The videos are only re-encoded if their codecs differ.
+#[tauri::command]
+#[specta::specta]
+pub async fn concatenate_recordings(
+    app: AppHandle,
+    video_ids: Vec<String>,
+    output_id: String,
+) -> Result<PathBuf, CommandError> {
+    if video_ids.len() < 2 {
+        return Err(CommandError::InvalidInput(
+            "At least two recordings are needed to concatenate".to_string(),
+        ));
+    }
+
+    let output_dir = recording_path(&app, &output_id);
+    if output_dir.exists() {
+        return Err(CommandError::InvalidInput(format!(
+            "Recording '{output_id}' already exists"
+        )));
+    }
+
+    let mut inputs = vec![];
+    for video_id in &video_ids {
+        let meta = RecordingMeta::load_for_project(&recording_path(&app, video_id))?;
+        let path = meta.output_path();
+        if !path.exists() {
+            return Err(CommandError::InvalidInput(format!(
+                "'{}' has not been exported yet",
+                meta.pretty_name
+            )));
+        }
+
+        let info = tokio::task::spawn_blocking({
+            let path = path.clone();
+            move || probe_video(&path)
+        })
+        .await
+        .map_err(|e| e.to_string())??;
+
+        inputs.push((meta, path, info));
+    }
+
+    let (first_meta, _, first) = &inputs[0];
+    let mismatches = inputs[1..]
+        .iter()
+        .filter(|(_, _, info)| {
+            info.width != first.width
+                || info.height != first.height
+                || (info.fps - first.fps).abs() > 0.01
+        })
+        .map(|(meta, _, info)| {
+            format!(
+                "'{}' is {}x{} at {:.2} fps",
+                meta.pretty_name, info.width, info.height, info.fps
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if !mismatches.is_empty() {
+        return Err(CommandError::InvalidInput(format!(
+            "Recordings must match '{}' ({}x{} at {:.2} fps): {}",
+            first_meta.pretty_name,
+            first.width,
+            first.height,
+            first.fps,
+            mismatches.join(", ")
+        )));
+    }
+
+    let same_codecs = inputs.iter().all(|(_, _, info)| {
+        info.video_codec == first.video_codec && info.audio_codec == first.audio_codec
+    });
+    let has_audio = inputs.iter().any(|(_, _, info)| info.audio_codec.is_some());
+
+    let content_dir = output_dir.join("content");
+    std::fs::create_dir_all(&content_dir)?;
+
+    let result = concatenate(&inputs, &content_dir, same_codecs, has_audio).await;
+    if let Err(e) = result {
+        std::fs::remove_dir_all(&output_dir).ok();
+        return Err(CommandError::Ffmpeg(e));
+    }
+
+    RecordingMeta {
+        project_path: output_dir.clone(),
+        pretty_name: format!("{} (Combined)", first_meta.pretty_name),
+        created_at: Some(chrono::Local::now().to_rfc3339()),
+        sharing: None,
+        notes: None,
+        tags: vec![],
+        content: Content::SingleSegment {
+            segment: SingleSegment {
+                display: Display {
+                    path: PathBuf::from("content/display.mp4"),
+                    scale_factor: None,
+                    color_space: None,
+                },
+                camera: None,
+                audio: has_audio.then(|| AudioMeta {
+                    path: PathBuf::from("content/audio-input.mp3"),
+                }),
+                system_audio: None,
+                cursor: None,
+                clicks: None,
+                keystrokes: None,
+            },
+        },
+    }
+    .save_for_project()
+    .map_err(|e| format!("Failed to save recording meta: {e}"))?;
+
+    Ok(output_dir)
+}
+
+async fn concatenate(
+    inputs: &[(RecordingMeta, PathBuf, VideoInfo)],
+    content_dir: &Path,
+    same_codecs: bool,
+    has_audio: bool,
+) -> Result<(), String> {
+    let combined_path = content_dir.join("combined.mp4");
+
+    let mut ffmpeg = cap_ffmpeg_cli::FFmpeg::new();
+    ffmpeg.command.args(["-y", "-loglevel", "error"]);
+
+    if same_codecs {
+        let list = inputs
+            .iter()
+            .map(|(_, path, _)| {
+                format!("file '{}'\n", path.to_string_lossy().replace('\'', r"'\''"))
+            })
+            .collect::<String>();
+        let list_path = content_dir.join("concat.txt");
+        std::fs::write(&list_path, list).map_err(|e| e.to_string())?;
+
+        ffmpeg
+            .command
+            .args(["-f", "concat", "-safe", "0", "-i"])
+            .arg(&list_path)
+            .args(["-c", "copy"]);
+    } else {
+        let mut filter = String::new();
+        for (i, (_, path, info)) in inputs.iter().enumerate() {
+            ffmpeg.command.arg("-i").arg(path);
+
+            filter.push_str(&format!("[{i}:v:0]"));
+            if !has_audio {
+                continue;
+            }
+
+            // The concat filter needs an audio stream from every input
+            let audio = if info.audio_codec.is_some() {
+                format!("[{i}:a:0]aresample=48000,aformat=channel_layouts=stereo")
+            } else {
+                format!(
+                    "anullsrc=r=48000:cl=stereo,atrim=duration={}",
+                    info.duration_secs
+                )
+            };
+            filter = format!("{audio}[a{i}];{filter}[a{i}]");
+        }
+        filter.push_str(&format!(
+            "concat=n={}:v=1:a={}",
+            inputs.len(),
+            has_audio as u8
+        ));
+
+        ffmpeg
+            .command
+            .args(["-filter_complex", &filter])
+            .args(["-c:v", "libx264", "-pix_fmt", "yuv420p"])
+            .args(["-c:a", "aac"]);
+    }
+
+    let output = ffmpeg
+        .command
+        .args(["-movflags", "+faststart"])
+        .arg(&combined_path)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+    std::fs::remove_file(content_dir.join("concat.txt")).ok();
+
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to concatenate recordings: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    // Recordings keep their audio separately from the display video
+    let mut ffmpeg = cap_ffmpeg_cli::FFmpeg::new();
+    ffmpeg
+        .command
+        .args(["-y", "-loglevel", "error", "-i"])
+        .arg(&combined_path)
+        .args(["-map", "0:v:0", "-c", "copy"])
+        .arg(content_dir.join("display.mp4"));
+    if has_audio {
+        ffmpeg
+            .command
+            .args(["-map", "0:a:0", "-c:a", "libmp3lame", "-q:a", "2"])
+            .arg(content_dir.join("audio-input.mp3"));
+    }
+
+    let output = ffmpeg
+        .command
+        .stdin(std::process::Stdio::null())
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+    std::fs::remove_file(&combined_path).ok();
+
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to split concatenated recording: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(())
+}
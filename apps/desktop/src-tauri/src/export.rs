@@ -1,10 +1,265 @@
 use crate::{
-    get_video_metadata, upsert_editor_instance, windows::ShowCapWindow, RenderProgress,
-    VideoRecordingMetadata, VideoType,
+    compress, general_settings::GeneralSettingsStore, get_video_metadata, recording_path,
+    windows::ShowCapWindow, CommandError, ExportProgress, RenderProgress, VideoRecordingMetadata,
+    VideoType,
 };
-use cap_project::ProjectConfiguration;
-use std::path::PathBuf;
-use tauri::AppHandle;
+use cap_export::{ExportError, ExportSettings};
+use cap_project::{Content, Encoder, ExportFormat, ProjectConfiguration, RecordingMeta};
+use serde::Serialize;
+use specta::Type;
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::{DefaultHasher, Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+use tokio::sync::{oneshot, watch, OwnedSemaphorePermit, Semaphore};
+
+/// Why rendering a video failed, so the UI can tell a missing recording apart from
+/// FFmpeg failing to encode it
+#[derive(Debug, Clone, Serialize, Type, thiserror::Error)]
+#[serde(tag = "type", content = "details")]
+pub enum RenderError {
+    #[error("Missing input: {}", .0.display())]
+    InputMissing(PathBuf),
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("Failed to start FFmpeg: {0}")]
+    FfmpegSpawn(String),
+    #[error(
+        "FFmpeg exited with code {}: {stderr_tail}",
+        .code.map_or("unknown".to_string(), |c| c.to_string())
+    )]
+    FfmpegExit {
+        code: Option<i32>,
+        stderr_tail: String,
+    },
+    #[error("Export was cancelled")]
+    Cancelled,
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<ExportError> for RenderError {
+    fn from(e: ExportError) -> Self {
+        match e {
+            ExportError::FFmpegSpawn(e) => Self::FfmpegSpawn(e),
+            ExportError::FFmpegExit { code, stderr_tail } => Self::FfmpegExit { code, stderr_tail },
+            ExportError::Cancelled => Self::Cancelled,
+            e => Self::Other(e.to_string()),
+        }
+    }
+}
+
+type ExportResult = Option<Result<PathBuf, RenderError>>;
+
+struct ExportTask {
+    format: ExportFormat,
+    cancel_tx: Option<oneshot::Sender<()>>,
+    result_rx: watch::Receiver<ExportResult>,
+}
+
+/// A video's id and the hash of the configuration it's being rendered with,
+/// so an export only shares the result of one rendering the same thing
+type ExportTaskKey = (String, String);
+
+type ExportTasksState = Arc<std::sync::Mutex<HashMap<ExportTaskKey, ExportTask>>>;
+
+fn export_tasks(app: &AppHandle) -> ExportTasksState {
+    (*app.state::<ExportTasksState>()).clone()
+}
+
+/// Registers the state shared by every export, before any of them can start
+pub fn init(app: &AppHandle) {
+    app.manage(ExportTasksState::default());
+    app.manage(RenderQueue::new());
+}
+
+/// Removes an export task once its render finishes, including when the render is
+/// dropped before it gets the chance to
+struct ExportTaskGuard {
+    tasks: ExportTasksState,
+    key: ExportTaskKey,
+}
+
+impl Drop for ExportTaskGuard {
+    fn drop(&mut self) {
+        self.tasks.lock().unwrap().remove(&self.key);
+    }
+}
+
+/// Renders run one at a time, as several FFmpeg processes encoding at once
+/// slow each other down and leave the machine unresponsive
+const MAX_CONCURRENT_RENDERS: usize = 1;
+
+/// How many renders are ahead of a queued video's render.
+/// Sent with a position of 0 once it starts rendering.
+#[derive(Serialize, Type, tauri_specta::Event, Debug, Clone)]
+pub struct RenderQueuePosition {
+    video_id: String,
+    position: u32,
+}
+
+/// Renders wait for a permit in the order they were queued, which the semaphore
+/// hands out fairly
+struct RenderQueue {
+    permits: Arc<Semaphore>,
+    /// Ids of the queued renders, in order, to report their positions
+    waiting: std::sync::Mutex<VecDeque<(u64, String)>>,
+    next_ticket: AtomicU64,
+}
+
+impl RenderQueue {
+    fn new() -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(MAX_CONCURRENT_RENDERS)),
+            waiting: Default::default(),
+            next_ticket: AtomicU64::new(0),
+        }
+    }
+
+    /// Waits until fewer than `MAX_CONCURRENT_RENDERS` renders are running.
+    /// Dropping the returned permit lets the next queued render start.
+    async fn join(&self, app: &AppHandle, video_id: &str) -> OwnedSemaphorePermit {
+        let ticket = self.next_ticket.fetch_add(1, Ordering::Relaxed);
+        self.waiting
+            .lock()
+            .unwrap()
+            .push_back((ticket, video_id.to_string()));
+
+        // Leaves the queue once the permit is acquired, or when cancelled while waiting
+        let _queued = QueuedRender {
+            queue: self,
+            app,
+            ticket,
+        };
+        self.emit_positions(app);
+
+        let permit = self
+            .permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("Render queue semaphore is never closed");
+
+        RenderQueuePosition {
+            video_id: video_id.to_string(),
+            position: 0,
+        }
+        .emit(app)
+        .ok();
+
+        permit
+    }
+
+    fn emit_positions(&self, app: &AppHandle) {
+        let running = MAX_CONCURRENT_RENDERS - self.permits.available_permits();
+
+        for (i, (_, video_id)) in self.waiting.lock().unwrap().iter().enumerate() {
+            RenderQueuePosition {
+                video_id: video_id.clone(),
+                position: (running + i) as u32,
+            }
+            .emit(app)
+            .ok();
+        }
+    }
+}
+
+struct QueuedRender<'a> {
+    queue: &'a RenderQueue,
+    app: &'a AppHandle,
+    ticket: u64,
+}
+
+impl Drop for QueuedRender<'_> {
+    fn drop(&mut self) {
+        self.queue
+            .waiting
+            .lock()
+            .unwrap()
+            .retain(|(ticket, _)| *ticket != self.ticket);
+        self.queue.emit_positions(self.app);
+    }
+}
+
+/// Identifies the configuration an output was rendered with, so a cached output
+/// is only reused if nothing has changed since
+fn config_hash(project: &ProjectConfiguration) -> Result<String, String> {
+    let json = serde_json::to_string(project).map_err(|e| e.to_string())?;
+
+    let mut hasher = DefaultHasher::new();
+    json.hash(&mut hasher);
+    Ok(format!("{:016x}", hasher.finish()))
+}
+
+fn config_hash_path(output_path: &Path) -> PathBuf {
+    let mut file_name = output_path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".config-hash");
+    output_path.with_file_name(file_name)
+}
+
+/// Records how many bytes were freed by removing a recording's raw tracks
+pub const RAW_TRACKS_REMOVED_FILE: &str = "raw-tracks-removed";
+
+pub fn raw_track_paths(meta: &RecordingMeta) -> Vec<PathBuf> {
+    match &meta.content {
+        Content::SingleSegment { segment } => std::iter::once(&segment.display.path)
+            .chain(segment.camera.as_ref().map(|c| &c.path))
+            .map(|path| segment.path(meta, path))
+            .collect(),
+        Content::MultipleSegments { inner } => inner
+            .segments
+            .iter()
+            .flat_map(|s| {
+                std::iter::once(&s.display.path).chain(s.camera.as_ref().map(|c| &c.path))
+            })
+            .map(|path| inner.path(meta, path))
+            .collect(),
+    }
+}
+
+/// Deletes the display and camera tracks, returning the number of bytes freed
+fn remove_raw_tracks(meta: &RecordingMeta) -> u64 {
+    let mut freed = 0;
+    for path in raw_track_paths(meta) {
+        let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        match std::fs::remove_file(&path) {
+            Ok(()) => freed += size,
+            Err(e) => tracing::error!("Failed to remove raw track {}: {e}", path.display()),
+        }
+    }
+
+    let path = meta.project_path.join(RAW_TRACKS_REMOVED_FILE);
+    let previous = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .unwrap_or(0);
+    std::fs::write(&path, (previous + freed).to_string()).ok();
+
+    freed
+}
+
+async fn wait_for_result(
+    mut result_rx: watch::Receiver<ExportResult>,
+) -> Result<PathBuf, RenderError> {
+    loop {
+        if let Some(result) = result_rx.borrow_and_update().clone() {
+            return result;
+        }
+
+        if result_rx.changed().await.is_err() {
+            return Err(RenderError::Other(
+                "Export task ended unexpectedly".to_string(),
+            ));
+        }
+    }
+}
 
 #[tauri::command]
 #[specta::specta]
@@ -14,50 +269,381 @@ pub async fn export_video(
     project: ProjectConfiguration,
     progress: tauri::ipc::Channel<RenderProgress>,
     force: bool,
-) -> Result<PathBuf, String> {
+    format: Option<ExportFormat>,
+) -> Result<PathBuf, RenderError> {
+    render_video(
+        &app,
+        video_id,
+        project,
+        move |p| {
+            progress.send(p).ok();
+        },
+        force,
+        format.unwrap_or_default(),
+    )
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn export_gif(
+    app: AppHandle,
+    video_id: String,
+    project: ProjectConfiguration,
+    fps: u32,
+    max_width: u32,
+) -> Result<PathBuf, RenderError> {
+    let video_path = render_video(
+        &app,
+        video_id,
+        project,
+        |_| {},
+        false,
+        ExportFormat::Mp4H264,
+    )
+    .await?;
+
+    cap_export::export_gif(
+        &video_path,
+        video_path.with_extension("gif"),
+        fps.clamp(1, 50),
+        max_width.max(2),
+    )
+    .await
+    .map_err(|e| {
+        sentry::capture_message(&e.to_string(), sentry::Level::Error);
+        e.into()
+    })
+}
+
+/// Copies the screen recording as it was captured, without the background, camera
+/// or any other editing. Segments are joined without being re-encoded.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_raw(app: AppHandle, video_id: String) -> Result<PathBuf, CommandError> {
+    let meta = RecordingMeta::load_for_project(&recording_path(&app, &video_id))?;
+
+    let display_paths = match &meta.content {
+        Content::SingleSegment { segment } => vec![segment.path(&meta, &segment.display.path)],
+        Content::MultipleSegments { inner } => inner
+            .segments
+            .iter()
+            .map(|s| inner.path(&meta, &s.display.path))
+            .collect(),
+    };
+
+    if display_paths.iter().any(|path| !path.exists()) {
+        return Err(CommandError::InvalidInput(format!(
+            "The screen recording of '{}' has been deleted",
+            meta.pretty_name
+        )));
+    }
+
+    let output_dir = meta.project_path.join("output");
+    std::fs::create_dir_all(&output_dir)?;
+    let output_path = output_dir.join("raw.mp4");
+
+    let list = display_paths
+        .iter()
+        .map(|path| format!("file '{}'\n", path.to_string_lossy().replace('\'', r"'\''")))
+        .collect::<String>();
+    let list_path = output_dir.join("raw.txt");
+    std::fs::write(&list_path, list)?;
+
+    let mut ffmpeg = cap_ffmpeg_cli::FFmpeg::new();
+    let output = ffmpeg
+        .command
+        .args([
+            "-y",
+            "-loglevel",
+            "error",
+            "-f",
+            "concat",
+            "-safe",
+            "0",
+            "-i",
+        ])
+        .arg(&list_path)
+        .args(["-c", "copy", "-movflags", "+faststart"])
+        .arg(&output_path)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .await
+        .map_err(|e| CommandError::Ffmpeg(format!("Failed to run ffmpeg: {}", e)))?;
+
+    std::fs::remove_file(&list_path).ok();
+
+    if !output.status.success() {
+        std::fs::remove_file(&output_path).ok();
+        return Err(CommandError::Ffmpeg(format!(
+            "Failed to export raw recording: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+
+    Ok(output_path)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_encoders() -> Vec<Encoder> {
+    cap_export::available_encoders().await.to_vec()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn cancel_render(app: AppHandle, video_id: String) -> Result<(), CommandError> {
+    let (result_rx, format) = {
+        let tasks = export_tasks(&app);
+        let mut tasks = tasks.lock().unwrap();
+        let Some(task) = tasks
+            .iter_mut()
+            .find_map(|((id, _), task)| (*id == video_id).then_some(task))
+        else {
+            return Ok(());
+        };
+
+        if let Some(cancel_tx) = task.cancel_tx.take() {
+            cancel_tx.send(()).ok();
+        }
+
+        (task.result_rx.clone(), task.format)
+    };
+
+    if wait_for_result(result_rx).await.is_err() {
+        let meta = RecordingMeta::load_for_project(&recording_path(&app, &video_id))?;
+        let output_path = meta.output_path_for(format);
+
+        if output_path.exists() {
+            std::fs::remove_file(&output_path)?;
+        }
+        std::fs::remove_file(config_hash_path(&output_path)).ok();
+    }
+
+    Ok(())
+}
+
+pub async fn render_video(
+    app: &AppHandle,
+    video_id: String,
+    project: ProjectConfiguration,
+    on_progress: impl Fn(RenderProgress) + Send + 'static,
+    force: bool,
+    format: ExportFormat,
+) -> Result<PathBuf, RenderError> {
+    let tasks = export_tasks(app);
+    let key = (
+        video_id.clone(),
+        config_hash(&project).map_err(RenderError::Other)?,
+    );
+
+    let (cancel_rx, result_tx, task_guard) = loop {
+        let (result_rx, same_render) = {
+            let mut running_tasks = tasks.lock().unwrap();
+
+            // Share the result of an export that's already rendering the same thing,
+            // or wait for the video's other export to finish as both write the same output
+            match running_tasks.iter().find(|((id, _), _)| *id == video_id) {
+                Some((running_key, task)) => (
+                    task.result_rx.clone(),
+                    *running_key == key && task.format == format,
+                ),
+                None => {
+                    let (cancel_tx, cancel_rx) = oneshot::channel();
+                    let (result_tx, result_rx) = watch::channel(None);
+
+                    running_tasks.insert(
+                        key.clone(),
+                        ExportTask {
+                            format,
+                            cancel_tx: Some(cancel_tx),
+                            result_rx,
+                        },
+                    );
+
+                    break (
+                        cancel_rx,
+                        result_tx,
+                        ExportTaskGuard {
+                            tasks: tasks.clone(),
+                            key: key.clone(),
+                        },
+                    );
+                }
+            }
+        };
+
+        let result = wait_for_result(result_rx).await;
+        if same_render {
+            return result;
+        }
+    };
+
+    let cancel = async move {
+        if cancel_rx.await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    };
+
+    let result =
+        render_video_inner(app, video_id, project, on_progress, force, format, cancel).await;
+
+    drop(task_guard);
+    result_tx.send(Some(result.clone())).ok();
+
+    result
+}
+
+async fn render_video_inner(
+    app: &AppHandle,
+    video_id: String,
+    project: ProjectConfiguration,
+    on_progress: impl Fn(RenderProgress) + Send + 'static,
+    force: bool,
+    format: ExportFormat,
+    cancel: impl std::future::Future<Output = ()> + Send + 'static,
+) -> Result<PathBuf, RenderError> {
+    let project_path = recording_path(app, &video_id);
+    let meta = RecordingMeta::load_for_project(&project_path).map_err(RenderError::Other)?;
+
+    let config_hash = config_hash(&project).map_err(RenderError::Other)?;
+
+    let output_path = meta.output_path_for(format);
+
+    // Without the raw tracks there's nothing to render from, so whatever was
+    // rendered last is the only output this recording will ever have
+    if let Some(missing) = raw_track_paths(&meta)
+        .into_iter()
+        .find(|path| !path.exists())
+    {
+        if output_path.exists() {
+            return Ok(output_path);
+        }
+
+        return Err(RenderError::InputMissing(missing));
+    }
+
     let VideoRecordingMetadata { duration, .. } =
         get_video_metadata(app.clone(), video_id.clone(), Some(VideoType::Screen))
             .await
-            .unwrap();
+            .map_err(|e| RenderError::Other(e.to_string()))?;
+
+    let saved_project = project.clone();
+    let mut project = project;
+    project
+        .apply_trim(duration)
+        .map_err(RenderError::InvalidInput)?;
+    project
+        .apply_speed(duration)
+        .map_err(RenderError::InvalidInput)?;
+
+    if let Some(watermark) = &project.watermark {
+        watermark.validate().map_err(RenderError::InvalidInput)?;
+    }
+
+    for redaction in &project.redactions {
+        redaction.validate().map_err(RenderError::InvalidInput)?;
+    }
+
+    if let Some(quality) = &project.quality {
+        quality.validate().map_err(RenderError::InvalidInput)?;
+    }
+
+    // Keep the settings used for this render so reopening the recording restores them,
+    // once they're known to render
+    saved_project
+        .write(&project_path)
+        .map_err(|e| RenderError::Other(format!("Failed to save project config: {e}")))?;
+
+    let duration = project.timeline().map(|t| t.duration()).unwrap_or(duration);
 
     // 30 FPS (calculated for output video)
     let total_frames = (duration * 30.0).round() as u32;
 
-    let editor_instance = upsert_editor_instance(&app, video_id.clone()).await;
+    let hash_path = config_hash_path(&output_path);
 
-    let output_path = editor_instance.meta().output_path();
-
-    // If the file exists, return it immediately
+    // Reuse the existing output only if it was rendered with the same configuration
     if output_path.exists() && !force {
-        return Ok(output_path);
+        let cached_hash = std::fs::read_to_string(&hash_path).ok();
+        if cached_hash.as_deref() == Some(config_hash.as_str()) {
+            return Ok(output_path);
+        }
     }
 
-    progress
-        .send(RenderProgress::EstimatedTotalFrames { total_frames })
-        .ok();
+    // Cleared up front so an interrupted render can't be mistaken for an up to date one
+    std::fs::remove_file(&hash_path).ok();
+    // Only the MP4 export is compressed
+    if output_path == meta.output_path() {
+        compress::remove_backup(&output_path);
+    }
+
+    let mut cancel = Box::pin(cancel);
+    let render_queue = app.state::<RenderQueue>();
+    let _permit = tokio::select! {
+        permit = render_queue.join(app, &video_id) => permit,
+        _ = &mut cancel => return Err(RenderError::Cancelled),
+    };
+
+    on_progress(RenderProgress::EstimatedTotalFrames { total_frames });
 
-    cap_export::export_video_to_file(
+    let settings = GeneralSettingsStore::get(app)
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+
+    cap_export::render_project(
+        &project_path,
         project,
         output_path.clone(),
-        move |frame_index| {
-            progress
-                .send(RenderProgress::FrameRendered {
+        ExportSettings {
+            format,
+            thumbnail: settings.thumbnail_options(),
+        },
+        {
+            let app = app.clone();
+            let video_id = video_id.clone();
+            move |frame_index| {
+                on_progress(RenderProgress::FrameRendered {
                     current_frame: frame_index + 1,
-                })
+                });
+
+                ExportProgress {
+                    video_id: video_id.clone(),
+                    progress: ((frame_index + 1) as f32 / total_frames.max(1) as f32).min(1.0),
+                }
+                .emit(&app)
                 .ok();
+            }
         },
-        &editor_instance.project_path,
-        editor_instance.meta(),
-        editor_instance.render_constants.clone(),
-        &editor_instance.segments,
+        cancel,
     )
     .await
     .map_err(|e| {
         sentry::capture_message(&e.to_string(), sentry::Level::Error);
-        e.to_string()
+        RenderError::from(e)
     })?;
 
-    ShowCapWindow::PrevRecordings.show(&app).ok();
+    std::fs::write(&hash_path, config_hash).ok();
+
+    // Exports that could be served from the cache may still need re-rendering with
+    // another config, so the raw tracks are only removed after a forced render
+    if settings.cleanup_after_render && force {
+        let freed = remove_raw_tracks(&meta);
+        tracing::info!("Removed raw tracks of {video_id}, freeing {freed} bytes");
+    }
+
+    ExportProgress {
+        video_id,
+        progress: 1.0,
+    }
+    .emit(app)
+    .ok();
+
+    ShowCapWindow::PrevRecordings {
+        screen_bounds: None,
+    }
+    .show(app)
+    .ok();
 
     Ok(output_path)
 }
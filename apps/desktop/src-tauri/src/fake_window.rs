@@ -1,8 +1,27 @@
+use crate::CommandError;
 use cap_media::platform::Bounds;
-use std::{collections::HashMap, sync::Arc, time::Duration};
-use tauri::{AppHandle, Manager, WebviewWindow};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tauri::{AppHandle, Manager, WebviewWindow, WindowEvent};
+use tauri_specta::Event;
 use tokio::{sync::RwLock, time::sleep};
 
+/// How long the cursor has to stay outside every fake window before the panel is hidden,
+/// so briefly crossing a gap between them doesn't make it flicker
+const HIDE_GRACE_PERIOD: Duration = Duration::from_millis(300);
+
+/// Emitted when the cursor enters one of the fake windows
+#[derive(Deserialize, specta::Type, Serialize, tauri_specta::Event, Debug, Clone)]
+pub struct ShowCapturesPanel;
+
+/// Emitted once the cursor has left the fake windows for `HIDE_GRACE_PERIOD`
+#[derive(Deserialize, specta::Type, Serialize, tauri_specta::Event, Debug, Clone)]
+pub struct HideCapturesPanel;
+
 pub struct FakeWindowBounds(pub Arc<RwLock<HashMap<String, HashMap<String, Bounds>>>>);
 
 #[tauri::command]
@@ -12,7 +31,7 @@ pub async fn set_fake_window_bounds(
     name: String,
     bounds: Bounds,
     state: tauri::State<'_, FakeWindowBounds>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let mut state = state.0.write().await;
     let map = state.entry(window.label().to_string()).or_default();
 
@@ -27,7 +46,7 @@ pub async fn remove_fake_window(
     window: tauri::Window,
     name: String,
     state: tauri::State<'_, FakeWindowBounds>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let mut state = state.0.write().await;
     let Some(map) = state.get_mut(window.label()) else {
         return Ok(());
@@ -42,56 +61,89 @@ pub async fn remove_fake_window(
     Ok(())
 }
 
+/// Lets clicks through the window unless the cursor is over one of its fake windows.
+/// The listener stops once the window is destroyed.
 pub fn spawn_fake_window_listener(app: AppHandle, window: WebviewWindow) {
     window.set_ignore_cursor_events(true).ok();
 
-    tokio::spawn(async move {
-        let state = app.state::<FakeWindowBounds>();
-
-        loop {
-            sleep(Duration::from_millis(1000 / 20)).await;
-
-            let map = state.0.read().await;
-            let Some(windows) = map.get(window.label()) else {
-                window.set_ignore_cursor_events(true).ok();
-                continue;
-            };
-
-            let window_position = window.outer_position().unwrap();
-            let mouse_position = window.cursor_position().unwrap();
-            let scale_factor = window.scale_factor().unwrap();
-
-            let mut ignore = true;
-
-            for bounds in windows.values() {
-                let x_min = (window_position.x as f64) + bounds.x * scale_factor;
-                let x_max = (window_position.x as f64) + (bounds.x + bounds.width) * scale_factor;
-                let y_min = (window_position.y as f64) + bounds.y * scale_factor;
-                let y_max = (window_position.y as f64) + (bounds.y + bounds.height) * scale_factor;
-
-                if mouse_position.x >= x_min
-                    && mouse_position.x <= x_max
-                    && mouse_position.y >= y_min
-                    && mouse_position.y <= y_max
-                {
-                    ignore = false;
-                    // ShowCapturesPanel.emit(&app).ok();
-                    break;
+    let handle = tokio::spawn({
+        let window = window.clone();
+        async move {
+            let state = app.state::<FakeWindowBounds>();
+            // Only changed when the cursor enters or leaves a fake window
+            let mut ignoring = true;
+            let mut left_at: Option<Instant> = None;
+
+            loop {
+                sleep(Duration::from_millis(1000 / 20)).await;
+
+                if left_at.is_some_and(|left_at| left_at.elapsed() >= HIDE_GRACE_PERIOD) {
+                    left_at = None;
+                    HideCapturesPanel.emit(&app).ok();
                 }
-            }
 
-            window.set_ignore_cursor_events(ignore).ok();
+                if !window.is_visible().unwrap_or(false) {
+                    continue;
+                }
 
-            let focused = window.is_focused().unwrap_or(false);
-            if !ignore {
-                if !focused {
-                    window.set_focus().ok();
+                let windows = {
+                    let map = state.0.read().await;
+                    map.get(window.label())
+                        .map(|windows| windows.values().cloned().collect::<Vec<_>>())
+                        .unwrap_or_default()
+                };
+
+                let (Ok(window_position), Ok(mouse_position), Ok(scale_factor)) = (
+                    window.outer_position(),
+                    window.cursor_position(),
+                    window.scale_factor(),
+                ) else {
+                    continue;
+                };
+
+                let ignore = !windows.iter().any(|bounds| {
+                    let x_min = (window_position.x as f64) + bounds.x * scale_factor;
+                    let x_max =
+                        (window_position.x as f64) + (bounds.x + bounds.width) * scale_factor;
+                    let y_min = (window_position.y as f64) + bounds.y * scale_factor;
+                    let y_max =
+                        (window_position.y as f64) + (bounds.y + bounds.height) * scale_factor;
+
+                    mouse_position.x >= x_min
+                        && mouse_position.x <= x_max
+                        && mouse_position.y >= y_min
+                        && mouse_position.y <= y_max
+                });
+
+                if ignore == ignoring {
+                    continue;
                 }
-            } else if focused {
+                ignoring = ignore;
+
                 window.set_ignore_cursor_events(ignore).ok();
+
+                if ignore {
+                    left_at = Some(Instant::now());
+                    continue;
+                }
+
+                // Re-entering during the grace period keeps the panel shown
+                if left_at.take().is_none() {
+                    ShowCapturesPanel.emit(&app).ok();
+                }
+
+                if !window.is_focused().unwrap_or(false) {
+                    window.set_focus().ok();
+                }
             }
         }
     });
+
+    window.on_window_event(move |event| {
+        if let WindowEvent::Destroyed = event {
+            handle.abort();
+        }
+    });
 }
 
 pub fn init(app: &AppHandle) {
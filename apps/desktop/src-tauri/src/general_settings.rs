@@ -1,11 +1,14 @@
+use crate::CommandError;
+use cap_project::{Encoder, ThumbnailFormat, ThumbnailOptions, DEFAULT_THUMBNAIL_QUALITY};
+use cap_recording::RecordingOptions;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use specta::Type;
-use std::sync::Mutex;
+use std::{path::PathBuf, sync::Mutex};
 use tauri::{AppHandle, Manager, Wry};
 use tauri_plugin_store::StoreExt;
 
-#[derive(Serialize, Deserialize, Type, Default)]
+#[derive(Serialize, Deserialize, Type, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GeneralSettingsStore {
     #[serde(default)]
@@ -25,6 +28,31 @@ pub struct GeneralSettingsStore {
     pub has_completed_startup: bool,
     #[serde(default)]
     pub dark_mode: bool,
+    /// Where new recordings are stored, defaults to the app data directory
+    #[serde(default)]
+    pub recordings_dir: Option<PathBuf>,
+    /// Don't cover the rest of the screen while recording a window or application
+    #[serde(default)]
+    pub disable_capture_occluder: bool,
+    /// Outline the captured area instead of dimming everything else
+    #[serde(default)]
+    pub capture_occluder_outline_only: bool,
+    /// Delete the raw display and camera tracks once a recording has been rendered
+    /// with caching disabled, as exporting from the editor does
+    #[serde(default)]
+    pub cleanup_after_render: bool,
+    /// Format recording screenshots and thumbnails are saved in
+    #[serde(default)]
+    pub thumbnail_format: ThumbnailFormat,
+    /// JPEG quality from 1 to 100, PNG and WebP thumbnails are lossless
+    #[serde(default)]
+    pub thumbnail_quality: Option<u8>,
+    /// Frame rate of new recordings, unless one is picked when starting them
+    #[serde(default)]
+    pub default_fps: Option<u32>,
+    /// Encoder new recordings are exported with
+    #[serde(default)]
+    pub default_encoder: Encoder,
 }
 
 fn true_b() -> bool {
@@ -32,6 +60,16 @@ fn true_b() -> bool {
 }
 
 impl GeneralSettingsStore {
+    pub fn thumbnail_options(&self) -> ThumbnailOptions {
+        ThumbnailOptions {
+            format: self.thumbnail_format,
+            quality: self
+                .thumbnail_quality
+                .unwrap_or(DEFAULT_THUMBNAIL_QUALITY)
+                .clamp(1, 100),
+        }
+    }
+
     pub fn get(app: &AppHandle<Wry>) -> Result<Option<Self>, String> {
         match app.get_store("store").map(|s| s.get("general_settings")) {
             Some(Some(store)) => {
@@ -58,14 +96,52 @@ impl GeneralSettingsStore {
     }
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn get_general_settings(app: AppHandle) -> Result<GeneralSettingsStore, CommandError> {
+    Ok(GeneralSettingsStore::get(&app)?.unwrap_or_default())
+}
+
+/// Replaces all of the settings, and the copy kept in app state
+#[tauri::command]
+#[specta::specta]
+pub fn update_general_settings(
+    app: AppHandle,
+    settings: GeneralSettingsStore,
+) -> Result<(), CommandError> {
+    if let Some(fps) = settings.default_fps {
+        if !RecordingOptions::FPS_RANGE.contains(&fps) {
+            return Err(CommandError::InvalidInput(format!(
+                "Frame rate must be between {} and {}, got {fps}",
+                RecordingOptions::FPS_RANGE.start(),
+                RecordingOptions::FPS_RANGE.end()
+            )));
+        }
+    }
+
+    // Changing it moves the existing recordings, which only set_recordings_dir does
+    let settings = GeneralSettingsStore {
+        recordings_dir: GeneralSettingsStore::get(&app)?.and_then(|s| s.recordings_dir),
+        ..settings
+    };
+
+    GeneralSettingsStore::update(&app, |s| *s = settings.clone())?;
+
+    if let Some(state) = app.try_state::<GeneralSettingsState>() {
+        *state.lock().unwrap() = settings;
+    }
+
+    Ok(())
+}
+
 pub type GeneralSettingsState = Mutex<GeneralSettingsStore>;
 
 pub fn init(app: &AppHandle) {
-    println!("Initializing GeneralSettingsStore");
+    tracing::info!("Initializing GeneralSettingsStore");
     // Use unwrap_or_default() to handle potential errors gracefully
     let store = GeneralSettingsStore::get(app)
         .unwrap_or(None)
         .unwrap_or_default();
     app.manage(GeneralSettingsState::new(store));
-    println!("GeneralSettingsState managed");
+    tracing::info!("GeneralSettingsState managed");
 }
@@ -1,10 +1,12 @@
 use crate::{
-    RequestNewScreenshot, RequestRestartRecording, RequestStartRecording, RequestStopRecording,
+    CommandError, RequestNewScreenshot, RequestRestartRecording, RequestStartRecording,
+    RequestStopRecording,
 };
 use global_hotkey::HotKeyState;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Mutex;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut};
@@ -40,15 +42,26 @@ impl Hotkey {
 
         Shortcut::new(Some(modifiers), self.code)
     }
+
+    fn from_shortcut(shortcut: &Shortcut) -> Self {
+        Self {
+            code: shortcut.key,
+            meta: shortcut.mods.contains(Modifiers::META),
+            ctrl: shortcut.mods.contains(Modifiers::CONTROL),
+            alt: shortcut.mods.contains(Modifiers::ALT),
+            shift: shortcut.mods.contains(Modifiers::SHIFT),
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize, Type, PartialEq, Eq, Hash)]
+#[derive(Serialize, Deserialize, Type, PartialEq, Eq, Hash, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
 pub enum HotkeyAction {
     StartRecording,
     StopRecording,
     RestartRecording,
     TakeScreenshot,
+    ToggleRecording,
 }
 
 #[derive(Serialize, Deserialize, Type, Default)]
@@ -64,6 +77,15 @@ impl HotkeysStore {
 
         serde_json::from_value(store).map_err(|e| e.to_string())
     }
+
+    fn save(&self, app: &AppHandle) -> Result<(), String> {
+        let Some(store) = app.get_store("store") else {
+            return Err("Store not found".to_string());
+        };
+
+        store.set("hotkeys", serde_json::json!(self));
+        store.save().map_err(|e| e.to_string())
+    }
 }
 
 pub type HotkeysState = Mutex<HotkeysStore>;
@@ -93,6 +115,10 @@ pub fn init(app: &AppHandle) {
                             HotkeyAction::TakeScreenshot => {
                                 let _ = RequestNewScreenshot.emit(app);
                             }
+                            // starts a recording, or stops the one in progress
+                            HotkeyAction::ToggleRecording => {
+                                let _ = RequestStartRecording.emit(app);
+                            }
                         }
                     }
                 }
@@ -106,7 +132,9 @@ pub fn init(app: &AppHandle) {
     let global_shortcut = app.global_shortcut();
 
     for hotkey in store.hotkeys.values() {
-        global_shortcut.register(hotkey.to_shortcut()).ok();
+        if let Err(e) = global_shortcut.register(hotkey.to_shortcut()) {
+            tracing::error!("Failed to register hotkey: {e}");
+        }
     }
 
     app.manage(Mutex::new(store));
@@ -114,18 +142,50 @@ pub fn init(app: &AppHandle) {
 
 #[tauri::command(async)]
 #[specta::specta]
-pub fn set_hotkey(app: AppHandle, action: HotkeyAction, hotkey: Option<Hotkey>) -> Result<(), ()> {
-    let global_shortcut = app.global_shortcut();
+pub fn set_hotkey(
+    app: AppHandle,
+    action: HotkeyAction,
+    hotkey: Option<Hotkey>,
+) -> Result<(), CommandError> {
     let state = app.state::<HotkeysState>();
-    let mut store = state.lock().unwrap();
+    let mut store = state.lock().map_err(|_| CommandError::StateLocked)?;
 
-    let prev = store.hotkeys.get(&action).cloned();
+    update_hotkey(&app, &mut store, action, hotkey)
+}
 
-    if let Some(hotkey) = hotkey {
-        store.hotkeys.insert(action, hotkey);
-    } else {
-        store.hotkeys.remove(&action);
-    }
+/// Binds the start/stop toggle to a shortcut like `CommandOrControl+Shift+R`
+/// and persists it.
+#[tauri::command(async)]
+#[specta::specta]
+pub fn set_recording_hotkey(app: AppHandle, shortcut: String) -> Result<(), CommandError> {
+    let parsed = Shortcut::from_str(&shortcut)
+        .map_err(|e| CommandError::InvalidInput(format!("Invalid shortcut '{shortcut}': {e}")))?;
+
+    let state = app.state::<HotkeysState>();
+    let mut store = state.lock().map_err(|_| CommandError::StateLocked)?;
+
+    update_hotkey(
+        &app,
+        &mut store,
+        HotkeyAction::ToggleRecording,
+        Some(Hotkey::from_shortcut(&parsed)),
+    )?;
+
+    store.save(&app).map_err(CommandError::InvalidInput)
+}
+
+fn update_hotkey(
+    app: &AppHandle,
+    store: &mut HotkeysStore,
+    action: HotkeyAction,
+    hotkey: Option<Hotkey>,
+) -> Result<(), CommandError> {
+    let global_shortcut = app.global_shortcut();
+
+    let prev = match hotkey {
+        Some(hotkey) => store.hotkeys.insert(action, hotkey),
+        None => store.hotkeys.remove(&action),
+    };
 
     if let Some(prev) = prev {
         if !store.hotkeys.values().any(|h| h == &prev) {
@@ -133,8 +193,31 @@ pub fn set_hotkey(app: AppHandle, action: HotkeyAction, hotkey: Option<Hotkey>)
         }
     }
 
-    if let Some(hotkey) = hotkey {
-        global_shortcut.register(hotkey.to_shortcut()).ok();
+    let Some(hotkey) = hotkey else {
+        return Ok(());
+    };
+
+    // another action of ours may already own it, in which case it's registered
+    let shared = store.hotkeys.values().filter(|h| *h == &hotkey).count() > 1;
+    if shared || global_shortcut.is_registered(hotkey.to_shortcut()) {
+        return Ok(());
+    }
+
+    if let Err(e) = global_shortcut.register(hotkey.to_shortcut()) {
+        // most likely taken by another application, so keep the old binding
+        match prev {
+            Some(prev) => {
+                store.hotkeys.insert(action, prev);
+                global_shortcut.register(prev.to_shortcut()).ok();
+            }
+            None => {
+                store.hotkeys.remove(&action);
+            }
+        }
+
+        return Err(CommandError::InvalidInput(format!(
+            "Shortcut is already in use by another application: {e}"
+        )));
     }
 
     Ok(())
@@ -0,0 +1,198 @@
+use crate::{recording_path, save_thumbnail, CommandError, NewRecordingAdded};
+use cap_project::{AudioMeta, Content, Display, RecordingMeta, SingleSegment};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tauri_specta::Event;
+
+struct VideoInfo {
+    duration_secs: f64,
+    has_audio: bool,
+}
+
+/// Checks that ffmpeg can decode a frame of the video, as opening it isn't enough
+/// to know the codec is supported
+fn probe_video(path: &Path) -> Result<VideoInfo, String> {
+    ffmpeg::init().map_err(|e| e.to_string())?;
+
+    let mut ictx = ffmpeg::format::input(&path)
+        .map_err(|e| format!("{} isn't a video that can be read: {e}", path.display()))?;
+
+    let stream = ictx
+        .streams()
+        .best(ffmpeg::media::Type::Video)
+        .ok_or_else(|| format!("No video stream found in {}", path.display()))?;
+    let stream_index = stream.index();
+    let mut decoder = ffmpeg::codec::context::Context::from_parameters(stream.parameters())
+        .and_then(|c| c.decoder().video())
+        .map_err(|e| format!("Failed to read video stream of {}: {e}", path.display()))?;
+
+    if decoder.width() == 0 || decoder.height() == 0 {
+        return Err(format!("{} has no video dimensions", path.display()));
+    }
+
+    let duration_secs = ictx.duration().max(0) as f64 / f64::from(ffmpeg::ffi::AV_TIME_BASE);
+    if duration_secs <= 0.0 {
+        return Err(format!("{} has no duration", path.display()));
+    }
+
+    let has_audio = ictx.streams().best(ffmpeg::media::Type::Audio).is_some();
+
+    let mut frame = ffmpeg::frame::Video::empty();
+    let mut decoded = false;
+    for (stream, packet) in ictx.packets() {
+        if stream.index() != stream_index {
+            continue;
+        }
+
+        decoder
+            .send_packet(&packet)
+            .map_err(|e| format!("Failed to decode {}: {e}", path.display()))?;
+        if decoder.receive_frame(&mut frame).is_ok() {
+            decoded = true;
+            break;
+        }
+    }
+
+    if !decoded {
+        decoder.send_eof().ok();
+        decoded = decoder.receive_frame(&mut frame).is_ok();
+    }
+
+    if !decoded {
+        return Err(format!(
+            "No frames could be decoded from {}",
+            path.display()
+        ));
+    }
+
+    Ok(VideoInfo {
+        duration_secs,
+        has_audio,
+    })
+}
+
+/// Creates a recording from a video made outside of Cap, so it can be edited and
+/// exported like any other recording. Returns the new recording's id.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_video(app: AppHandle, source: PathBuf) -> Result<String, CommandError> {
+    let info = tokio::task::spawn_blocking({
+        let source = source.clone();
+        move || probe_video(&source)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(CommandError::InvalidInput)?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let project_path = recording_path(&app, &id);
+    let content_dir = project_path.join("content");
+    std::fs::create_dir_all(&content_dir)?;
+
+    let result = import(&app, &source, &project_path, &info).await;
+    if let Err(e) = result {
+        std::fs::remove_dir_all(&project_path).ok();
+        return Err(e.into());
+    }
+
+    Ok(id)
+}
+
+async fn import(
+    app: &AppHandle,
+    source: &Path,
+    project_path: &Path,
+    info: &VideoInfo,
+) -> Result<(), String> {
+    let display_path = project_path.join("content/display.mp4");
+
+    // Other containers such as MKV and WebM are remuxed rather than copied, as the
+    // display track has to be an actual MP4. Codecs MP4 can't hold are rejected.
+    run_ffmpeg(
+        source,
+        &["-map", "0:v:0", "-c:v", "copy", "-f", "mp4"],
+        &display_path,
+    )
+    .await
+    .map_err(|e| format!("Failed to convert {} to MP4: {e}", source.display()))?;
+
+    // Recordings keep their audio separately from the display video
+    if info.has_audio {
+        run_ffmpeg(
+            source,
+            &["-map", "0:a:0", "-c:a", "libmp3lame", "-q:a", "2"],
+            &project_path.join("content/audio-input.mp3"),
+        )
+        .await
+        .map_err(|e| format!("Failed to extract audio: {e}"))?;
+    }
+
+    let pretty_name = source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "Imported Video".to_string());
+
+    RecordingMeta {
+        project_path: project_path.to_path_buf(),
+        pretty_name,
+        created_at: Some(chrono::Local::now().to_rfc3339()),
+        sharing: None,
+        notes: None,
+        tags: vec![],
+        content: Content::SingleSegment {
+            segment: SingleSegment {
+                display: Display {
+                    path: PathBuf::from("content/display.mp4"),
+                    scale_factor: None,
+                    color_space: None,
+                },
+                camera: None,
+                audio: info.has_audio.then(|| AudioMeta {
+                    path: PathBuf::from("content/audio-input.mp3"),
+                }),
+                system_audio: None,
+                cursor: None,
+                clicks: None,
+                keystrokes: None,
+            },
+        },
+    }
+    .save_for_project()
+    .map_err(|e| format!("Failed to save recording meta: {e}"))?;
+
+    // Skips past fade ins without going beyond the end of short videos
+    let thumbnail_at = (info.duration_secs / 2.0).min(1.0);
+    let thumbnail_path = save_thumbnail(app, project_path, display_path, thumbnail_at)
+        .await
+        .map_err(|e| tracing::error!("Failed to create thumbnail: {e}"))
+        .ok();
+
+    NewRecordingAdded {
+        path: project_path.to_path_buf(),
+        thumbnail_path,
+    }
+    .emit(app)
+    .ok();
+
+    Ok(())
+}
+
+async fn run_ffmpeg(input: &Path, args: &[&str], output: &Path) -> Result<(), String> {
+    let mut ffmpeg = cap_ffmpeg_cli::FFmpeg::new();
+    let result = ffmpeg
+        .command
+        .args(["-y", "-loglevel", "error", "-i"])
+        .arg(input)
+        .args(args)
+        .arg(output)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+    if !result.status.success() {
+        return Err(String::from_utf8_lossy(&result.stderr).trim().to_string());
+    }
+
+    Ok(())
+}
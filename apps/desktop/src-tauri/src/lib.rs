@@ -1,9 +1,15 @@
 mod audio;
 mod auth;
+mod bundle;
 mod camera;
+mod clip;
+mod compress;
+mod concat;
 mod flags;
 mod general_settings;
 mod hotkeys;
+mod import;
+mod logging;
 mod notifications;
 mod permissions;
 mod platform;
@@ -22,13 +28,17 @@ use auth::{AuthStore, AuthenticationInvalid};
 use cap_editor::EditorState;
 use cap_editor::{EditorInstance, FRAMES_WS_PATH};
 use cap_media::feeds::{AudioInputFeed, AudioInputSamplesSender};
+use cap_media::platform::Bounds;
 use cap_media::sources::CaptureScreen;
 use cap_media::{
     feeds::{CameraFeed, CameraFrameSender},
     sources::ScreenCaptureTarget,
 };
-use cap_project::{Content, ProjectConfiguration, RecordingMeta, SharingMeta};
-use cap_recording::RecordingOptions;
+use cap_project::{
+    Content, ExportFormat, ProjectConfiguration, RecordingMeta, RecordingMetaPatch, SharingMeta,
+    ThumbnailFormat,
+};
+use cap_recording::{CursorMode, RecordingOptions};
 use cap_rendering::ProjectRecordings;
 // use display::{list_capture_windows, Bounds, CaptureTarget, FPS};
 use general_settings::GeneralSettingsStore;
@@ -46,20 +56,25 @@ use std::{
     future::Future,
     io::{BufReader, BufWriter},
     marker::PhantomData,
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::Command,
     str::FromStr,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::Duration,
 };
-use tauri::{AppHandle, Emitter, Manager, Runtime, State, WindowEvent};
-use tauri_plugin_dialog::DialogExt;
+use tauri::{
+    AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize, Runtime, State, WindowEvent,
+};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons};
 use tauri_plugin_notification::{NotificationExt, PermissionState};
 use tauri_plugin_shell::ShellExt;
 use tauri_specta::Event;
 use tokio::sync::{Mutex, RwLock};
 use upload::{get_s3_config, upload_image, upload_video, S3UploadMeta};
-use windows::{CapWindowId, ShowCapWindow};
+use windows::{monitor_for_bounds, CapWindowId, ShowCapWindow};
 
 #[derive(specta::Type, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -80,6 +95,16 @@ pub struct App {
     current_recording: Option<cap_recording::ActorHandle>,
     #[serde(skip)]
     pre_created_video: Option<PreCreatedVideo>,
+    #[serde(skip)]
+    recording_progress_task: Option<tokio::task::JoinHandle<()>>,
+    #[serde(skip)]
+    recording_countdown: Option<tokio::sync::oneshot::Sender<()>>,
+    /// Position and size of the monitor the occluder window was last fitted to
+    #[serde(skip)]
+    occluder_monitor: Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>,
+    /// Bounds of the recorded windows relative to the occluder window, which leaves them uncovered
+    #[serde(skip)]
+    occluder_holes: Vec<Bounds>,
 }
 
 #[derive(specta::Type, Serialize, Deserialize, Clone, Debug)]
@@ -97,6 +122,41 @@ enum UploadResult {
     UpgradeRequired,
 }
 
+#[derive(Debug, Serialize, Type, thiserror::Error)]
+#[serde(tag = "type", content = "message")]
+pub enum CommandError {
+    #[error("Application state is locked")]
+    StateLocked,
+    #[error("IO: {0}")]
+    Io(String),
+    #[error("FFmpeg: {0}")]
+    Ffmpeg(String),
+    #[error("Media: {0}")]
+    Media(String),
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<String> for CommandError {
+    fn from(e: String) -> Self {
+        Self::Other(e)
+    }
+}
+
+impl From<std::io::Error> for CommandError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e.to_string())
+    }
+}
+
+impl From<cap_media::MediaError> for CommandError {
+    fn from(e: cap_media::MediaError) -> Self {
+        Self::Media(e.to_string())
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, specta::Type)]
 pub struct PreCreatedVideo {
     id: String,
@@ -106,36 +166,224 @@ pub struct PreCreatedVideo {
 
 impl App {
     pub fn set_current_recording(&mut self, actor: cap_recording::ActorHandle) {
-        let current_recording = self.current_recording.insert(actor);
+        if let Some(task) = self.recording_progress_task.take() {
+            task.abort();
+        }
+        self.recording_progress_task = Some(tokio::spawn({
+            let app = self.handle.clone();
+            let actor = actor.clone();
+            async move {
+                let tick = Duration::from_secs(1);
+                let mut interval = tokio::time::interval(tick);
+                interval.tick().await;
+
+                let mut elapsed = Duration::ZERO;
+                let mut warned_low_disk_space = false;
+                loop {
+                    interval.tick().await;
+                    if actor.is_paused() {
+                        continue;
+                    }
 
-        CurrentRecordingChanged.emit(&self.handle).ok();
+                    elapsed += tick;
+                    RecordingProgress {
+                        elapsed_secs: elapsed.as_secs_f64(),
+                    }
+                    .emit(&app)
+                    .ok();
+
+                    // Stopped the same way as from the UI, so the recording is finalized as usual
+                    if actor
+                        .options
+                        .max_duration_secs
+                        .is_some_and(|max| elapsed.as_secs() >= u64::from(max))
+                    {
+                        RecordingAutoStopped {
+                            elapsed_secs: elapsed.as_secs_f64(),
+                        }
+                        .emit(&app)
+                        .ok();
+                        RequestStopRecording.emit(&app).ok();
+                        break;
+                    }
+
+                    if elapsed.as_secs() % DISK_SPACE_CHECK_INTERVAL_SECS != 0 {
+                        continue;
+                    }
 
-        if matches!(
-            current_recording.options.capture_target,
-            ScreenCaptureTarget::Window(_)
-        ) {
-            let _ = ShowCapWindow::WindowCaptureOccluder.show(&self.handle);
+                    let Ok(disk) = disk_info(&recordings_path(&app)) else {
+                        continue;
+                    };
+
+                    if disk.free < CRITICAL_DISK_SPACE {
+                        LowDiskSpace {
+                            free_bytes: disk.free as f64,
+                            stopping: true,
+                        }
+                        .emit(&app)
+                        .ok();
+                        notifications::send_notification(
+                            &app,
+                            NotificationType::RecordingStoppedLowDiskSpace,
+                        );
+                        RequestStopRecording.emit(&app).ok();
+                        break;
+                    }
+
+                    if disk.free < LOW_DISK_SPACE {
+                        if !warned_low_disk_space {
+                            warned_low_disk_space = true;
+                            LowDiskSpace {
+                                free_bytes: disk.free as f64,
+                                stopping: false,
+                            }
+                            .emit(&app)
+                            .ok();
+                            notifications::send_notification(&app, NotificationType::LowDiskSpace);
+                        }
+                    } else {
+                        warned_low_disk_space = false;
+                    }
+                }
+            }
+        }));
+
+        let current_recording = self.current_recording.insert(actor);
+
+        let occluder_disabled = GeneralSettingsStore::get(&self.handle)
+            .ok()
+            .flatten()
+            .is_some_and(|s| s.disable_capture_occluder);
+
+        // The occluder relies on macOS window levels that aren't available on Linux
+        if !cfg!(target_os = "linux")
+            && !occluder_disabled
+            && !current_recording.options.camera_only
+            && matches!(
+                current_recording.options.capture_target,
+                ScreenCaptureTarget::Window(_) | ScreenCaptureTarget::Application(_)
+            )
+        {
+            let target = &current_recording.options.capture_target;
+            let screen_bounds = target.get_bounds();
+            let window_bounds = match target {
+                ScreenCaptureTarget::Window(window) => vec![window.bounds],
+                ScreenCaptureTarget::Application(application) => {
+                    application.windows.iter().map(|w| w.bounds).collect()
+                }
+                _ => vec![],
+            };
+            self.show_occluder_window(screen_bounds, window_bounds);
         } else {
-            self.close_occluder_window();
+            self.hide_occluder_window();
         }
+
+        // Sent once the occluder's holes are known, as the occluder window reads them
+        CurrentRecordingChanged.emit(&self.handle).ok();
     }
 
     pub fn clear_current_recording(&mut self) -> Option<cap_recording::ActorHandle> {
-        self.close_occluder_window();
+        self.hide_occluder_window();
+        self.occluder_holes.clear();
+
+        if let Some(task) = self.recording_progress_task.take() {
+            task.abort();
+        }
 
         self.current_recording.take()
     }
 
-    fn close_occluder_window(&self) {
+    /// Reuses the occluder window from previous recordings, as recreating it flickers.
+    /// It's only moved if the recording is on a different monitor than last time.
+    fn show_occluder_window(&mut self, screen_bounds: Bounds, window_bounds: Vec<Bounds>) {
+        let Ok(Some(monitor)) = monitor_for_bounds(&self.handle, Some(screen_bounds)) else {
+            tracing::warn!("No monitor found for the recorded area, not showing the occluder");
+            return;
+        };
+        let geometry = (*monitor.position(), *monitor.size());
+
+        // Window bounds are in global coordinates, the occluder only covers this monitor
+        let origin = monitor.position().to_logical::<f64>(monitor.scale_factor());
+        self.occluder_holes = window_bounds
+            .into_iter()
+            .map(|bounds| Bounds {
+                x: bounds.x - origin.x,
+                y: bounds.y - origin.y,
+                ..bounds
+            })
+            .collect();
+
         if let Some(window) = CapWindowId::WindowCaptureOccluder.get(&self.handle) {
-            window.close().ok();
+            if self.occluder_monitor != Some(geometry) {
+                window.set_position(geometry.0).ok();
+                window.set_size(geometry.1).ok();
+            }
+            window.show().ok();
+        } else {
+            let occluder = ShowCapWindow::WindowCaptureOccluder { screen_bounds };
+            if occluder.show(&self.handle).is_err() {
+                return;
+            }
+        }
+
+        self.occluder_monitor = Some(geometry);
+    }
+
+    /// Hides the occluder while none of the recorded windows are on the active space,
+    /// so it doesn't cover fullscreen apps or other desktops, and shows it again after
+    #[cfg(target_os = "macos")]
+    fn update_occluder_for_active_space(&self) {
+        // Only set while the occluder is in use
+        if self.occluder_holes.is_empty() {
+            return;
         }
+        let Some(recording) = &self.current_recording else {
+            return;
+        };
+
+        // Only lists windows on the active space
+        let on_screen = cap_media::platform::get_on_screen_windows();
+        let visible = match &recording.options.capture_target {
+            ScreenCaptureTarget::Window(window) => {
+                on_screen.iter().any(|w| w.window_id == window.id)
+            }
+            ScreenCaptureTarget::Application(application) => {
+                on_screen.iter().any(|w| w.process_id == application.id)
+            }
+            _ => return,
+        };
+
+        if visible {
+            if let Some(window) = CapWindowId::WindowCaptureOccluder.get(&self.handle) {
+                window.show().ok();
+            }
+        } else {
+            self.hide_occluder_window();
+        }
+    }
+
+    fn hide_occluder_window(&self) {
+        if let Some(window) = CapWindowId::WindowCaptureOccluder.get(&self.handle) {
+            window.hide().ok();
+        }
+    }
+
+    fn watch_camera_disconnect(&self, feed: &CameraFeed) {
+        let disconnected = feed.disconnected();
+        let app = self.handle.clone();
+
+        tokio::spawn(async move {
+            if let Ok(label) = disconnected.recv_async().await {
+                tracing::warn!("Camera {label} disconnected");
+                CameraDisconnected { label }.emit(&app).ok();
+            }
+        });
     }
 
     async fn set_start_recording_options(
         &mut self,
         new_options: RecordingOptions,
-    ) -> Result<(), String> {
+    ) -> Result<(), CommandError> {
         let options = new_options.clone();
         sentry::configure_scope(move |scope| {
             let mut ctx = std::collections::BTreeMap::new();
@@ -144,6 +392,8 @@ impl App {
                 match options.capture_target {
                     ScreenCaptureTarget::Screen(screen) => screen.name,
                     ScreenCaptureTarget::Window(window) => window.owner_name,
+                    ScreenCaptureTarget::Region { .. } => "Region".to_string(),
+                    ScreenCaptureTarget::Application(application) => application.name,
                 }
                 .into(),
             );
@@ -158,13 +408,17 @@ impl App {
             scope.set_context("recording_options", sentry::protocol::Context::Other(ctx));
         });
 
+        let camera_changed =
+            new_options.camera_label() != self.start_recording_options.camera_label();
+        let had_camera = self.start_recording_options.camera_label().is_some();
+
         match CapWindowId::Camera.get(&self.handle) {
             Some(window) if new_options.camera_label().is_none() => {
-                println!("closing camera window");
+                tracing::info!("closing camera window");
                 window.close().ok();
             }
             None if new_options.camera_label().is_some() => {
-                println!("creating camera window");
+                tracing::info!("creating camera window");
                 ShowCapWindow::Camera {
                     ws_port: self.camera_ws_port,
                 }
@@ -180,17 +434,18 @@ impl App {
                     camera_feed
                         .lock()
                         .await
-                        .switch_cameras(camera_label)
-                        .await
-                        .map_err(|e| e.to_string())?;
+                        .switch_cameras(camera_label, new_options.camera_resolution)
+                        .await?;
                 } else {
-                    self.camera_feed = Some(
-                        CameraFeed::init(camera_label, self.camera_tx.clone())
-                            .await
-                            .map(Mutex::new)
-                            .map(Arc::new)
-                            .map_err(|e| e.to_string())?,
-                    );
+                    let feed = CameraFeed::init(
+                        camera_label,
+                        new_options.camera_resolution,
+                        self.camera_tx.clone(),
+                    )
+                    .await?;
+
+                    self.watch_camera_disconnect(&feed);
+                    self.camera_feed = Some(Arc::new(Mutex::new(feed)));
                 }
             }
             None => {
@@ -198,19 +453,22 @@ impl App {
             }
         }
 
+        // The preview was set up for the previous device's frames, so rebuild it for the new one
+        if camera_changed && had_camera {
+            if let Some(window) = CapWindowId::Camera.get(&self.handle) {
+                window.eval("window.location.reload()").ok();
+            }
+        }
+
         self.audio_input_feed = match new_options.audio_input_name() {
             Some(audio_input_name) => Some(match self.audio_input_feed.take() {
                 Some(mut feed) => {
-                    feed.switch_input(audio_input_name)
-                        .await
-                        .map_err(|e| e.to_string())?;
+                    feed.switch_input(audio_input_name).await?;
 
                     feed
                 }
                 None => {
-                    let feed = AudioInputFeed::init(audio_input_name)
-                        .await
-                        .map_err(|e| e.to_string())?;
+                    let feed = AudioInputFeed::init(audio_input_name).await?;
 
                     feed.add_sender(self.audio_input_tx.clone()).await.unwrap();
 
@@ -331,6 +589,14 @@ pub struct RecordingOptionsChanged;
 #[derive(Deserialize, specta::Type, Serialize, tauri_specta::Event, Debug, Clone)]
 pub struct NewRecordingAdded {
     path: PathBuf,
+    thumbnail_path: Option<PathBuf>,
+}
+
+#[derive(Deserialize, specta::Type, Serialize, tauri_specta::Event, Debug, Clone)]
+pub struct ThumbnailReady {
+    video_id: String,
+    path: PathBuf,
+    thumbnail_path: PathBuf,
 }
 
 #[derive(Deserialize, specta::Type, Serialize, tauri_specta::Event, Debug, Clone)]
@@ -341,6 +607,16 @@ pub struct NewScreenshotAdded {
 #[derive(Deserialize, specta::Type, Serialize, tauri_specta::Event, Debug, Clone)]
 pub struct RecordingStarted;
 
+#[derive(Deserialize, specta::Type, Serialize, tauri_specta::Event, Debug, Clone)]
+pub struct RecordingCountdown {
+    remaining: u32,
+}
+
+#[derive(Deserialize, specta::Type, Serialize, tauri_specta::Event, Debug, Clone)]
+pub struct RecordingProgress {
+    elapsed_secs: f64,
+}
+
 #[derive(Deserialize, specta::Type, Serialize, tauri_specta::Event, Debug, Clone)]
 pub struct RecordingStopped {
     path: PathBuf,
@@ -363,6 +639,25 @@ pub struct RequestOpenSettings {
     page: String,
 }
 
+#[derive(Deserialize, specta::Type, Serialize, tauri_specta::Event, Debug, Clone)]
+pub struct CameraDisconnected {
+    label: String,
+}
+
+#[derive(Deserialize, specta::Type, Serialize, tauri_specta::Event, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LowDiskSpace {
+    free_bytes: f64,
+    /// The recording is being stopped as the disk is almost full
+    stopping: bool,
+}
+
+#[derive(Deserialize, specta::Type, Serialize, tauri_specta::Event, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingAutoStopped {
+    elapsed_secs: f64,
+}
+
 #[derive(Deserialize, specta::Type, Serialize, tauri_specta::Event, Debug, Clone)]
 pub struct NewNotification {
     title: String,
@@ -374,7 +669,9 @@ type MutableState<'a, T> = State<'a, Arc<RwLock<T>>>;
 
 #[tauri::command]
 #[specta::specta]
-async fn get_recording_options(state: MutableState<'_, App>) -> Result<RecordingOptions, ()> {
+async fn get_recording_options(
+    state: MutableState<'_, App>,
+) -> Result<RecordingOptions, CommandError> {
     let mut state = state.write().await;
 
     // If there's a saved audio input but no feed, initialize it
@@ -382,7 +679,7 @@ async fn get_recording_options(state: MutableState<'_, App>) -> Result<Recording
         if state.audio_input_feed.is_none() {
             state.audio_input_feed = if let Ok(feed) = AudioInputFeed::init(audio_input_name)
                 .await
-                .map_err(|error| eprintln!("{error}"))
+                .map_err(|error| tracing::error!("{error}"))
             {
                 feed.add_sender(state.audio_input_tx.clone()).await.unwrap();
                 Some(feed)
@@ -400,7 +697,17 @@ async fn get_recording_options(state: MutableState<'_, App>) -> Result<Recording
 async fn set_recording_options(
     state: MutableState<'_, App>,
     options: RecordingOptions,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
+    if let Some(fps) = options.fps {
+        if !RecordingOptions::FPS_RANGE.contains(&fps) {
+            return Err(CommandError::InvalidInput(format!(
+                "Frame rate must be between {} and {}, got {fps}",
+                RecordingOptions::FPS_RANGE.start(),
+                RecordingOptions::FPS_RANGE.end()
+            )));
+        }
+    }
+
     state
         .write()
         .await
@@ -410,6 +717,58 @@ async fn set_recording_options(
     Ok(())
 }
 
+/// Re-acquires the selected camera and rebuilds its preview,
+/// eg. after the device was unplugged and reconnected
+#[tauri::command]
+#[specta::specta]
+async fn refresh_camera_window(
+    app: AppHandle,
+    state: MutableState<'_, App>,
+) -> Result<(), CommandError> {
+    let mut state = state.write().await;
+
+    let Some(camera_label) = state
+        .start_recording_options
+        .camera_label()
+        .map(|l| l.to_string())
+    else {
+        return Ok(());
+    };
+
+    if state.current_recording.is_some() {
+        return Err(CommandError::InvalidInput(
+            "Can't refresh the camera while recording".to_string(),
+        ));
+    }
+
+    // Drop the old feed first so its capture thread lets go of the device
+    state.camera_feed = None;
+    let feed = CameraFeed::init(
+        &camera_label,
+        state.start_recording_options.camera_resolution,
+        state.camera_tx.clone(),
+    )
+    .await?;
+
+    state.watch_camera_disconnect(&feed);
+    state.camera_feed = Some(Arc::new(Mutex::new(feed)));
+
+    match CapWindowId::Camera.get(&app) {
+        Some(window) => {
+            window.eval("window.location.reload()").ok();
+        }
+        None => {
+            ShowCapWindow::Camera {
+                ws_port: state.camera_ws_port,
+            }
+            .show(&app)
+            .ok();
+        }
+    }
+
+    Ok(())
+}
+
 type Bruh<T> = (T,);
 
 #[derive(Serialize, Type)]
@@ -434,17 +793,22 @@ impl<T: Serialize> JsonValue<T> {
 #[serde(rename_all = "camelCase")]
 pub struct RecordingInfo {
     capture_target: ScreenCaptureTarget,
+    paused: bool,
+    /// Areas the occluder window leaves uncovered, relative to the window
+    occluder_holes: Vec<Bounds>,
 }
 
 #[tauri::command]
 #[specta::specta]
 async fn get_current_recording(
     state: MutableState<'_, App>,
-) -> Result<JsonValue<Option<RecordingInfo>>, ()> {
+) -> Result<JsonValue<Option<RecordingInfo>>, CommandError> {
     let state = state.read().await;
     Ok(JsonValue::new(&state.current_recording.as_ref().map(|r| {
         RecordingInfo {
             capture_target: r.options.capture_target.clone(),
+            paused: r.is_paused(),
+            occluder_holes: state.occluder_holes.clone(),
         }
     })))
 }
@@ -454,22 +818,24 @@ pub struct CurrentRecordingChanged;
 
 async fn create_screenshot(
     input: PathBuf,
-    output: PathBuf,
     size: Option<(u32, u32)>,
-) -> Result<(), String> {
-    println!(
-        "Creating screenshot: input={:?}, output={:?}, size={:?}",
-        input, output, size
+    at_secs: f64,
+) -> Result<image::RgbImage, String> {
+    tracing::info!(
+        "Creating screenshot: input={:?}, size={:?}, at={}s",
+        input,
+        size,
+        at_secs
     );
 
-    let result: Result<(), String> = tokio::task::spawn_blocking(move || -> Result<(), String> {
+    let result = tokio::task::spawn_blocking(move || -> Result<image::RgbImage, String> {
         ffmpeg::init().map_err(|e| {
-            eprintln!("Failed to initialize ffmpeg: {}", e);
+            tracing::error!("Failed to initialize ffmpeg: {}", e);
             e.to_string()
         })?;
 
         let mut ictx = ffmpeg::format::input(&input).map_err(|e| {
-            eprintln!("Failed to create input context: {}", e);
+            tracing::error!("Failed to create input context: {}", e);
             e.to_string()
         })?;
         let input_stream = ictx
@@ -477,18 +843,19 @@ async fn create_screenshot(
             .best(ffmpeg::media::Type::Video)
             .ok_or("No video stream found")?;
         let video_stream_index = input_stream.index();
-        println!("Found video stream at index {}", video_stream_index);
+        let time_base = f64::from(input_stream.time_base());
+        tracing::info!("Found video stream at index {}", video_stream_index);
 
         let mut decoder =
             ffmpeg::codec::context::Context::from_parameters(input_stream.parameters())
                 .map_err(|e| {
-                    eprintln!("Failed to create decoder context: {}", e);
+                    tracing::error!("Failed to create decoder context: {}", e);
                     e.to_string()
                 })?
                 .decoder()
                 .video()
                 .map_err(|e| {
-                    eprintln!("Failed to create video decoder: {}", e);
+                    tracing::error!("Failed to create video decoder: {}", e);
                     e.to_string()
                 })?;
 
@@ -502,24 +869,45 @@ async fn create_screenshot(
             ffmpeg::software::scaling::flag::Flags::BILINEAR,
         )
         .map_err(|e| {
-            eprintln!("Failed to create scaler: {}", e);
+            tracing::error!("Failed to create scaler: {}", e);
             e.to_string()
         })?;
 
-        println!("Decoder and scaler initialized");
+        tracing::info!("Decoder and scaler initialized");
+
+        if at_secs > 0.0 {
+            let duration_secs = ictx.duration() as f64 / f64::from(ffmpeg::ffi::AV_TIME_BASE);
+            if ictx.duration() > 0 && at_secs > duration_secs {
+                return Err(format!(
+                    "Timestamp {at_secs}s is past the end of the {duration_secs:.2}s video"
+                ));
+            }
+
+            // Seeks to the keyframe before the timestamp, the frames in between are decoded below
+            let seek_ts = (at_secs * f64::from(ffmpeg::ffi::AV_TIME_BASE)) as i64;
+            ictx.seek(seek_ts, ..seek_ts).map_err(|e| {
+                tracing::error!("Failed to seek: {}", e);
+                e.to_string()
+            })?;
+        }
 
         let mut frame = ffmpeg::frame::Video::empty();
         for (stream, packet) in ictx.packets() {
             if stream.index() == video_stream_index {
                 decoder.send_packet(&packet).map_err(|e| {
-                    eprintln!("Failed to send packet to decoder: {}", e);
+                    tracing::error!("Failed to send packet to decoder: {}", e);
                     e.to_string()
                 })?;
                 if decoder.receive_frame(&mut frame).is_ok() {
-                    println!("Frame received, scaling...");
+                    let frame_ts = frame.timestamp().or(frame.pts()).unwrap_or(0);
+                    if (frame_ts as f64) * time_base < at_secs {
+                        continue;
+                    }
+
+                    tracing::info!("Frame received, scaling...");
                     let mut rgb_frame = ffmpeg::frame::Video::empty();
                     scaler.run(&frame, &mut rgb_frame).map_err(|e| {
-                        eprintln!("Failed to scale frame: {}", e);
+                        tracing::error!("Failed to scale frame: {}", e);
                         e.to_string()
                     })?;
 
@@ -540,21 +928,13 @@ async fn create_screenshot(
 
                     let img = image::RgbImage::from_raw(width as u32, height as u32, img_buffer)
                         .ok_or("Failed to create image from frame data")?;
-                    println!("Saving image to {:?}", output);
-
-                    img.save_with_format(&output, image::ImageFormat::Jpeg)
-                        .map_err(|e| {
-                            eprintln!("Failed to save image: {}", e);
-                            e.to_string()
-                        })?;
-
-                    println!("Screenshot created successfully");
-                    return Ok(());
+                    tracing::info!("Screenshot created successfully");
+                    return Ok(img);
                 }
             }
         }
 
-        eprintln!("Failed to create screenshot: No suitable frame found");
+        tracing::error!("Failed to create screenshot: No suitable frame found");
         Err("Failed to create screenshot".to_string())
     })
     .await
@@ -563,15 +943,43 @@ async fn create_screenshot(
     result
 }
 
+/// The screenshot shown for a recording, in whichever format it was saved in
+fn thumbnail_path(project_path: &Path) -> Option<PathBuf> {
+    ThumbnailFormat::ALL
+        .iter()
+        .map(|format| format.path(project_path))
+        .find(|path| path.exists())
+}
+
+/// Saves a frame of `display_path` as the recording's thumbnail, using the format and quality
+/// from the settings. Thumbnails previously saved in other formats are removed.
+async fn save_thumbnail(
+    app: &AppHandle,
+    project_path: &Path,
+    display_path: PathBuf,
+    at_secs: f64,
+) -> Result<PathBuf, String> {
+    let settings = GeneralSettingsStore::get(app)?.unwrap_or_default();
+
+    let image = create_screenshot(display_path, None, at_secs).await?;
+
+    cap_export::save_screenshot(&image, project_path, settings.thumbnail_options()).map_err(|e| {
+        tracing::error!("Failed to save image: {}", e);
+        e.to_string()
+    })
+}
+
 async fn create_thumbnail(input: PathBuf, output: PathBuf, size: (u32, u32)) -> Result<(), String> {
-    println!(
+    tracing::info!(
         "Creating thumbnail: input={:?}, output={:?}, size={:?}",
-        input, output, size
+        input,
+        output,
+        size
     );
 
     tokio::task::spawn_blocking(move || -> Result<(), String> {
         let img = image::open(&input).map_err(|e| {
-            eprintln!("Failed to open image: {}", e);
+            tracing::error!("Failed to open image: {}", e);
             e.to_string()
         })?;
 
@@ -605,20 +1013,25 @@ async fn create_thumbnail(input: PathBuf, output: PathBuf, size: (u32, u32)) ->
         thumbnail
             .save_with_format(&output, image::ImageFormat::Png)
             .map_err(|e| {
-                eprintln!("Failed to save thumbnail: {}", e);
+                tracing::error!("Failed to save thumbnail: {}", e);
                 e.to_string()
             })?;
 
-        println!("Thumbnail created successfully");
+        tracing::info!("Thumbnail created successfully");
         Ok(())
     })
     .await
     .map_err(|e| format!("Task join error: {}", e))?
 }
 
-async fn get_rendered_video_path(app: AppHandle, video_id: String) -> Result<PathBuf, String> {
-    let editor_instance = upsert_editor_instance(&app, video_id.clone()).await;
-    let output_path = editor_instance.meta().output_path();
+async fn get_rendered_video_path(
+    app: AppHandle,
+    video_id: String,
+    format: ExportFormat,
+) -> Result<PathBuf, String> {
+    // Read the meta directly so a broken recording-meta.json is reported instead of panicking
+    let meta = RecordingMeta::load_for_project(&recording_path(&app, &video_id))?;
+    let output_path = meta.output_path_for(format);
 
     // If the file doesn't exist, return an error to trigger the progress-enabled path
     if !output_path.exists() {
@@ -630,17 +1043,19 @@ async fn get_rendered_video_path(app: AppHandle, video_id: String) -> Result<Pat
 
 #[tauri::command]
 #[specta::specta]
-async fn copy_file_to_path(app: AppHandle, src: String, dst: String) -> Result<(), String> {
-    println!("Attempting to copy file from {} to {}", src, dst);
+async fn copy_file_to_path(app: AppHandle, src: String, dst: String) -> Result<(), CommandError> {
+    tracing::info!("Attempting to copy file from {} to {}", src, dst);
 
     // Determine if this is a screenshot based on the path
     let is_screenshot = src.contains("screenshots/");
 
     match tokio::fs::copy(&src, &dst).await {
         Ok(bytes) => {
-            println!(
+            tracing::info!(
                 "Successfully copied {} bytes from {} to {}",
-                bytes, src, dst
+                bytes,
+                src,
+                dst
             );
             // Send appropriate success notification
             notifications::send_notification(
@@ -654,7 +1069,7 @@ async fn copy_file_to_path(app: AppHandle, src: String, dst: String) -> Result<(
             Ok(())
         }
         Err(e) => {
-            eprintln!("Failed to copy file from {} to {}: {}", src, dst, e);
+            tracing::error!("Failed to copy file from {} to {}: {}", src, dst, e);
             notifications::send_notification(
                 &app,
                 if is_screenshot {
@@ -663,94 +1078,129 @@ async fn copy_file_to_path(app: AppHandle, src: String, dst: String) -> Result<(
                     notifications::NotificationType::VideoSaveFailed
                 },
             );
-            Err(e.to_string())
+            Err(e.into())
         }
     }
 }
 
 #[tauri::command]
 #[specta::specta]
-async fn copy_screenshot_to_clipboard(app: AppHandle, path: PathBuf) -> Result<(), String> {
-    println!("Copying screenshot to clipboard: {:?}", path);
+async fn copy_screenshot_to_clipboard(app: AppHandle, path: PathBuf) -> Result<(), CommandError> {
+    tracing::info!("Copying screenshot to clipboard: {:?}", path);
 
     let image_data = match tokio::fs::read(&path).await {
         Ok(data) => data,
         Err(e) => {
-            println!("Failed to read screenshot file: {}", e);
+            tracing::error!("Failed to read screenshot file: {}", e);
             notifications::send_notification(
                 &app,
                 notifications::NotificationType::ScreenshotCopyFailed,
             );
-            return Err(format!("Failed to read screenshot file: {}", e));
+            return Err(CommandError::Io(format!(
+                "Failed to read screenshot file: {}",
+                e
+            )));
         }
     };
 
-    #[cfg(target_os = "macos")]
-    {
-        use cocoa::appkit::{NSImage, NSPasteboard};
-        use cocoa::base::{id, nil};
-        use cocoa::foundation::{NSArray, NSData};
-        use objc::rc::autoreleasepool;
+    if let Err(e) = write_image_to_clipboard(&image_data) {
+        notifications::send_notification(
+            &app,
+            notifications::NotificationType::ScreenshotCopyFailed,
+        );
+        return Err(e.into());
+    }
 
-        let result = unsafe {
-            autoreleasepool(|| {
-                let pasteboard: id = NSPasteboard::generalPasteboard(nil);
-                NSPasteboard::clearContents(pasteboard);
+    notifications::send_notification(
+        &app,
+        notifications::NotificationType::ScreenshotCopiedToClipboard,
+    );
 
-                let ns_data = NSData::dataWithBytes_length_(
-                    nil,
-                    image_data.as_ptr() as *const std::os::raw::c_void,
-                    image_data.len() as u64,
-                );
+    Ok(())
+}
 
-                let image = NSImage::initWithData_(NSImage::alloc(nil), ns_data);
-                if image != nil {
-                    NSPasteboard::writeObjects(pasteboard, NSArray::arrayWithObject(nil, image));
-                    Ok(())
-                } else {
-                    Err("Failed to create NSImage from data".to_string())
-                }
-            })
-        };
+/// Puts encoded image data on the clipboard as an image, so it can be pasted into other apps
+#[cfg(target_os = "macos")]
+fn write_image_to_clipboard(image_data: &[u8]) -> Result<(), String> {
+    use cocoa::appkit::{NSImage, NSPasteboard};
+    use cocoa::base::{id, nil};
+    use cocoa::foundation::{NSArray, NSData};
+    use objc::rc::autoreleasepool;
 
-        if let Err(e) = result {
-            notifications::send_notification(
-                &app,
-                notifications::NotificationType::ScreenshotCopyFailed,
+    unsafe {
+        autoreleasepool(|| {
+            let pasteboard: id = NSPasteboard::generalPasteboard(nil);
+            NSPasteboard::clearContents(pasteboard);
+
+            let ns_data = NSData::dataWithBytes_length_(
+                nil,
+                image_data.as_ptr() as *const std::os::raw::c_void,
+                image_data.len() as u64,
             );
-            return Err(e);
-        }
 
-        notifications::send_notification(
-            &app,
-            notifications::NotificationType::ScreenshotCopiedToClipboard,
-        );
+            let image = NSImage::initWithData_(NSImage::alloc(nil), ns_data);
+            if image != nil {
+                NSPasteboard::writeObjects(pasteboard, NSArray::arrayWithObject(nil, image));
+                Ok(())
+            } else {
+                Err("Failed to create NSImage from data".to_string())
+            }
+        })
     }
+}
 
-    // TODO(Ilya) (Windows) Add support
-    #[cfg(not(target_os = "macos"))]
-    {
-        notifications::send_notification(
-            &app,
-            notifications::NotificationType::ScreenshotCopyFailed,
-        );
-        return Err("Clipboard operations are only supported on macOS".to_string());
+// TODO(Ilya) (Windows) Add support
+#[cfg(not(target_os = "macos"))]
+fn write_image_to_clipboard(_image_data: &[u8]) -> Result<(), String> {
+    Err("Clipboard operations are only supported on macOS".to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn copy_frame_to_clipboard(
+    app: AppHandle,
+    video_id: String,
+    at_secs: f64,
+) -> Result<(), CommandError> {
+    if !at_secs.is_finite() || at_secs < 0.0 {
+        return Err(CommandError::InvalidInput(format!(
+            "Invalid frame timestamp {at_secs}"
+        )));
     }
 
-    Ok(())
+    let meta = RecordingMeta::load_for_project(&recording_path(&app, &video_id))?;
+
+    let result = async {
+        let frame = extract_frame_png(&display_path(&meta), at_secs, None).await?;
+        write_image_to_clipboard(&frame)
+    }
+    .await;
+
+    notifications::send_notification(
+        &app,
+        if result.is_ok() {
+            notifications::NotificationType::FrameCopiedToClipboard
+        } else {
+            notifications::NotificationType::FrameCopyFailed
+        },
+    );
+
+    Ok(result?)
 }
 
 #[tauri::command]
 #[specta::specta]
-async fn open_file_path(_app: AppHandle, path: PathBuf) -> Result<(), String> {
-    let path_str = path.to_str().ok_or("Invalid path")?;
+async fn open_file_path(_app: AppHandle, path: PathBuf) -> Result<(), CommandError> {
+    let path_str = path
+        .to_str()
+        .ok_or_else(|| CommandError::InvalidInput("Invalid path".to_string()))?;
 
     #[cfg(target_os = "windows")]
     {
         Command::new("explorer")
             .args(["/select,", path_str])
             .spawn()
-            .map_err(|e| format!("Failed to open folder: {}", e))?;
+            .map_err(|e| CommandError::Io(format!("Failed to open folder: {}", e)))?;
     }
 
     #[cfg(target_os = "macos")]
@@ -759,7 +1209,7 @@ async fn open_file_path(_app: AppHandle, path: PathBuf) -> Result<(), String> {
             .arg("-R")
             .arg(path_str)
             .spawn()
-            .map_err(|e| format!("Failed to open folder: {}", e))?;
+            .map_err(|e| CommandError::Io(format!("Failed to open folder: {}", e)))?;
     }
 
     #[cfg(target_os = "linux")]
@@ -767,12 +1217,60 @@ async fn open_file_path(_app: AppHandle, path: PathBuf) -> Result<(), String> {
         Command::new("xdg-open")
             .arg(
                 path.parent()
-                    .ok_or("Invalid path")?
-                    .to_str()
-                    .ok_or("Invalid path")?,
+                    .and_then(|parent| parent.to_str())
+                    .ok_or_else(|| CommandError::InvalidInput("Invalid path".to_string()))?,
             )
             .spawn()
-            .map_err(|e| format!("Failed to open folder: {}", e))?;
+            .map_err(|e| CommandError::Io(format!("Failed to open folder: {}", e)))?;
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn reveal_recording_in_finder(app: AppHandle, video_id: String) -> Result<(), CommandError> {
+    let recording_dir = recording_path(&app, &video_id);
+
+    if !recording_dir.is_dir() {
+        return Err(CommandError::InvalidInput(format!(
+            "Recording {} does not exist",
+            video_id
+        )));
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        Command::new("explorer")
+            .arg(&recording_dir)
+            .spawn()
+            .map_err(|e| CommandError::Io(format!("Failed to open folder: {}", e)))?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let output_path = RecordingMeta::load_for_project(&recording_dir)
+            .map(|meta| meta.output_path())
+            .ok()
+            .filter(|path| path.exists());
+
+        let mut command = Command::new("open");
+        match output_path {
+            Some(output_path) => command.arg("-R").arg(output_path),
+            None => command.arg(&recording_dir),
+        };
+
+        command
+            .spawn()
+            .map_err(|e| CommandError::Io(format!("Failed to open folder: {}", e)))?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        Command::new("xdg-open")
+            .arg(&recording_dir)
+            .spawn()
+            .map_err(|e| CommandError::Io(format!("Failed to open folder: {}", e)))?;
     }
 
     Ok(())
@@ -796,6 +1294,11 @@ impl EditorStateChanged {
     }
 }
 
+#[derive(Serialize, specta::Type, tauri_specta::Event, Debug, Clone)]
+struct EditorRenderError {
+    message: String,
+}
+
 #[tauri::command]
 #[specta::specta]
 async fn start_playback(app: AppHandle, video_id: String) {
@@ -826,6 +1329,7 @@ struct SerializedEditorInstance {
     recordings: ProjectRecordings,
     path: PathBuf,
     pretty_name: String,
+    thumbnail_path: Option<PathBuf>,
 }
 
 #[tauri::command]
@@ -833,14 +1337,25 @@ struct SerializedEditorInstance {
 async fn create_editor_instance(
     app: AppHandle,
     video_id: String,
-) -> Result<SerializedEditorInstance, String> {
+) -> Result<SerializedEditorInstance, CommandError> {
+    let meta = RecordingMeta::load_for_project(&recording_path(&app, &video_id))?;
+    if export::raw_track_paths(&meta)
+        .iter()
+        .any(|path| !path.exists())
+    {
+        return Err(CommandError::InvalidInput(format!(
+            "The raw tracks of '{}' were removed after rendering, so it can't be edited anymore",
+            meta.pretty_name
+        )));
+    }
+
     let editor_instance = upsert_editor_instance(&app, video_id).await;
 
     // Load the RecordingMeta to get the pretty name
     let meta = RecordingMeta::load_for_project(&editor_instance.project_path)
         .map_err(|e| format!("Failed to load recording meta: {}", e))?;
 
-    println!("Pretty name: {}", meta.pretty_name);
+    tracing::info!("Pretty name: {}", meta.pretty_name);
 
     Ok(SerializedEditorInstance {
         frames_socket_url: format!("ws://localhost:{}{FRAMES_WS_PATH}", editor_instance.ws_port),
@@ -852,13 +1367,56 @@ async fn create_editor_instance(
         recordings: editor_instance.recordings.clone(),
         path: editor_instance.project_path.clone(),
         pretty_name: meta.pretty_name,
+        thumbnail_path: thumbnail_path(&editor_instance.project_path),
     })
 }
 
+#[derive(Deserialize, specta::Type, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ClipboardFormat {
+    /// A reference to the file, which pastes as a file in Finder and Explorer
+    #[default]
+    FileReference,
+    /// The raw video bytes, which some apps paste as an inline video. macOS only.
+    VideoData,
+}
+
 #[tauri::command]
 #[specta::specta]
-async fn copy_video_to_clipboard(app: AppHandle, path: String) -> Result<(), String> {
-    println!("copying");
+async fn copy_video_to_clipboard(
+    app: AppHandle,
+    path: String,
+    format: Option<ClipboardFormat>,
+) -> Result<(), CommandError> {
+    tracing::info!("copying");
+
+    let format = format.unwrap_or_default();
+
+    #[cfg(target_os = "macos")]
+    if format == ClipboardFormat::VideoData {
+        if let Err(e) = platform::write_video_data_to_pasteboard(&path) {
+            tracing::error!("Failed to copy to clipboard: {}", e);
+            notifications::send_notification(
+                &app,
+                notifications::NotificationType::VideoCopyFailed,
+            );
+            return Err(e.into());
+        }
+
+        notifications::send_notification(
+            &app,
+            notifications::NotificationType::VideoCopiedToClipboard,
+        );
+
+        return Ok(());
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    if format == ClipboardFormat::VideoData {
+        tracing::info!(
+            "Video data clipboard format is only supported on macOS, copying file instead"
+        );
+    }
 
     #[cfg(target_os = "macos")]
     {
@@ -905,15 +1463,36 @@ async fn copy_video_to_clipboard(app: AppHandle, path: String) -> Result<(), Str
         };
 
         if let Err(e) = result {
-            println!("Failed to copy to clipboard: {}", e);
+            tracing::error!("Failed to copy to clipboard: {}", e);
             notifications::send_notification(
                 &app,
                 notifications::NotificationType::VideoCopyFailed,
             );
-            return Err(e);
+            return Err(e.into());
         }
     }
 
+    #[cfg(target_os = "windows")]
+    {
+        if let Err(e) = platform::write_file_to_clipboard(&path) {
+            tracing::error!("Failed to copy to clipboard: {}", e);
+            notifications::send_notification(
+                &app,
+                notifications::NotificationType::VideoCopyFailed,
+            );
+            return Err(e.into());
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        notifications::send_notification(&app, notifications::NotificationType::VideoCopyFailed);
+        return Err(CommandError::Other(
+            "Copying videos to the clipboard is not supported on this platform".to_string(),
+        ));
+    }
+
+    #[allow(unreachable_code)]
     notifications::send_notification(
         &app,
         notifications::NotificationType::VideoCopiedToClipboard,
@@ -926,6 +1505,26 @@ async fn copy_video_to_clipboard(app: AppHandle, path: String) -> Result<(), Str
 pub struct VideoRecordingMetadata {
     duration: f64,
     size: f64,
+    width: Option<u32>,
+    height: Option<u32>,
+    fps: Option<f64>,
+    codec: Option<String>,
+    bitrate: Option<u32>,
+    /// Clockwise rotation in degrees from the video track's display matrix.
+    /// `width` and `height` are the stored size, before it's rotated.
+    rotation: Option<u32>,
+    /// Color primaries and transfer function, eg. "BT2020 / SMPTE2084"
+    color_space: Option<String>,
+    bit_depth: Option<u32>,
+    /// Whether the video uses a PQ or HLG transfer function
+    hdr: bool,
+}
+
+/// Clockwise rotation of a track from the first row of its transformation matrix,
+/// snapped to a quarter turn
+fn track_rotation(a: i32, b: i32) -> u32 {
+    let angle = (b as f64).atan2(a as f64).to_degrees();
+    ((angle / 90.0).round() as i32).rem_euclid(4) as u32 * 90
 }
 
 #[tauri::command]
@@ -934,95 +1533,467 @@ async fn get_video_metadata(
     app: AppHandle,
     video_id: String,
     video_type: Option<VideoType>,
-) -> Result<VideoRecordingMetadata, String> {
+) -> Result<VideoRecordingMetadata, CommandError> {
     let video_id = if video_id.ends_with(".cap") {
         video_id.trim_end_matches(".cap").to_string()
     } else {
         video_id
     };
 
-    let project_path = app
-        .path()
-        .app_data_dir()
-        .unwrap()
-        .join("recordings")
-        .join(format!("{}.cap", video_id));
+    let project_path = recording_path(&app, &video_id);
+
+    let meta = RecordingMeta::load_for_project(&project_path)?;
+
+    fn content_paths(project_path: &PathBuf, meta: &RecordingMeta) -> Vec<PathBuf> {
+        match &meta.content {
+            Content::SingleSegment { segment } => {
+                vec![segment.path(&meta, &segment.display.path)]
+            }
+            Content::MultipleSegments { inner } => inner
+                .segments
+                .iter()
+                .map(|s| inner.path(&meta, &s.display.path))
+                .collect(),
+        }
+    }
+
+    let output_video_path = project_path.join("output").join("result.mp4");
+
+    let paths = match video_type {
+        // Once the raw tracks have been removed the output has the same duration
+        Some(VideoType::Screen)
+            if export::raw_track_paths(&meta)
+                .iter()
+                .any(|path| !path.exists())
+                && output_video_path.exists() =>
+        {
+            vec![output_video_path]
+        }
+        Some(VideoType::Screen) => content_paths(&project_path, &meta),
+        Some(VideoType::Output) | None => {
+            tracing::info!("Using output video path: {:?}", output_video_path);
+            if output_video_path.exists() {
+                vec![output_video_path]
+            } else {
+                tracing::warn!("Output video not found, falling back to screen paths");
+                content_paths(&project_path, &meta)
+            }
+        }
+    };
+
+    let mut ret = VideoRecordingMetadata {
+        size: 0.0,
+        duration: 0.0,
+        width: None,
+        height: None,
+        fps: None,
+        codec: None,
+        bitrate: None,
+        rotation: None,
+        color_space: None,
+        bit_depth: None,
+        hdr: false,
+    };
+
+    for path in paths {
+        let file = File::open(&path)
+            .map_err(|e| CommandError::Io(format!("Failed to open video file: {}", e)))?;
+
+        if ret.color_space.is_none() {
+            if let Some(color) = cap_rendering::tonemap::probe_color(&path) {
+                ret.color_space = Some(color.name());
+                ret.bit_depth = Some(color.bit_depth);
+                ret.hdr = color.hdr().is_some();
+            }
+        }
+
+        ret.size += (file
+            .metadata()
+            .map_err(|e| CommandError::Io(format!("Failed to get file metadata: {}", e)))?
+            .len() as f64)
+            / (1024.0 * 1024.0);
+
+        let reader = BufReader::new(file);
+        let file_size = path
+            .metadata()
+            .map_err(|e| CommandError::Io(format!("Failed to get file metadata: {}", e)))?
+            .len();
+
+        ret.duration += match Mp4Reader::read_header(reader, file_size) {
+            Ok(mp4) => {
+                // Track details are taken from the first file that has a video track
+                if ret.codec.is_none() {
+                    if let Some(track) = mp4
+                        .tracks()
+                        .values()
+                        .find(|t| matches!(t.track_type(), Ok(mp4::TrackType::Video)))
+                    {
+                        ret.width = Some(track.width() as u32).filter(|w| *w > 0);
+                        ret.height = Some(track.height() as u32).filter(|h| *h > 0);
+                        ret.fps = Some(track.frame_rate()).filter(|fps| *fps > 0.0);
+                        ret.codec = track.media_type().ok().map(|t| t.to_string());
+                        ret.bitrate = Some(track.bitrate()).filter(|b| *b > 0);
+                        let matrix = &track.trak.tkhd.matrix;
+                        ret.rotation = Some(track_rotation(matrix.a, matrix.b));
+                    }
+                }
+
+                mp4.duration().as_secs_f64()
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to read MP4 header: {}. Falling back to default duration.",
+                    e
+                );
+                // Return a default duration (e.g., 0.0) or try to estimate it based on file size
+                0.0 // or some estimated value
+            }
+        };
+    }
+
+    Ok(ret)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn generate_thumbnail(
+    app: AppHandle,
+    video_id: String,
+    at_secs: f64,
+) -> Result<(), CommandError> {
+    if !at_secs.is_finite() || at_secs < 0.0 {
+        return Err(CommandError::InvalidInput(format!(
+            "Invalid thumbnail timestamp {at_secs}"
+        )));
+    }
+
+    let project_path = recording_path(&app, &video_id);
+    let meta = RecordingMeta::load_for_project(&project_path)?;
+
+    save_thumbnail(&app, &project_path, display_path(&meta), at_secs).await?;
+
+    Ok(())
+}
+
+/// Thumbnails can be saved in any of the supported formats, so their path has to be looked up
+#[tauri::command]
+#[specta::specta]
+fn get_thumbnail_path(app: AppHandle, video_id: String) -> Option<PathBuf> {
+    thumbnail_path(&recording_path(&app, &video_id))
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn get_preview_frame(
+    app: AppHandle,
+    video_id: String,
+    at_secs: f64,
+    max_width: u32,
+) -> Result<Vec<u8>, CommandError> {
+    if !at_secs.is_finite() || at_secs < 0.0 {
+        return Err(CommandError::InvalidInput(format!(
+            "Invalid preview timestamp {at_secs}"
+        )));
+    }
+
+    let meta = RecordingMeta::load_for_project(&recording_path(&app, &video_id))?;
+
+    Ok(extract_frame_png(&display_path(&meta), at_secs, Some(max_width)).await?)
+}
+
+/// Widest the composited previews from `render_preview` are
+const PREVIEW_MAX_WIDTH: u32 = 960;
+
+/// Bytes returned from a command as is, instead of serialized as a JSON array of numbers.
+/// The frontend receives them as an `ArrayBuffer`.
+pub struct RawResponse(tauri::ipc::Response);
+
+impl tauri::ipc::IpcResponse for RawResponse {
+    fn body(self) -> tauri::Result<tauri::ipc::InvokeResponseBody> {
+        self.0.body()
+    }
+}
+
+impl specta::Type for RawResponse {
+    fn inline(_: &mut specta::TypeMap, _: specta::Generics) -> specta::datatype::DataType {
+        // specta has no way to refer to `ArrayBuffer`
+        specta::datatype::DataType::Unknown
+    }
+}
+
+/// Composites a single frame with `project` as a PNG, so changes to the background or camera
+/// can be seen without rendering the video or changing the editor's configuration
+#[tauri::command]
+#[specta::specta]
+async fn render_preview(
+    app: AppHandle,
+    video_id: String,
+    project: ProjectConfiguration,
+    at_secs: f64,
+) -> Result<RawResponse, CommandError> {
+    if !at_secs.is_finite() || at_secs < 0.0 {
+        return Err(CommandError::InvalidInput(format!(
+            "Invalid preview timestamp {at_secs}"
+        )));
+    }
+
+    let editor_instance = upsert_editor_instance(&app, video_id).await;
+    let (frame, (width, height)) = editor_instance
+        .render_frame_at(&project, at_secs, PREVIEW_MAX_WIDTH)
+        .await
+        .map_err(|e| format!("Failed to render preview: {e}"))?
+        .ok_or_else(|| format!("There's no frame at {at_secs}s to preview"))?;
+
+    let png = tokio::task::spawn_blocking(move || {
+        let image = image::RgbaImage::from_raw(width, height, frame)
+            .ok_or("Rendered preview doesn't match its size")?;
+
+        let mut png = vec![];
+        image
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .map_err(|e| format!("Failed to encode preview: {e}"))?;
+        Ok::<_, String>(png)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    Ok(RawResponse(tauri::ipc::Response::new(png)))
+}
+
+/// The display track of the first segment
+fn display_path(meta: &RecordingMeta) -> PathBuf {
+    match &meta.content {
+        Content::SingleSegment { segment } => segment.path(meta, &segment.display.path),
+        Content::MultipleSegments { inner } => inner.path(meta, &inner.segments[0].display.path),
+    }
+}
+
+/// Decodes a single frame as PNG, optionally scaled down to `max_width`
+async fn extract_frame_png(
+    video_path: &Path,
+    at_secs: f64,
+    max_width: Option<u32>,
+) -> Result<Vec<u8>, String> {
+    let mut ffmpeg = cap_ffmpeg_cli::FFmpeg::new();
+    // Seeking before the input jumps to the nearest keyframe and decodes from there,
+    // which is much faster than decoding from the start
+    ffmpeg
+        .command
+        .args(["-loglevel", "error"])
+        .args(["-ss", &at_secs.to_string()])
+        .arg("-i")
+        .arg(video_path)
+        .args(["-frames:v", "1"]);
+
+    if let Some(max_width) = max_width {
+        ffmpeg
+            .command
+            .args(["-vf", &format!("scale='min({max_width},iw)':-2")]);
+    }
+
+    ffmpeg
+        .command
+        .args(["-f", "image2pipe", "-c:v", "png", "pipe:1"])
+        .stdin(std::process::Stdio::null());
+
+    let output = ffmpeg
+        .command
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+    if !output.status.success() || output.stdout.is_empty() {
+        return Err(format!(
+            "Failed to extract frame: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(output.stdout)
+}
+
+fn is_playable_mp4(path: &Path) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    let Ok(file_size) = file.metadata().map(|m| m.len()) else {
+        return false;
+    };
+
+    Mp4Reader::read_header(BufReader::new(file), file_size).is_ok()
+}
+
+/// Written to a recording's directory when it starts and removed once its files have been
+/// finalized, so recordings interrupted by a crash can be found on the next launch
+const UNFINALIZED_MARKER: &str = ".unfinalized";
+
+/// Set once quitting has started finalizing the current recording
+static QUITTING: AtomicBool = AtomicBool::new(false);
+/// Set once the current recording is finalized and the app can exit
+static READY_TO_QUIT: AtomicBool = AtomicBool::new(false);
+
+fn recording_video_paths(meta: &RecordingMeta) -> Vec<PathBuf> {
+    match &meta.content {
+        Content::SingleSegment { segment } => std::iter::once(&segment.display.path)
+            .chain(segment.camera.as_ref().map(|c| &c.path))
+            .map(|p| segment.path(meta, p))
+            .collect(),
+        Content::MultipleSegments { inner } => inner
+            .segments
+            .iter()
+            .flat_map(|s| {
+                std::iter::once(&s.display.path).chain(s.camera.as_ref().map(|c| &c.path))
+            })
+            .map(|p| inner.path(meta, p))
+            .collect(),
+    }
+}
+
+/// Rewrites a video file without re-encoding, optionally keeping the original as `.bak`
+async fn remux_video(path: &Path, keep_backup: bool) -> Result<(), String> {
+    let remuxed_path = path.with_extension("remuxed.mp4");
+
+    let mut ffmpeg = cap_ffmpeg_cli::FFmpeg::new();
+    ffmpeg
+        .command
+        .args(["-y", "-loglevel", "error"])
+        .args(["-err_detect", "ignore_err"])
+        .arg("-i")
+        .arg(path)
+        .args(["-c", "copy", "-movflags", "+faststart"])
+        .arg(&remuxed_path)
+        .stdin(std::process::Stdio::null());
+
+    let output = ffmpeg
+        .command
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+    if !output.status.success() || !is_playable_mp4(&remuxed_path) {
+        std::fs::remove_file(&remuxed_path).ok();
+        return Err(format!(
+            "Failed to remux {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    if keep_backup {
+        std::fs::rename(path, path.with_extension("mp4.bak"))
+            .map_err(|e| format!("Failed to back up {}: {}", path.display(), e))?;
+    }
+
+    std::fs::rename(&remuxed_path, path)
+        .map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+}
+
+/// Turns the fragmented files written during recording into regular mp4s,
+/// which have the duration and seek information other tools expect
+async fn finalize_recording(recording_dir: &Path, meta: &RecordingMeta) {
+    for path in recording_video_paths(meta) {
+        if !path.exists() {
+            continue;
+        }
+
+        if let Err(e) = remux_video(&path, false).await {
+            tracing::error!("Failed to finalize recording file: {e}");
+        }
+    }
+
+    std::fs::remove_file(recording_dir.join(UNFINALIZED_MARKER)).ok();
+}
 
+/// Remuxes the video files of a recording that was interrupted (eg. by a crash) or that can't be
+/// read, keeping the originals next to them as `.bak`. Returns whether anything was repaired.
+#[tauri::command]
+#[specta::specta]
+async fn repair_recording(app: AppHandle, video_id: String) -> Result<bool, CommandError> {
+    let project_path = recording_path(&app, &video_id);
     let meta = RecordingMeta::load_for_project(&project_path)?;
 
-    fn content_paths(project_path: &PathBuf, meta: &RecordingMeta) -> Vec<PathBuf> {
-        match &meta.content {
-            Content::SingleSegment { segment } => {
-                vec![segment.path(&meta, &segment.display.path)]
-            }
-            Content::MultipleSegments { inner } => inner
-                .segments
-                .iter()
-                .map(|s| inner.path(&meta, &s.display.path))
-                .collect(),
+    let unfinalized = project_path.join(UNFINALIZED_MARKER).exists();
+    let mut repaired = false;
+
+    for path in recording_video_paths(&meta) {
+        if !path.exists() || (!unfinalized && is_playable_mp4(&path)) {
+            continue;
         }
+
+        tracing::info!("Repairing video file: {:?}", path);
+        remux_video(&path, true).await?;
+        repaired = true;
     }
 
-    let paths = match video_type {
-        Some(VideoType::Screen) => content_paths(&project_path, &meta),
-        Some(VideoType::Output) | None => {
-            let output_video_path = project_path.join("output").join("result.mp4");
-            println!("Using output video path: {:?}", output_video_path);
-            if output_video_path.exists() {
-                vec![output_video_path]
-            } else {
-                println!("Output video not found, falling back to screen paths");
-                content_paths(&project_path, &meta)
-            }
-        }
-    };
+    std::fs::remove_file(project_path.join(UNFINALIZED_MARKER)).ok();
 
-    let mut ret = VideoRecordingMetadata {
-        size: 0.0,
-        duration: 0.0,
+    Ok(repaired)
+}
+
+/// Asks whether to repair recordings that were still in progress when the app last exited
+async fn offer_unfinalized_recordings_repair(app: AppHandle) {
+    let Ok(entries) = std::fs::read_dir(recordings_path(&app)) else {
+        return;
     };
 
-    for path in paths {
-        let file = File::open(&path).map_err(|e| format!("Failed to open video file: {}", e))?;
+    let ids = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.join(UNFINALIZED_MARKER).exists())
+        .filter_map(|path| {
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .map(|s| s.to_string())
+        })
+        .collect::<Vec<_>>();
 
-        ret.size += (file
-            .metadata()
-            .map_err(|e| format!("Failed to get file metadata: {}", e))?
-            .len() as f64)
-            / (1024.0 * 1024.0);
+    if ids.is_empty() {
+        return;
+    }
 
-        let reader = BufReader::new(file);
-        let file_size = path
-            .metadata()
-            .map_err(|e| format!("Failed to get file metadata: {}", e))?
-            .len();
+    let message = if ids.len() == 1 {
+        "A recording wasn't saved properly the last time Cap closed. Repair it now?".to_string()
+    } else {
+        format!(
+            "{} recordings weren't saved properly the last time Cap closed. Repair them now?",
+            ids.len()
+        )
+    };
 
-        ret.duration += match Mp4Reader::read_header(reader, file_size) {
-            Ok(mp4) => mp4.duration().as_secs_f64(),
-            Err(e) => {
-                println!(
-                    "Failed to read MP4 header: {}. Falling back to default duration.",
-                    e
-                );
-                // Return a default duration (e.g., 0.0) or try to estimate it based on file size
-                0.0 // or some estimated value
+    let app_handle = app.clone();
+    app.dialog()
+        .message(message)
+        .title("Repair Recordings")
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Repair".to_string(),
+            "Not Now".to_string(),
+        ))
+        .show(move |repair| {
+            if !repair {
+                return;
             }
-        };
-    }
 
-    Ok(ret)
+            tauri::async_runtime::spawn(async move {
+                for id in ids {
+                    if let Err(e) = repair_recording(app_handle.clone(), id.clone()).await {
+                        tracing::error!("Failed to repair recording {id}: {e}");
+                    }
+                }
+            });
+        });
 }
 
 #[tauri::command(async)]
 #[specta::specta]
 fn open_editor(app: AppHandle, id: String) {
-    println!("Opening editor for recording: {}", id);
+    tracing::info!("Opening editor for recording: {}", id);
 
     if let Some(window) = CapWindowId::Camera.get(&app) {
         window.close().ok();
     }
 
-    ShowCapWindow::Editor { project_id: id }.show(&app).unwrap();
+    if let Err(e) = (ShowCapWindow::Editor { project_id: id }).show(&app) {
+        tracing::error!("Failed to open editor: {e}");
+    }
 }
 
 #[tauri::command(async)]
@@ -1058,6 +2029,12 @@ enum RenderProgress {
     FrameRendered { current_frame: u32 },
 }
 
+#[derive(Serialize, Type, tauri_specta::Event, Debug, Clone)]
+pub struct ExportProgress {
+    video_id: String,
+    progress: f32,
+}
+
 #[tauri::command]
 #[specta::specta]
 async fn set_playhead_position(app: AppHandle, video_id: String, frame_number: u32) {
@@ -1082,15 +2059,111 @@ async fn set_project_config(app: AppHandle, video_id: String, config: ProjectCon
 
 #[tauri::command]
 #[specta::specta]
-async fn list_audio_devices() -> Result<Vec<String>, ()> {
-    if !permissions::do_permissions_check(false)
-        .microphone
-        .permitted()
-    {
-        return Ok(vec![]);
+async fn check_ffmpeg() -> Result<String, CommandError> {
+    cap_ffmpeg_cli::check_ffmpeg().await.map_err(|e| {
+        tracing::error!("FFmpeg is unavailable: {}", e);
+        CommandError::Ffmpeg(format!("FFmpeg is unavailable: {}", e))
+    })
+}
+
+#[derive(Serialize, Type, tauri_specta::Event, Debug, Clone)]
+pub struct FfmpegInstallProgress {
+    stage: String,
+    percent: Option<f32>,
+}
+
+/// Finds and verifies the ffmpeg binary, then detects its encoders, emitting the
+/// progress of each step. FFmpeg ships with the app so nothing is downloaded, but the
+/// binary can still turn out to be missing or unusable until this is retried.
+async fn install_ffmpeg(app: &AppHandle) -> Result<String, CommandError> {
+    let progress = |stage: &str, percent: Option<f32>| {
+        FfmpegInstallProgress {
+            stage: stage.to_string(),
+            percent,
+        }
+        .emit(app)
+        .ok();
+    };
+
+    let result = async {
+        progress("Locating FFmpeg", Some(0.0));
+        cap_ffmpeg_cli::ffmpeg_binary()?;
+
+        progress("Verifying FFmpeg", Some(1.0 / 3.0));
+        let version = cap_ffmpeg_cli::check_ffmpeg().await?;
+
+        progress("Detecting encoders", Some(2.0 / 3.0));
+        cap_export::available_encoders().await;
+
+        Ok::<_, std::io::Error>(version)
+    }
+    .await;
+
+    match result {
+        Ok(version) => {
+            progress("Ready", Some(1.0));
+            Ok(version)
+        }
+        Err(e) => {
+            tracing::error!("FFmpeg is unavailable: {}", e);
+            progress("Failed", None);
+            Err(CommandError::Ffmpeg(format!(
+                "FFmpeg is unavailable: {}",
+                e
+            )))
+        }
     }
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn retry_ffmpeg_install(app: AppHandle) -> Result<String, CommandError> {
+    install_ffmpeg(&app).await
+}
+
+#[derive(Serialize, Type, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FfmpegInfo {
+    path: Option<PathBuf>,
+    /// The first line of `ffmpeg -version`
+    version: Option<String>,
+    /// Whether the sidecar shipped with Cap is used, rather than a system install
+    bundled: bool,
+    error: Option<String>,
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn get_ffmpeg_info() -> FfmpegInfo {
+    let (path, source) = match cap_ffmpeg_cli::ffmpeg_binary() {
+        Ok(binary) => binary,
+        Err(e) => {
+            return FfmpegInfo {
+                path: None,
+                version: None,
+                bundled: false,
+                error: Some(e.to_string()),
+            }
+        }
+    };
 
-    Ok(AudioInputFeed::list_devices().keys().cloned().collect())
+    let (version, error) = match cap_ffmpeg_cli::check_ffmpeg().await {
+        Ok(version) => (Some(version), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+
+    FfmpegInfo {
+        path: Some(path),
+        version,
+        bundled: source == cap_ffmpeg_cli::FFmpegSource::Bundled,
+        error,
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn get_project_config(app: AppHandle, video_id: String) -> ProjectConfiguration {
+    ProjectConfiguration::load(recording_path(&app, &video_id)).unwrap_or_default()
 }
 
 #[tauri::command(async)]
@@ -1125,9 +2198,9 @@ async fn upload_exported_video(
     app: AppHandle,
     video_id: String,
     mode: UploadMode,
-) -> Result<UploadResult, String> {
+) -> Result<UploadResult, CommandError> {
     let Ok(Some(mut auth)) = AuthStore::get(&app) else {
-        AuthStore::set(&app, None).map_err(|e| e.to_string())?;
+        AuthStore::set(&app, None)?;
         return Ok(UploadResult::NotAuthenticated);
     };
 
@@ -1141,7 +2214,7 @@ async fn upload_exported_video(
                 Ok(None) => {
                     return Ok(UploadResult::NotAuthenticated);
                 }
-                Err(e) => return Err(format!("Failed to refresh auth: {}", e)),
+                Err(e) => return Err(format!("Failed to refresh auth: {}", e).into()),
             },
             Err(e) => {
                 if e.contains("Authentication expired") {
@@ -1163,7 +2236,9 @@ async fn upload_exported_video(
     let output_path = meta.output_path();
     if !output_path.exists() {
         notifications::send_notification(&app, notifications::NotificationType::UploadFailed);
-        return Err("Failed to upload video: Rendered video not found".to_string());
+        return Err(CommandError::Other(
+            "Failed to upload video: Rendered video not found".to_string(),
+        ));
     }
 
     // Start upload progress
@@ -1224,7 +2299,7 @@ async fn upload_exported_video(
         }
         Err(e) => {
             NotificationType::UploadFailed.send(&app);
-            Err(e)
+            Err(e.into())
         }
     }
 }
@@ -1234,10 +2309,10 @@ async fn upload_exported_video(
 async fn upload_screenshot(
     app: AppHandle,
     screenshot_path: PathBuf,
-) -> Result<UploadResult, String> {
+) -> Result<UploadResult, CommandError> {
     let Ok(Some(mut auth)) = AuthStore::get(&app) else {
         // Sign out and redirect to sign in
-        AuthStore::set(&app, None).map_err(|e| e.to_string())?;
+        AuthStore::set(&app, None)?;
         return Ok(UploadResult::NotAuthenticated);
     };
 
@@ -1250,7 +2325,7 @@ async fn upload_screenshot(
                 Ok(None) => {
                     return Ok(UploadResult::NotAuthenticated);
                 }
-                Err(e) => return Err(format!("Failed to refresh auth: {}", e)),
+                Err(e) => return Err(format!("Failed to refresh auth: {}", e).into()),
             },
             Err(e) => {
                 if e.contains("Authentication expired") {
@@ -1266,20 +2341,18 @@ async fn upload_screenshot(
         }
     }
 
-    println!("Uploading screenshot: {:?}", screenshot_path);
+    tracing::info!("Uploading screenshot: {:?}", screenshot_path);
 
     let screenshot_dir = screenshot_path.parent().unwrap().to_path_buf();
     let mut meta = RecordingMeta::load_for_project(&screenshot_dir).unwrap();
 
     let share_link = if let Some(sharing) = meta.sharing.as_ref() {
         // Screenshot already uploaded, use existing link
-        println!("Screenshot already uploaded, using existing link");
+        tracing::info!("Screenshot already uploaded, using existing link");
         sharing.link.clone()
     } else {
         // Upload the screenshot
-        let uploaded = upload_image(&app, screenshot_path.clone())
-            .await
-            .map_err(|e| e.to_string())?;
+        let uploaded = upload_image(&app, screenshot_path.clone()).await?;
 
         meta.sharing = Some(SharingMeta {
             link: uploaded.link.clone(),
@@ -1301,7 +2374,7 @@ async fn upload_screenshot(
         uploaded.link
     };
 
-    println!("Copying to clipboard: {:?}", share_link);
+    tracing::info!("Copying to clipboard: {:?}", share_link);
 
     // Copy link to clipboard
     #[cfg(target_os = "macos")]
@@ -1315,7 +2388,10 @@ async fn upload_screenshot(
 
 #[tauri::command]
 #[specta::specta]
-async fn take_screenshot(app: AppHandle, _state: MutableState<'_, App>) -> Result<(), String> {
+async fn take_screenshot(
+    app: AppHandle,
+    _state: MutableState<'_, App>,
+) -> Result<(), CommandError> {
     let id = uuid::Uuid::new_v4().to_string();
 
     let recording_dir = app
@@ -1325,7 +2401,7 @@ async fn take_screenshot(app: AppHandle, _state: MutableState<'_, App>) -> Resul
         .join("screenshots")
         .join(format!("{id}.cap"));
 
-    std::fs::create_dir_all(&recording_dir).map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&recording_dir)?;
 
     // Capture the screenshot synchronously before any await points
     let (width, height, bgra_data) = {
@@ -1412,15 +2488,23 @@ async fn take_screenshot(app: AppHandle, _state: MutableState<'_, App>) -> Resul
         RecordingMeta {
             project_path: recording_dir.clone(),
             sharing: None,
+            notes: None,
+            tags: vec![],
             pretty_name: screenshot_name,
+            created_at: Some(now.to_rfc3339()),
             content: cap_project::Content::SingleSegment {
                 segment: cap_project::SingleSegment {
                     display: Display {
                         path: screenshot_path.clone(),
+                        scale_factor: None,
+                        color_space: None,
                     },
                     camera: None,
                     audio: None,
+                    system_audio: None,
                     cursor: None,
+                    clicks: None,
+                    keystrokes: None,
                 },
             },
         }
@@ -1446,12 +2530,11 @@ async fn save_file_dialog(
     app: AppHandle,
     file_name: String,
     file_type: String,
-) -> Result<Option<String>, String> {
-    use tauri_plugin_dialog::DialogExt;
-
-    println!(
+) -> Result<Option<String>, CommandError> {
+    tracing::info!(
         "save_file_dialog called with file_name: {}, file_type: {}",
-        file_name, file_type
+        file_name,
+        file_type
     );
 
     // Remove the ".cap" suffix if present
@@ -1459,31 +2542,32 @@ async fn save_file_dialog(
         .strip_suffix(".cap")
         .unwrap_or(&file_name)
         .to_string();
-    println!("File name after removing .cap suffix: {}", file_name);
+    tracing::info!("File name after removing .cap suffix: {}", file_name);
 
     // Determine the file type and extension
     let (name, extension) = match file_type.as_str() {
         "recording" => {
-            println!("File type is recording");
+            tracing::info!("File type is recording");
             ("MP4 Video", "mp4")
         }
         "screenshot" => {
-            println!("File type is screenshot");
+            tracing::info!("File type is screenshot");
             ("PNG Image", "png")
         }
         _ => {
-            println!("Invalid file type: {}", file_type);
-            return Err("Invalid file type".to_string());
+            tracing::warn!("Invalid file type: {}", file_type);
+            return Err(CommandError::InvalidInput("Invalid file type".to_string()));
         }
     };
 
-    println!(
+    tracing::info!(
         "Showing save dialog with name: {}, extension: {}",
-        name, extension
+        name,
+        extension
     );
 
     let (tx, rx) = std::sync::mpsc::channel();
-    println!("Created channel for communication");
+    tracing::info!("Created channel for communication");
 
     app.dialog()
         .file()
@@ -1491,7 +2575,7 @@ async fn save_file_dialog(
         .set_file_name(file_name)
         .add_filter(name, &[extension])
         .save_file(move |path| {
-            println!("Save file callback triggered");
+            tracing::info!("Save file callback triggered");
             let _ = tx.send(
                 path.as_ref()
                     .and_then(|p| p.as_path())
@@ -1499,20 +2583,20 @@ async fn save_file_dialog(
             );
         });
 
-    println!("Waiting for user selection");
+    tracing::info!("Waiting for user selection");
     match rx.recv() {
         Ok(result) => {
-            println!("Save dialog result: {:?}", result);
+            tracing::info!("Save dialog result: {:?}", result);
             // Don't send any notifications here - we'll do it after the file is actually copied
             Ok(result)
         }
         Err(e) => {
-            println!("Error receiving result: {}", e);
+            tracing::error!("Error receiving result: {}", e);
             notifications::send_notification(
                 &app,
                 notifications::NotificationType::VideoSaveFailed,
             );
-            Err(e.to_string())
+            Err(CommandError::Other(e.to_string()))
         }
     }
 }
@@ -1528,20 +2612,34 @@ fn get_recording_meta(
     app: AppHandle,
     id: String,
     file_type: String,
-) -> Result<RecordingMeta, String> {
+) -> Result<RecordingMeta, CommandError> {
     let meta_path = match file_type.as_str() {
         "recording" => recording_path(&app, &id),
         "screenshot" => screenshot_path(&app, &id),
-        _ => return Err("Invalid file type".to_string()),
+        _ => return Err(CommandError::InvalidInput("Invalid file type".to_string())),
     };
 
     RecordingMeta::load_for_project(&meta_path)
-        .map_err(|e| format!("Failed to load recording meta: {}", e))
+        .map_err(|e| CommandError::Other(format!("Failed to load recording meta: {}", e)))
+}
+
+#[tauri::command]
+#[specta::specta]
+fn update_recording_meta(
+    app: AppHandle,
+    video_id: String,
+    patch: RecordingMetaPatch,
+) -> Result<RecordingMeta, CommandError> {
+    let meta = RecordingMeta::patch_for_project(&recording_path(&app, &video_id), &patch)?;
+
+    RecordingMetaChanged { id: video_id }.emit(&app).ok();
+
+    Ok(meta)
 }
 
 #[tauri::command]
 #[specta::specta]
-fn list_recordings(app: AppHandle) -> Result<Vec<(String, PathBuf, RecordingMeta)>, String> {
+fn list_recordings(app: AppHandle) -> Result<Vec<(String, PathBuf, RecordingMeta)>, CommandError> {
     let recordings_dir = recordings_path(&app);
 
     // First check if directory exists
@@ -1550,7 +2648,7 @@ fn list_recordings(app: AppHandle) -> Result<Vec<(String, PathBuf, RecordingMeta
     }
 
     let mut result = std::fs::read_dir(&recordings_dir)
-        .map_err(|e| format!("Failed to read recordings directory: {}", e))?
+        .map_err(|e| CommandError::Io(format!("Failed to read recordings directory: {}", e)))?
         .filter_map(|entry| {
             let entry = match entry {
                 Ok(e) => e,
@@ -1582,31 +2680,205 @@ fn list_recordings(app: AppHandle) -> Result<Vec<(String, PathBuf, RecordingMeta
         })
         .collect::<Vec<_>>();
 
-    // Sort the result by creation date of the actual file, newest first
+    // Sort the result by creation date, newest first
     result.sort_by(|a, b| {
-        let b_time =
-            b.1.metadata()
-                .and_then(|m| m.created())
-                .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-
-        let a_time =
-            a.1.metadata()
-                .and_then(|m| m.created())
-                .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-
-        b_time.cmp(&a_time)
+        recording_created_at(&b.1, &b.2).total_cmp(&recording_created_at(&a.1, &a.2))
     });
 
     Ok(result)
 }
 
+/// Unix timestamp in seconds of when the recording was made. Legacy recordings don't store
+/// one, so the directory's creation time is used instead.
+fn recording_created_at(path: &Path, meta: &RecordingMeta) -> f64 {
+    meta.created_at
+        .as_deref()
+        .and_then(|time| chrono::DateTime::parse_from_rfc3339(time).ok())
+        .map(|time| time.timestamp_millis() as f64 / 1000.0)
+        .or_else(|| {
+            path.metadata()
+                .and_then(|m| m.created().or_else(|_| m.modified()))
+                .ok()
+                .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|duration| duration.as_secs_f64())
+        })
+        .unwrap_or_default()
+}
+
+#[derive(Serialize, Type, Debug)]
+#[serde(rename_all = "camelCase")]
+struct RecordingSummary {
+    id: String,
+    pretty_name: String,
+    /// Unix timestamp in seconds
+    created_at: f64,
+    duration: f64,
+    /// Size of the recorded content in megabytes
+    size: f64,
+    thumbnail_path: Option<PathBuf>,
+    has_rendered_output: bool,
+    notes: Option<String>,
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize, Type, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+enum RecordingSort {
+    #[default]
+    Newest,
+    Oldest,
+    Size,
+}
+
+#[derive(Serialize, Type, Debug)]
+#[serde(rename_all = "camelCase")]
+struct RecordingsPage {
+    recordings: Vec<RecordingSummary>,
+    total: u32,
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn get_prev_recordings_detailed(
+    app: AppHandle,
+    sort: Option<RecordingSort>,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> Result<RecordingsPage, CommandError> {
+    let mut summaries = vec![];
+
+    for (id, path, meta) in list_recordings(app.clone())? {
+        let created_at = recording_created_at(&path, &meta);
+
+        let (duration, size) = get_video_metadata(app.clone(), id.clone(), Some(VideoType::Screen))
+            .await
+            .map(|metadata| (metadata.duration, metadata.size))
+            .unwrap_or_default();
+
+        summaries.push(RecordingSummary {
+            has_rendered_output: meta.output_path().exists(),
+            thumbnail_path: thumbnail_path(&path),
+            pretty_name: meta.pretty_name,
+            notes: meta.notes,
+            tags: meta.tags,
+            id,
+            created_at,
+            duration,
+            size,
+        });
+    }
+
+    match sort.unwrap_or_default() {
+        RecordingSort::Newest => summaries.sort_by(|a, b| b.created_at.total_cmp(&a.created_at)),
+        RecordingSort::Oldest => summaries.sort_by(|a, b| a.created_at.total_cmp(&b.created_at)),
+        RecordingSort::Size => summaries.sort_by(|a, b| b.size.total_cmp(&a.size)),
+    }
+
+    let total = summaries.len() as u32;
+    let recordings = summaries
+        .into_iter()
+        .skip(offset.unwrap_or(0) as usize)
+        .take(limit.map(|limit| limit as usize).unwrap_or(usize::MAX))
+        .collect();
+
+    Ok(RecordingsPage { recordings, total })
+}
+
+/// Changes where recordings are stored. Existing recordings are moved into the new
+/// directory, as only the current one is listed. A recording that already exists
+/// there by name is left where it was.
+#[tauri::command]
+#[specta::specta]
+async fn set_recordings_dir(
+    app: AppHandle,
+    state: MutableState<'_, App>,
+    path: Option<PathBuf>,
+) -> Result<(), CommandError> {
+    // The recording being made would be moved out from under it
+    if state.read().await.current_recording.is_some() {
+        return Err(CommandError::InvalidInput(
+            "The recordings folder can't be changed while recording".to_string(),
+        ));
+    }
+
+    if let Some(path) = &path {
+        std::fs::create_dir_all(path)
+            .map_err(|e| CommandError::Io(format!("Failed to create {}: {}", path.display(), e)))?;
+
+        // Permission bits don't account for ACLs or read-only mounts, so try writing instead
+        let probe_path = path.join(".cap-write-test");
+        std::fs::write(&probe_path, b"")
+            .map_err(|e| CommandError::Io(format!("{} is not writable: {}", path.display(), e)))?;
+        std::fs::remove_file(&probe_path).ok();
+    }
+
+    let previous_dir = recordings_path(&app);
+
+    GeneralSettingsStore::update(&app, |settings| settings.recordings_dir = path)?;
+
+    let new_dir = recordings_path(&app);
+    if new_dir != previous_dir {
+        move_recordings(&previous_dir, &new_dir)?;
+    }
+
+    Ok(())
+}
+
+fn move_recordings(from: &Path, to: &Path) -> Result<(), String> {
+    let entries =
+        std::fs::read_dir(from).map_err(|e| format!("Failed to read {}: {}", from.display(), e))?;
+
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let source = entry.path();
+        if !source.is_dir() || source.extension().and_then(|s| s.to_str()) != Some("cap") {
+            continue;
+        }
+
+        let target = to.join(entry.file_name());
+        if target.exists() {
+            tracing::warn!(
+                "Not moving {}, {} already exists",
+                source.display(),
+                target.display()
+            );
+            continue;
+        }
+
+        // Renaming fails across volumes, where the recording has to be copied instead
+        if std::fs::rename(&source, &target).is_err() {
+            copy_dir(&source, &target)
+                .and_then(|_| std::fs::remove_dir_all(&source))
+                .map_err(|e| format!("Failed to move {}: {}", source.display(), e))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
-fn list_screenshots(app: AppHandle) -> Result<Vec<(String, PathBuf, RecordingMeta)>, String> {
+fn list_screenshots(app: AppHandle) -> Result<Vec<(String, PathBuf, RecordingMeta)>, CommandError> {
     let screenshots_dir = screenshots_path(&app);
 
     let mut result = std::fs::read_dir(&screenshots_dir)
-        .map_err(|e| format!("Failed to read screenshots directory: {}", e))?
+        .map_err(|e| CommandError::Io(format!("Failed to read screenshots directory: {}", e)))?
         .filter_map(|entry| {
             let entry = entry.ok()?;
             let path = entry.path();
@@ -1649,22 +2921,22 @@ fn list_screenshots(app: AppHandle) -> Result<Vec<(String, PathBuf, RecordingMet
 
 #[tauri::command]
 #[specta::specta]
-async fn check_upgraded_and_update(app: AppHandle) -> Result<bool, String> {
+async fn check_upgraded_and_update(app: AppHandle) -> Result<bool, CommandError> {
     if let Err(e) = AuthStore::fetch_and_update_plan(&app).await {
-        return Err(format!(
+        return Err(CommandError::Other(format!(
             "Failed to update plan information. Try signing out and signing back in: {}",
             e
-        ));
+        )));
     }
 
-    let auth = AuthStore::get(&app).map_err(|e| e.to_string())?;
+    let auth = AuthStore::get(&app)?;
 
     Ok(auth.map_or(false, |a| a.is_upgraded()))
 }
 
 #[tauri::command]
 #[specta::specta]
-fn open_external_link(app: tauri::AppHandle, url: String) -> Result<(), String> {
+fn open_external_link(app: tauri::AppHandle, url: String) -> Result<(), CommandError> {
     // Check settings first
     if let Ok(Some(settings)) = GeneralSettingsStore::get(&app) {
         if settings.disable_auto_open_links {
@@ -1674,14 +2946,14 @@ fn open_external_link(app: tauri::AppHandle, url: String) -> Result<(), String>
 
     app.shell()
         .open(&url, None)
-        .map_err(|e| format!("Failed to open URL: {}", e))?;
+        .map_err(|e| CommandError::Other(format!("Failed to open URL: {}", e)))?;
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-async fn delete_auth_open_signin(app: AppHandle) -> Result<(), String> {
-    AuthStore::set(&app, None).map_err(|e| e.to_string())?;
+async fn delete_auth_open_signin(app: AppHandle) -> Result<(), CommandError> {
+    AuthStore::set(&app, None)?;
 
     if let Some(window) = CapWindowId::Settings.get(&app) {
         window.close().ok();
@@ -1703,7 +2975,7 @@ async fn delete_auth_open_signin(app: AppHandle) -> Result<(), String> {
 
 #[tauri::command]
 #[specta::specta]
-async fn reset_camera_permissions(_app: AppHandle) -> Result<(), ()> {
+async fn reset_camera_permissions(_app: AppHandle) -> Result<(), CommandError> {
     #[cfg(target_os = "macos")]
     {
         #[cfg(debug_assertions)]
@@ -1716,8 +2988,7 @@ async fn reset_camera_permissions(_app: AppHandle) -> Result<(), ()> {
             .arg("reset")
             .arg("Camera")
             .arg(bundle_id)
-            .output()
-            .expect("Failed to reset camera permissions");
+            .output()?;
     }
 
     Ok(())
@@ -1725,7 +2996,7 @@ async fn reset_camera_permissions(_app: AppHandle) -> Result<(), ()> {
 
 #[tauri::command]
 #[specta::specta]
-async fn reset_microphone_permissions(_app: AppHandle) -> Result<(), ()> {
+async fn reset_microphone_permissions(_app: AppHandle) -> Result<(), CommandError> {
     #[cfg(debug_assertions)]
     let bundle_id = "com.apple.Terminal";
     #[cfg(not(debug_assertions))]
@@ -1735,8 +3006,7 @@ async fn reset_microphone_permissions(_app: AppHandle) -> Result<(), ()> {
         .arg("reset")
         .arg("Microphone")
         .arg(bundle_id)
-        .output()
-        .expect("Failed to reset microphone permissions");
+        .output()?;
 
     Ok(())
 }
@@ -1777,10 +3047,10 @@ async fn check_notification_permissions(app: AppHandle) {
 
     match app.notification().permission_state() {
         Ok(state) if state != PermissionState::Granted => {
-            println!("Requesting notification permission");
+            tracing::info!("Requesting notification permission");
             match app.notification().request_permission() {
                 Ok(PermissionState::Granted) => {
-                    println!("Notification permission granted");
+                    tracing::info!("Notification permission granted");
                 }
                 Ok(_) | Err(_) => {
                     GeneralSettingsStore::update(&app, |s| {
@@ -1791,10 +3061,10 @@ async fn check_notification_permissions(app: AppHandle) {
             }
         }
         Ok(_) => {
-            println!("Notification permission already granted");
+            tracing::info!("Notification permission already granted");
         }
         Err(e) => {
-            eprintln!("Error checking notification permission state: {}", e);
+            tracing::error!("Error checking notification permission state: {}", e);
         }
     }
 }
@@ -1817,45 +3087,86 @@ pub async fn run() {
         .commands(tauri_specta::collect_commands![
             get_recording_options,
             set_recording_options,
+            refresh_camera_window,
             recording::start_recording,
             recording::stop_recording,
             recording::pause_recording,
             recording::resume_recording,
+            recording::add_marker,
+            recording::get_markers,
             recording::list_cameras,
+            recording::list_camera_devices,
+            recording::list_camera_resolutions,
             recording::list_capture_windows,
+            recording::list_capture_applications,
             recording::list_capture_screens,
+            recording::get_displays,
             take_screenshot,
-            list_audio_devices,
+            recording::list_audio_devices,
+            recording::get_capabilities,
+            recording::validate_recording_options,
             close_previous_recordings_window,
             fake_window::set_fake_window_bounds,
             fake_window::remove_fake_window,
             focus_captures_panel,
+            camera::start_camera_preview,
+            camera::stop_camera_preview,
             get_current_recording,
             export::export_video,
+            export::export_gif,
+            export::export_raw,
+            export::cancel_render,
+            export::get_encoders,
             copy_file_to_path,
             copy_video_to_clipboard,
             copy_screenshot_to_clipboard,
+            copy_frame_to_clipboard,
             open_file_path,
+            reveal_recording_in_finder,
             get_video_metadata,
+            get_disk_space,
+            generate_thumbnail,
+            get_thumbnail_path,
+            get_preview_frame,
+            render_preview,
+            repair_recording,
+            concat::concatenate_recordings,
+            compress::compress_recording,
+            clip::export_clip,
+            import::import_video,
+            bundle::export_project_bundle,
+            bundle::import_project_bundle,
+            logging::get_log_path,
+            general_settings::get_general_settings,
+            general_settings::update_general_settings,
             create_editor_instance,
             start_playback,
             stop_playback,
             set_playhead_position,
             set_project_config,
+            get_project_config,
+            check_ffmpeg,
+            retry_ffmpeg_install,
+            get_ffmpeg_info,
             open_editor,
             open_main_window,
             permissions::open_permission_settings,
             permissions::do_permissions_check,
+            permissions::check_permission,
             permissions::request_permission,
             upload_exported_video,
             upload_screenshot,
             get_recording_meta,
+            update_recording_meta,
             save_file_dialog,
             list_recordings,
+            get_prev_recordings_detailed,
+            set_recordings_dir,
             list_screenshots,
             check_upgraded_and_update,
             open_external_link,
             hotkeys::set_hotkey,
+            hotkeys::set_recording_hotkey,
             delete_auth_open_signin,
             reset_camera_permissions,
             reset_microphone_permissions,
@@ -1870,12 +3181,19 @@ pub async fn run() {
         .events(tauri_specta::collect_events![
             RecordingOptionsChanged,
             NewRecordingAdded,
+            ThumbnailReady,
             NewScreenshotAdded,
             RenderFrameEvent,
             EditorStateChanged,
+            EditorRenderError,
+            FfmpegInstallProgress,
             CurrentRecordingChanged,
             RecordingMetaChanged,
             RecordingStarted,
+            RecordingCountdown,
+            RecordingProgress,
+            ExportProgress,
+            export::RenderQueuePosition,
             RecordingStopped,
             RequestStartRecording,
             RequestRestartRecording,
@@ -1883,9 +3201,14 @@ pub async fn run() {
             RequestNewScreenshot,
             RequestOpenSettings,
             NewNotification,
+            CameraDisconnected,
+            LowDiskSpace,
+            RecordingAutoStopped,
             AuthenticationInvalid,
             audio_meter::AudioInputLevelChange,
             UploadProgress,
+            fake_window::ShowCapturesPanel,
+            fake_window::HideCapturesPanel,
         ])
         .error_handling(tauri_specta::ErrorHandlingMode::Throw)
         .typ::<ProjectConfiguration>()
@@ -1966,10 +3289,13 @@ pub async fn run() {
         })
         .setup(move |app| {
             let app = app.handle().clone();
+            logging::init(&app);
             specta_builder.mount_events(&app);
             hotkeys::init(&app);
             general_settings::init(&app);
+            export::init(&app);
             fake_window::init(&app);
+            camera::init(&app, camera_rx);
 
             if let Ok(Some(auth)) = AuthStore::load(&app) {
                 sentry::configure_scope(|scope| {
@@ -1983,9 +3309,27 @@ pub async fn run() {
             // Add this line to check notification permissions on startup
             tokio::spawn(check_notification_permissions(app.clone()));
 
-            println!("Checking startup completion and permissions...");
+            // Probe ffmpeg up front so a missing binary is reported and the first
+            // export doesn't wait on encoder detection
+            tokio::spawn({
+                let app = app.clone();
+                async move { install_ffmpeg(&app).await.ok() }
+            });
+            tokio::spawn(offer_unfinalized_recordings_repair(app.clone()));
+
+            // Quit the same way as from the menu, so the recording is finalized
+            tokio::spawn({
+                let app = app.clone();
+                async move {
+                    if tokio::signal::ctrl_c().await.is_ok() {
+                        app.exit(0);
+                    }
+                }
+            });
+
+            tracing::info!("Checking startup completion and permissions...");
             let permissions = permissions::do_permissions_check(false);
-            println!("Permissions check result: {:?}", permissions);
+            tracing::info!("Permissions check result: {:?}", permissions);
 
             if !permissions.screen_recording.permitted()
                 || !permissions.accessibility.permitted()
@@ -1997,12 +3341,16 @@ pub async fn run() {
             {
                 ShowCapWindow::Setup.show(&app).ok();
             } else {
-                println!("Permissions granted, showing main window");
+                tracing::info!("Permissions granted, showing main window");
 
                 ShowCapWindow::Main.show(&app).ok();
             }
 
-            ShowCapWindow::PrevRecordings.show(&app).ok();
+            ShowCapWindow::PrevRecordings {
+                screen_bounds: None,
+            }
+            .show(&app)
+            .ok();
 
             audio_meter::spawn_event_emitter(app.clone(), audio_input_rx);
 
@@ -2020,13 +3368,36 @@ pub async fn run() {
                     }),
                     camera_label: None,
                     audio_input_name: None,
+                    fps: None,
+                    camera_only: false,
+                    countdown_secs: None,
+                    capture_system_audio: false,
+                    camera_resolution: None,
+                    max_duration_secs: None,
+                    capture_cursor: CursorMode::Baked,
                 },
                 current_recording: None,
                 pre_created_video: None,
+                recording_progress_task: None,
+                recording_countdown: None,
+                occluder_monitor: None,
+                occluder_holes: vec![],
             })));
 
             tray::create_tray(&app).unwrap();
 
+            #[cfg(target_os = "macos")]
+            platform::observe_active_space({
+                let app = app.clone();
+                move || {
+                    let app = app.clone();
+                    tokio::spawn(async move {
+                        let state = app.state::<Arc<RwLock<App>>>();
+                        state.read().await.update_occluder_for_active_space();
+                    });
+                }
+            });
+
             RequestStartRecording::listen_any_spawn(&app, |_, app| async move {
                 let state = app.state::<Arc<RwLock<App>>>();
                 let is_recording = {
@@ -2036,16 +3407,16 @@ pub async fn run() {
 
                 if is_recording {
                     if let Err(e) = recording::stop_recording(app.clone(), app.state()).await {
-                        eprintln!("Failed to stop recording: {}", e);
+                        tracing::error!("Failed to stop recording: {}", e);
                     }
                 } else if let Err(e) = recording::start_recording(app.clone(), app.state()).await {
-                    eprintln!("Failed to start recording: {}", e);
+                    tracing::error!("Failed to start recording: {}", e);
                 }
             });
 
             RequestStopRecording::listen_any_spawn(&app, |_, app| async move {
                 if let Err(e) = recording::stop_recording(app.clone(), app.state()).await {
-                    eprintln!("Failed to stop recording: {}", e);
+                    tracing::error!("Failed to stop recording: {}", e);
                 }
             });
 
@@ -2067,15 +3438,15 @@ pub async fn run() {
                 }
 
                 if let Err(e) = recording::start_recording(app.clone(), state).await {
-                    eprintln!("Failed to start new recording: {}", e);
+                    tracing::error!("Failed to start new recording: {}", e);
                 } else {
-                    println!("New recording started successfully");
+                    tracing::info!("New recording started successfully");
                 }
             });
 
             RequestNewScreenshot::listen_any_spawn(&app, |_, app| async move {
                 if let Err(e) = take_screenshot(app.clone(), app.state()).await {
-                    eprintln!("Failed to take screenshot: {}", e);
+                    tracing::error!("Failed to take screenshot: {}", e);
                 }
             });
 
@@ -2169,6 +3540,19 @@ pub async fn run() {
                     open_main_window(handle.clone());
                 }
             }
+            // A recording that isn't finalized is left unplayable
+            tauri::RunEvent::ExitRequested { api, .. } if !READY_TO_QUIT.load(Ordering::SeqCst) => {
+                api.prevent_exit();
+
+                if !QUITTING.swap(true, Ordering::SeqCst) {
+                    let handle = handle.clone();
+                    tokio::spawn(async move {
+                        recording::stop_recording_for_quit(&handle).await;
+                        READY_TO_QUIT.store(true, Ordering::SeqCst);
+                        handle.exit(0);
+                    });
+                }
+            }
             _ => {}
         });
 }
@@ -2218,12 +3602,26 @@ pub async fn upsert_editor_instance(app: &AppHandle, video_id: String) -> Arc<Ed
 }
 
 async fn create_editor_instance_impl(app: &AppHandle, video_id: String) -> Arc<EditorInstance> {
-    let instance = EditorInstance::new(recordings_path(app), video_id, {
-        let app = app.clone();
-        move |state| {
-            EditorStateChanged::new(state).emit(&app).ok();
-        }
-    })
+    let instance = EditorInstance::new(
+        recordings_path(app),
+        video_id,
+        {
+            let app = app.clone();
+            move |state| {
+                EditorStateChanged::new(state).emit(&app).ok();
+            }
+        },
+        {
+            let app = app.clone();
+            move |error| {
+                EditorRenderError {
+                    message: error.to_string(),
+                }
+                .emit(&app)
+                .ok();
+            }
+        },
+    )
     .await;
 
     RenderFrameEvent::listen_any(app, {
@@ -2238,11 +3636,80 @@ async fn create_editor_instance_impl(app: &AppHandle, video_id: String) -> Arc<E
 
 // use EditorInstance.project_path instead of this
 fn recordings_path(app: &AppHandle) -> PathBuf {
-    let path = app.path().app_data_dir().unwrap().join("recordings");
+    let path = GeneralSettingsStore::get(app)
+        .ok()
+        .flatten()
+        .and_then(|settings| settings.recordings_dir)
+        .unwrap_or_else(|| app.path().app_data_dir().unwrap().join("recordings"));
     std::fs::create_dir_all(&path).unwrap_or_default();
     path
 }
 
+/// Free space below which a warning is shown while recording
+const LOW_DISK_SPACE: u64 = 2 * 1024 * 1024 * 1024;
+/// Free space below which the current recording is stopped
+const CRITICAL_DISK_SPACE: u64 = 500 * 1024 * 1024;
+const DISK_SPACE_CHECK_INTERVAL_SECS: u64 = 10;
+
+struct DiskSpace {
+    free: u64,
+    total: u64,
+}
+
+fn disk_info(path: &Path) -> std::io::Result<DiskSpace> {
+    // The path may not have been created yet, so use the volume it will be created on
+    let path = path.ancestors().find(|p| p.exists()).unwrap_or(path);
+
+    Ok(DiskSpace {
+        free: fs2::available_space(path)?,
+        total: fs2::total_space(path)?,
+    })
+}
+
+#[derive(Serialize, Type, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskInfo {
+    free_bytes: f64,
+    total_bytes: f64,
+    /// Bytes freed by removing raw tracks after rendering, only counted for the recordings dir
+    cleanup_freed_bytes: f64,
+}
+
+#[tauri::command]
+#[specta::specta]
+fn get_disk_space(app: AppHandle, for_recordings: bool) -> Result<DiskInfo, CommandError> {
+    let path = if for_recordings {
+        recordings_path(&app)
+    } else {
+        app.path()
+            .app_data_dir()
+            .map_err(|e| CommandError::Io(e.to_string()))?
+    };
+
+    let disk =
+        disk_info(&path).map_err(|e| CommandError::Io(format!("Failed to get disk space: {e}")))?;
+
+    let cleanup_freed_bytes = if for_recordings {
+        std::fs::read_dir(&path)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                std::fs::read_to_string(entry.path().join(export::RAW_TRACKS_REMOVED_FILE)).ok()
+            })
+            .filter_map(|s| s.trim().parse::<u64>().ok())
+            .sum::<u64>()
+    } else {
+        0
+    };
+
+    Ok(DiskInfo {
+        free_bytes: disk.free as f64,
+        total_bytes: disk.total as f64,
+        cleanup_freed_bytes: cleanup_freed_bytes as f64,
+    })
+}
+
 fn recording_path(app: &AppHandle, recording_id: &str) -> PathBuf {
     recordings_path(app).join(format!("{}.cap", recording_id))
 }
@@ -2259,10 +3726,9 @@ fn screenshot_path(app: &AppHandle, screenshot_id: &str) -> PathBuf {
 
 #[tauri::command]
 #[specta::specta]
-async fn send_feedback_request(app: AppHandle, feedback: String) -> Result<(), String> {
-    let auth = AuthStore::get(&app)
-        .map_err(|e| e.to_string())?
-        .ok_or("Not authenticated")?;
+async fn send_feedback_request(app: AppHandle, feedback: String) -> Result<(), CommandError> {
+    let auth = AuthStore::get(&app)?
+        .ok_or_else(|| CommandError::Other("Not authenticated".to_string()))?;
 
     let feedback_url = web_api::make_url("/api/desktop/feedback");
 
@@ -2276,17 +3742,17 @@ async fn send_feedback_request(app: AppHandle, feedback: String) -> Result<(), S
         .multipart(form)
         .send()
         .await
-        .map_err(|e| format!("Failed to send feedback: {}", e))?;
+        .map_err(|e| CommandError::Other(format!("Failed to send feedback: {}", e)))?;
 
     if !response.status().is_success() {
-        println!("Feedback request failed with status: {}", response.status());
+        tracing::error!("Feedback request failed with status: {}", response.status());
 
         let error_text = response
             .text()
             .await
-            .map_err(|_| "Failed to read error response")?;
+            .map_err(|_| CommandError::Other("Failed to read error response".to_string()))?;
 
-        println!("Error response: {}", error_text);
+        tracing::error!("Error response: {}", error_text);
 
         // Parse the error response and convert to owned String immediately
         let error = match serde_json::from_str::<serde_json::Value>(&error_text) {
@@ -2298,7 +3764,7 @@ async fn send_feedback_request(app: AppHandle, feedback: String) -> Result<(), S
             Err(_) => "Failed to submit feedback".to_string(),
         };
 
-        return Err(error);
+        return Err(CommandError::Other(error));
     }
 
     Ok(())
@@ -0,0 +1,52 @@
+use crate::CommandError;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+/// Days of logs kept in the log directory
+const MAX_LOG_FILES: usize = 7;
+
+/// Logs to stdout and to a daily log file in the app's log directory.
+/// Set `RUST_LOG` to change what's logged, e.g. `RUST_LOG=cap_media=debug`.
+pub fn init(app: &AppHandle) {
+    let filter = || EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+
+    let file_layer = log_dir(app)
+        .and_then(|dir| {
+            RollingFileAppender::builder()
+                .rotation(Rotation::DAILY)
+                .filename_prefix("cap")
+                .filename_suffix("log")
+                .max_log_files(MAX_LOG_FILES)
+                .build(dir)
+                .map_err(|e| e.to_string())
+        })
+        .map_err(|e| eprintln!("Failed to open log file, only logging to stdout: {e}"))
+        .ok()
+        .map(|appender| {
+            fmt::layer()
+                .with_ansi(false)
+                .with_writer(appender)
+                .with_filter(filter())
+        });
+
+    tracing_subscriber::registry()
+        .with(fmt::layer().with_filter(filter()))
+        .with(file_layer)
+        .try_init()
+        .ok();
+}
+
+fn log_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path().app_log_dir().map_err(|e| e.to_string())
+}
+
+/// The directory log files are written to, so they can be attached to bug reports
+#[tauri::command]
+#[specta::specta]
+pub fn get_log_path(app: AppHandle) -> Result<PathBuf, CommandError> {
+    let dir = log_dir(&app)?;
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
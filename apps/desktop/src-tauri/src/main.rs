@@ -17,10 +17,10 @@ fn main() {
                 #[cfg(debug_assertions)]
                 {
                     let msg = event.message.clone().unwrap_or("No message".into());
-                    println!("Sentry captured {}: {}", &event.level, &msg);
-                    println!("-- user: {:?}", &event.user);
-                    println!("-- event tags: {:?}", &event.tags);
-                    println!("-- event contexts: {:?}", &event.contexts);
+                    tracing::info!("Sentry captured {}: {}", &event.level, &msg);
+                    tracing::info!("-- user: {:?}", &event.user);
+                    tracing::info!("-- event tags: {:?}", &event.tags);
+                    tracing::info!("-- event contexts: {:?}", &event.contexts);
                     None
                 }
 
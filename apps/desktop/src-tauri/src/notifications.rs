@@ -13,6 +13,10 @@ pub enum NotificationType {
     ScreenshotCopiedToClipboard,
     ScreenshotSaveFailed,
     ScreenshotCopyFailed,
+    FrameCopiedToClipboard,
+    FrameCopyFailed,
+    LowDiskSpace,
+    RecordingStoppedLowDiskSpace,
 }
 
 impl NotificationType {
@@ -61,6 +65,24 @@ impl NotificationType {
                 "Unable to copy screenshot to clipboard. Please try again",
                 true,
             ),
+            NotificationType::FrameCopiedToClipboard => {
+                ("Frame Copied", "Frame copied to clipboard", false)
+            }
+            NotificationType::FrameCopyFailed => (
+                "Copy Failed",
+                "Unable to copy frame to clipboard. Please try again",
+                true,
+            ),
+            NotificationType::LowDiskSpace => (
+                "Low Disk Space",
+                "Your disk is almost full. Stop recording soon to avoid losing footage",
+                true,
+            ),
+            NotificationType::RecordingStoppedLowDiskSpace => (
+                "Recording Stopped",
+                "Your disk is full, so the recording was stopped",
+                true,
+            ),
         }
     }
 
@@ -104,9 +126,11 @@ pub fn send_notification(app: &tauri::AppHandle, notification_type: Notification
         .show()
         .ok();
 
-    println!(
+    tracing::error!(
         "Sending notification: Title: '{}', Body: '{}', Error: {}",
-        title, body, is_error
+        title,
+        body,
+        is_error
     );
 
     AppSounds::Notification.play();
@@ -122,36 +122,62 @@ impl OSPermissionsCheck {
     }
 }
 
+#[cfg(target_os = "macos")]
+fn check_av_permission(media_type: cap_media::platform::AVMediaType) -> OSPermissionStatus {
+    use cap_media::platform::AVAuthorizationStatus;
+    use objc::*;
+
+    let cls = objc::class!(AVCaptureDevice);
+    let status: AVAuthorizationStatus =
+        unsafe { msg_send![cls, authorizationStatusForMediaType:media_type.into_ns_str()] };
+    match status {
+        AVAuthorizationStatus::NotDetermined => OSPermissionStatus::Empty,
+        AVAuthorizationStatus::Authorized => OSPermissionStatus::Granted,
+        _ => OSPermissionStatus::Denied,
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn check_screen_recording_permission(initial_check: bool) -> OSPermissionStatus {
+    match (scap::has_permission(), initial_check) {
+        (true, _) => OSPermissionStatus::Granted,
+        (false, true) => OSPermissionStatus::Empty,
+        (false, false) => OSPermissionStatus::Denied,
+    }
+}
+
+/// Checks a single permission, eg. before recording from a device that needs it
 #[tauri::command(async)]
 #[specta::specta]
-pub fn do_permissions_check(initial_check: bool) -> OSPermissionsCheck {
+pub fn check_permission(permission: OSPermission) -> OSPermissionStatus {
     #[cfg(target_os = "macos")]
     {
         use cap_media::platform::AVMediaType;
 
-        fn check_av_permission(media_type: AVMediaType) -> OSPermissionStatus {
-            use cap_media::platform::AVAuthorizationStatus;
-            use objc::*;
-
-            let cls = objc::class!(AVCaptureDevice);
-            let status: AVAuthorizationStatus =
-                unsafe { msg_send![cls, authorizationStatusForMediaType:media_type.into_ns_str()] };
-            match status {
-                AVAuthorizationStatus::NotDetermined => OSPermissionStatus::Empty,
-                AVAuthorizationStatus::Authorized => OSPermissionStatus::Granted,
-                _ => OSPermissionStatus::Denied,
-            }
+        match permission {
+            OSPermission::ScreenRecording => check_screen_recording_permission(false),
+            OSPermission::Camera => check_av_permission(AVMediaType::Video),
+            OSPermission::Microphone => check_av_permission(AVMediaType::Audio),
+            OSPermission::Accessibility => check_accessibility_permission(),
         }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = permission;
+        OSPermissionStatus::NotNeeded
+    }
+}
+
+#[tauri::command(async)]
+#[specta::specta]
+pub fn do_permissions_check(initial_check: bool) -> OSPermissionsCheck {
+    #[cfg(target_os = "macos")]
+    {
+        use cap_media::platform::AVMediaType;
 
         OSPermissionsCheck {
-            screen_recording: {
-                let result = scap::has_permission();
-                match (result, initial_check) {
-                    (true, _) => OSPermissionStatus::Granted,
-                    (false, true) => OSPermissionStatus::Empty,
-                    (false, false) => OSPermissionStatus::Denied,
-                }
-            },
+            screen_recording: check_screen_recording_permission(initial_check),
             microphone: check_av_permission(AVMediaType::Audio),
             camera: check_av_permission(AVMediaType::Video),
             accessibility: { check_accessibility_permission() },
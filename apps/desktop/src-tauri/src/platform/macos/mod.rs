@@ -8,7 +8,11 @@ use core_graphics::{
     base::boolean_t,
     display::{CFDictionaryRef, CGRect},
 };
-use objc::{class, msg_send, sel, sel_impl};
+use objc::{
+    class, msg_send,
+    runtime::{Object, Sel},
+    sel, sel_impl,
+};
 
 pub mod delegates;
 
@@ -45,6 +49,39 @@ pub fn get_ns_window_number(ns_window: *mut c_void) -> isize {
     unsafe { (*ns_window).windowNumber() }
 }
 
+/// Calls `callback` on the main thread whenever the active space changes,
+/// eg. when switching to or from a fullscreen app. Only call this once, as the
+/// observer's class can't be registered twice.
+pub fn observe_active_space(callback: impl Fn() + 'static) {
+    extern "C" fn on_active_space_did_change(this: &Object, _cmd: Sel, _notification: id) {
+        unsafe {
+            let callback: *mut c_void = *this.get_ivar("callback");
+            (*(callback as *const Box<dyn Fn()>))();
+        }
+    }
+
+    let callback: Box<Box<dyn Fn()>> = Box::new(Box::new(callback));
+    let callback = Box::into_raw(callback) as *mut c_void;
+
+    unsafe {
+        let observer = cocoa::delegate!("activeSpaceObserver_cap", {
+            callback: *mut c_void = callback,
+            (activeSpaceDidChange:) => on_active_space_did_change as extern fn(&Object, Sel, id)
+        });
+
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let center: id = msg_send![workspace, notificationCenter];
+        let name = NSString::alloc(nil).init_str("NSWorkspaceActiveSpaceDidChangeNotification");
+        let _: () = msg_send![
+            center,
+            addObserver: observer
+            selector: sel!(activeSpaceDidChange:)
+            name: name
+            object: nil
+        ];
+    }
+}
+
 #[link(name = "CoreGraphics", kind = "framework")]
 extern "C" {
     pub fn CGRectMakeWithDictionaryRepresentation(
@@ -69,6 +106,35 @@ pub fn write_string_to_pasteboard(string: &str) {
     }
 }
 
+/// Writes the contents of an mp4 to the pasteboard as `public.mpeg-4` data,
+/// which some chat apps accept as an inline video rather than a file attachment.
+pub fn write_video_data_to_pasteboard(path: &str) -> Result<(), String> {
+    use cocoa::appkit::NSPasteboard;
+    use objc::rc::autoreleasepool;
+    use objc::runtime::{BOOL, NO};
+
+    unsafe {
+        autoreleasepool(|| {
+            let path = NSString::alloc(nil).init_str(path);
+            let data: id = msg_send![class!(NSData), dataWithContentsOfFile: path];
+            if data == nil {
+                return Err("Failed to read video file".to_string());
+            }
+
+            let pasteboard: id = NSPasteboard::generalPasteboard(nil);
+            NSPasteboard::clearContents(pasteboard);
+
+            let uti = NSString::alloc(nil).init_str("public.mpeg-4");
+            let result: BOOL = msg_send![pasteboard, setData: data forType: uti];
+            if result == NO {
+                return Err("Failed to write to pasteboard".to_string());
+            }
+
+            Ok(())
+        })
+    }
+}
+
 /// Makes the background of the WKWebView layer transparent.
 /// This differs from Tauri's implementation as it does not change the window background which causes performance performance issues and artifacts when shadows are enabled on the window.
 /// Use Tauri's implementation to make the window itself transparent.
@@ -1 +1,71 @@
+use std::ffi::OsStr;
+use std::os::windows::ffi::OsStrExt;
 
+use windows::Win32::{
+    Foundation::{HANDLE, HWND, POINT},
+    System::{
+        DataExchange::{CloseClipboard, EmptyClipboard, OpenClipboard, SetClipboardData},
+        Memory::{GlobalAlloc, GlobalFree, GlobalLock, GlobalUnlock, GMEM_MOVEABLE},
+        Ole::CF_HDROP,
+    },
+    UI::Shell::DROPFILES,
+};
+
+/// Places a file reference on the clipboard so it can be pasted into Explorer and other apps.
+pub fn write_file_to_clipboard(path: &str) -> Result<(), String> {
+    // CF_HDROP expects a DROPFILES header followed by a double-null-terminated list of wide paths
+    let wide_path: Vec<u16> = OsStr::new(path)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .chain(std::iter::once(0))
+        .collect();
+
+    let header_size = std::mem::size_of::<DROPFILES>();
+    let total_size = header_size + wide_path.len() * std::mem::size_of::<u16>();
+
+    unsafe {
+        let hglobal = GlobalAlloc(GMEM_MOVEABLE, total_size)
+            .map_err(|e| format!("Failed to allocate clipboard memory: {e}"))?;
+
+        let ptr = GlobalLock(hglobal) as *mut u8;
+        if ptr.is_null() {
+            let _ = GlobalFree(hglobal);
+            return Err("Failed to lock clipboard memory".to_string());
+        }
+
+        std::ptr::write(
+            ptr as *mut DROPFILES,
+            DROPFILES {
+                pFiles: header_size as u32,
+                pt: POINT { x: 0, y: 0 },
+                fNC: false.into(),
+                fWide: true.into(),
+            },
+        );
+        std::ptr::copy_nonoverlapping(
+            wide_path.as_ptr(),
+            ptr.add(header_size) as *mut u16,
+            wide_path.len(),
+        );
+
+        let _ = GlobalUnlock(hglobal);
+
+        if let Err(e) = OpenClipboard(HWND::default()) {
+            let _ = GlobalFree(hglobal);
+            return Err(format!("Failed to open clipboard: {e}"));
+        }
+
+        let result = EmptyClipboard()
+            .and_then(|_| SetClipboardData(CF_HDROP.0 as u32, HANDLE(hglobal.0)))
+            .map(|_| ())
+            .map_err(|e| format!("Failed to write to clipboard: {e}"));
+
+        if result.is_err() {
+            let _ = GlobalFree(hglobal);
+        }
+
+        let _ = CloseClipboard();
+
+        result
+    }
+}
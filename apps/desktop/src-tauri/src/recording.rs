@@ -1,28 +1,40 @@
-use std::time::Instant;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use crate::{
     audio::AppSounds,
     auth::AuthStore,
-    create_screenshot,
     export::export_video,
     general_settings::GeneralSettingsStore,
-    list_recordings, notifications, open_editor, open_external_link, platform,
+    list_recordings, notifications, open_editor, open_external_link,
+    permissions::{self, OSPermission},
+    platform, recording_path, recordings_path, save_thumbnail,
     upload::get_s3_config,
     upload_exported_video, web_api,
-    windows::{CapWindowId, ShowCapWindow},
-    App, CurrentRecordingChanged, MutableState, NewRecordingAdded, PreCreatedVideo,
-    RecordingStarted, RecordingStopped, UploadMode,
+    windows::{monitor_for_bounds, CapWindowId, ShowCapWindow},
+    App, CommandError, CurrentRecordingChanged, MutableState, NewRecordingAdded, PreCreatedVideo,
+    RecordingCountdown, RecordingStarted, RecordingStopped, ThumbnailReady, UploadMode,
 };
 use cap_flags::FLAGS;
-use cap_media::feeds::CameraFeed;
-use cap_media::sources::{AVFrameCapture, CaptureScreen, CaptureWindow, ScreenCaptureSource};
+use cap_media::feeds::{AudioInputFeed, CameraDeviceInfo, CameraFeed};
+use cap_media::platform::Bounds;
+use cap_media::sources::{
+    AVFrameCapture, CaptureApplication, CaptureScreen, CaptureWindow, ScreenCaptureSource,
+    ScreenCaptureTarget,
+};
 use cap_project::{
-    Content, ProjectConfiguration, TimelineConfiguration, TimelineSegment, ZoomSegment,
+    Content, Encoder, Marker, ProjectConfiguration, TimelineConfiguration, TimelineSegment,
+    ZoomMode, ZoomSegment,
 };
-use cap_recording::CompletedRecording;
+use cap_recording::{CompletedRecording, RecordingOptions};
 use cap_rendering::{ProjectRecordings, ZOOM_DURATION};
+use serde::Serialize;
+use specta::Type;
 use tauri::{AppHandle, Manager};
 use tauri_specta::Event;
+use tokio::sync::RwLock;
 
 #[tauri::command(async)]
 #[specta::specta]
@@ -30,37 +42,327 @@ pub fn list_capture_screens() -> Vec<CaptureScreen> {
     ScreenCaptureSource::<AVFrameCapture>::list_screens()
 }
 
+#[derive(Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayInfo {
+    id: u32,
+    name: String,
+    bounds: Bounds,
+    scale_factor: f64,
+}
+
+#[tauri::command(async)]
+#[specta::specta]
+pub fn get_displays(app: AppHandle) -> Vec<DisplayInfo> {
+    ScreenCaptureSource::<AVFrameCapture>::list_screens()
+        .into_iter()
+        .map(|screen| {
+            let bounds = cap_media::platform::monitor_bounds(screen.id);
+            let scale_factor = monitor_for_bounds(&app, Some(bounds))
+                .ok()
+                .flatten()
+                .map(|monitor| monitor.scale_factor())
+                .unwrap_or(1.0);
+
+            DisplayInfo {
+                id: screen.id,
+                name: screen.name,
+                bounds,
+                scale_factor,
+            }
+        })
+        .collect()
+}
+
 #[tauri::command(async)]
 #[specta::specta]
 pub fn list_capture_windows() -> Vec<CaptureWindow> {
     ScreenCaptureSource::<AVFrameCapture>::list_windows()
 }
 
+#[tauri::command(async)]
+#[specta::specta]
+pub fn list_capture_applications() -> Vec<CaptureApplication> {
+    ScreenCaptureSource::<AVFrameCapture>::list_applications()
+}
+
 #[tauri::command(async)]
 #[specta::specta]
 pub fn list_cameras() -> Vec<String> {
     CameraFeed::list_cameras()
 }
 
+/// Slower than `list_cameras` since each device is opened to query its formats
+#[tauri::command(async)]
+#[specta::specta]
+pub fn list_camera_devices() -> Vec<CameraDeviceInfo> {
+    CameraFeed::list_cameras_with_formats()
+}
+
+#[tauri::command(async)]
+#[specta::specta]
+pub fn list_camera_resolutions(camera_label: String) -> Result<Vec<(u32, u32)>, CommandError> {
+    Ok(CameraFeed::list_resolutions(&camera_label)?)
+}
+
+#[derive(Serialize, Type, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum CaptureTargetKind {
+    Screen,
+    Window,
+    Application,
+    Region,
+}
+
+/// What can be recorded and exported on the running platform
+#[derive(Serialize, Type, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    capture_targets: Vec<CaptureTargetKind>,
+    /// Whether window and application recordings leave out everything else on screen,
+    /// otherwise the area they cover is recorded as is
+    isolates_windows: bool,
+    /// Whether a loopback device is installed to record system audio from
+    system_audio: bool,
+    /// Whether the OS cursor can be left out of screen captures
+    hides_os_cursor: bool,
+    encoders: Vec<Encoder>,
+}
+
 #[tauri::command]
 #[specta::specta]
-pub async fn start_recording(app: AppHandle, state: MutableState<'_, App>) -> Result<(), String> {
-    let mut state = state.write().await;
+pub async fn get_capabilities() -> Capabilities {
+    use CaptureTargetKind::*;
+
+    let isolates_windows = !cfg!(target_os = "linux");
+
+    // Linux captures through x11grab, which needs an X11 display
+    let capture_targets = if cfg!(target_os = "linux") && std::env::var_os("DISPLAY").is_none() {
+        vec![]
+    } else {
+        let mut targets = vec![Screen];
+        // Platforms that can't enumerate windows have none to pick from
+        if !ScreenCaptureSource::<AVFrameCapture>::list_windows().is_empty() {
+            targets.push(Window);
+        }
+        // Without isolation an application recording is just the whole screen
+        if isolates_windows {
+            targets.push(Application);
+        }
+        targets.push(Region);
+        targets
+    };
+
+    Capabilities {
+        capture_targets,
+        isolates_windows,
+        system_audio: AudioInputFeed::loopback_device_name().is_some(),
+        hides_os_cursor: !cfg!(target_os = "linux"),
+        encoders: cap_export::available_encoders().await.to_vec(),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_audio_devices() -> Result<Vec<String>, CommandError> {
+    if !permissions::do_permissions_check(false)
+        .microphone
+        .permitted()
+    {
+        return Ok(vec![]);
+    }
+
+    Ok(AudioInputFeed::list_devices().keys().cloned().collect())
+}
+
+/// Less free space than this is reported as a problem before recording
+const MIN_FREE_DISK_SPACE: u64 = 1024 * 1024 * 1024;
+
+#[derive(Serialize, Type, Debug)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum ValidationResult {
+    Ok,
+    /// Nothing to check, eg. no camera is selected
+    Skipped,
+    Failed {
+        reason: String,
+    },
+}
+
+impl ValidationResult {
+    fn check(passed: bool, reason: impl FnOnce() -> String) -> Self {
+        if passed {
+            Self::Ok
+        } else {
+            Self::Failed { reason: reason() }
+        }
+    }
+
+    fn failed(&self) -> bool {
+        matches!(self, Self::Failed { .. })
+    }
+}
+
+#[derive(Serialize, Type, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReport {
+    /// Whether every check passed or was skipped
+    ready: bool,
+    capture_target: ValidationResult,
+    camera: ValidationResult,
+    audio_input: ValidationResult,
+    ffmpeg: ValidationResult,
+    disk_space: ValidationResult,
+}
+
+/// Checks that a recording could be started with `options` without starting one
+#[tauri::command]
+#[specta::specta]
+pub async fn validate_recording_options(
+    app: AppHandle,
+    options: RecordingOptions,
+) -> ValidationReport {
+    let capture_target = if options.camera_only {
+        ValidationResult::Skipped
+    } else if !permissions::check_permission(OSPermission::ScreenRecording).permitted() {
+        ValidationResult::Failed {
+            reason: "Screen recording access needs to be granted in System Settings".to_string(),
+        }
+    } else {
+        match &options.capture_target {
+            ScreenCaptureTarget::Screen(screen) => ValidationResult::check(
+                ScreenCaptureSource::<AVFrameCapture>::list_screens()
+                    .iter()
+                    .any(|s| s.id == screen.id),
+                || format!("Screen '{}' is no longer connected", screen.name),
+            ),
+            ScreenCaptureTarget::Window(window) => ValidationResult::check(
+                ScreenCaptureSource::<AVFrameCapture>::list_windows()
+                    .iter()
+                    .any(|w| w.id == window.id),
+                || format!("Window '{}' is no longer open", window.name),
+            ),
+            ScreenCaptureTarget::Application(application) => ValidationResult::check(
+                ScreenCaptureSource::<AVFrameCapture>::list_applications()
+                    .iter()
+                    .any(|a| a.bundle_id == application.bundle_id),
+                || format!("{} has no open windows", application.name),
+            ),
+            ScreenCaptureTarget::Region { bounds } => {
+                ValidationResult::check(bounds.width >= 1.0 && bounds.height >= 1.0, || {
+                    "The selected region is empty".to_string()
+                })
+            }
+        }
+    };
+
+    let camera = match options.camera_label() {
+        Some(label) if !permissions::check_permission(OSPermission::Camera).permitted() => {
+            ValidationResult::Failed {
+                reason: format!("Camera access is needed to record from '{label}'"),
+            }
+        }
+        Some(label) => ValidationResult::check(
+            CameraFeed::list_cameras().iter().any(|c| c == label),
+            || format!("Camera '{label}' isn't connected"),
+        ),
+        None if options.camera_only => ValidationResult::Failed {
+            reason: "Camera only recordings need a camera".to_string(),
+        },
+        None => ValidationResult::Skipped,
+    };
+
+    let audio_input = match options.audio_input_name() {
+        Some(name) if !permissions::check_permission(OSPermission::Microphone).permitted() => {
+            ValidationResult::Failed {
+                reason: format!("Microphone access is needed to record from '{name}'"),
+            }
+        }
+        Some(name) => {
+            ValidationResult::check(AudioInputFeed::list_devices().contains_key(name), || {
+                format!("Microphone '{name}' isn't connected")
+            })
+        }
+        None => ValidationResult::Skipped,
+    };
+
+    let ffmpeg = match cap_ffmpeg_cli::check_ffmpeg().await {
+        Ok(_) => ValidationResult::Ok,
+        Err(e) => ValidationResult::Failed {
+            reason: format!("FFmpeg is unavailable: {e}"),
+        },
+    };
+
+    let disk_space = match crate::disk_info(&recordings_path(&app)) {
+        Ok(disk) => ValidationResult::check(disk.free >= MIN_FREE_DISK_SPACE, || {
+            format!(
+                "Only {} MB of disk space is left",
+                disk.free / (1024 * 1024)
+            )
+        }),
+        Err(e) => ValidationResult::Failed {
+            reason: format!("Failed to check free disk space: {e}"),
+        },
+    };
+
+    ValidationReport {
+        ready: ![&capture_target, &camera, &audio_input, &ffmpeg, &disk_space]
+            .iter()
+            .any(|r| r.failed()),
+        capture_target,
+        camera,
+        audio_input,
+        ffmpeg,
+        disk_space,
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn start_recording(
+    app: AppHandle,
+    state_lock: MutableState<'_, App>,
+) -> Result<(), CommandError> {
+    let mut state = state_lock.write().await;
+
+    // Captures without the permission are black instead of failing
+    if !state.start_recording_options.camera_only
+        && !permissions::check_permission(OSPermission::ScreenRecording).permitted()
+    {
+        return Err(CommandError::InvalidInput(
+            "Screen recording access needs to be granted in System Settings".to_string(),
+        ));
+    }
+
+    let app_state = &mut *state;
+    let countdown = claim_recording_start(
+        &app_state.current_recording,
+        &mut app_state.recording_countdown,
+        app_state.start_recording_options.countdown_secs,
+    )?;
+
+    if let Some((secs, cancel_rx)) = countdown {
+        drop(state);
+
+        if !run_countdown(&app, secs, cancel_rx).await {
+            return Ok(());
+        }
+
+        state = state_lock.write().await;
+
+        // stop_recording takes the countdown to cancel it, which may have raced the final tick
+        if state.recording_countdown.take().is_none() {
+            return Ok(());
+        }
+    }
 
     let id = uuid::Uuid::new_v4().to_string();
 
-    let recording_dir = app
-        .path()
-        .app_data_dir()
-        .unwrap()
-        .join("recordings")
-        .join(format!("{id}.cap"));
+    let recording_dir = recording_path(&app, &id);
 
     // Check if auto_create_shareable_link is true and user is upgraded
-    let general_settings = GeneralSettingsStore::get(&app)?;
-    let auto_create_shareable_link = general_settings
-        .map(|settings| settings.auto_create_shareable_link)
-        .unwrap_or(false);
+    let general_settings = GeneralSettingsStore::get(&app)?.unwrap_or_default();
+    let auto_create_shareable_link = general_settings.auto_create_shareable_link;
 
     if auto_create_shareable_link {
         sentry::configure_scope(|scope| {
@@ -79,21 +381,26 @@ pub async fn start_recording(app: AppHandle, state: MutableState<'_, App>) -> Re
                         config: s3_config,
                     });
 
-                    println!("Pre-created shareable link: {}", link);
+                    tracing::info!("Pre-created shareable link: {}", link);
                 };
             }
         }
     }
 
+    let mut recording_options = state.start_recording_options.clone();
+    recording_options.fps = recording_options.fps.or(general_settings.default_fps);
+
     let actor = cap_recording::spawn_recording_actor(
         id,
-        recording_dir,
-        state.start_recording_options.clone(),
+        recording_dir.clone(),
+        recording_options,
         state.camera_feed.clone(),
         state.audio_input_feed.clone(),
     )
     .await
-    .map_err(|e| e.to_string())?;
+    .map_err(|e| CommandError::Media(e.to_string()))?;
+
+    std::fs::write(recording_dir.join(crate::UNFINALIZED_MARKER), "").ok();
 
     state.set_current_recording(actor);
 
@@ -117,13 +424,56 @@ pub async fn start_recording(app: AppHandle, state: MutableState<'_, App>) -> Re
     Ok(())
 }
 
+/// Fails if a recording is already running or counting down, otherwise returns the
+/// countdown to run before recording, if there is one. Its cancel sender is stored
+/// before the lock guarding both is released, so another start_recording can't also
+/// pass the check while this one counts down.
+fn claim_recording_start<R>(
+    current_recording: &Option<R>,
+    recording_countdown: &mut Option<tokio::sync::oneshot::Sender<()>>,
+    countdown_secs: Option<u32>,
+) -> Result<Option<(u32, tokio::sync::oneshot::Receiver<()>)>, CommandError> {
+    if current_recording.is_some() || recording_countdown.is_some() {
+        return Err(CommandError::InvalidInput(
+            "Recording already in progress".to_string(),
+        ));
+    }
+
+    Ok(countdown_secs.filter(|secs| *secs > 0).map(|secs| {
+        let (cancel_tx, cancel_rx) = tokio::sync::oneshot::channel();
+        *recording_countdown = Some(cancel_tx);
+        (secs, cancel_rx)
+    }))
+}
+
+/// Emits `RecordingCountdown` every second, returning false if the countdown was cancelled
+async fn run_countdown(
+    app: &AppHandle,
+    secs: u32,
+    mut cancel_rx: tokio::sync::oneshot::Receiver<()>,
+) -> bool {
+    for remaining in (1..=secs).rev() {
+        RecordingCountdown { remaining }.emit(app).ok();
+
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(1)) => {}
+            _ = &mut cancel_rx => return false,
+        }
+    }
+
+    true
+}
+
 #[tauri::command]
 #[specta::specta]
-pub async fn pause_recording(state: MutableState<'_, App>) -> Result<(), String> {
+pub async fn pause_recording(state: MutableState<'_, App>) -> Result<(), CommandError> {
     let mut state = state.write().await;
 
     if let Some(recording) = state.current_recording.as_mut() {
-        recording.pause().await.map_err(|e| e.to_string())?;
+        recording
+            .pause()
+            .await
+            .map_err(|e| CommandError::Media(e.to_string()))?;
     }
 
     Ok(())
@@ -131,27 +481,113 @@ pub async fn pause_recording(state: MutableState<'_, App>) -> Result<(), String>
 
 #[tauri::command]
 #[specta::specta]
-pub async fn resume_recording(state: MutableState<'_, App>) -> Result<(), String> {
+pub async fn resume_recording(state: MutableState<'_, App>) -> Result<(), CommandError> {
     let mut state = state.write().await;
 
     if let Some(recording) = state.current_recording.as_mut() {
-        recording.resume().await.map_err(|e| e.to_string())?;
+        recording
+            .resume()
+            .await
+            .map_err(|e| CommandError::Media(e.to_string()))?;
     }
 
     Ok(())
 }
 
+/// Marks the current point of the recording in progress
+#[tauri::command]
+#[specta::specta]
+pub async fn add_marker(
+    state: MutableState<'_, App>,
+    label: Option<String>,
+) -> Result<Marker, CommandError> {
+    let state = state.read().await;
+
+    let Some(recording) = state.current_recording.as_ref() else {
+        return Err(CommandError::InvalidInput(
+            "Recording not in progress".to_string(),
+        ));
+    };
+
+    recording
+        .add_marker(label)
+        .await
+        .map_err(|e| CommandError::Media(e.to_string()))
+}
+
 #[tauri::command]
 #[specta::specta]
-pub async fn stop_recording(app: AppHandle, state: MutableState<'_, App>) -> Result<(), String> {
+pub async fn get_markers(app: AppHandle, video_id: String) -> Result<Vec<Marker>, CommandError> {
+    Ok(Marker::load_for_project(&recording_path(&app, &video_id))?)
+}
+
+/// Longest quitting waits for the current recording to be finalized
+const QUIT_FINALIZE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Stops and finalizes the current recording so it's playable after the app quits,
+/// skipping everything else done when a recording stops normally
+pub async fn stop_recording_for_quit(app: &AppHandle) {
+    let Some(current_recording) = app
+        .state::<Arc<RwLock<App>>>()
+        .write()
+        .await
+        .clear_current_recording()
+    else {
+        return;
+    };
+
+    tracing::info!("Finalizing the current recording before quitting");
+
+    let finalize = async {
+        let completed_recording = current_recording.stop().await.map_err(|e| e.to_string())?;
+        crate::finalize_recording(
+            &completed_recording.recording_dir,
+            &completed_recording.meta,
+        )
+        .await;
+        Ok::<_, String>(())
+    };
+
+    match tokio::time::timeout(QUIT_FINALIZE_TIMEOUT, finalize).await {
+        Ok(Ok(())) => tracing::info!("Finalized the recording"),
+        Ok(Err(e)) => tracing::error!("Failed to stop the recording: {e}"),
+        Err(_) => tracing::error!("Timed out finalizing the recording, quitting anyway"),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_recording(
+    app: AppHandle,
+    state: MutableState<'_, App>,
+) -> Result<(), CommandError> {
     let mut state = state.write().await;
+
+    if let Some(countdown) = state.recording_countdown.take() {
+        countdown.send(()).ok();
+        return Ok(());
+    }
+
     let Some(current_recording) = state.clear_current_recording() else {
-        return Err("Recording not in progress".to_string())?;
+        return Err(CommandError::InvalidInput(
+            "Recording not in progress".to_string(),
+        ));
     };
 
     let now = Instant::now();
-    let completed_recording = current_recording.stop().await.map_err(|e| e.to_string())?;
-    println!("stopped recording in {:?}", now.elapsed());
+    let completed_recording = current_recording
+        .stop()
+        .await
+        .map_err(|e| CommandError::Media(e.to_string()))?;
+    tracing::info!("stopped recording in {:?}", now.elapsed());
+
+    let now = Instant::now();
+    crate::finalize_recording(
+        &completed_recording.recording_dir,
+        &completed_recording.meta,
+    )
+    .await;
+    tracing::info!("finalized recording in {:?}", now.elapsed());
 
     if let Some(window) = CapWindowId::InProgressRecording.get(&app) {
         window.hide().unwrap();
@@ -161,9 +597,6 @@ pub async fn stop_recording(app: AppHandle, state: MutableState<'_, App>) -> Res
         window.unminimize().ok();
     }
 
-    let screenshots_dir = completed_recording.recording_dir.join("screenshots");
-    std::fs::create_dir_all(&screenshots_dir).ok();
-
     let display_output_path = match &completed_recording.meta.content {
         Content::SingleSegment { segment } => {
             segment.path(&completed_recording.meta, &segment.display.path)
@@ -173,10 +606,29 @@ pub async fn stop_recording(app: AppHandle, state: MutableState<'_, App>) -> Res
         }
     };
 
-    let display_screenshot = screenshots_dir.join("display.jpg");
-    let now = Instant::now();
-    create_screenshot(display_output_path, display_screenshot.clone(), None).await?;
-    println!("created screenshot in {:?}", now.elapsed());
+    // Grabbing the frame can take a while for long recordings, so the thumbnail is
+    // filled in once it's ready instead of holding up the captures panel
+    tauri::async_runtime::spawn({
+        let app = app.clone();
+        let video_id = completed_recording.id.clone();
+        let recording_dir = completed_recording.recording_dir.clone();
+        async move {
+            let now = Instant::now();
+            match save_thumbnail(&app, &recording_dir, display_output_path, 0.0).await {
+                Ok(thumbnail_path) => {
+                    tracing::info!("created screenshot in {:?}", now.elapsed());
+                    ThumbnailReady {
+                        video_id,
+                        path: recording_dir,
+                        thumbnail_path,
+                    }
+                    .emit(&app)
+                    .ok();
+                }
+                Err(e) => tracing::error!("Failed to create screenshot: {e}"),
+            }
+        }
+    });
 
     // let thumbnail = screenshots_dir.join("thumbnail.png");
     // let now = Instant::now();
@@ -185,10 +637,15 @@ pub async fn stop_recording(app: AppHandle, state: MutableState<'_, App>) -> Res
 
     let recording_dir = completed_recording.recording_dir.clone();
 
-    ShowCapWindow::PrevRecordings.show(&app).ok();
+    ShowCapWindow::PrevRecordings {
+        screen_bounds: Some(completed_recording.display_source.get_bounds()),
+    }
+    .show(&app)
+    .ok();
 
     NewRecordingAdded {
         path: recording_dir.clone(),
+        thumbnail_path: None,
     }
     .emit(&app)
     .ok();
@@ -201,11 +658,15 @@ pub async fn stop_recording(app: AppHandle, state: MutableState<'_, App>) -> Res
 
     let recordings = ProjectRecordings::new(&completed_recording.meta);
 
-    let config = project_config_from_recording(&completed_recording, &recordings);
+    let default_encoder = GeneralSettingsStore::get(&app)
+        .ok()
+        .flatten()
+        .map(|settings| settings.default_encoder)
+        .unwrap_or_default();
+
+    let config = project_config_from_recording(&completed_recording, &recordings, default_encoder);
 
-    config
-        .write(&completed_recording.recording_dir)
-        .map_err(|e| e.to_string())?;
+    config.write(&completed_recording.recording_dir)?;
 
     AppSounds::StopRecording.play();
 
@@ -242,6 +703,7 @@ pub async fn stop_recording(app: AppHandle, state: MutableState<'_, App>) -> Res
                         config,
                         tauri::ipc::Channel::new(|_| Ok(())),
                         true,
+                        None,
                     )
                     .await
                     .ok();
@@ -257,21 +719,23 @@ pub async fn stop_recording(app: AppHandle, state: MutableState<'_, App>) -> Res
                         .await
                         {
                             Ok(_) => {
-                                println!("Video uploaded successfully");
+                                tracing::info!("Video uploaded successfully");
                                 // Don't send notification here since we already did it above
                                 break;
                             }
                             Err(e) => {
                                 retry_count += 1;
-                                println!(
+                                tracing::error!(
                                     "Error during auto-upload (attempt {}/{}): {}",
-                                    retry_count, max_retries, e
+                                    retry_count,
+                                    max_retries,
+                                    e
                                 );
 
                                 if retry_count < max_retries {
                                     tokio::time::sleep(std::time::Duration::from_secs(5)).await;
                                 } else {
-                                    println!("Max retries reached. Upload failed.");
+                                    tracing::error!("Max retries reached. Upload failed.");
                                     notifications::send_notification(
                                         &app,
                                         notifications::NotificationType::UploadFailed,
@@ -315,6 +779,7 @@ fn generate_zoom_segments_from_clicks(
                 start: (click.process_time_ms / 1000.0 - (ZOOM_DURATION + 0.2)).max(0.0),
                 end: click.process_time_ms / 1000.0 + ZOOM_SEGMENT_AFTER_CLICK_PADDING,
                 amount: 2.0,
+                mode: ZoomMode::Auto,
             });
         } else {
             let last_segment = segments.last_mut().unwrap();
@@ -328,6 +793,7 @@ fn generate_zoom_segments_from_clicks(
                         start: (time - ZOOM_DURATION).max(0.0),
                         end: time + ZOOM_SEGMENT_AFTER_CLICK_PADDING,
                         amount: 2.0,
+                        mode: ZoomMode::Auto,
                     });
                 }
             } else {
@@ -337,14 +803,39 @@ fn generate_zoom_segments_from_clicks(
         }
     }
 
+    // Target each zoom at the centre of the clicks that produced it
+    for segment in &mut segments {
+        let (sum_x, sum_y, count) = recording
+            .cursor_data
+            .clicks
+            .iter()
+            .filter(|click| click.down)
+            .filter(|click| {
+                let time = click.process_time_ms / 1000.0;
+                time >= segment.start && time <= segment.end
+            })
+            .fold((0.0, 0.0, 0), |(x, y, n), click| {
+                (x + click.x, y + click.y, n + 1)
+            });
+
+        if count > 0 {
+            segment.mode = ZoomMode::Manual {
+                x: (sum_x / count as f64).clamp(0.0, 1.0) as f32,
+                y: (sum_y / count as f64).clamp(0.0, 1.0) as f32,
+            };
+        }
+    }
+
     segments
 }
 
 fn project_config_from_recording(
     completed_recording: &CompletedRecording,
     recordings: &ProjectRecordings,
+    encoder: Encoder,
 ) -> ProjectConfiguration {
     ProjectConfiguration {
+        encoder,
         timeline: Some(TimelineConfiguration {
             segments: recordings
                 .segments
@@ -362,3 +853,103 @@ fn project_config_from_recording(
         ..Default::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The parts of `App` that `start_recording` claims the start with
+    #[derive(Default)]
+    struct RecordingState {
+        current_recording: Option<()>,
+        recording_countdown: Option<tokio::sync::oneshot::Sender<()>>,
+    }
+
+    type Countdown = Option<(u32, tokio::sync::oneshot::Receiver<()>)>;
+
+    async fn claim(state: &RwLock<RecordingState>) -> Result<Countdown, CommandError> {
+        let mut state = state.write().await;
+        let state = &mut *state;
+
+        claim_recording_start(
+            &state.current_recording,
+            &mut state.recording_countdown,
+            Some(3),
+        )
+    }
+
+    #[tokio::test]
+    async fn only_one_of_two_concurrent_starts_succeeds() {
+        let state = Arc::new(RwLock::new(RecordingState::default()));
+
+        let (a, b) = tokio::join!(
+            tokio::spawn({
+                let state = state.clone();
+                async move { claim(&state).await }
+            }),
+            tokio::spawn({
+                let state = state.clone();
+                async move { claim(&state).await }
+            })
+        );
+
+        let results = [a.unwrap(), b.unwrap()];
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+        assert!(results
+            .iter()
+            .any(|r| matches!(r, Err(CommandError::InvalidInput(_)))));
+    }
+
+    #[test]
+    fn a_rejected_start_leaves_the_countdown_cancellable() {
+        let mut recording_countdown = None;
+
+        let (secs, mut cancel_rx) =
+            claim_recording_start(&None::<()>, &mut recording_countdown, Some(3))
+                .unwrap()
+                .unwrap();
+        assert_eq!(secs, 3);
+
+        assert!(matches!(
+            claim_recording_start(&None::<()>, &mut recording_countdown, Some(3)),
+            Err(CommandError::InvalidInput(_))
+        ));
+
+        // What stop_recording does to cancel the countdown
+        let cancel_tx = recording_countdown.take().unwrap();
+        assert!(cancel_tx.send(()).is_ok());
+        assert!(cancel_rx.try_recv().is_ok());
+    }
+
+    #[test]
+    fn starting_while_recording_fails() {
+        let mut recording_countdown = None;
+
+        assert!(matches!(
+            claim_recording_start(&Some(()), &mut recording_countdown, None),
+            Err(CommandError::InvalidInput(_))
+        ));
+        assert!(recording_countdown.is_none());
+    }
+
+    #[test]
+    fn starting_again_after_a_cancelled_countdown_succeeds() {
+        let mut recording_countdown = None;
+
+        let countdown = claim_recording_start(&None::<()>, &mut recording_countdown, Some(3));
+        assert!(countdown.unwrap().is_some());
+
+        recording_countdown.take();
+
+        assert!(claim_recording_start(&None::<()>, &mut recording_countdown, Some(3)).is_ok());
+    }
+
+    #[test]
+    fn a_zero_second_countdown_starts_immediately() {
+        let mut recording_countdown = None;
+
+        let countdown = claim_recording_start(&None::<()>, &mut recording_countdown, Some(0));
+        assert!(countdown.unwrap().is_none());
+        assert!(recording_countdown.is_none());
+    }
+}
@@ -11,7 +11,7 @@ use tokio::task;
 
 use crate::web_api::{self, ManagerExt};
 
-use crate::UploadProgress;
+use crate::{thumbnail_path, UploadProgress};
 use serde::de::{self, Deserializer};
 use serde::{Deserialize, Serialize};
 use specta::Type;
@@ -165,7 +165,7 @@ pub async fn upload_video(
     is_individual: bool,
     existing_config: Option<S3UploadMeta>,
 ) -> Result<UploadedVideo, String> {
-    println!("Uploading video {video_id}...");
+    tracing::info!("Uploading video {video_id}...");
 
     let file_name = file_path
         .file_name()
@@ -252,18 +252,13 @@ pub async fn upload_video(
     let form = form.part("file", file_part);
 
     // Prepare screenshot upload
-    let screenshot_path = file_path
-        .parent()
-        .unwrap()
-        .parent()
-        .unwrap()
-        .join("screenshots")
-        .join("display.jpg");
-
-    let screenshot_upload = if screenshot_path.exists() {
-        Some(prepare_screenshot_upload(app, &s3_config, screenshot_path).await?)
-    } else {
-        None
+    let screenshot_path = thumbnail_path(file_path.parent().unwrap().parent().unwrap());
+
+    let screenshot_upload = match screenshot_path {
+        Some(screenshot_path) => {
+            Some(prepare_screenshot_upload(app, &s3_config, screenshot_path).await?)
+        }
+        None => None,
     };
 
     let (video_upload, screenshot_result): (
@@ -296,13 +291,13 @@ pub async fn upload_video(
         .emit(app)
         .ok();
 
-        println!("Video uploaded successfully");
+        tracing::info!("Video uploaded successfully");
 
         if let Some(Ok(screenshot_response)) = screenshot_result {
             if screenshot_response.status().is_success() {
-                println!("Screenshot uploaded successfully");
+                tracing::info!("Screenshot uploaded successfully");
             } else {
-                println!(
+                tracing::error!(
                     "Failed to upload screenshot: {}",
                     screenshot_response.status()
                 );
@@ -344,7 +339,7 @@ pub async fn upload_image(app: &AppHandle, file_path: PathBuf) -> Result<Uploade
 
     let file_key = format!("{}/{}/{}", s3_config.user_id, s3_config.id, file_name);
 
-    println!("File key: {file_key}");
+    tracing::info!("File key: {file_key}");
 
     let body = S3ImageUploadBody {
         base: S3UploadBody {
@@ -375,7 +370,7 @@ pub async fn upload_image(app: &AppHandle, file_path: PathBuf) -> Result<Uploade
         .map_err(|e| format!("Failed to send upload file request: {}", e))?;
 
     if response.status().is_success() {
-        println!("File uploaded successfully");
+        tracing::info!("File uploaded successfully");
         return Ok(UploadedImage {
             link: web_api::make_url(format!("/s/{}", &s3_config.id)),
             id: s3_config.id,
@@ -410,7 +405,7 @@ pub async fn upload_audio(app: &AppHandle, file_path: PathBuf) -> Result<Uploade
 
     let file_key = format!("{}/{}/{}", s3_config.user_id, s3_config.id, file_name);
 
-    println!("File key: {file_key}");
+    tracing::info!("File key: {file_key}");
 
     let body = build_audio_upload_body(
         &file_path,
@@ -448,7 +443,7 @@ pub async fn upload_audio(app: &AppHandle, file_path: PathBuf) -> Result<Uploade
         .map_err(|e| format!("Failed to send upload file request: {}", e))?;
 
     if response.status().is_success() {
-        println!("Audio file uploaded successfully");
+        tracing::info!("Audio file uploaded successfully");
         return Ok(UploadedAudio {
             link: web_api::make_url(format!("/s/{}", &s3_config.id)),
             id: s3_config.id.clone(),
@@ -749,7 +744,7 @@ pub async fn upload_individual_file(
         .map_err(|e| format!("Failed to send upload file request: {}", e))?;
 
     if response.status().is_success() {
-        println!("Individual file uploaded successfully");
+        tracing::info!("Individual file uploaded successfully");
         Ok(())
     } else {
         let status = response.status();
@@ -34,7 +34,7 @@ impl<T: Manager<R> + Emitter<R>, R: Runtime> ManagerExt<R> for T {
         build: impl FnOnce(reqwest::Client) -> reqwest::RequestBuilder,
     ) -> Result<reqwest::Response, String> {
         let Some(auth) = AuthStore::get(self.app_handle())? else {
-            println!("Not logged in");
+            tracing::warn!("Not logged in");
 
             AuthenticationInvalid.emit(self).ok();
 
@@ -46,7 +46,7 @@ impl<T: Manager<R> + Emitter<R>, R: Runtime> ManagerExt<R> for T {
             .map_err(|e| e.to_string())?;
 
         if response.status() == StatusCode::UNAUTHORIZED {
-            println!("Authentication expired. Please log in again.");
+            tracing::warn!("Authentication expired. Please log in again.");
 
             AuthenticationInvalid.emit(self).ok();
 
@@ -2,15 +2,21 @@
 
 use crate::fake_window;
 use cap_flags::FLAGS;
+use cap_media::platform::Bounds;
 use serde::Deserialize;
 use specta::Type;
 use std::{path::PathBuf, str::FromStr};
 use tauri::{
-    AppHandle, LogicalPosition, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder, Wry,
+    AppHandle, LogicalPosition, LogicalSize, Manager, Monitor, WebviewUrl, WebviewWindow,
+    WebviewWindowBuilder, Wry,
 };
 
 const DEFAULT_TRAFFIC_LIGHTS_INSET: LogicalPosition<f64> = LogicalPosition::new(12.0, 12.0);
 
+/// Where windows are placed when no monitor is reported,
+/// which can happen while displays are asleep or a laptop is being docked
+const FALLBACK_MONITOR_SIZE: LogicalSize<f64> = LogicalSize::new(1440.0, 900.0);
+
 #[derive(Clone)]
 pub enum CapWindowId {
     // Contains onboarding + permissions
@@ -107,8 +113,8 @@ pub enum ShowCapWindow {
     Main,
     Settings { page: Option<String> },
     Editor { project_id: String },
-    PrevRecordings,
-    WindowCaptureOccluder,
+    PrevRecordings { screen_bounds: Option<Bounds> },
+    WindowCaptureOccluder { screen_bounds: Bounds },
     Camera { ws_port: u16 },
     InProgressRecording { position: Option<(f64, f64)> },
     Upgrade,
@@ -125,7 +131,33 @@ impl ShowCapWindow {
 
         let id = self.id();
 
-        let monitor = app.primary_monitor()?.unwrap();
+        let monitor = match self {
+            Self::PrevRecordings { screen_bounds } => monitor_for_bounds(app, *screen_bounds)?,
+            Self::WindowCaptureOccluder { screen_bounds } => {
+                monitor_for_bounds(app, Some(*screen_bounds))?
+            }
+            _ => monitor_for_bounds(app, None)?,
+        };
+
+        // These windows cover a whole monitor, so there's nothing to show them on without one
+        if monitor.is_none()
+            && matches!(
+                self,
+                Self::PrevRecordings { .. } | Self::WindowCaptureOccluder { .. }
+            )
+        {
+            return Err(anyhow::anyhow!("No monitor available to show the {id} window on").into());
+        }
+
+        let (monitor_position, monitor_size) = monitor
+            .map(|monitor| {
+                let scale_factor = monitor.scale_factor();
+                (
+                    monitor.position().to_logical::<f64>(scale_factor),
+                    monitor.size().to_logical::<f64>(scale_factor),
+                )
+            })
+            .unwrap_or((LogicalPosition::new(0.0, 0.0), FALLBACK_MONITOR_SIZE));
 
         let window = match self {
             Self::Setup => self
@@ -188,12 +220,7 @@ impl ShowCapWindow {
                     .min_inner_size(WINDOW_SIZE, WINDOW_SIZE * 2.0)
                     .inner_size(WINDOW_SIZE, WINDOW_SIZE * 2.0)
                     .skip_taskbar(true)
-                    .position(
-                        100.0,
-                        (monitor.size().height as f64) / monitor.scale_factor()
-                            - WINDOW_SIZE
-                            - 100.0,
-                    )
+                    .position(100.0, monitor_size.height - WINDOW_SIZE - 100.0)
                     .initialization_script(&format!(
                         "
 			                window.__CAP__ = window.__CAP__ ?? {{}};
@@ -204,7 +231,7 @@ impl ShowCapWindow {
 
                 window_builder.build()?
             }
-            Self::WindowCaptureOccluder => {
+            Self::WindowCaptureOccluder { .. } => {
                 let mut window_builder = self
                     .window_builder(app, "/window-capture-occluder")
                     .maximized(false)
@@ -215,11 +242,8 @@ impl ShowCapWindow {
                     .visible_on_all_workspaces(true)
                     .content_protected(true)
                     .skip_taskbar(true)
-                    .inner_size(
-                        (monitor.size().width as f64) / monitor.scale_factor(),
-                        (monitor.size().height as f64) / monitor.scale_factor(),
-                    )
-                    .position(0.0, 0.0)
+                    .inner_size(monitor_size.width, monitor_size.height)
+                    .position(monitor_position.x, monitor_position.y)
                     .transparent(true);
 
                 let window = window_builder.build()?;
@@ -256,14 +280,14 @@ impl ShowCapWindow {
                     .content_protected(true)
                     .inner_size(width, height)
                     .position(
-                        ((monitor.size().width as f64) / monitor.scale_factor() - width) / 2.0,
-                        (monitor.size().height as f64) / monitor.scale_factor() - height - 120.0,
+                        (monitor_size.width - width) / 2.0,
+                        monitor_size.height - height - 120.0,
                     )
                     // .theme(Some(tauri::Theme::Dark))
                     .skip_taskbar(true)
                     .build()?
             }
-            Self::PrevRecordings => {
+            Self::PrevRecordings { .. } => {
                 let window = self
                     .window_builder(app, "/prev-recordings")
                     .maximized(false)
@@ -274,12 +298,9 @@ impl ShowCapWindow {
                     .visible_on_all_workspaces(true)
                     .accept_first_mouse(true)
                     .content_protected(true)
-                    .inner_size(
-                        (monitor.size().width as f64) / monitor.scale_factor(),
-                        (monitor.size().height as f64) / monitor.scale_factor(),
-                    )
+                    .inner_size(monitor_size.width, monitor_size.height)
                     .skip_taskbar(true)
-                    .position(0.0, 0.0)
+                    .position(monitor_position.x, monitor_position.y)
                     .transparent(true)
                     .build()?;
 
@@ -310,7 +331,7 @@ impl ShowCapWindow {
 		                }).ok();
                 }
 
-                println!("about to spawn fake window listener");
+                tracing::info!("about to spawn fake window listener");
 
                 fake_window::spawn_fake_window_listener(app.clone(), window.clone());
 
@@ -369,8 +390,8 @@ impl ShowCapWindow {
             ShowCapWindow::Editor { project_id } => CapWindowId::Editor {
                 project_id: project_id.clone(),
             },
-            ShowCapWindow::PrevRecordings => CapWindowId::PrevRecordings,
-            ShowCapWindow::WindowCaptureOccluder => CapWindowId::WindowCaptureOccluder,
+            ShowCapWindow::PrevRecordings { .. } => CapWindowId::PrevRecordings,
+            ShowCapWindow::WindowCaptureOccluder { .. } => CapWindowId::WindowCaptureOccluder,
             ShowCapWindow::Camera { .. } => CapWindowId::Camera,
             ShowCapWindow::InProgressRecording { .. } => CapWindowId::InProgressRecording,
             ShowCapWindow::Upgrade => CapWindowId::Upgrade,
@@ -378,6 +399,38 @@ impl ShowCapWindow {
     }
 }
 
+/// The monitor containing the centre of `bounds`, falling back to the primary monitor and then
+/// any monitor. Returns `None` if no monitors are reported, eg. while they're all asleep.
+pub fn monitor_for_bounds(
+    app: &AppHandle<Wry>,
+    bounds: Option<Bounds>,
+) -> tauri::Result<Option<Monitor>> {
+    if let Some(bounds) = bounds {
+        let center = (
+            bounds.x + bounds.width / 2.0,
+            bounds.y + bounds.height / 2.0,
+        );
+
+        let monitor = app.available_monitors()?.into_iter().find(|monitor| {
+            let position = monitor.position().to_logical::<f64>(monitor.scale_factor());
+            let size = monitor.size().to_logical::<f64>(monitor.scale_factor());
+
+            (position.x..position.x + size.width).contains(&center.0)
+                && (position.y..position.y + size.height).contains(&center.1)
+        });
+
+        if let Some(monitor) = monitor {
+            return Ok(Some(monitor));
+        }
+    }
+
+    if let Some(monitor) = app.primary_monitor()? {
+        return Ok(Some(monitor));
+    }
+
+    Ok(app.available_monitors()?.into_iter().next())
+}
+
 #[cfg(target_os = "macos")]
 fn add_traffic_lights(window: &WebviewWindow<Wry>, controls_inset: Option<LogicalPosition<f64>>) {
     use crate::platform::delegates;
@@ -32,8 +32,10 @@ import {
   getPermissions,
   createVideoDevicesQuery,
   listScreens,
+  listApplications,
 } from "~/utils/queries";
 import {
+  type CaptureApplication,
   CaptureScreen,
   type CaptureWindow,
   commands,
@@ -296,15 +298,18 @@ function TargetSelects(props: {
 }) {
   const screens = createQuery(() => listScreens);
   const windows = createQuery(() => listWindows);
+  const applications = createQuery(() => listApplications);
 
   return (
     <div class="flex flex-row items-center rounded-[0.5rem] relative border">
       <div
-        class="w-1/2 absolute flex p-px inset-0 transition-transform peer-focus-visible:outline outline-2 outline-blue-300 outline-offset-2 rounded-[0.6rem] overflow-hidden"
+        class="w-1/3 absolute flex p-px inset-0 transition-transform peer-focus-visible:outline outline-2 outline-blue-300 outline-offset-2 rounded-[0.6rem] overflow-hidden"
         style={{
           transform:
             props.options?.captureTarget.variant === "window"
               ? "translateX(100%)"
+              : props.options?.captureTarget.variant === "application"
+              ? "translateX(200%)"
               : undefined,
         }}
       >
@@ -354,6 +359,35 @@ function TargetSelects(props: {
           </div>
         )}
       />
+      <TargetSelect<CaptureApplication>
+        options={applications.data ?? []}
+        onChange={(value) => {
+          if (!props.options) return;
+
+          commands.setRecordingOptions({
+            ...props.options,
+            captureTarget: { ...value, variant: "application" },
+          });
+        }}
+        value={
+          props.options?.captureTarget.variant === "application"
+            ? props.options.captureTarget
+            : null
+        }
+        placeholder="App"
+        optionsEmptyText="No applications found"
+        selected={props.options?.captureTarget.variant === "application"}
+        itemComponent={(props) => (
+          <div class="flex-1 flex flex-col overflow-x-hidden">
+            <div class="w-full truncate">{props.item.rawValue?.name}</div>
+            <div class="w-full text-xs">
+              {props.item.rawValue?.windows.length === 1
+                ? "1 window"
+                : `${props.item.rawValue?.windows.length} windows`}
+            </div>
+          </div>
+        )}
+      />
     </div>
   );
 }
@@ -661,7 +695,7 @@ function TargetSelect<T extends { id: number; name: string }>(props: {
         </MenuItem>
       )}
       placement="bottom"
-      class="max-w-[50%] w-full z-10"
+      class="max-w-[33.333%] w-full z-10"
       placeholder={props.placeholder}
       onChange={(value) => {
         if (!value) return;
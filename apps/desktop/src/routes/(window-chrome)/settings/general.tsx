@@ -1,7 +1,11 @@
 import { createResource, Show, For } from "solid-js";
 import { createStore } from "solid-js/store";
 import { generalSettingsStore } from "~/store";
-import { commands, type GeneralSettingsStore } from "~/utils/tauri";
+import {
+  commands,
+  type GeneralSettingsStore,
+  type ThumbnailFormat,
+} from "~/utils/tauri";
 // import { themeStore } from "~/store/theme";
 import {
   isPermissionGranted,
@@ -60,6 +64,26 @@ const settingsList: Array<{
       "When enabled, Cap will not automatically open links in your browser (e.g. after creating a shareable link).",
     pro: true,
   },
+  {
+    key: "disableCaptureOccluder",
+    label: "Don't cover the screen when recording a window",
+    description:
+      "By default everything outside the window or app being recorded is darkened while recording, so it's clear what's being captured.",
+    platforms: ["macos", "windows"],
+  },
+  {
+    key: "captureOccluderOutlineOnly",
+    label: "Outline the recorded window instead of darkening the screen",
+    description:
+      "Draws a border around what's being recorded and leaves the rest of the screen as is.",
+    platforms: ["macos", "windows"],
+  },
+  {
+    key: "cleanupAfterRender",
+    label: "Delete raw recording files after rendering",
+    description:
+      "Frees up storage by removing the original screen and camera tracks once a recording has been rendered. Recordings can no longer be edited or re-rendered afterwards.",
+  },
   {
     key: "enableNotifications",
     label: "Enable System Notifications",
@@ -69,6 +93,12 @@ const settingsList: Array<{
   },
 ];
 
+const thumbnailFormats: Array<{ value: ThumbnailFormat; label: string }> = [
+  { value: "jpeg", label: "JPEG" },
+  { value: "png", label: "PNG" },
+  { value: "webp", label: "WebP" },
+];
+
 export default function GeneralSettings() {
   const [store] = createResource(() => generalSettingsStore.get());
 
@@ -198,6 +228,83 @@ function Inner(props: { initialStore: GeneralSettingsStore | null }) {
               </Show>
             )}
           </For>
+          <div class="space-y-2 py-3">
+            <div class="flex items-center justify-between">
+              <p class="text-[--text-primary]">Default frame rate</p>
+              <input
+                type="number"
+                min="1"
+                max="120"
+                placeholder="30"
+                class="w-16 text-sm bg-transparent text-[--text-primary]"
+                value={settings.defaultFps ?? ""}
+                onChange={(e) => {
+                  const value = e.currentTarget.valueAsNumber;
+                  const defaultFps = Number.isNaN(value)
+                    ? null
+                    : Math.min(120, Math.max(1, Math.round(value)));
+                  setSettings("defaultFps", defaultFps);
+                  generalSettingsStore.set({ defaultFps });
+                }}
+              />
+            </div>
+            <p class="text-xs text-[--text-tertiary]">
+              Frames per second new recordings are captured at, unless another
+              frame rate is picked before recording.
+            </p>
+          </div>
+          <div class="space-y-2 py-3">
+            <div class="flex items-center justify-between">
+              <p class="text-[--text-primary]">Thumbnail format</p>
+              <select
+                class="text-sm bg-transparent text-[--text-primary]"
+                value={settings.thumbnailFormat ?? "jpeg"}
+                onChange={(e) => {
+                  const thumbnailFormat = e.currentTarget
+                    .value as ThumbnailFormat;
+                  setSettings("thumbnailFormat", thumbnailFormat);
+                  generalSettingsStore.set({ thumbnailFormat });
+                }}
+              >
+                <For each={thumbnailFormats}>
+                  {(format) => (
+                    <option value={format.value}>{format.label}</option>
+                  )}
+                </For>
+              </select>
+            </div>
+            <p class="text-xs text-[--text-tertiary]">
+              Used for recording screenshots and thumbnails. PNG stays sharp in
+              the app, WebP is smaller and JPEG works everywhere.
+            </p>
+          </div>
+          <Show when={(settings.thumbnailFormat ?? "jpeg") === "jpeg"}>
+            <div class="space-y-2 py-3">
+              <div class="flex items-center justify-between">
+                <p class="text-[--text-primary]">Thumbnail quality</p>
+                <input
+                  type="number"
+                  min="1"
+                  max="100"
+                  class="w-16 text-sm bg-transparent text-[--text-primary]"
+                  value={settings.thumbnailQuality ?? 75}
+                  onChange={(e) => {
+                    const value = e.currentTarget.valueAsNumber;
+                    if (Number.isNaN(value)) return;
+                    const thumbnailQuality = Math.min(
+                      100,
+                      Math.max(1, Math.round(value))
+                    );
+                    setSettings("thumbnailQuality", thumbnailQuality);
+                    generalSettingsStore.set({ thumbnailQuality });
+                  }}
+                />
+              </div>
+              <p class="text-xs text-[--text-tertiary]">
+                JPEG quality from 1 to 100.
+              </p>
+            </div>
+          </Show>
         </div>
       </div>
     </div>
@@ -25,6 +25,7 @@ const ACTION_TEXT: Record<HotkeyAction, string> = {
   stopRecording: "Stop Recording",
   restartRecording: "Restart Recording",
   takeScreenshot: "Take Screenshot",
+  toggleRecording: "Start/Stop Recording",
 };
 
 export default function () {
@@ -87,6 +88,7 @@ function Inner(props: { initialStore: HotkeysStore | null }) {
               "stopRecording",
               "restartRecording",
               "takeScreenshot",
+              "toggleRecording",
             ] as Array<HotkeyAction>
           }
         >
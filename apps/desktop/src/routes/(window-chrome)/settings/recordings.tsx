@@ -9,7 +9,7 @@ type MediaEntry = {
   path: string;
   prettyName: string;
   isNew: boolean;
-  thumbnailPath: string;
+  thumbnailPath: string | null;
 };
 
 export default function Recordings() {
@@ -23,7 +23,9 @@ export default function Recordings() {
       const recordings = await Promise.all(
         result.map(async (file) => {
           const [id, path, meta] = file;
-          const thumbnailPath = `${path}/screenshots/display.jpg`;
+          const thumbnailPath = await commands
+            .getThumbnailPath(id)
+            .catch(() => null);
 
           return {
             id,
@@ -39,7 +41,10 @@ export default function Recordings() {
   }));
 
   const handleRecordingClick = (recording: MediaEntry) => {
-    events.newRecordingAdded.emit({ path: recording.path });
+    events.newRecordingAdded.emit({
+      path: recording.path,
+      thumbnail_path: recording.thumbnailPath,
+    });
   };
 
   const handleOpenFolder = (path: string) => {
@@ -93,17 +98,17 @@ function RecordingItem(props: {
     <li class="w-full flex flex-row justify-between items-center p-2 hover:bg-gray-100 rounded">
       <div class="flex items-center">
         <Show
-          when={imageExists()}
+          when={imageExists() && props.recording.thumbnailPath}
           fallback={<div class="w-8 h-8 bg-gray-400 mr-4 rounded" />}
         >
-          <img
-            class="w-8 h-8 object-cover mr-4 rounded"
-            alt="Recording thumbnail"
-            src={`${convertFileSrc(
-              props.recording.thumbnailPath
-            )}?t=${Date.now()}`}
-            onError={() => setImageExists(false)}
-          />
+          {(thumbnailPath) => (
+            <img
+              class="w-8 h-8 object-cover mr-4 rounded"
+              alt="Recording thumbnail"
+              src={`${convertFileSrc(thumbnailPath())}?t=${Date.now()}`}
+              onError={() => setImageExists(false)}
+            />
+          )}
         </Show>
         <span>{props.recording.prettyName}</span>
       </div>
@@ -9,11 +9,14 @@ import { Dynamic } from "solid-js/web";
 import { createWritableMemo } from "@solid-primitives/memo";
 import { createEventListenerMap } from "@solid-primitives/event-listener";
 import { produce } from "solid-js/store";
+import { open } from "@tauri-apps/plugin-dialog";
 
 import type {
   BackgroundSource,
+  Corner,
   CursorType,
   CursorAnimationStyle,
+  Layout,
 } from "~/utils/tauri";
 import { useEditorContext } from "./context";
 import {
@@ -24,7 +27,21 @@ import {
   Toggle,
   Slider,
 } from "./ui";
-import { DEFAULT_GRADIENT_FROM, DEFAULT_GRADIENT_TO } from "./projectConfig";
+import {
+  DEFAULT_CLICK_HIGHLIGHT,
+  DEFAULT_GRADIENT_FROM,
+  DEFAULT_GRADIENT_TO,
+  DEFAULT_WATERMARK,
+} from "./projectConfig";
+
+const LAYOUTS: Record<Layout["type"], string> = {
+  pictureInPicture: "Overlay",
+  sideBySide: "Side by Side",
+  screenOnly: "Screen",
+  cameraOnly: "Camera",
+};
+
+const DEFAULT_SIDE_BY_SIDE_SPLIT = 0.65;
 
 const BACKGROUND_SOURCES = {
   wallpaper: "Wallpaper",
@@ -79,6 +96,8 @@ export function ConfigSidebar() {
     },
   };
 
+  const clickHighlight = () => project.clickHighlight ?? DEFAULT_CLICK_HIGHLIGHT;
+
   return (
     <KTabs
       value={selectedTab()}
@@ -376,6 +395,15 @@ export function ConfigSidebar() {
               step={0.1}
             />
           </Field>
+          <Field name="Shadow" icon={<IconCapShadow />}>
+            <Slider
+              value={[project.background.shadow ?? 0]}
+              onChange={(v) => setProject("background", "shadow", v[0])}
+              minValue={0}
+              maxValue={100}
+              step={0.1}
+            />
+          </Field>
           <ComingSoonTooltip>
             <Field name="Inset" icon={<IconCapInset />}>
               <Slider
@@ -387,8 +415,158 @@ export function ConfigSidebar() {
               />
             </Field>
           </ComingSoonTooltip>
+          <Field name="Watermark" icon={<IconCapImage />}>
+            <div class="flex flex-col gap-[0.75rem]">
+              <button
+                type="button"
+                class="w-full bg-gray-400/20 hover:bg-gray-400/30 transition-colors duration-100 rounded-full py-1.5 truncate px-3"
+                onClick={async () => {
+                  const path = await open({
+                    filters: [
+                      { name: "Image", extensions: ["png", "jpg", "jpeg", "webp"] },
+                    ],
+                  });
+                  if (typeof path !== "string") return;
+
+                  setProject("watermark", {
+                    ...DEFAULT_WATERMARK,
+                    ...project.watermark,
+                    path,
+                  });
+                }}
+              >
+                {project.watermark
+                  ? project.watermark.path.split(/[\\/]/).pop()
+                  : "Choose Image"}
+              </button>
+              <Show when={project.watermark}>
+                {(watermark) => (
+                  <>
+                    <KRadioGroup
+                      value={watermark().position ?? DEFAULT_WATERMARK.position}
+                      onChange={(position) =>
+                        setProject("watermark", {
+                          ...watermark(),
+                          position: position as Corner,
+                        })
+                      }
+                      class="rounded-[0.5rem] border border-gray-200 bg-gray-100 w-full h-[5rem] relative"
+                    >
+                      <For
+                        each={
+                          [
+                            "topLeft",
+                            "topRight",
+                            "bottomLeft",
+                            "bottomRight",
+                          ] as const
+                        }
+                      >
+                        {(corner) => (
+                          <RadioGroup.Item value={corner}>
+                            <RadioGroup.ItemInput class="peer" />
+                            <RadioGroup.ItemControl
+                              class={cx(
+                                "cursor-pointer size-[1.25rem] shink-0 rounded-[0.375rem] bg-gray-300 absolute flex justify-center items-center ui-checked:bg-blue-300 focus-visible:outline peer-focus-visible:outline outline-2 outline-offset-2 outline-blue-300 transition-colors duration-100",
+                                corner.endsWith("Left") ? "left-2" : "right-2",
+                                corner.startsWith("top") ? "top-2" : "bottom-2"
+                              )}
+                            >
+                              <div class="size-[0.5rem] shrink-0 bg-gray-50 rounded-full" />
+                            </RadioGroup.ItemControl>
+                          </RadioGroup.Item>
+                        )}
+                      </For>
+                    </KRadioGroup>
+                    <Subfield name="Opacity" />
+                    <Slider
+                      value={[watermark().opacity * 100]}
+                      onChange={(v) =>
+                        setProject("watermark", {
+                          ...watermark(),
+                          opacity: v[0] / 100,
+                        })
+                      }
+                      minValue={0}
+                      maxValue={100}
+                      step={1}
+                    />
+                    <Subfield name="Size" />
+                    <Slider
+                      value={[watermark().scale * 100]}
+                      onChange={(v) =>
+                        setProject("watermark", {
+                          ...watermark(),
+                          scale: v[0] / 100,
+                        })
+                      }
+                      minValue={2}
+                      maxValue={50}
+                      step={1}
+                    />
+                    <button
+                      type="button"
+                      class="text-gray-400 hover:text-gray-500 text-sm"
+                      onClick={() => setProject("watermark", null)}
+                    >
+                      Remove Watermark
+                    </button>
+                  </>
+                )}
+              </Show>
+            </div>
+          </Field>
         </KTabs.Content>
         <KTabs.Content value="camera" class="flex flex-col gap-[1.5rem]">
+          <Field name="Layout" icon={<IconCapLayout />}>
+            <div class="flex flex-col gap-[0.75rem]">
+              <KRadioGroup
+                value={project.layout?.type ?? "pictureInPicture"}
+                onChange={(type) =>
+                  setProject(
+                    "layout",
+                    type === "sideBySide"
+                      ? { type, split: DEFAULT_SIDE_BY_SIDE_SPLIT }
+                      : ({ type } as Layout)
+                  )
+                }
+                class="grid grid-cols-4 gap-2"
+              >
+                <For each={Object.entries(LAYOUTS)}>
+                  {([value, label]) => (
+                    <KRadioGroup.Item value={value}>
+                      <KRadioGroup.ItemInput class="peer" />
+                      <KRadioGroup.ItemControl class="cursor-pointer bg-gray-100 rounded-lg px-2 py-1.5 text-xs text-center text-gray-500 border border-gray-200 ui-checked:border-blue-300 peer-focus-visible:border-2 peer-focus-visible:border-blue-300">
+                        {label}
+                      </KRadioGroup.ItemControl>
+                    </KRadioGroup.Item>
+                  )}
+                </For>
+              </KRadioGroup>
+              <Show
+                when={
+                  project.layout?.type === "sideBySide" && project.layout
+                }
+              >
+                {(layout) => (
+                  <Subfield name="Screen Width">
+                    <Slider
+                      value={[layout().split * 100]}
+                      onChange={(v) =>
+                        setProject("layout", {
+                          type: "sideBySide",
+                          split: v[0] / 100,
+                        })
+                      }
+                      minValue={30}
+                      maxValue={80}
+                      step={1}
+                    />
+                  </Subfield>
+                )}
+              </Show>
+            </div>
+          </Field>
           <Field name="Camera" icon={<IconCapCamera />}>
             <div class="flex flex-col gap-[0.75rem]">
               <Subfield name="Hide Camera">
@@ -403,6 +581,55 @@ export function ConfigSidebar() {
                   onChange={(mirror) => setProject("camera", "mirror", mirror)}
                 />
               </Subfield>
+              <Subfield name="Remove Green Screen">
+                <Toggle
+                  checked={
+                    project.camera.background_removal?.type === "chromaKey"
+                  }
+                  onChange={(v) =>
+                    setProject(
+                      "camera",
+                      "background_removal",
+                      v
+                        ? { type: "chromaKey", color: [0, 255, 0], similarity: 0.2 }
+                        : null
+                    )
+                  }
+                />
+              </Subfield>
+              <Show
+                when={
+                  project.camera.background_removal?.type === "chromaKey" &&
+                  project.camera.background_removal
+                }
+              >
+                {(removal) => (
+                  <Slider
+                    value={[removal().similarity]}
+                    onChange={(v) =>
+                      setProject("camera", "background_removal", {
+                        ...removal(),
+                        similarity: v[0],
+                      })
+                    }
+                    minValue={0}
+                    maxValue={0.5}
+                    step={0.01}
+                  />
+                )}
+              </Show>
+              <Subfield name="Remove Plain Background">
+                <Toggle
+                  checked={project.camera.background_removal?.type === "auto"}
+                  onChange={(v) =>
+                    setProject(
+                      "camera",
+                      "background_removal",
+                      v ? { type: "auto" } : null
+                    )
+                  }
+                />
+              </Subfield>
               <div>
                 <Subfield name="Camera Position" class="mt-[0.75rem]" />
                 <KRadioGroup
@@ -516,6 +743,146 @@ export function ConfigSidebar() {
                   <Toggle disabled />
                 </Subfield>
               </ComingSoonTooltip>
+              <Subfield name="Normalize Volume">
+                <Toggle
+                  checked={project.audio.normalize ?? false}
+                  onChange={(v) => setProject("audio", "normalize", v)}
+                />
+              </Subfield>
+            </div>
+          </Field>
+          <Field name="Speed" icon={<IconLucideRabbit />}>
+            <div class="flex flex-col gap-2">
+              <Slider
+                value={[project.speed ?? 1]}
+                onChange={(v) => setProject("speed", v[0])}
+                minValue={0.25}
+                maxValue={10}
+                step={0.25}
+              />
+              <span class="text-gray-400 text-xs">
+                {project.speed ?? 1}x, applied when exporting. Audio is muted above 4x.
+              </span>
+            </div>
+          </Field>
+          <Field name="Fade" icon={<IconLucideSunset />}>
+            <div class="flex flex-col gap-2">
+              <Subfield name="Fade In" />
+              <Slider
+                value={[project.fadeInSecs ?? 0]}
+                onChange={(v) => setProject("fadeInSecs", v[0])}
+                minValue={0}
+                maxValue={5}
+                step={0.25}
+              />
+              <Subfield name="Fade Out" />
+              <Slider
+                value={[project.fadeOutSecs ?? 0]}
+                onChange={(v) => setProject("fadeOutSecs", v[0])}
+                minValue={0}
+                maxValue={5}
+                step={0.25}
+              />
+              <span class="text-gray-400 text-xs">
+                Fades the video from and to black, and the audio in and out,
+                when exporting.
+              </span>
+            </div>
+          </Field>
+          <Field name="HDR" icon={<IconLucideSun />}>
+            <div class="flex flex-col gap-2">
+              <Subfield name="Convert HDR to SDR">
+                <Toggle
+                  checked={project.tonemapHdr ?? true}
+                  onChange={(v) => setProject("tonemapHdr", v)}
+                />
+              </Subfield>
+              <span class="text-gray-400 text-xs">
+                Keeps HDR recordings from looking washed out. Has no effect on
+                SDR recordings.
+              </span>
+            </div>
+          </Field>
+          <Field name="Quality" icon={<IconLucideGauge />}>
+            <div class="flex flex-col gap-2">
+              <Subfield name="Constant Quality">
+                <Toggle
+                  checked={project.quality?.type === "crf"}
+                  onChange={(v) =>
+                    setProject("quality", v ? { type: "crf", value: 23 } : null)
+                  }
+                />
+              </Subfield>
+              <Show when={project.quality?.type === "crf" && project.quality}>
+                {(quality) => (
+                  <>
+                    <Slider
+                      value={[quality().value]}
+                      onChange={(v) =>
+                        setProject("quality", { type: "crf", value: v[0] })
+                      }
+                      minValue={0}
+                      maxValue={51}
+                      step={1}
+                    />
+                    <span class="text-gray-400 text-xs">
+                      CRF {quality().value}, lower values look better but make
+                      bigger files.
+                    </span>
+                  </>
+                )}
+              </Show>
+              <Subfield name="Target Bitrate">
+                <Toggle
+                  checked={project.quality?.type === "bitrate"}
+                  onChange={(v) =>
+                    setProject(
+                      "quality",
+                      v ? { type: "bitrate", kbps: 8000 } : null
+                    )
+                  }
+                />
+              </Subfield>
+              <Show
+                when={project.quality?.type === "bitrate" && project.quality}
+              >
+                {(quality) => {
+                  const duration = () =>
+                    (project.timeline?.segments.reduce(
+                      (acc, s) => acc + (s.end - s.start) / s.timescale,
+                      0
+                    ) ?? editorInstance.recordingDuration) /
+                    (project.speed ?? 1);
+
+                  // Video at the target bitrate plus roughly 128 kbit/s of audio
+                  const estimatedMB = () =>
+                    ((quality().kbps + 128) * 1000 * duration()) / 8 / 1e6;
+
+                  return (
+                    <>
+                      <Slider
+                        value={[quality().kbps]}
+                        onChange={(v) =>
+                          setProject("quality", {
+                            type: "bitrate",
+                            kbps: v[0],
+                          })
+                        }
+                        minValue={500}
+                        maxValue={50000}
+                        step={500}
+                      />
+                      <span class="text-gray-400 text-xs">
+                        {(quality().kbps / 1000).toFixed(1)} Mbit/s, about{" "}
+                        {estimatedMB().toFixed(0)} MB when exported as MP4.
+                      </span>
+                    </>
+                  );
+                }}
+              </Show>
+              <span class="text-gray-400 text-xs">
+                Without either, the encoder's defaults are used.
+              </span>
             </div>
           </Field>
         </KTabs.Content>
@@ -539,6 +906,35 @@ export function ConfigSidebar() {
               />
             </ComingSoonTooltip>
           </Field>
+          <Field name="Smoothing" icon={<IconLucideSpline />}>
+            <Slider
+              value={[(project.cursor.smoothing ?? 0) * 100]}
+              onChange={(v) => setProject("cursor", "smoothing", v[0] / 100)}
+              minValue={0}
+              maxValue={100}
+              step={1}
+            />
+          </Field>
+          <Field name="Click Highlight" icon={<IconCapCursor />}>
+            <div class="flex flex-col gap-[0.75rem]">
+              <Subfield name="Highlight clicks">
+                <Toggle
+                  checked={clickHighlight().show}
+                  onChange={(show) =>
+                    setProject("clickHighlight", { ...clickHighlight(), show })
+                  }
+                />
+              </Subfield>
+              <Show when={clickHighlight().show}>
+                <RgbInput
+                  value={clickHighlight().color}
+                  onChange={(color) =>
+                    setProject("clickHighlight", { ...clickHighlight(), color })
+                  }
+                />
+              </Show>
+            </div>
+          </Field>
           {window.FLAGS.zoom && (
             <Field name="Animation Style" icon={<IconLucideRabbit />}>
               <RadioGroup
@@ -589,11 +985,12 @@ export function ConfigSidebar() {
         </KTabs.Content>
         <KTabs.Content value="hotkeys">
           <Field name="Hotkeys" icon={<IconCapHotkeys />}>
-            <ComingSoonTooltip>
-              <Subfield name="Show hotkeys">
-                <Toggle disabled />
-              </Subfield>
-            </ComingSoonTooltip>
+            <Subfield name="Show hotkeys">
+              <Toggle
+                checked={project.hotkeys.show}
+                onChange={(show) => setProject("hotkeys", "show", show)}
+              />
+            </Subfield>
           </Field>
         </KTabs.Content>
       </div>
@@ -22,6 +22,7 @@ import {
   createEventListenerMap,
 } from "@solid-primitives/event-listener";
 import { convertFileSrc } from "@tauri-apps/api/core";
+import { message } from "@tauri-apps/plugin-dialog";
 
 import { events, commands } from "~/utils/tauri";
 import { EditorContextProvider, useEditorContext } from "./context";
@@ -87,6 +88,10 @@ function Inner() {
       renderFrame.clear();
       setPlaybackTime(e.payload.playhead_position / 30);
     });
+
+    events.editorRenderError.listen((e) => {
+      message(e.payload.message, { title: "Preview failed", kind: "error" });
+    });
   });
 
   const renderFrame = throttle((time: number) => {
@@ -392,7 +397,8 @@ function Dialogs() {
                               class="shadow pointer-events-none max-h-[70vh]"
                               alt="screenshot"
                               src={convertFileSrc(
-                                `${editorInstance.path}/screenshots/display.jpg`
+                                editorInstance.thumbnailPath ??
+                                  `${editorInstance.path}/screenshots/display.jpg`
                               )}
                             />
                           </div>
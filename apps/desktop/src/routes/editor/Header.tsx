@@ -262,7 +262,8 @@ function ExportButton() {
         videoId,
         project,
         progress,
-        true
+        true,
+        null
       );
       await commands.copyFileToPath(videoPath, path);
 
@@ -381,7 +382,7 @@ function ShareButton() {
           }
         };
 
-        await commands.exportVideo(videoId, projectConfig, progress, true);
+        await commands.exportVideo(videoId, projectConfig, progress, true, null);
 
         // Now proceed with upload
         const result = recordingMeta()?.sharing
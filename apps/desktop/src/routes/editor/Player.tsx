@@ -6,9 +6,9 @@ import { cx } from "cva";
 import { For, Show, Suspense, createEffect, createSignal } from "solid-js";
 import { reconcile } from "solid-js/store";
 
-import { type AspectRatio, commands } from "~/utils/tauri";
+import { commands } from "~/utils/tauri";
 import { useEditorContext } from "./context";
-import { ASPECT_RATIOS } from "./projectConfig";
+import { ASPECT_RATIOS, type PresetAspectRatio } from "./projectConfig";
 import {
   ComingSoonTooltip,
   DropdownItem,
@@ -136,6 +136,25 @@ export function Player() {
           >
             Crop
           </EditorButton>
+          <EditorButton
+            leftIcon={<IconCapCopy />}
+            onClick={() => {
+              // The playhead is in timeline time, find the matching recording time
+              let time = playbackTime();
+              for (const segment of project.timeline?.segments ?? []) {
+                const duration = (segment.end - segment.start) / segment.timescale;
+                if (time <= duration) {
+                  time = segment.start + time * segment.timescale;
+                  break;
+                }
+                time -= duration;
+              }
+
+              commands.copyFrameToClipboard(videoId, time);
+            }}
+          >
+            Copy Frame
+          </EditorButton>
           <PresetsDropdown />
         </div>
         <div class="flex flex-row place-items-center gap-2">
@@ -251,48 +270,81 @@ export function Player() {
   );
 }
 
+type AspectRatioOption = PresetAspectRatio | "auto" | "custom";
+
+const ASPECT_RATIO_OPTIONS: AspectRatioOption[] = [
+  "auto",
+  "wide",
+  "vertical",
+  "square",
+  "classic",
+  "tall",
+];
+
 function AspectRatioSelect() {
   const { project, setProject } = useEditorContext();
 
+  const value = (): AspectRatioOption => {
+    const aspectRatio = project.aspectRatio;
+    if (aspectRatio === null) return "auto";
+    return typeof aspectRatio === "object" ? "custom" : aspectRatio;
+  };
+
+  const customRatio = () => {
+    const aspectRatio = project.aspectRatio;
+    return aspectRatio !== null && typeof aspectRatio === "object"
+      ? aspectRatio.custom
+      : null;
+  };
+
+  const name = (option: AspectRatioOption) =>
+    option === "auto"
+      ? "Auto"
+      : option === "custom"
+      ? "Custom"
+      : ASPECT_RATIOS[option].name;
+
+  const ratio = (option: AspectRatioOption) =>
+    option === "auto"
+      ? null
+      : option === "custom"
+      ? customRatio()
+      : ASPECT_RATIOS[option].ratio;
+
   return (
-    <KSelect<AspectRatio | "auto">
-      value={project.aspectRatio ?? "auto"}
+    <KSelect<AspectRatioOption>
+      value={value()}
       onChange={(v) => {
-        if (v === null) return;
+        // Custom ratios are set in the project config, so there's nothing to switch to
+        if (v === null || v === "custom") return;
         setProject("aspectRatio", v === "auto" ? null : v);
       }}
       defaultValue="auto"
+      // Only listed while in use, so the current ratio can be shown
       options={
-        ["auto", "wide", "vertical", "square", "classic", "tall"] as const
+        customRatio()
+          ? [...ASPECT_RATIO_OPTIONS, "custom"]
+          : ASPECT_RATIO_OPTIONS
       }
       multiple={false}
-      itemComponent={(props) => {
-        const item = () =>
-          props.item.rawValue === "auto"
-            ? null
-            : ASPECT_RATIOS[props.item.rawValue];
-
-        return (
-          <MenuItem<typeof KSelect.Item> as={KSelect.Item} item={props.item}>
-            <KSelect.ItemLabel class="flex-1">
-              {props.item.rawValue === "auto"
-                ? "Auto"
-                : ASPECT_RATIOS[props.item.rawValue].name}
-              <Show when={item()}>
-                {(item) => (
-                  <span class="text-gray-400">
-                    {"⋅"}
-                    {item().ratio[0]}:{item().ratio[1]}
-                  </span>
-                )}
-              </Show>
-            </KSelect.ItemLabel>
-            <KSelect.ItemIndicator class="ml-auto">
-              <IconCapCircleCheck />
-            </KSelect.ItemIndicator>
-          </MenuItem>
-        );
-      }}
+      itemComponent={(props) => (
+        <MenuItem<typeof KSelect.Item> as={KSelect.Item} item={props.item}>
+          <KSelect.ItemLabel class="flex-1">
+            {name(props.item.rawValue)}
+            <Show when={ratio(props.item.rawValue)}>
+              {(ratio) => (
+                <span class="text-gray-400">
+                  {"⋅"}
+                  {ratio()[0]}:{ratio()[1]}
+                </span>
+              )}
+            </Show>
+          </KSelect.ItemLabel>
+          <KSelect.ItemIndicator class="ml-auto">
+            <IconCapCircleCheck />
+          </KSelect.ItemIndicator>
+        </MenuItem>
+      )}
       placement="top-start"
     >
       <EditorButton<typeof KSelect.Trigger>
@@ -304,14 +356,8 @@ function AspectRatioSelect() {
           </KSelect.Icon>
         }
       >
-        <KSelect.Value<AspectRatio | "auto">>
-          {(state) => {
-            const text = () => {
-              const option = state.selectedOption();
-              return option === "auto" ? "Auto" : ASPECT_RATIOS[option].name;
-            };
-            return <>{text()}</>;
-          }}
+        <KSelect.Value<AspectRatioOption>>
+          {(state) => <>{name(state.selectedOption())}</>}
         </KSelect.Value>
       </EditorButton>
       <KSelect.Portal>
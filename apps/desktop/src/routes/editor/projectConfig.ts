@@ -1,10 +1,27 @@
-import type { AspectRatio, ProjectConfiguration } from "~/utils/tauri";
+import type {
+  AspectRatio,
+  ClickHighlightConfiguration,
+  ProjectConfiguration,
+  WatermarkConfiguration,
+} from "~/utils/tauri";
 
 export type RGBColor = [number, number, number];
 
 export const DEFAULT_GRADIENT_FROM = [71, 133, 255] satisfies RGBColor;
 export const DEFAULT_GRADIENT_TO = [255, 71, 102] satisfies RGBColor;
 
+export const DEFAULT_CLICK_HIGHLIGHT: ClickHighlightConfiguration = {
+  show: false,
+  color: [255, 214, 10],
+};
+
+export const DEFAULT_WATERMARK = {
+  position: "bottomRight",
+  opacity: 0.8,
+  scale: 0.12,
+  margin: 0.03,
+} satisfies Omit<WatermarkConfiguration, "path">;
+
 export const DEFAULT_PROJECT_CONFIG: ProjectConfiguration = {
   aspectRatio: null,
   background: {
@@ -35,14 +52,21 @@ export const DEFAULT_PROJECT_CONFIG: ProjectConfiguration = {
     type: "pointer",
     animationStyle: "regular" as const
   },
+  clickHighlight: DEFAULT_CLICK_HIGHLIGHT,
   hotkeys: { show: false },
   motionBlur: 0.2,
+  speed: 1,
 };
 
+export type PresetAspectRatio = Exclude<AspectRatio, { custom: [number, number] }>;
+
 export const ASPECT_RATIOS = {
   wide: { name: "Wide", ratio: [16, 9] },
   vertical: { name: "Vertical", ratio: [9, 16] },
   square: { name: "Square", ratio: [1, 1] },
   classic: { name: "Classic", ratio: [4, 3] },
   tall: { name: "Tall", ratio: [3, 4] },
-} satisfies Record<AspectRatio, { name: string; ratio: [number, number] }>;
+} satisfies Record<
+  PresetAspectRatio,
+  { name: string; ratio: [number, number] }
+>;
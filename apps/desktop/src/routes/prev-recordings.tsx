@@ -13,6 +13,7 @@ import {
   createMemo,
   createResource,
   createSignal,
+  on,
   onCleanup,
   startTransition,
   onMount,
@@ -41,6 +42,7 @@ type MediaEntry = {
   prettyName: string;
   isNew: boolean;
   type?: "recording" | "screenshot";
+  thumbnailPath?: string | null;
 };
 
 export default function () {
@@ -98,7 +100,11 @@ export default function () {
     });
   });
 
-  const addMediaEntry = (path: string, type?: "recording" | "screenshot") => {
+  const addMediaEntry = (
+    path: string,
+    type?: "recording" | "screenshot",
+    thumbnailPath?: string | null
+  ) => {
     const setMedia = type === "screenshot" ? setScreenshots : setRecordings;
     setMedia(
       produce((state) => {
@@ -108,7 +114,7 @@ export default function () {
           /Cap (\d{4}-\d{2}-\d{2} at \d{2}\.\d{2}\.\d{2})/
         );
         const prettyName = match ? match[1].replace(/\./g, ":") : fileName;
-        state.unshift({ path, prettyName, isNew: true, type });
+        state.unshift({ path, prettyName, isNew: true, type, thumbnailPath });
       })
     );
 
@@ -125,7 +131,19 @@ export default function () {
   };
 
   events.newRecordingAdded.listen((event) => {
-    addMediaEntry(event.payload.path, "recording");
+    addMediaEntry(
+      event.payload.path,
+      "recording",
+      event.payload.thumbnail_path
+    );
+  });
+
+  events.thumbnailReady.listen((event) => {
+    setRecordings(
+      (entry) => entry.path === event.payload.path,
+      "thumbnailPath",
+      event.payload.thumbnail_path
+    );
   });
 
   events.newScreenshotAdded.listen((event) => {
@@ -185,6 +203,14 @@ export default function () {
                 });
 
                 const [imageExists, setImageExists] = createSignal(true);
+                // The thumbnail is generated after the recording is added
+                createEffect(
+                  on(
+                    () => media.thumbnailPath,
+                    () => setImageExists(true),
+                    { defer: true }
+                  )
+                );
                 const [showUpgradeTooltip, setShowUpgradeTooltip] =
                   createSignal(false);
 
@@ -239,7 +265,8 @@ export default function () {
                             alt="media preview"
                             src={`${convertFileSrc(
                               isRecording
-                                ? `${media.path}/screenshots/display.jpg`
+                                ? media.thumbnailPath ??
+                                  `${media.path}/screenshots/display.jpg`
                                 : `${media.path}`
                             )}?t=${Date.now()}`}
                             onError={() => setImageExists(false)}
@@ -744,7 +771,8 @@ function createRecordingMutations(
             mediaId,
             presets.getDefaultConfig() ?? DEFAULT_PROJECT_CONFIG,
             progress,
-            false
+            false,
+            null
           );
 
           // Show quick progress animation for existing video
@@ -758,7 +786,7 @@ function createRecordingMutations(
             stage: "rendering",
           });
 
-          await commands.copyVideoToClipboard(outputPath);
+          await commands.copyVideoToClipboard(outputPath, null);
         } else {
           // For screenshots, show quick progress animation
           setProgressState({
@@ -868,7 +896,8 @@ function createRecordingMutations(
             mediaId,
             presets.getDefaultConfig() ?? DEFAULT_PROJECT_CONFIG,
             progress,
-            true // Force re-render
+            true, // Force re-render
+            null
           );
 
           await commands.copyFileToPath(outputPath, savePath);
@@ -997,7 +1026,8 @@ function createRecordingMutations(
             mediaId,
             presets.getDefaultConfig() ?? DEFAULT_PROJECT_CONFIG,
             progress,
-            false
+            false,
+            null
           );
           console.log("Using existing rendered video");
 
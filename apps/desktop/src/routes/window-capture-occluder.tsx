@@ -1,47 +1,62 @@
-import { Show, Suspense } from "solid-js";
+import { createResource, For, Show, Suspense } from "solid-js";
+import { generalSettingsStore } from "~/store";
 import { createCurrentRecordingQuery } from "~/utils/queries";
+import type { Bounds } from "~/utils/tauri";
 
 export default function () {
   const currentRecording = createCurrentRecordingQuery();
+  const [settings] = createResource(() => generalSettingsStore.get());
+
+  // The areas being captured, which are left uncovered
+  const captured = (): Bounds[] | undefined => {
+    const holes = currentRecording.data?.occluderHoles;
+    if (holes?.length) return holes;
+  };
 
   return (
     <Suspense>
-      <Show
-        when={
-          currentRecording.data &&
-          currentRecording.data.captureTarget.variant === "window" &&
-          currentRecording.data.captureTarget.bounds
-        }
-      >
-        {(bounds) => (
-          <div class="w-screen h-screen relative animate-in fade-in">
-            <div
-              class="bg-black-transparent-40 absolute inset-x-0 top-0"
-              style={{ height: `${bounds().y}px` }}
-            />
-            <div
-              class="bg-black-transparent-40 absolute left-0"
-              style={{
-                top: `${bounds().y}px`,
-                height: `${bounds().height}px`,
-                width: `${bounds().x}px`,
-              }}
-            />
-            <div
-              class="bg-black-transparent-40 absolute right-0"
-              style={{
-                top: `${bounds().y}px`,
-                height: `${bounds().height}px`,
-                width: `calc(100vw - ${bounds().x + bounds().width}px)`,
-              }}
-            />
-            <div
-              class="bg-black-transparent-40 absolute inset-x-0 bottom-0"
-              style={{
-                height: `calc(100vh - ${bounds().y + bounds().height}px)`,
-              }}
-            />
-          </div>
+      <Show when={settings.state === "ready" && captured()}>
+        {(captured) => (
+          <svg class="w-screen h-screen animate-in fade-in">
+            <Show
+              when={!settings()?.captureOccluderOutlineOnly}
+              fallback={
+                <For each={captured()}>
+                  {(bounds) => (
+                    <rect
+                      class="fill-none stroke-blue-300"
+                      stroke-width="2"
+                      x={bounds.x}
+                      y={bounds.y}
+                      width={bounds.width}
+                      height={bounds.height}
+                    />
+                  )}
+                </For>
+              }
+            >
+              <mask id="captured-area">
+                <rect width="100%" height="100%" fill="white" />
+                <For each={captured()}>
+                  {(bounds) => (
+                    <rect
+                      x={bounds.x}
+                      y={bounds.y}
+                      width={bounds.width}
+                      height={bounds.height}
+                      fill="black"
+                    />
+                  )}
+                </For>
+              </mask>
+              <rect
+                class="fill-black-transparent-40"
+                width="100%"
+                height="100%"
+                mask="url(#captured-area)"
+              />
+            </Show>
+          </svg>
         )}
       </Show>
     </Suspense>
@@ -17,6 +17,13 @@ export const listWindows = queryOptions({
   refetchInterval: 1000,
 });
 
+export const listApplications = queryOptions({
+  queryKey: ["capture", "applications"] as const,
+  queryFn: () => commands.listCaptureApplications(),
+  reconcile: "id",
+  refetchInterval: 1000,
+});
+
 export const listScreens = queryOptions({
   queryKey: ["capture", "screens"] as const,
   queryFn: () => commands.listCaptureScreens(),
@@ -11,6 +11,9 @@ async getRecordingOptions() : Promise<RecordingOptions> {
 async setRecordingOptions(options: RecordingOptions) : Promise<null> {
     return await TAURI_INVOKE("set_recording_options", { options });
 },
+async refreshCameraWindow() : Promise<null> {
+    return await TAURI_INVOKE("refresh_camera_window");
+},
 async startRecording() : Promise<null> {
     return await TAURI_INVOKE("start_recording");
 },
@@ -23,21 +26,45 @@ async pauseRecording() : Promise<null> {
 async resumeRecording() : Promise<null> {
     return await TAURI_INVOKE("resume_recording");
 },
+async addMarker(label: string | null) : Promise<Marker> {
+    return await TAURI_INVOKE("add_marker", { label });
+},
+async getMarkers(videoId: string) : Promise<Marker[]> {
+    return await TAURI_INVOKE("get_markers", { videoId });
+},
 async listCameras() : Promise<string[]> {
     return await TAURI_INVOKE("list_cameras");
 },
+async listCameraDevices() : Promise<CameraDeviceInfo[]> {
+    return await TAURI_INVOKE("list_camera_devices");
+},
+async listCameraResolutions(cameraLabel: string) : Promise<([number, number])[]> {
+    return await TAURI_INVOKE("list_camera_resolutions", { cameraLabel });
+},
 async listCaptureWindows() : Promise<CaptureWindow[]> {
     return await TAURI_INVOKE("list_capture_windows");
 },
+async listCaptureApplications() : Promise<CaptureApplication[]> {
+    return await TAURI_INVOKE("list_capture_applications");
+},
 async listCaptureScreens() : Promise<CaptureScreen[]> {
     return await TAURI_INVOKE("list_capture_screens");
 },
+async getDisplays() : Promise<DisplayInfo[]> {
+    return await TAURI_INVOKE("get_displays");
+},
 async takeScreenshot() : Promise<null> {
     return await TAURI_INVOKE("take_screenshot");
 },
 async listAudioDevices() : Promise<string[]> {
     return await TAURI_INVOKE("list_audio_devices");
 },
+async getCapabilities() : Promise<Capabilities> {
+    return await TAURI_INVOKE("get_capabilities");
+},
+async validateRecordingOptions(options: RecordingOptions) : Promise<ValidationReport> {
+    return await TAURI_INVOKE("validate_recording_options", { options });
+},
 async closePreviousRecordingsWindow() : Promise<void> {
     await TAURI_INVOKE("close_previous_recordings_window");
 },
@@ -50,27 +77,96 @@ async removeFakeWindow(name: string) : Promise<null> {
 async focusCapturesPanel() : Promise<void> {
     await TAURI_INVOKE("focus_captures_panel");
 },
+async startCameraPreview(frames: TAURI_CHANNEL<number[]>, maxWidth: number | null) : Promise<void> {
+    return await TAURI_INVOKE("start_camera_preview", { frames, maxWidth });
+},
+async stopCameraPreview() : Promise<void> {
+    return await TAURI_INVOKE("stop_camera_preview");
+},
 async getCurrentRecording() : Promise<JsonValue<RecordingInfo | null>> {
     return await TAURI_INVOKE("get_current_recording");
 },
-async exportVideo(videoId: string, project: ProjectConfiguration, progress: TAURI_CHANNEL<RenderProgress>, force: boolean) : Promise<string> {
-    return await TAURI_INVOKE("export_video", { videoId, project, progress, force });
+async exportVideo(videoId: string, project: ProjectConfiguration, progress: TAURI_CHANNEL<RenderProgress>, force: boolean, format: ExportFormat | null) : Promise<string> {
+    return await TAURI_INVOKE("export_video", { videoId, project, progress, force, format });
+},
+async exportGif(videoId: string, project: ProjectConfiguration, fps: number, maxWidth: number) : Promise<string> {
+    return await TAURI_INVOKE("export_gif", { videoId, project, fps, maxWidth });
+},
+async exportRaw(videoId: string) : Promise<string> {
+    return await TAURI_INVOKE("export_raw", { videoId });
+},
+async cancelRender(videoId: string) : Promise<null> {
+    return await TAURI_INVOKE("cancel_render", { videoId });
+},
+async getEncoders() : Promise<Encoder[]> {
+    return await TAURI_INVOKE("get_encoders");
 },
 async copyFileToPath(src: string, dst: string) : Promise<null> {
     return await TAURI_INVOKE("copy_file_to_path", { src, dst });
 },
-async copyVideoToClipboard(path: string) : Promise<null> {
-    return await TAURI_INVOKE("copy_video_to_clipboard", { path });
+async copyVideoToClipboard(path: string, format: ClipboardFormat | null) : Promise<null> {
+    return await TAURI_INVOKE("copy_video_to_clipboard", { path, format });
 },
 async copyScreenshotToClipboard(path: string) : Promise<null> {
     return await TAURI_INVOKE("copy_screenshot_to_clipboard", { path });
 },
+async copyFrameToClipboard(videoId: string, atSecs: number) : Promise<null> {
+    return await TAURI_INVOKE("copy_frame_to_clipboard", { videoId, atSecs });
+},
 async openFilePath(path: string) : Promise<null> {
     return await TAURI_INVOKE("open_file_path", { path });
 },
+async revealRecordingInFinder(videoId: string) : Promise<null> {
+    return await TAURI_INVOKE("reveal_recording_in_finder", { videoId });
+},
 async getVideoMetadata(videoId: string, videoType: VideoType | null) : Promise<VideoRecordingMetadata> {
     return await TAURI_INVOKE("get_video_metadata", { videoId, videoType });
 },
+async getDiskSpace(forRecordings: boolean) : Promise<DiskInfo> {
+    return await TAURI_INVOKE("get_disk_space", { forRecordings });
+},
+async generateThumbnail(videoId: string, atSecs: number) : Promise<null> {
+    return await TAURI_INVOKE("generate_thumbnail", { videoId, atSecs });
+},
+async getThumbnailPath(videoId: string) : Promise<string | null> {
+    return await TAURI_INVOKE("get_thumbnail_path", { videoId });
+},
+async getPreviewFrame(videoId: string, atSecs: number, maxWidth: number) : Promise<number[]> {
+    return await TAURI_INVOKE("get_preview_frame", { videoId, atSecs, maxWidth });
+},
+async renderPreview(videoId: string, project: ProjectConfiguration, atSecs: number) : Promise<unknown> {
+    return await TAURI_INVOKE("render_preview", { videoId, project, atSecs });
+},
+async repairRecording(videoId: string) : Promise<boolean> {
+    return await TAURI_INVOKE("repair_recording", { videoId });
+},
+async concatenateRecordings(videoIds: string[], outputId: string) : Promise<string> {
+    return await TAURI_INVOKE("concatenate_recordings", { videoIds, outputId });
+},
+async compressRecording(videoId: string, target: CompressionPreset) : Promise<CompressionResult> {
+    return await TAURI_INVOKE("compress_recording", { videoId, target });
+},
+async exportClip(videoId: string, project: ProjectConfiguration, startSecs: number, endSecs: number, dest: string, mode: ClipMode) : Promise<string> {
+    return await TAURI_INVOKE("export_clip", { videoId, project, startSecs, endSecs, dest, mode });
+},
+async importVideo(source: string) : Promise<string> {
+    return await TAURI_INVOKE("import_video", { source });
+},
+async exportProjectBundle(videoId: string, dest: string) : Promise<string> {
+    return await TAURI_INVOKE("export_project_bundle", { videoId, dest });
+},
+async importProjectBundle(source: string) : Promise<string> {
+    return await TAURI_INVOKE("import_project_bundle", { source });
+},
+async getLogPath() : Promise<string> {
+    return await TAURI_INVOKE("get_log_path");
+},
+async getGeneralSettings() : Promise<GeneralSettingsStore> {
+    return await TAURI_INVOKE("get_general_settings");
+},
+async updateGeneralSettings(settings: GeneralSettingsStore) : Promise<null> {
+    return await TAURI_INVOKE("update_general_settings", { settings });
+},
 async createEditorInstance(videoId: string) : Promise<SerializedEditorInstance> {
     return await TAURI_INVOKE("create_editor_instance", { videoId });
 },
@@ -86,6 +182,18 @@ async setPlayheadPosition(videoId: string, frameNumber: number) : Promise<void>
 async setProjectConfig(videoId: string, config: ProjectConfiguration) : Promise<void> {
     await TAURI_INVOKE("set_project_config", { videoId, config });
 },
+async getProjectConfig(videoId: string) : Promise<ProjectConfiguration> {
+    return await TAURI_INVOKE("get_project_config", { videoId });
+},
+async checkFfmpeg() : Promise<string> {
+    return await TAURI_INVOKE("check_ffmpeg");
+},
+async retryFfmpegInstall() : Promise<string> {
+    return await TAURI_INVOKE("retry_ffmpeg_install");
+},
+async getFfmpegInfo() : Promise<FfmpegInfo> {
+    return await TAURI_INVOKE("get_ffmpeg_info");
+},
 async openEditor(id: string) : Promise<void> {
     await TAURI_INVOKE("open_editor", { id });
 },
@@ -98,6 +206,9 @@ async openPermissionSettings(permission: OSPermission) : Promise<void> {
 async doPermissionsCheck(initialCheck: boolean) : Promise<OSPermissionsCheck> {
     return await TAURI_INVOKE("do_permissions_check", { initialCheck });
 },
+async checkPermission(permission: OSPermission) : Promise<OSPermissionStatus> {
+    return await TAURI_INVOKE("check_permission", { permission });
+},
 async requestPermission(permission: OSPermission) : Promise<void> {
     await TAURI_INVOKE("request_permission", { permission });
 },
@@ -110,12 +221,21 @@ async uploadScreenshot(screenshotPath: string) : Promise<UploadResult> {
 async getRecordingMeta(id: string, fileType: string) : Promise<RecordingMeta> {
     return await TAURI_INVOKE("get_recording_meta", { id, fileType });
 },
+async updateRecordingMeta(videoId: string, patch: RecordingMetaPatch) : Promise<RecordingMeta> {
+    return await TAURI_INVOKE("update_recording_meta", { videoId, patch });
+},
 async saveFileDialog(fileName: string, fileType: string) : Promise<string | null> {
     return await TAURI_INVOKE("save_file_dialog", { fileName, fileType });
 },
 async listRecordings() : Promise<([string, string, RecordingMeta])[]> {
     return await TAURI_INVOKE("list_recordings");
 },
+async getPrevRecordingsDetailed(sort: RecordingSort | null, offset: number | null, limit: number | null) : Promise<RecordingsPage> {
+    return await TAURI_INVOKE("get_prev_recordings_detailed", { sort, offset, limit });
+},
+async setRecordingsDir(path: string | null) : Promise<null> {
+    return await TAURI_INVOKE("set_recordings_dir", { path });
+},
 async listScreenshots() : Promise<([string, string, RecordingMeta])[]> {
     return await TAURI_INVOKE("list_screenshots");
 },
@@ -128,6 +248,9 @@ async openExternalLink(url: string) : Promise<null> {
 async setHotkey(action: HotkeyAction, hotkey: Hotkey | null) : Promise<null> {
     return await TAURI_INVOKE("set_hotkey", { action, hotkey });
 },
+async setRecordingHotkey(shortcut: string) : Promise<null> {
+    return await TAURI_INVOKE("set_recording_hotkey", { shortcut });
+},
 async deleteAuthOpenSignin() : Promise<null> {
     return await TAURI_INVOKE("delete_auth_open_signin");
 },
@@ -166,40 +289,64 @@ async setWindowTheme(dark: boolean) : Promise<void> {
 export const events = __makeEvents__<{
 audioInputLevelChange: AudioInputLevelChange,
 authenticationInvalid: AuthenticationInvalid,
+cameraDisconnected: CameraDisconnected,
 currentRecordingChanged: CurrentRecordingChanged,
+editorRenderError: EditorRenderError,
 editorStateChanged: EditorStateChanged,
+exportProgress: ExportProgress,
+ffmpegInstallProgress: FfmpegInstallProgress,
+hideCapturesPanel: HideCapturesPanel,
+lowDiskSpace: LowDiskSpace,
 newNotification: NewNotification,
 newRecordingAdded: NewRecordingAdded,
 newScreenshotAdded: NewScreenshotAdded,
+recordingAutoStopped: RecordingAutoStopped,
+recordingCountdown: RecordingCountdown,
 recordingMetaChanged: RecordingMetaChanged,
 recordingOptionsChanged: RecordingOptionsChanged,
+recordingProgress: RecordingProgress,
 recordingStarted: RecordingStarted,
 recordingStopped: RecordingStopped,
 renderFrameEvent: RenderFrameEvent,
+renderQueuePosition: RenderQueuePosition,
 requestNewScreenshot: RequestNewScreenshot,
 requestOpenSettings: RequestOpenSettings,
 requestRestartRecording: RequestRestartRecording,
 requestStartRecording: RequestStartRecording,
 requestStopRecording: RequestStopRecording,
+showCapturesPanel: ShowCapturesPanel,
+thumbnailReady: ThumbnailReady,
 uploadProgress: UploadProgress
 }>({
 audioInputLevelChange: "audio-input-level-change",
 authenticationInvalid: "authentication-invalid",
+cameraDisconnected: "camera-disconnected",
 currentRecordingChanged: "current-recording-changed",
+editorRenderError: "editor-render-error",
 editorStateChanged: "editor-state-changed",
+exportProgress: "export-progress",
+ffmpegInstallProgress: "ffmpeg-install-progress",
+hideCapturesPanel: "hide-captures-panel",
+lowDiskSpace: "low-disk-space",
 newNotification: "new-notification",
 newRecordingAdded: "new-recording-added",
 newScreenshotAdded: "new-screenshot-added",
+recordingAutoStopped: "recording-auto-stopped",
+recordingCountdown: "recording-countdown",
 recordingMetaChanged: "recording-meta-changed",
 recordingOptionsChanged: "recording-options-changed",
+recordingProgress: "recording-progress",
 recordingStarted: "recording-started",
 recordingStopped: "recording-stopped",
 renderFrameEvent: "render-frame-event",
+renderQueuePosition: "render-queue-position",
 requestNewScreenshot: "request-new-screenshot",
 requestOpenSettings: "request-open-settings",
 requestRestartRecording: "request-restart-recording",
 requestStartRecording: "request-start-recording",
 requestStopRecording: "request-stop-recording",
+showCapturesPanel: "show-captures-panel",
+thumbnailReady: "thumbnail-ready",
 uploadProgress: "upload-progress"
 })
 
@@ -209,80 +356,125 @@ uploadProgress: "upload-progress"
 
 /** user-defined types **/
 
-export type AspectRatio = "wide" | "vertical" | "square" | "classic" | "tall"
+export type AspectRatio = "wide" | "vertical" | "square" | "classic" | "tall" | { custom: [number, number] }
 export type Audio = { duration: number; sample_rate: number; channels: number }
-export type AudioConfiguration = { mute: boolean; improve: boolean }
+export type AudioConfiguration = { mute: boolean; improve: boolean; normalize?: boolean }
 export type AudioInputLevelChange = number
 export type AudioMeta = { path: string }
 export type AuthStore = { token: string; user_id: string | null; expires: number; plan: Plan | null }
 export type AuthenticationInvalid = null
-export type BackgroundConfiguration = { source: BackgroundSource; blur: number; padding: number; rounding: number; inset: number; crop: Crop | null }
+export type BackgroundConfiguration = { source: BackgroundSource; blur: number; padding: number; rounding: number; shadow?: number; inset: number; crop: Crop | null }
+export type BackgroundRemoval = { type: "chromaKey"; color: [number, number, number]; similarity: number } | { type: "auto" }
 export type BackgroundSource = { type: "wallpaper"; id: number } | { type: "image"; path: string | null } | { type: "color"; value: [number, number, number] } | { type: "gradient"; from: [number, number, number]; to: [number, number, number]; angle?: number }
 export type Bounds = { x: number; y: number; width: number; height: number }
-export type Camera = { hide: boolean; mirror: boolean; position: CameraPosition; size: number; zoom_size: number | null; rounding: number; shadow: number }
+export type Camera = { hide: boolean; mirror: boolean; position: CameraPosition; size: number; zoom_size: number | null; rounding: number; shadow: number; background_removal?: BackgroundRemoval | null }
+export type CameraDeviceInfo = { label: string; formats: CameraFormatInfo[] }
+export type CameraDisconnected = { label: string }
+export type CameraFormatInfo = { width: number; height: number; frameRate: number }
 export type CameraMeta = { path: string }
-export type CameraPosition = { x: CameraXPosition; y: CameraYPosition }
+export type CameraPosition = { x: CameraXPosition; y: CameraYPosition; custom?: XY<number> | null }
 export type CameraXPosition = "left" | "center" | "right"
 export type CameraYPosition = "top" | "bottom"
+export type Capabilities = { captureTargets: CaptureTargetKind[]; isolatesWindows: boolean; systemAudio: boolean; hidesOsCursor: boolean; encoders: Encoder[] }
+export type CaptureApplication = { id: number; bundle_id: string; name: string; windows: CaptureWindow[] }
 export type CaptureScreen = { id: number; name: string }
+export type CaptureTargetKind = "screen" | "window" | "application" | "region"
 export type CaptureWindow = { id: number; owner_name: string; name: string; bounds: Bounds }
+export type ClickHighlightConfiguration = { show: boolean; color: [number, number, number] }
+export type ClipboardFormat = "fileReference" | "videoData"
+export type ClipMode = "fast" | "accurate"
+export type CompressionPreset = "high" | "medium" | "low"
+export type CompressionResult = { path: string; backupPath: string; beforeBytes: number; afterBytes: number }
+export type Corner = "topLeft" | "topRight" | "bottomLeft" | "bottomRight"
 export type Crop = { position: XY<number>; size: XY<number> }
 export type CurrentRecordingChanged = null
 export type CursorAnimationStyle = "regular" | "slow" | "fast"
-export type CursorConfiguration = { hideWhenIdle: boolean; size: number; type: CursorType; animationStyle: CursorAnimationStyle }
+export type CursorConfiguration = { hideWhenIdle: boolean; size: number; type: CursorType; animationStyle: CursorAnimationStyle; smoothing?: number }
+export type CursorMode = "baked" | "overlay" | "hidden"
 export type CursorType = "pointer" | "circle"
-export type Display = { path: string }
+export type DiskInfo = { freeBytes: number; totalBytes: number; cleanupFreedBytes: number }
+export type Display = { path: string; scale_factor?: number | null; color_space?: string | null }
+export type DisplayInfo = { id: number; name: string; bounds: Bounds; scaleFactor: number }
+export type EditorRenderError = { message: string }
 export type EditorStateChanged = { playhead_position: number }
+export type Encoder = "software" | "videotoolboxH264" | "nvencH264"
+export type ExportFormat = "mp4H264" | "webmVp9"
+export type ExportProgress = { video_id: string; progress: number }
+export type FfmpegInfo = { path: string | null; version: string | null; bundled: boolean; error: string | null }
+export type FfmpegInstallProgress = { stage: string; percent: number | null }
 export type Flags = { recordMouse: boolean; split: boolean; pauseResume: boolean; zoom: boolean; customS3: boolean }
-export type GeneralSettingsStore = { uploadIndividualFiles?: boolean; openEditorAfterRecording?: boolean; hideDockIcon?: boolean; autoCreateShareableLink?: boolean; enableNotifications?: boolean; disableAutoOpenLinks?: boolean; hasCompletedStartup?: boolean; darkMode?: boolean }
+export type GeneralSettingsStore = { uploadIndividualFiles?: boolean; openEditorAfterRecording?: boolean; hideDockIcon?: boolean; autoCreateShareableLink?: boolean; enableNotifications?: boolean; disableAutoOpenLinks?: boolean; hasCompletedStartup?: boolean; darkMode?: boolean; recordingsDir?: string | null; disableCaptureOccluder?: boolean; captureOccluderOutlineOnly?: boolean; cleanupAfterRender?: boolean; thumbnailFormat?: ThumbnailFormat; thumbnailQuality?: number | null; defaultFps?: number | null; defaultEncoder?: Encoder }
+export type HideCapturesPanel = null
 export type Hotkey = { code: string; meta: boolean; ctrl: boolean; alt: boolean; shift: boolean }
-export type HotkeyAction = "startRecording" | "stopRecording" | "restartRecording" | "takeScreenshot"
+export type HotkeyAction = "startRecording" | "stopRecording" | "restartRecording" | "takeScreenshot" | "toggleRecording"
 export type HotkeysConfiguration = { show: boolean }
 export type HotkeysStore = { hotkeys: { [key in HotkeyAction]: Hotkey } }
 export type JsonValue<T> = [T]
+export type Layout = { type: "pictureInPicture" } | { type: "sideBySide"; split: number } | { type: "screenOnly" } | { type: "cameraOnly" }
+export type LowDiskSpace = { freeBytes: number; stopping: boolean }
+export type Marker = { timestamp: number; label?: string | null }
 export type MultipleSegment = { display: Display; camera?: CameraMeta | null; audio?: AudioMeta | null; cursor?: string | null }
 export type MultipleSegments = { segments: MultipleSegment[]; cursors: { [key in string]: string } }
 export type NewNotification = { title: string; body: string; is_error: boolean }
-export type NewRecordingAdded = { path: string }
+export type NewRecordingAdded = { path: string; thumbnail_path: string | null }
 export type NewScreenshotAdded = { path: string }
 export type OSPermission = "screenRecording" | "camera" | "microphone" | "accessibility"
 export type OSPermissionStatus = "notNeeded" | "empty" | "granted" | "denied"
 export type OSPermissionsCheck = { screenRecording: OSPermissionStatus; microphone: OSPermissionStatus; camera: OSPermissionStatus; accessibility: OSPermissionStatus }
 export type Plan = { upgraded: boolean; last_checked: number }
 export type PreCreatedVideo = { id: string; link: string; config: S3UploadMeta }
-export type ProjectConfiguration = { aspectRatio: AspectRatio | null; background: BackgroundConfiguration; camera: Camera; audio: AudioConfiguration; cursor: CursorConfiguration; hotkeys: HotkeysConfiguration; timeline?: TimelineConfiguration | null; motionBlur: number | null }
+export type ProjectConfiguration = { aspectRatio: AspectRatio | null; background: BackgroundConfiguration; camera: Camera; audio: AudioConfiguration; cursor: CursorConfiguration; clickHighlight?: ClickHighlightConfiguration; hotkeys: HotkeysConfiguration; timeline?: TimelineConfiguration | null; motionBlur: number | null; trim?: TrimConfiguration | null; outputScale?: number | null; encoder?: Encoder; quality?: QualitySetting | null; speed?: number; watermark?: WatermarkConfiguration | null; redactions?: Redaction[]; fadeInSecs?: number; fadeOutSecs?: number; layout?: Layout; tonemapHdr?: boolean }
 export type ProjectRecordings = { segments: SegmentRecordings[] }
-export type RecordingInfo = { captureTarget: ScreenCaptureTarget }
-export type RecordingMeta = ({ segment: SingleSegment } | { inner: MultipleSegments }) & { pretty_name: string; sharing?: SharingMeta | null }
+export type QualitySetting = { type: "crf"; value: number } | { type: "bitrate"; kbps: number }
+export type RecordingAutoStopped = { elapsedSecs: number }
+export type RecordingCountdown = { remaining: number }
+export type RecordingInfo = { captureTarget: ScreenCaptureTarget; paused: boolean; occluderHoles: Bounds[] }
+export type RecordingMeta = ({ segment: SingleSegment } | { inner: MultipleSegments }) & { pretty_name: string; created_at?: string | null; sharing?: SharingMeta | null; notes?: string | null; tags?: string[] }
 export type RecordingMetaChanged = { id: string }
-export type RecordingOptions = { captureTarget: ScreenCaptureTarget; cameraLabel: string | null; audioInputName: string | null }
+export type RecordingMetaPatch = { title: string | null; notes: string | null; tags: string[] | null }
+export type RecordingOptions = { captureTarget: ScreenCaptureTarget; cameraLabel: string | null; audioInputName: string | null; fps?: number | null; cameraOnly?: boolean; countdownSecs?: number | null; captureSystemAudio?: boolean; cameraResolution?: [number, number] | null; maxDurationSecs?: number | null; captureCursor?: CursorMode }
 export type RecordingOptionsChanged = null
+export type RecordingProgress = { elapsed_secs: number }
+export type RecordingSort = "newest" | "oldest" | "size"
+export type RecordingsPage = { recordings: RecordingSummary[]; total: number }
 export type RecordingStarted = null
 export type RecordingStopped = { path: string }
+export type RecordingSummary = { id: string; prettyName: string; createdAt: number; duration: number; size: number; thumbnailPath: string | null; hasRenderedOutput: boolean; notes: string | null; tags: string[] }
+export type Redaction = { bounds: Crop; kind?: RedactionKind; startSecs: number; endSecs: number }
+export type RedactionKind = "blur" | "pixelate"
 export type RenderFrameEvent = { frame_number: number }
 export type RenderProgress = { type: "Starting"; total_frames: number } | { type: "EstimatedTotalFrames"; total_frames: number } | { type: "FrameRendered"; current_frame: number }
+export type RenderQueuePosition = { video_id: string; position: number }
 export type RequestNewScreenshot = null
 export type RequestOpenSettings = { page: string }
 export type RequestRestartRecording = null
 export type RequestStartRecording = null
 export type RequestStopRecording = null
 export type S3UploadMeta = { id: string; user_id: string; aws_region?: string; aws_bucket?: string }
-export type ScreenCaptureTarget = ({ variant: "window" } & CaptureWindow) | ({ variant: "screen" } & CaptureScreen)
+export type ScreenCaptureTarget = ({ variant: "window" } & CaptureWindow) | ({ variant: "screen" } & CaptureScreen) | { variant: "region"; bounds: Bounds } | ({ variant: "application" } & CaptureApplication)
 export type SegmentRecordings = { display: Video; camera: Video | null; audio: Audio | null }
-export type SerializedEditorInstance = { framesSocketUrl: string; recordingDuration: number; savedProjectConfig: ProjectConfiguration; recordings: ProjectRecordings; path: string; prettyName: string }
+export type SerializedEditorInstance = { framesSocketUrl: string; recordingDuration: number; savedProjectConfig: ProjectConfiguration; recordings: ProjectRecordings; path: string; prettyName: string; thumbnailPath: string | null }
 export type SharingMeta = { id: string; link: string }
-export type ShowCapWindow = "Setup" | "Main" | { Settings: { page: string | null } } | { Editor: { project_id: string } } | "PrevRecordings" | "WindowCaptureOccluder" | { Camera: { ws_port: number } } | { InProgressRecording: { position: [number, number] | null } } | "Upgrade"
-export type SingleSegment = { display: Display; camera?: CameraMeta | null; audio?: AudioMeta | null; cursor?: string | null }
+export type ShowCapturesPanel = null
+export type ShowCapWindow = "Setup" | "Main" | { Settings: { page: string | null } } | { Editor: { project_id: string } } | { PrevRecordings: { screen_bounds: Bounds | null } } | { WindowCaptureOccluder: { screen_bounds: Bounds } } | { Camera: { ws_port: number } } | { InProgressRecording: { position: [number, number] | null } } | "Upgrade"
+export type SingleSegment = { display: Display; camera?: CameraMeta | null; audio?: AudioMeta | null; system_audio?: AudioMeta | null; cursor?: string | null; clicks?: string | null; keystrokes?: string | null }
+export type ThumbnailFormat = "jpeg" | "png" | "webp"
+export type ThumbnailReady = { video_id: string; path: string; thumbnail_path: string }
 export type TimelineConfiguration = { segments: TimelineSegment[]; zoomSegments?: ZoomSegment[] }
 export type TimelineSegment = { recordingSegment: number | null; timescale: number; start: number; end: number }
+export type TrimConfiguration = { startSecs: number; endSecs: number }
 export type UploadMode = { Initial: { pre_created_video: PreCreatedVideo | null } } | "Reupload"
 export type UploadProgress = { stage: string; progress: number; message: string }
 export type UploadResult = { Success: string } | "NotAuthenticated" | "PlanCheckFailed" | "UpgradeRequired"
-export type Video = { duration: number; width: number; height: number }
-export type VideoRecordingMetadata = { duration: number; size: number }
+export type ValidationReport = { ready: boolean; captureTarget: ValidationResult; camera: ValidationResult; audioInput: ValidationResult; ffmpeg: ValidationResult; diskSpace: ValidationResult }
+export type ValidationResult = { status: "ok" } | { status: "skipped" } | { status: "failed"; reason: string }
+export type Video = { duration: number; width: number; height: number; rotation: number }
+export type VideoRecordingMetadata = { duration: number; size: number; width: number | null; height: number | null; fps: number | null; codec: string | null; bitrate: number | null; rotation: number | null; colorSpace: string | null; bitDepth: number | null; hdr: boolean }
 export type VideoType = "screen" | "output"
+export type WatermarkConfiguration = { path: string; position?: Corner; opacity: number; scale: number; margin?: number }
 export type XY<T> = { x: T; y: T }
-export type ZoomSegment = { start: number; end: number; amount: number }
+export type ZoomMode = "auto" | { manual: { x: number; y: number } }
+export type ZoomSegment = { start: number; end: number; amount: number; mode?: ZoomMode }
 
 /** tauri-specta globals **/
 
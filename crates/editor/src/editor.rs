@@ -1,7 +1,12 @@
-use std::{sync::Arc, time::Instant};
+use std::{
+    sync::{Arc, Mutex as StdMutex},
+    time::Instant,
+};
 
-use cap_project::{BackgroundSource, ProjectConfiguration};
-use cap_rendering::{decoder::DecodedFrame, produce_frame, ProjectUniforms, RenderVideoConstants};
+use cap_project::{BackgroundSource, CursorEvents, ProjectConfiguration};
+use cap_rendering::{
+    decoder::DecodedFrame, produce_frame, ProjectUniforms, RenderVideoConstants, RenderingError,
+};
 use tokio::{
     sync::{mpsc, oneshot},
     task::JoinHandle,
@@ -101,6 +106,7 @@ pub enum RendererMessage {
     RenderFrame {
         screen_frame: DecodedFrame,
         camera_frame: Option<DecodedFrame>,
+        cursor: Arc<CursorEvents>,
         background: BackgroundSource,
         uniforms: ProjectUniforms,
         time: f32, // Add this field
@@ -111,10 +117,15 @@ pub enum RendererMessage {
     },
 }
 
+pub type RenderErrorHandler = Arc<dyn Fn(&RenderingError) + Send + Sync + 'static>;
+
 pub struct Renderer {
     rx: mpsc::Receiver<RendererMessage>,
     frame_tx: mpsc::Sender<SocketMessage>,
     render_constants: Arc<RenderVideoConstants>,
+    on_error: RenderErrorHandler,
+    // The same error would otherwise be reported for every frame until it's fixed
+    reported_error: Arc<StdMutex<Option<String>>>,
 }
 
 pub struct RendererHandle {
@@ -125,6 +136,7 @@ impl Renderer {
     pub fn spawn(
         render_constants: Arc<RenderVideoConstants>,
         frame_tx: mpsc::Sender<SocketMessage>,
+        on_error: RenderErrorHandler,
     ) -> RendererHandle {
         let (tx, rx) = mpsc::channel(4);
 
@@ -132,6 +144,8 @@ impl Renderer {
             rx,
             frame_tx,
             render_constants,
+            on_error,
+            reported_error: Default::default(),
         };
 
         tokio::spawn(this.run());
@@ -148,6 +162,7 @@ impl Renderer {
                     RendererMessage::RenderFrame {
                         screen_frame,
                         camera_frame,
+                        cursor,
                         background,
                         uniforms,
                         time, // Add this
@@ -163,19 +178,40 @@ impl Renderer {
 
                         let render_constants = self.render_constants.clone();
                         let frame_tx = self.frame_tx.clone();
+                        let on_error = self.on_error.clone();
+                        let reported_error = self.reported_error.clone();
 
                         frame_task = Some(tokio::spawn(async move {
                             let time_instant = Instant::now();
-                            let frame = produce_frame(
+                            let frame = match produce_frame(
                                 &render_constants,
                                 &screen_frame,
                                 &camera_frame,
-                                cap_rendering::Background::from(background),
+                                &cursor,
+                                &cap_rendering::Background::from(background),
                                 &uniforms,
                                 time, // Pass the actual time value
                             )
                             .await
-                            .unwrap();
+                            {
+                                Ok(frame) => {
+                                    reported_error.lock().unwrap().take();
+                                    frame
+                                }
+                                Err(e) => {
+                                    tracing::error!("Failed to render frame: {e}");
+
+                                    let message = e.to_string();
+                                    let mut reported_error = reported_error.lock().unwrap();
+                                    if reported_error.as_deref() != Some(message.as_str()) {
+                                        on_error(&e);
+                                        *reported_error = Some(message);
+                                    }
+
+                                    finished.send(()).ok();
+                                    return;
+                                }
+                            };
                             // println!("produced frame in {:?}", time_instant.elapsed());
 
                             frame_tx
@@ -213,6 +249,7 @@ impl RendererHandle {
         &self,
         screen_frame: DecodedFrame,
         camera_frame: Option<DecodedFrame>,
+        cursor: Arc<CursorEvents>,
         background: BackgroundSource,
         uniforms: ProjectUniforms,
         time: f32, // Add this parameter
@@ -222,6 +259,7 @@ impl RendererHandle {
         self.send(RendererMessage::RenderFrame {
             screen_frame,
             camera_frame,
+            cursor,
             background,
             uniforms,
             time, // Pass the time
@@ -236,7 +274,7 @@ impl RendererHandle {
         // Send a stop message to the renderer
         let (tx, rx) = oneshot::channel();
         if let Err(_) = self.tx.send(RendererMessage::Stop { finished: tx }).await {
-            println!("Failed to send stop message to renderer");
+            tracing::warn!("Failed to send stop message to renderer");
         }
         // Wait for the renderer to acknowledge the stop
         let _ = rx.await;
@@ -1,10 +1,10 @@
 use crate::editor;
 use crate::playback::{self, PlaybackHandle};
 use cap_media::feeds::AudioData;
-use cap_project::{CursorEvents, ProjectConfiguration, RecordingMeta, XY};
+use cap_project::{CursorEvents, ProjectConfiguration, RecordingMeta};
 use cap_rendering::{
-    ProjectRecordings, ProjectUniforms, RecordingSegmentDecoders, RenderOptions,
-    RenderVideoConstants, SegmentVideoPaths,
+    read_frame, render_frame, Background, ProjectRecordings, ProjectUniforms,
+    RecordingSegmentDecoders, RenderVideoConstants, RenderingError, SegmentVideoPaths,
 };
 use std::ops::Deref;
 use std::sync::Mutex as StdMutex;
@@ -36,6 +36,7 @@ impl EditorInstance {
         projects_path: PathBuf,
         video_id: String,
         on_state_change: impl Fn(&EditorState) + Send + Sync + 'static,
+        on_render_error: impl Fn(&RenderingError) + Send + Sync + 'static,
     ) -> Arc<Self> {
         sentry::configure_scope(|scope| {
             scope.set_tag("crate", "editor");
@@ -52,7 +53,7 @@ impl EditorInstance {
         ));
 
         if !project_path.exists() {
-            println!("Video path {} not found!", project_path.display());
+            tracing::error!("Video path {} not found!", project_path.display());
             // return Err(format!("Video path {} not found!", path.display()));
             panic!("Video path {} not found!", project_path.display());
         }
@@ -61,69 +62,8 @@ impl EditorInstance {
 
         let recordings = ProjectRecordings::new(&meta);
 
-        let render_options = RenderOptions {
-            screen_size: XY::new(
-                recordings.segments[0].display.width,
-                recordings.segments[0].display.height,
-            ),
-            camera_size: recordings.segments[0]
-                .camera
-                .as_ref()
-                .map(|c| XY::new(c.width, c.height)),
-        };
-
-        let segments =
-            match &meta.content {
-                cap_project::Content::SingleSegment { segment: s } => {
-                    let audio =
-                        Arc::new(s.audio.as_ref().map(|meta| {
-                            AudioData::from_file(project_path.join(&meta.path)).unwrap()
-                        }));
-
-                    let cursor = Arc::new(s.cursor_data(&meta).into());
-
-                    let decoders = RecordingSegmentDecoders::new(
-                        &meta,
-                        SegmentVideoPaths {
-                            display: s.display.path.as_path(),
-                            camera: s.camera.as_ref().map(|c| c.path.as_path()),
-                        },
-                    );
-
-                    vec![Segment {
-                        audio,
-                        cursor,
-                        decoders,
-                    }]
-                }
-                cap_project::Content::MultipleSegments { inner } => {
-                    let mut segments = vec![];
-
-                    for s in &inner.segments {
-                        let audio = Arc::new(s.audio.as_ref().map(|meta| {
-                            AudioData::from_file(project_path.join(&meta.path)).unwrap()
-                        }));
-
-                        let cursor = Arc::new(s.cursor_events(&meta));
-
-                        let decoders = RecordingSegmentDecoders::new(
-                            &meta,
-                            SegmentVideoPaths {
-                                display: s.display.path.as_path(),
-                                camera: s.camera.as_ref().map(|c| c.path.as_path()),
-                            },
-                        );
-
-                        segments.push(Segment {
-                            audio,
-                            cursor,
-                            decoders,
-                        });
-                    }
-
-                    segments
-                }
-            };
+        let render_options = recordings.render_options();
+        let segments = load_segments(&meta);
 
         let (frame_tx, frame_rx) = tokio::sync::mpsc::channel(4);
 
@@ -135,7 +75,11 @@ impl EditorInstance {
                 .unwrap(),
         );
 
-        let renderer = Arc::new(editor::Renderer::spawn(render_constants.clone(), frame_tx));
+        let renderer = Arc::new(editor::Renderer::spawn(
+            render_constants.clone(),
+            frame_tx,
+            Arc::new(on_render_error),
+        ));
 
         let (preview_tx, preview_rx) = watch::channel(None);
 
@@ -169,31 +113,31 @@ impl EditorInstance {
     }
 
     pub async fn dispose(&self) {
-        println!("Disposing EditorInstance");
+        tracing::debug!("Disposing EditorInstance");
 
         let mut state = self.state.lock().await;
 
         // Stop playback
         if let Some(handle) = state.playback_task.take() {
-            println!("Stopping playback");
+            tracing::debug!("Stopping playback");
             handle.stop();
         }
 
         // Stop preview
         if let Some(task) = state.preview_task.take() {
-            println!("Stopping preview");
+            tracing::debug!("Stopping preview");
             task.abort();
             task.await.ok(); // Await the task to ensure it's fully stopped
         }
 
         // Stop WebSocket server
         if let Some(ws_shutdown) = self.ws_shutdown.lock().unwrap().take() {
-            println!("Shutting down WebSocket server");
+            tracing::debug!("Shutting down WebSocket server");
             let _ = ws_shutdown.send(());
         }
 
         // Stop renderer
-        println!("Stopping renderer");
+        tracing::debug!("Stopping renderer");
         self.renderer.stop().await;
 
         // // Clear audio data
@@ -207,7 +151,7 @@ impl EditorInstance {
 
         drop(state);
 
-        println!("EditorInstance disposed");
+        tracing::debug!("EditorInstance disposed");
     }
 
     pub async fn modify_and_emit_state(&self, modify: impl Fn(&mut EditorState)) {
@@ -264,6 +208,59 @@ impl EditorInstance {
         }
     }
 
+    /// Composites the frame at `time` on the timeline using `project` instead of the editor's
+    /// configuration, returning it as RGBA with its size, scaled down to at most `max_width`.
+    /// `None` if there's no frame there.
+    pub async fn render_frame_at(
+        &self,
+        project: &ProjectConfiguration,
+        time: f64,
+        max_width: u32,
+    ) -> Result<Option<(Vec<u8>, (u32, u32))>, RenderingError> {
+        let Some((time, segment)) = project
+            .timeline
+            .as_ref()
+            .map(|timeline| timeline.get_recording_time(time))
+            .unwrap_or(Some((time, None)))
+        else {
+            return Ok(None);
+        };
+
+        let Some(segment) = self.segments.get(segment.unwrap_or(0) as usize) else {
+            return Ok(None);
+        };
+
+        let Some((screen_frame, camera_frame)) = segment
+            .decoders
+            .get_frames((time * FPS as f64) as u32, project.tonemap_hdr)
+            .await
+        else {
+            return Ok(None);
+        };
+
+        let uniforms = ProjectUniforms::new(&self.render_constants, project, time as f32);
+        let frame = render_frame(
+            &self.render_constants,
+            &screen_frame,
+            &camera_frame,
+            &segment.cursor,
+            &Background::from(project.background.source.clone()),
+            &uniforms,
+            time as f32,
+        )?;
+
+        let (width, height) = uniforms.output_size;
+        let size = if width > max_width {
+            (max_width, (height * max_width / width).max(1))
+        } else {
+            (width, height)
+        };
+
+        let frame = read_frame(&self.render_constants, &frame, size).await?;
+
+        Ok(Some((frame, size)))
+    }
+
     fn spawn_preview_renderer(
         self: Arc<Self>,
         mut preview_rx: watch::Receiver<Option<u32>>,
@@ -290,7 +287,7 @@ impl EditorInstance {
 
                 let Some((screen_frame, camera_frame)) = segment
                     .decoders
-                    .get_frames((time * FPS as f64) as u32)
+                    .get_frames((time * FPS as f64) as u32, project.tonemap_hdr)
                     .await
                 else {
                     continue;
@@ -300,6 +297,7 @@ impl EditorInstance {
                     .render_frame(
                         screen_frame,
                         camera_frame,
+                        segment.cursor.clone(),
                         project.background.source.clone(),
                         ProjectUniforms::new(&self.render_constants, &project, time as f32),
                         time as f32, // Add the time parameter
@@ -314,7 +312,7 @@ impl Drop for EditorInstance {
     fn drop(&mut self) {
         // TODO: Ensure that *all* resources have been released by this point?
         // For now the `dispose` method is adequate.
-        println!("*** Editor instance {} has been released. ***", self.id);
+        tracing::info!("*** Editor instance {} has been released. ***", self.id);
     }
 }
 
@@ -341,7 +339,7 @@ async fn create_frames_ws(frame_rx: mpsc::Receiver<SocketMessage>) -> (u16, mpsc
 
     async fn handle_socket(mut socket: WebSocket, state: RouterState) {
         let mut rx = state.lock().await;
-        println!("socket connection established");
+        tracing::debug!("socket connection established");
         let now = std::time::Instant::now();
 
         loop {
@@ -366,7 +364,7 @@ async fn create_frames_ws(frame_rx: mpsc::Receiver<SocketMessage>) -> (u16, mpsc
             }
         }
         let elapsed = now.elapsed();
-        println!("Websocket closing after {elapsed:.2?}");
+        tracing::debug!("Websocket closing after {elapsed:.2?}");
     }
 
     let router = axum::Router::new()
@@ -383,7 +381,7 @@ async fn create_frames_ws(frame_rx: mpsc::Receiver<SocketMessage>) -> (u16, mpsc
         tokio::select! {
             _ = server => {},
             _ = shutdown_rx.recv() => {
-                println!("WebSocket server shutting down");
+                tracing::debug!("WebSocket server shutting down");
             }
         }
     });
@@ -414,3 +412,69 @@ pub struct Segment {
     pub cursor: Arc<CursorEvents>,
     pub decoders: RecordingSegmentDecoders,
 }
+
+/// Loads the audio, cursor and video decoders of each of a recording's segments
+pub fn load_segments(meta: &RecordingMeta) -> Vec<Segment> {
+    match &meta.content {
+        cap_project::Content::SingleSegment { segment: s } => {
+            let audio_paths = [&s.audio, &s.system_audio]
+                .into_iter()
+                .flatten()
+                .map(|audio| meta.project_path.join(&audio.path))
+                .collect::<Vec<_>>();
+
+            let audio = Arc::new(if audio_paths.is_empty() {
+                None
+            } else {
+                AudioData::mixed(audio_paths)
+                    .map_err(|e| tracing::error!("Failed to load audio: {e}"))
+                    .ok()
+            });
+
+            let cursor = Arc::new(s.cursor_data(meta).into());
+
+            let decoders = RecordingSegmentDecoders::new(
+                meta,
+                SegmentVideoPaths {
+                    display: s.display.path.as_path(),
+                    camera: s.camera.as_ref().map(|c| c.path.as_path()),
+                },
+            );
+
+            vec![Segment {
+                audio,
+                cursor,
+                decoders,
+            }]
+        }
+        cap_project::Content::MultipleSegments { inner } => {
+            let mut segments = vec![];
+
+            for s in &inner.segments {
+                let audio = Arc::new(s.audio.as_ref().and_then(|audio| {
+                    AudioData::from_file(meta.project_path.join(&audio.path))
+                        .map_err(|e| tracing::error!("Failed to load audio: {e}"))
+                        .ok()
+                }));
+
+                let cursor = Arc::new(s.cursor_events(meta));
+
+                let decoders = RecordingSegmentDecoders::new(
+                    meta,
+                    SegmentVideoPaths {
+                        display: s.display.path.as_path(),
+                        camera: s.camera.as_ref().map(|c| c.path.as_path()),
+                    },
+                );
+
+                segments.push(Segment {
+                    audio,
+                    cursor,
+                    decoders,
+                });
+            }
+
+            segments
+        }
+    }
+}
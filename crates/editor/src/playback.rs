@@ -96,7 +96,7 @@ impl Playback {
                         _ = stop_rx.changed() => {
                            break;
                         },
-                        value = segment.decoders.get_frames((time * FPS as f64) as u32) => {
+                        value = segment.decoders.get_frames((time * FPS as f64) as u32, project.tonemap_hdr) => {
                             if let Some((screen_frame, camera_frame)) = value {
                                 let uniforms = ProjectUniforms::new(&self.render_constants, &project, time as f32);
 
@@ -105,6 +105,7 @@ impl Playback {
                                     .render_frame(
                                         screen_frame,
                                         camera_frame,
+                                        segment.cursor.clone(),
                                         project.background.source.clone(),
                                         uniforms.clone(),
                                         time as f32  // Add the time parameter
@@ -129,7 +130,7 @@ impl Playback {
                 frame_number += 1;
             }
 
-            println!("stopped playback");
+            tracing::debug!("stopped playback");
 
             stop_tx.send(true).ok();
 
@@ -165,7 +166,7 @@ impl AudioPlayback {
         std::thread::spawn(move || {
             let host = cpal::default_host();
             let device = host.default_output_device().unwrap();
-            println!("Output device: {}", device.name().unwrap());
+            tracing::info!("Output device: {}", device.name().unwrap());
             let supported_config = device
                 .default_output_config()
                 .expect("Failed to get default output format");
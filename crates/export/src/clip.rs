@@ -0,0 +1,94 @@
+use std::path::{Path, PathBuf};
+
+use cap_project::{Encoder, QualitySetting};
+use mp4::Mp4Reader;
+
+use crate::{gif::run, h264_quality_args, resolve_encoder, ExportError};
+
+/// CRF used for re-encoded clips when the project doesn't set a quality
+const DEFAULT_CLIP_CRF: u8 = 18;
+
+pub enum ClipEncoding {
+    /// Copies the streams, so the clip starts at the keyframe before its start
+    Copy,
+    /// Re-encodes the clip so it starts and ends exactly where requested
+    Reencode {
+        encoder: Encoder,
+        quality: Option<QualitySetting>,
+    },
+}
+
+/// Cuts `start_secs` to `end_secs` out of a rendered video into an MP4
+pub async fn export_clip(
+    video_path: &Path,
+    output_path: PathBuf,
+    start_secs: f64,
+    end_secs: f64,
+    encoding: ClipEncoding,
+) -> Result<PathBuf, ExportError> {
+    let mut ffmpeg = cap_ffmpeg_cli::FFmpeg::new();
+    ffmpeg
+        .command
+        .args(["-y", "-loglevel", "error"])
+        // Seeking before the input is fast, and exact when re-encoding
+        .args(["-ss", &start_secs.to_string(), "-i"])
+        .arg(video_path)
+        .args(["-t", &(end_secs - start_secs).to_string()]);
+
+    match encoding {
+        ClipEncoding::Copy => {
+            ffmpeg
+                .command
+                .args(["-c", "copy", "-avoid_negative_ts", "make_zero"]);
+        }
+        ClipEncoding::Reencode { encoder, quality } => {
+            let encoder = resolve_encoder(encoder).await;
+
+            ffmpeg
+                .command
+                .args(["-codec:v", encoder.ffmpeg_codec(), "-codec:a", "aac"])
+                .args(["-pix_fmt", "yuv420p"]);
+
+            if encoder == Encoder::Software {
+                ffmpeg.command.args(["-preset", "medium"]);
+            }
+
+            ffmpeg.command.args(h264_quality_args(
+                encoder,
+                Some(quality.unwrap_or(QualitySetting::Crf {
+                    value: DEFAULT_CLIP_CRF,
+                })),
+                video_size(video_path)?,
+            ));
+        }
+    }
+
+    ffmpeg
+        .command
+        .args(["-f", "mp4", "-movflags", "+faststart"])
+        .arg(&output_path);
+
+    run(ffmpeg).await?;
+
+    Ok(output_path)
+}
+
+fn video_size(video_path: &Path) -> Result<(u32, u32), ExportError> {
+    let file = std::fs::File::open(video_path)?;
+    let size = file.metadata()?.len();
+    let reader = Mp4Reader::read_header(std::io::BufReader::new(file), size)
+        .map_err(std::io::Error::other)?;
+
+    reader
+        .tracks()
+        .values()
+        .find(|track| matches!(track.track_type(), Ok(mp4::TrackType::Video)))
+        .map(|track| (u32::from(track.width()), u32::from(track.height())))
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} has no video", video_path.display()),
+            )
+            .into()
+        })
+}
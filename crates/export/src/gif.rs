@@ -0,0 +1,64 @@
+use std::path::{Path, PathBuf};
+
+use crate::ExportError;
+
+/// Converts a rendered video to a GIF using a generated palette for better colour quality.
+pub async fn export_gif(
+    video_path: &Path,
+    output_path: PathBuf,
+    fps: u32,
+    max_width: u32,
+) -> Result<PathBuf, ExportError> {
+    let palette_dir = tempfile::tempdir()?;
+    let palette_path = palette_dir.path().join("palette.png");
+
+    let filters = format!("fps={fps},scale='min({max_width},iw)':-2:flags=lanczos");
+
+    let mut palettegen = cap_ffmpeg_cli::FFmpeg::new();
+    palettegen
+        .command
+        .args(["-loglevel", "error"])
+        .arg("-i")
+        .arg(video_path)
+        .args(["-vf", &format!("{filters},palettegen=stats_mode=diff")])
+        .arg("-y")
+        .arg(&palette_path);
+    run(palettegen).await?;
+
+    let mut paletteuse = cap_ffmpeg_cli::FFmpeg::new();
+    paletteuse
+        .command
+        .args(["-loglevel", "error"])
+        .arg("-i")
+        .arg(video_path)
+        .arg("-i")
+        .arg(&palette_path)
+        .args([
+            "-lavfi",
+            &format!(
+                "{filters}[x];[x][1:v]paletteuse=dither=bayer:bayer_scale=5:diff_mode=rectangle"
+            ),
+        ])
+        .arg("-y")
+        .arg(&output_path);
+    run(paletteuse).await?;
+
+    Ok(output_path)
+}
+
+pub(crate) async fn run(ffmpeg: cap_ffmpeg_cli::FFmpeg) -> Result<(), ExportError> {
+    let mut process = ffmpeg
+        .start()
+        .map_err(|e| ExportError::FFmpegSpawn(e.to_string()))?;
+
+    let status = process
+        .wait()
+        .await
+        .map_err(|e| ExportError::FFmpeg(e.to_string()))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(ExportError::ffmpeg_exit(&mut process, status).await)
+    }
+}
@@ -1,17 +1,33 @@
-use cap_editor::Segment;
-use image::{ImageBuffer, Rgba};
+mod clip;
+mod gif;
+mod loudnorm;
+
+pub use clip::{export_clip, ClipEncoding};
+pub use gif::export_gif;
+use loudnorm::Loudness;
+
+use image::{ImageBuffer, RgbImage, Rgba};
 use mp4::Mp4Reader;
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    future::Future,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
-use cap_media::feeds::AudioFrameBuffer;
-use cap_project::{ProjectConfiguration, RecordingMeta};
+use cap_media::feeds::{AudioData, AudioFrameBuffer};
+use cap_project::{
+    Encoder, ExportFormat, ProjectConfiguration, QualitySetting, RecordingMeta, ThumbnailFormat,
+    ThumbnailOptions, TimelineConfiguration, MAX_CRF,
+};
 use cap_rendering::{
-    ProjectUniforms, RecordingSegmentDecoders, RenderSegment, RenderVideoConstants,
-    SegmentVideoPaths,
+    ProjectRecordings, ProjectUniforms, RecordingSegmentDecoders, RenderSegment,
+    RenderVideoConstants, SegmentVideoPaths,
 };
 
 struct AudioRender {
     buffer: AudioFrameBuffer,
+    /// `None` if the audio has already been laid out along the timeline
+    timeline: Option<TimelineConfiguration>,
     pipe_tx: tokio::sync::mpsc::Sender<Vec<u8>>,
 }
 
@@ -22,6 +38,19 @@ pub enum ExportError {
     #[error("FFmpeg: {0}")]
     FFmpeg(String),
 
+    #[error("Failed to start FFmpeg: {0}")]
+    FFmpegSpawn(String),
+
+    #[error(
+        "FFmpeg exited with code {}: {stderr_tail}",
+        .code.map_or("unknown".to_string(), |c| c.to_string())
+    )]
+    FFmpegExit {
+        code: Option<i32>,
+        /// The end of FFmpeg's output, which is where it explains what went wrong
+        stderr_tail: String,
+    },
+
     #[error("IO: {0}")]
     IO(#[from] std::io::Error),
 
@@ -30,23 +59,191 @@ pub enum ExportError {
 
     #[error("Rendering: {0}")]
     Rendering(#[from] cap_rendering::RenderingError),
+
+    #[error("Export was cancelled")]
+    Cancelled,
+
+    #[error("Project: {0}")]
+    Project(String),
+}
+
+/// Lines of FFmpeg's output kept in `ExportError::FFmpegExit`
+const STDERR_TAIL_LINES: usize = 20;
+
+impl ExportError {
+    async fn ffmpeg_exit(
+        process: &mut cap_ffmpeg_cli::FFmpegProcess,
+        status: std::process::ExitStatus,
+    ) -> Self {
+        let stderr = process
+            .read_stderr()
+            .await
+            .unwrap_or_else(|_| "Failed to read FFmpeg error".to_string());
+        let lines = stderr.trim_end().lines().collect::<Vec<_>>();
+
+        Self::FFmpegExit {
+            code: status.code(),
+            stderr_tail: lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n"),
+        }
+    }
+}
+
+static AVAILABLE_ENCODERS: tokio::sync::OnceCell<Vec<Encoder>> = tokio::sync::OnceCell::const_new();
+
+/// Encoders supported by the bundled ffmpeg, queried once and cached for the process lifetime.
+/// Failing to query them isn't cached, so they're detected once ffmpeg becomes usable.
+pub async fn available_encoders() -> &'static [Encoder] {
+    AVAILABLE_ENCODERS
+        .get_or_try_init(|| async {
+            let names = cap_ffmpeg_cli::list_encoders().await?;
+
+            Ok::<_, std::io::Error>(
+                Encoder::ALL
+                    .into_iter()
+                    .filter(|encoder| {
+                        *encoder == Encoder::Software
+                            || names.iter().any(|name| name == encoder.ffmpeg_codec())
+                    })
+                    .collect(),
+            )
+        })
+        .await
+        .map(Vec::as_slice)
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to query ffmpeg encoders: {e}");
+            &[Encoder::Software]
+        })
+}
+
+async fn resolve_encoder(preferred: Encoder) -> Encoder {
+    if available_encoders().await.contains(&preferred) {
+        preferred
+    } else {
+        tracing::warn!("Encoder {preferred:?} is unavailable, falling back to software");
+        Encoder::Software
+    }
+}
+
+/// Bitrate that VideoToolbox is given for a CRF, as it ignores `-q:v` on Intel Macs.
+/// Starts from about 0.1 bits per pixel at CRF 23 and halves every 6 steps like x264 does.
+fn videotoolbox_crf_kbps(crf: u8, (width, height): (u32, u32)) -> u32 {
+    let bits_per_pixel = 0.1 * 2f64.powf((23.0 - f64::from(crf.min(MAX_CRF))) / 6.0);
+    let kbps = bits_per_pixel * f64::from(width) * f64::from(height) * f64::from(FPS) / 1000.0;
+
+    (kbps.round() as u32).max(1)
+}
+
+fn h264_quality_args(
+    encoder: Encoder,
+    quality: Option<QualitySetting>,
+    size: (u32, u32),
+) -> Vec<String> {
+    match (encoder, quality) {
+        (Encoder::VideotoolboxH264, None) => vec!["-b:v".into(), "12M".into()],
+        (_, None) => vec![],
+        (_, Some(QualitySetting::Bitrate { kbps })) => vec!["-b:v".into(), format!("{kbps}k")],
+        (Encoder::Software, Some(QualitySetting::Crf { value })) => {
+            vec!["-crf".into(), value.to_string()]
+        }
+        (Encoder::NvencH264, Some(QualitySetting::Crf { value })) => {
+            // NVENC's equivalent of CRF is constant quality within variable bitrate
+            vec![
+                "-rc".into(),
+                "vbr".into(),
+                "-cq".into(),
+                value.to_string(),
+                "-b:v".into(),
+                "0".into(),
+            ]
+        }
+        (Encoder::VideotoolboxH264, Some(QualitySetting::Crf { value })) => {
+            vec![
+                "-b:v".into(),
+                format!("{}k", videotoolbox_crf_kbps(value, size)),
+            ]
+        }
+    }
+}
+
+fn vp9_quality_args(quality: Option<QualitySetting>) -> Vec<String> {
+    match quality {
+        None => vec!["-crf".into(), "32".into(), "-b:v".into(), "0".into()],
+        Some(QualitySetting::Crf { value }) => {
+            vec!["-crf".into(), value.to_string(), "-b:v".into(), "0".into()]
+        }
+        Some(QualitySetting::Bitrate { kbps }) => vec!["-b:v".into(), format!("{kbps}k")],
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExportSettings {
+    pub format: ExportFormat,
+    /// Used for the screenshot saved from the first rendered frame
+    pub thumbnail: ThumbnailOptions,
+}
+
+/// Saves `image` as the recording's screenshot, removing any saved earlier in other formats
+/// so an outdated one is never shown instead
+pub fn save_screenshot(
+    image: &RgbImage,
+    project_path: &Path,
+    options: ThumbnailOptions,
+) -> Result<PathBuf, image::ImageError> {
+    let path = options.format.path(project_path);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    match options.format {
+        ThumbnailFormat::Jpeg => {
+            let file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+            image::codecs::jpeg::JpegEncoder::new_with_quality(file, options.quality)
+                .encode_image(image)?;
+        }
+        ThumbnailFormat::Png => image.save_with_format(&path, image::ImageFormat::Png)?,
+        // The image crate only encodes lossless WebP
+        ThumbnailFormat::Webp => image.save_with_format(&path, image::ImageFormat::WebP)?,
+    }
+
+    for other in ThumbnailFormat::ALL
+        .into_iter()
+        .filter(|format| *format != options.format)
+    {
+        std::fs::remove_file(other.path(project_path)).ok();
+    }
+
+    Ok(path)
 }
 
-pub async fn export_video_to_file(
+/// Renders a recording straight from its project directory, with no editor or window
+/// involved, so it can also be run against fixture projects
+pub async fn render_project(
+    project_path: &PathBuf,
     project: ProjectConfiguration,
     output_path: PathBuf,
+    settings: ExportSettings,
     on_progress: impl Fn(u32) + Send + 'static,
-    project_path: &PathBuf,
-    meta: RecordingMeta,
-    render_constants: Arc<RenderVideoConstants>,
-    segments: &[Segment],
+    cancel: impl Future<Output = ()> + Send + 'static,
 ) -> Result<PathBuf, ExportError> {
+    let meta = RecordingMeta::load_for_project(project_path).map_err(ExportError::Project)?;
+
+    let render_options = ProjectRecordings::try_new(&meta)
+        .map_err(ExportError::Project)?
+        .render_options();
+    let render_constants = Arc::new(RenderVideoConstants::new(render_options, &meta).await?);
+    let segments = cap_editor::load_segments(&meta);
+
+    let ExportSettings { format, thumbnail } = settings;
     let (tx_image_data, mut rx_image_data) = tokio::sync::mpsc::channel::<Vec<u8>>(4);
 
+    let encoder = resolve_encoder(project.encoder).await;
+
     let output_folder = output_path.parent().unwrap();
     std::fs::create_dir_all(output_folder)?;
 
     let output_size = ProjectUniforms::get_output_size(&render_constants.options, &project);
+    let scaled_output_size =
+        ProjectUniforms::get_scaled_output_size(&render_constants.options, &project);
 
     let (render_segments, audio_segments): (Vec<_>, Vec<_>) = segments
         .iter()
@@ -77,17 +274,31 @@ pub async fn export_video_to_file(
         })
         .unzip();
 
+    // The timeline already has the trim and speed applied, so this is the output duration
+    let duration = project
+        .timeline()
+        .map(|t| t.duration())
+        .unwrap_or_else(|| ProjectRecordings::new(&meta).duration());
+    let (fade_in, fade_out) = project.fades(duration);
+
     let ffmpeg_handle = tokio::spawn({
         let project = project.clone();
         let project_path = project_path.clone();
         async move {
-            println!("Starting FFmpeg output process...");
+            tracing::debug!("Starting FFmpeg output process...");
             let mut ffmpeg = cap_ffmpeg_cli::FFmpeg::new();
 
             let audio_dir = tempfile::tempdir().unwrap();
             let video_dir = tempfile::tempdir().unwrap();
-            let mut audio = if let Some(audio_data) =
-                audio_segments.get(0).and_then(|d| d.as_ref().as_ref())
+            let has_audio =
+                !audio_segments.is_empty() && audio_segments.iter().all(|s| s.as_ref().is_some());
+
+            let mut loudness = None;
+
+            let mut audio = if let Some(audio_data) = audio_segments
+                .get(0)
+                .and_then(|d| d.as_ref().as_ref())
+                .filter(|_| has_audio)
             {
                 let (tx, rx) = tokio::sync::mpsc::channel::<Vec<u8>>(30);
 
@@ -101,14 +312,45 @@ pub async fn export_video_to_file(
                     channels: audio_data.info.channels as u16,
                 });
 
-                Some(AudioRender {
-                    buffer: AudioFrameBuffer::new(
-                        audio_segments
-                            .iter()
-                            .map(|s| s.as_ref().as_ref().unwrap().clone())
-                            .collect(),
-                    ),
-                    pipe_tx: tx,
+                let audio_data = audio_segments
+                    .iter()
+                    .map(|s| s.as_ref().as_ref().unwrap().clone())
+                    .collect::<Vec<_>>();
+
+                // The audio buffer can only skip around the recording, changing speed
+                // requires stretching the audio up front
+                let retimed = project
+                    .timeline()
+                    .filter(|t| t.segments.iter().any(|s| s.timescale != 1.0))
+                    .and_then(|timeline| {
+                        AudioData::retimed(&audio_data, timeline)
+                            .map_err(|e| tracing::error!("Failed to change audio speed: {e}"))
+                            .ok()
+                    });
+
+                if project.audio.normalize {
+                    let measured = match &retimed {
+                        Some(retimed) => {
+                            Loudness::measure(std::slice::from_ref(retimed), audio_dir.path()).await
+                        }
+                        None => Loudness::measure(&audio_data, audio_dir.path()).await,
+                    };
+
+                    loudness =
+                        measured?.map(|loudness| loudness.filter(audio_data[0].info.sample_rate));
+                }
+
+                Some(match retimed {
+                    Some(retimed) => AudioRender {
+                        buffer: AudioFrameBuffer::new(vec![retimed]),
+                        timeline: None,
+                        pipe_tx: tx,
+                    },
+                    None => AudioRender {
+                        buffer: AudioFrameBuffer::new(audio_data),
+                        timeline: project.timeline().cloned(),
+                        pipe_tx: tx,
+                    },
                 })
             } else {
                 None
@@ -131,38 +373,96 @@ pub async fn export_video_to_file(
                 tx
             };
 
-            ffmpeg
-                .command
-                .args(["-f", "mp4"])
-                .args(["-codec:v", "libx264", "-codec:a", "aac"])
-                .args(["-preset", "ultrafast"])
-                .args(["-pix_fmt", "yuv420p", "-tune", "zerolatency"])
-                .arg("-y")
-                .arg(&output_path);
+            let mut video_filters = vec![];
+            let mut audio_filters = vec![];
+
+            if scaled_output_size != output_size {
+                video_filters.push(format!(
+                    "scale={}:{}",
+                    scaled_output_size.0, scaled_output_size.1
+                ));
+            }
+
+            audio_filters.extend(loudness);
+
+            if fade_in > 0.0 {
+                video_filters.push(format!("fade=t=in:st=0:d={fade_in}"));
+                audio_filters.push(format!("afade=t=in:st=0:d={fade_in}"));
+            }
+            if fade_out > 0.0 {
+                let start = duration - fade_out;
+                video_filters.push(format!("fade=t=out:st={start}:d={fade_out}"));
+                audio_filters.push(format!("afade=t=out:st={start}:d={fade_out}"));
+            }
+
+            if !video_filters.is_empty() {
+                ffmpeg.command.args(["-vf", &video_filters.join(",")]);
+            }
+            if audio.is_some() && !audio_filters.is_empty() {
+                ffmpeg.command.args(["-af", &audio_filters.join(",")]);
+            }
+
+            match format {
+                ExportFormat::Mp4H264 => {
+                    ffmpeg
+                        .command
+                        .args(["-f", "mp4"])
+                        .args(["-codec:v", encoder.ffmpeg_codec(), "-codec:a", "aac"])
+                        .args(["-pix_fmt", "yuv420p"]);
+
+                    match encoder {
+                        Encoder::Software => {
+                            ffmpeg
+                                .command
+                                .args(["-preset", "ultrafast", "-tune", "zerolatency"]);
+                        }
+                        Encoder::VideotoolboxH264 => {
+                            ffmpeg.command.args(["-realtime", "1"]);
+                        }
+                        Encoder::NvencH264 => {
+                            ffmpeg.command.args(["-preset", "p1", "-tune", "ll"]);
+                        }
+                    }
+
+                    ffmpeg.command.args(h264_quality_args(
+                        encoder,
+                        project.quality,
+                        scaled_output_size,
+                    ));
+                }
+                ExportFormat::WebmVp9 => {
+                    ffmpeg
+                        .command
+                        .args(["-f", "webm"])
+                        .args(["-codec:v", "libvpx-vp9", "-codec:a", "libopus"])
+                        .args(["-deadline", "realtime", "-cpu-used", "8", "-row-mt", "1"])
+                        .args(vp9_quality_args(project.quality))
+                        .args(["-pix_fmt", "yuv420p"]);
+                }
+            }
+
+            ffmpeg.command.arg("-y").arg(&output_path);
 
-            let mut ffmpeg_process = ffmpeg.start();
+            let mut ffmpeg_process = ffmpeg
+                .start()
+                .map_err(|e| ExportError::FFmpegSpawn(e.to_string()))?;
 
             let mut frame_count = 0;
             let mut first_frame = None;
 
+            tokio::pin!(cancel);
+
             loop {
                 tokio::select! {
+                    _ = &mut cancel => {
+                        ffmpeg_process.kill();
+                        return Err(ExportError::Cancelled);
+                    }
                     result = ffmpeg_process.wait() => {
-                        match result {
-                            Err(e) => Err(ExportError::FFmpeg(e.to_string())),
-                            Ok(status) => {
-                                if status.success() {
-                                    Ok(())
-                                } else {
-                                    Err(ExportError::FFmpeg(
-                                        ffmpeg_process
-                                            .read_stderr()
-                                            .await
-                                            .unwrap_or_else(|_| "Failed to read FFmpegg error".to_string())
-                                    ))
-                                }
-                            }
-                        }?;
+                        let status = result.map_err(|e| ExportError::FFmpeg(e.to_string()))?;
+                        if !status.success() {
+                            return Err(ExportError::ffmpeg_exit(&mut ffmpeg_process, status).await);
+                        }
                     }
                     frame = rx_image_data.recv()  => {
                         match frame {
@@ -175,7 +475,7 @@ pub async fn export_video_to_file(
 
                                 if let Some(audio) = &mut audio {
                                     if frame_count == 0 {
-                                        audio.buffer.set_playhead(0., project.timeline());
+                                        audio.buffer.set_playhead(0., audio.timeline.as_ref());
                                     }
 
                                     let audio_info = audio.buffer.info();
@@ -183,12 +483,21 @@ pub async fn export_video_to_file(
                                         f64::from(audio_info.sample_rate) / f64::from(FPS);
                                     let samples = estimated_samples_per_frame.ceil() as usize;
 
-                                    if let Some((_, frame_data)) =
-                                        audio.buffer.next_frame_data(samples, project.timeline())
+                                    // Pad with silence once the audio runs out so the track
+                                    // matches the video duration
+                                    let frame_samples = match audio
+                                        .buffer
+                                        .next_frame_data(samples, audio.timeline.as_ref())
                                     {
-                                        let frame_samples = frame_data.to_vec();
-                                        audio.pipe_tx.send(frame_samples).await.unwrap();
-                                    }
+                                        Some((_, frame_data)) => frame_data.to_vec(),
+                                        None => vec![
+                                            0;
+                                            samples
+                                                * audio_info.channels
+                                                * audio_info.sample_format.bytes()
+                                        ],
+                                    };
+                                    audio.pipe_tx.send(frame_samples).await.unwrap();
                                 }
 
                                 video_tx.send(frame).await.unwrap();
@@ -196,7 +505,7 @@ pub async fn export_video_to_file(
                                 frame_count += 1;
                             }
                             None => {
-                                println!("All frames sent to FFmpeg");
+                                tracing::debug!("All frames sent to FFmpeg");
                                 break;
                             }
                         }
@@ -221,16 +530,9 @@ pub async fn export_video_to_file(
                         image::Rgb([rgba[0], rgba[1], rgba[2]])
                     });
 
-                let screenshots_dir = project_path.join("screenshots");
-                std::fs::create_dir_all(&screenshots_dir).unwrap_or_else(|e| {
-                    eprintln!("Failed to create screenshots directory: {:?}", e);
-                });
-
-                // Save full-size screenshot
-                let screenshot_path = screenshots_dir.join("display.jpg");
-                rgb_img.save(&screenshot_path).unwrap_or_else(|e| {
-                    eprintln!("Failed to save screenshot: {:?}", e);
-                });
+                if let Err(e) = save_screenshot(&rgb_img, &project_path, thumbnail) {
+                    tracing::error!("Failed to save screenshot: {e}");
+                }
 
                 // // Create and save thumbnail
                 // let thumbnail = image::imageops::resize(
@@ -244,28 +546,32 @@ pub async fn export_video_to_file(
                 //     eprintln!("Failed to save thumbnail: {:?}", e);
                 // });
             } else {
-                eprintln!("No frames were processed, cannot save screenshot or thumbnail");
+                tracing::error!("No frames were processed, cannot save screenshot or thumbnail");
             }
 
             Ok::<_, ExportError>(output_path)
         }
     });
 
-    println!("Rendering video to channel");
+    tracing::debug!("Rendering video to channel");
 
-    cap_rendering::render_video_to_channel(
+    let render_result = cap_rendering::render_video_to_channel(
         render_constants.options,
         project,
         tx_image_data,
         &meta,
         render_segments,
     )
-    .await?;
+    .await;
 
     let output_path = ffmpeg_handle.await??;
+    render_result?;
 
-    println!("Copying file to {:?}", project_path);
-    let result_path = project_path.join("output").join("result.mp4");
+    tracing::debug!("Copying file to {:?}", project_path);
+    let result_path = project_path
+        .join("output")
+        .join("result")
+        .with_extension(format.extension());
     // Function to check if the file is a valid MP4
     fn is_valid_mp4(path: &std::path::Path) -> bool {
         if let Ok(file) = std::fs::File::open(path) {
@@ -280,31 +586,157 @@ pub async fn export_video_to_file(
         }
     }
 
-    if output_path != result_path {
-        println!("Waiting for valid MP4 file at {:?}", output_path);
+    if output_path != result_path && format == ExportFormat::Mp4H264 {
+        tracing::debug!("Waiting for valid MP4 file at {:?}", output_path);
         // Wait for the file to become a valid MP4
         let mut attempts = 0;
         while attempts < 10 {
             // Wait for up to 60 seconds
             if is_valid_mp4(&output_path) {
-                println!("Valid MP4 file detected after {} seconds", attempts);
+                tracing::debug!("Valid MP4 file detected after {} seconds", attempts);
                 match std::fs::copy(&output_path, &result_path) {
                     Ok(bytes) => {
-                        println!("Successfully copied {} bytes to {:?}", bytes, result_path)
+                        tracing::info!("Successfully copied {} bytes to {:?}", bytes, result_path)
                     }
-                    Err(e) => eprintln!("Failed to copy file: {:?}", e),
+                    Err(e) => tracing::debug!("Failed to copy file: {:?}", e),
                 }
                 break;
             }
-            println!("Attempt {}: File not yet valid, waiting...", attempts + 1);
+            tracing::debug!("Attempt {}: File not yet valid, waiting...", attempts + 1);
             std::thread::sleep(std::time::Duration::from_secs(1));
             attempts += 1;
         }
 
         if attempts == 10 {
-            eprintln!("Timeout: Failed to detect a valid MP4 file after 60 seconds");
+            tracing::error!("Timeout: Failed to detect a valid MP4 file after 60 seconds");
         }
     }
 
     Ok(output_path)
 }
+
+#[cfg(test)]
+mod tests {
+    use cap_project::{TimelineConfiguration, TimelineSegment};
+    use cap_rendering::RenderingError;
+
+    use super::*;
+
+    /// Half a second of 64x48 video at 30fps
+    const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/screen.cap");
+
+    fn project(end: f64) -> ProjectConfiguration {
+        let mut project = ProjectConfiguration::default();
+        project.background.padding = 0.0;
+        // Without a timeline the last frame depends on how the decoder handles seeking past
+        // the end, a trimmed timeline has an exact frame count
+        project.timeline = Some(TimelineConfiguration {
+            segments: vec![TimelineSegment {
+                recording_segment: None,
+                timescale: 1.0,
+                start: 0.0,
+                end,
+            }],
+            zoom_segments: vec![],
+        });
+        project
+    }
+
+    /// Renders a copy of the fixture, as exports write screenshots into the project.
+    /// Returns the output's frame count and size, or `None` without a GPU or FFmpeg.
+    async fn render(project: ProjectConfiguration) -> Option<(u32, u16, u16)> {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("content")).unwrap();
+        for file in ["recording-meta.json", "content/display.mp4"] {
+            std::fs::copy(Path::new(FIXTURE).join(file), dir.path().join(file)).unwrap();
+        }
+
+        let project_path = dir.path().to_path_buf();
+        let output_path = project_path.join("output").join("result.mp4");
+
+        match render_project(
+            &project_path,
+            project,
+            output_path.clone(),
+            ExportSettings::default(),
+            |_| {},
+            std::future::pending(),
+        )
+        .await
+        {
+            Ok(_) => {}
+            Err(
+                ExportError::Rendering(RenderingError::NoAdapter) | ExportError::FFmpegSpawn(_),
+            ) => return None,
+            Err(e) => panic!("Failed to render the fixture: {e}"),
+        }
+
+        let file = std::fs::File::open(&output_path).unwrap();
+        let size = file.metadata().unwrap().len();
+        let reader = Mp4Reader::read_header(std::io::BufReader::new(file), size).unwrap();
+        let track = reader
+            .tracks()
+            .values()
+            .find(|track| matches!(track.track_type(), Ok(mp4::TrackType::Video)))
+            .unwrap();
+
+        Some((track.sample_count(), track.width(), track.height()))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn renders_every_frame_at_the_recording_size() {
+        let Some(output) = render(project(0.5)).await else {
+            return;
+        };
+
+        assert_eq!(output, (15, 64, 48));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn renders_only_the_trimmed_frames() {
+        let Some(output) = render(project(0.3)).await else {
+            return;
+        };
+
+        assert_eq!(output, (9, 64, 48));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn output_scale_shrinks_the_frames() {
+        let mut project = project(0.5);
+        project.output_scale = Some(0.5);
+
+        let Some(output) = render(project).await else {
+            return;
+        };
+
+        assert_eq!(output, (15, 32, 24));
+    }
+
+    #[test]
+    fn videotoolbox_crf_uses_a_bitrate() {
+        let args = h264_quality_args(
+            Encoder::VideotoolboxH264,
+            Some(QualitySetting::Crf { value: 23 }),
+            (1920, 1080),
+        );
+
+        assert_eq!(args, ["-b:v", "6221k"]);
+    }
+
+    #[test]
+    fn videotoolbox_bitrate_halves_every_6_crf() {
+        let size = (1920, 1080);
+
+        assert_eq!(
+            videotoolbox_crf_kbps(17, size),
+            videotoolbox_crf_kbps(23, size) * 2
+        );
+        assert!(videotoolbox_crf_kbps(0, size) > videotoolbox_crf_kbps(MAX_CRF, size));
+        assert_eq!(
+            videotoolbox_crf_kbps(u8::MAX, size),
+            videotoolbox_crf_kbps(MAX_CRF, size)
+        );
+        assert_eq!(videotoolbox_crf_kbps(MAX_CRF, (2, 2)), 1);
+    }
+}
@@ -0,0 +1,186 @@
+use std::path::Path;
+
+use cap_media::feeds::AudioData;
+
+use crate::ExportError;
+
+/// EBU R128 integrated loudness target, in LUFS
+const TARGET_LOUDNESS: f64 = -16.0;
+const TARGET_TRUE_PEAK: f64 = -1.5;
+const TARGET_LOUDNESS_RANGE: f64 = 11.0;
+
+/// How loud the audio was measured to be by the first `loudnorm` pass
+#[derive(Debug, Clone, Copy)]
+pub struct Loudness {
+    integrated: f64,
+    true_peak: f64,
+    range: f64,
+    threshold: f64,
+    offset: f64,
+}
+
+impl Loudness {
+    /// Runs `loudnorm` over the audio without writing anything, so the actual
+    /// normalization can be done in a single linear pass while rendering.
+    /// Returns `None` for silent audio, as there's nothing to normalize.
+    pub async fn measure(audio: &[AudioData], dir: &Path) -> Result<Option<Self>, ExportError> {
+        let Some(info) = audio.first().map(|a| a.info) else {
+            return Ok(None);
+        };
+
+        let samples_path = dir.join("loudness.raw");
+        {
+            let mut samples = Vec::with_capacity(audio.iter().map(|a| a.buffer.len()).sum());
+            for data in audio {
+                samples.extend_from_slice(&data.buffer);
+            }
+            tokio::fs::write(&samples_path, samples).await?;
+        }
+
+        let mut ffmpeg = cap_ffmpeg_cli::FFmpeg::new();
+        let output = ffmpeg
+            .command
+            .args(["-hide_banner", "-nostats", "-f", "f64le"])
+            .args(["-ar", &info.sample_rate.to_string()])
+            .args(["-ac", &info.channels.to_string()])
+            .arg("-i")
+            .arg(&samples_path)
+            .args(["-af", &format!("{}:print_format=json", targets())])
+            .args(["-f", "null", "-"])
+            .stdin(std::process::Stdio::null())
+            .output()
+            .await
+            .map_err(|e| ExportError::FFmpegSpawn(e.to_string()))?;
+
+        tokio::fs::remove_file(&samples_path).await.ok();
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() {
+            return Err(ExportError::FFmpeg(format!(
+                "Failed to measure loudness: {stderr}"
+            )));
+        }
+
+        Self::parse(&stderr)
+    }
+
+    /// `loudnorm` prints its measurements as the last JSON object in FFmpeg's output
+    fn parse(output: &str) -> Result<Option<Self>, ExportError> {
+        let invalid = || ExportError::FFmpeg("loudnorm didn't report its measurements".into());
+
+        let start = output.rfind('{').ok_or_else(invalid)?;
+        let end = output[start..].find('}').ok_or_else(invalid)? + start;
+        let fields = output[start + 1..end]
+            .split(',')
+            .filter_map(|field| {
+                let (key, value) = field.split_once(':')?;
+                Some((key.trim().trim_matches('"'), value.trim().trim_matches('"')))
+            })
+            .collect::<Vec<_>>();
+
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(key, _)| *key == name)
+                .and_then(|(_, value)| value.parse::<f64>().ok())
+                .ok_or_else(invalid)
+        };
+
+        let loudness = Self {
+            integrated: field("input_i")?,
+            true_peak: field("input_tp")?,
+            range: field("input_lra")?,
+            threshold: field("input_thresh")?,
+            offset: field("target_offset")?,
+        };
+
+        let measured = [
+            loudness.integrated,
+            loudness.true_peak,
+            loudness.range,
+            loudness.threshold,
+            loudness.offset,
+        ];
+        Ok(measured.iter().all(|v| v.is_finite()).then_some(loudness))
+    }
+
+    /// The second `loudnorm` pass, resampled back to `sample_rate` as `loudnorm`
+    /// always outputs 192kHz
+    pub fn filter(&self, sample_rate: u32) -> String {
+        let measured = format!(
+            "measured_I={}:measured_TP={}:measured_LRA={}:measured_thresh={}",
+            self.integrated, self.true_peak, self.range, self.threshold
+        );
+
+        format!(
+            "{}:{measured}:offset={}:linear=true,aresample={sample_rate}",
+            targets(),
+            self.offset
+        )
+    }
+}
+
+fn targets() -> String {
+    format!("loudnorm=I={TARGET_LOUDNESS}:TP={TARGET_TRUE_PEAK}:LRA={TARGET_LOUDNESS_RANGE}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Captured from FFmpeg 7.1 measuring a speech recording
+    const OUTPUT: &str = r#"Input #0, f64le, from 'loudness.raw':
+  Duration: 00:00:12.48, bitrate: 3072 kb/s
+  Stream #0:0: Audio: pcm_f64le, 48000 Hz, mono, dbl, 3072 kb/s
+Stream mapping:
+  Stream #0:0 -> #0:0 (pcm_f64le (native) -> pcm_s16le (native))
+Output #0, null, to 'pipe:':
+  Metadata:
+    encoder         : Lavf61.7.100
+  Stream #0:0: Audio: pcm_s16le, 192000 Hz, mono, s16, 3072 kb/s
+      Metadata:
+        encoder         : Lavc61.19.100 pcm_s16le
+[Parsed_loudnorm_0 @ 0x600002d1c000] 
+{
+	"input_i" : "-27.61",
+	"input_tp" : "-4.47",
+	"input_lra" : "18.06",
+	"input_thresh" : "-39.20",
+	"output_i" : "-16.58",
+	"output_tp" : "-1.50",
+	"output_lra" : "14.78",
+	"output_thresh" : "-27.71",
+	"normalization_type" : "dynamic",
+	"target_offset" : "0.58"
+}
+[out#0/null @ 0x600002a18000] video:0KiB audio:4680KiB subtitle:0KiB other streams:0KiB global headers:0KiB muxing overhead: unknown
+"#;
+
+    #[test]
+    fn parses_loudnorm_measurements() {
+        let loudness = Loudness::parse(OUTPUT).unwrap().unwrap();
+
+        assert_eq!(loudness.integrated, -27.61);
+        assert_eq!(loudness.true_peak, -4.47);
+        assert_eq!(loudness.range, 18.06);
+        assert_eq!(loudness.threshold, -39.20);
+        assert_eq!(loudness.offset, 0.58);
+    }
+
+    #[test]
+    fn silent_audio_has_no_loudness() {
+        let output = OUTPUT
+            .replace(r#""-27.61""#, r#""-inf""#)
+            .replace(r#""-4.47""#, r#""-inf""#)
+            .replace(r#""0.58""#, r#""inf""#);
+
+        assert!(Loudness::parse(&output).unwrap().is_none());
+    }
+
+    #[test]
+    fn missing_measurements_are_an_error() {
+        let output = &OUTPUT[..OUTPUT.find("[Parsed_loudnorm_0").unwrap()];
+
+        assert!(Loudness::parse(output).is_err());
+    }
+}
@@ -18,7 +18,7 @@ pub struct FFmpegProcess {
 }
 
 impl FFmpegProcess {
-    pub fn spawn(mut command: Command) -> Self {
+    pub fn spawn(mut command: Command) -> std::io::Result<Self> {
         #[cfg(target_os = "windows")]
         {
             use std::os::windows::process::CommandExt;
@@ -30,27 +30,26 @@ impl FFmpegProcess {
             .stdin(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()
-            .unwrap_or_else(|e| {
-                println!("Failed to start FFmpeg: {}", e);
-                println!("Command: {:?}", command);
-                panic!("Failed to start FFmpeg");
-            });
-
-        let ffmpeg_stdin = cmd.stdin.take().unwrap_or_else(|| {
-            println!("Failed to capture FFmpeg stdin");
-            panic!("Failed to capture FFmpeg stdin");
-        });
-
-        let ffmpeg_stderr = cmd.stderr.take().unwrap_or_else(|| {
-            println!("Failed to capture FFmpeg stderr");
-            panic!("Failed to capture FFmpeg stderr");
-        });
-
-        Self {
+            .inspect_err(|e| {
+                tracing::error!("Failed to start FFmpeg: {}", e);
+                tracing::error!("Command: {:?}", command);
+            })?;
+
+        let ffmpeg_stdin = cmd
+            .stdin
+            .take()
+            .ok_or_else(|| std::io::Error::other("Failed to capture FFmpeg stdin"))?;
+
+        let ffmpeg_stderr = cmd
+            .stderr
+            .take()
+            .ok_or_else(|| std::io::Error::other("Failed to capture FFmpeg stderr"))?;
+
+        Ok(Self {
             ffmpeg_stdin,
             ffmpeg_stderr,
             cmd,
-        }
+        })
     }
 
     pub async fn write(&mut self, data: &[u8]) -> std::io::Result<()> {
@@ -60,7 +59,7 @@ impl FFmpegProcess {
     pub async fn stop(&mut self) {
         self.ffmpeg_stdin.write_all(b"q").await.ok();
         self.ffmpeg_stdin.flush().await.ok();
-        println!("Sent stop command to FFmpeg");
+        tracing::debug!("Sent stop command to FFmpeg");
     }
 
     pub async fn wait(&mut self) -> std::io::Result<std::process::ExitStatus> {
@@ -78,7 +77,7 @@ impl FFmpegProcess {
     }
 
     pub fn kill(&mut self) {
-        let _ = self.cmd.kill();
+        let _ = self.cmd.start_kill();
     }
 
     pub fn wait_with_timeout(
@@ -111,7 +110,7 @@ impl FFmpegProcess {
             .await
         {
             Ok(_) => {
-                println!("Read video frame of size: {}", buffer.len());
+                tracing::trace!("Read video frame of size: {}", buffer.len());
                 Ok(Some(buffer))
             }
             Err(ref e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(None),
@@ -153,7 +152,7 @@ impl FFmpegProcess {
                 Pid::from_raw(self.cmd.id().unwrap() as i32),
                 Signal::SIGSTOP,
             )?;
-            println!("Sent SIGSTOP to FFmpeg");
+            tracing::debug!("Sent SIGSTOP to FFmpeg");
         }
         Ok(())
     }
@@ -167,7 +166,7 @@ impl FFmpegProcess {
                 Pid::from_raw(self.cmd.id().unwrap() as i32),
                 Signal::SIGCONT,
             )?;
-            println!("Sent SIGCONT to FFmpeg");
+            tracing::debug!("Sent SIGCONT to FFmpeg");
         }
         Ok(())
     }
@@ -216,7 +215,7 @@ pub struct FFmpegRawVideoInput {
 
 impl ApplyFFmpegArgs for FFmpegRawVideoInput {
     fn apply_ffmpeg_args(&self, command: &mut Command) {
-        dbg!(&self);
+        tracing::debug!("{self:?}");
         let size = format!("{}x{}", self.width, self.height);
 
         command
@@ -249,7 +248,7 @@ pub struct FFmpegRawAudioInput {
 
 impl ApplyFFmpegArgs for FFmpegRawAudioInput {
     fn apply_ffmpeg_args(&self, command: &mut Command) {
-        dbg!(&self);
+        tracing::debug!("{self:?}");
         command
             .args(["-f", &self.sample_format])
             .args(["-ar", &self.sample_rate.to_string()])
@@ -278,7 +277,12 @@ impl Default for FFmpeg {
 
 impl FFmpeg {
     pub fn new() -> Self {
-        let mut command = Command::new(relative_command_path("ffmpeg").unwrap());
+        let path = match ffmpeg_binary() {
+            Ok((path, _)) => path,
+            // Spawning will report that the binary is missing
+            Err(_) => relative_command_path("ffmpeg").unwrap(),
+        };
+        let mut command = Command::new(path);
         command.arg("-hide_banner");
 
         Self {
@@ -330,11 +334,96 @@ impl FFmpeg {
         }
     }
 
-    pub fn start(self) -> FFmpegProcess {
+    pub fn start(self) -> std::io::Result<FFmpegProcess> {
         FFmpegProcess::spawn(self.command)
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FFmpegSource {
+    /// The sidecar shipped next to the app's executable
+    Bundled,
+    /// An install found on `PATH`, used when the sidecar is missing
+    System,
+}
+
+/// Finds the ffmpeg binary to run, preferring the bundled sidecar
+pub fn ffmpeg_binary() -> std::io::Result<(PathBuf, FFmpegSource)> {
+    let bundled = relative_command_path("ffmpeg")
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::NotFound, e.to_string()))?;
+
+    if bundled.is_file() {
+        return Ok((bundled, FFmpegSource::Bundled));
+    }
+
+    let file_name = bundled.file_name().unwrap_or_default();
+    std::env::var_os("PATH")
+        .and_then(|paths| {
+            std::env::split_paths(&paths)
+                .map(|dir| dir.join(file_name))
+                .find(|path| path.is_file())
+        })
+        .map(|path| (path, FFmpegSource::System))
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("ffmpeg not found at {} or on PATH", bundled.display()),
+            )
+        })
+}
+
+/// Runs the ffmpeg binary and returns its version line,
+/// erroring if it's missing or can't be executed
+pub async fn check_ffmpeg() -> std::io::Result<String> {
+    let output = ffmpeg_command(["-version"])?.output().await?;
+
+    if !output.status.success() {
+        return Err(std::io::Error::other(format!(
+            "ffmpeg exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string())
+}
+
+/// Names of the encoders the ffmpeg binary was built with
+pub async fn list_encoders() -> std::io::Result<Vec<String>> {
+    let output = ffmpeg_command(["-hide_banner", "-encoders"])?
+        .output()
+        .await?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    Ok(stdout
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with("------"))
+        .skip(1)
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .map(str::to_string)
+        .collect())
+}
+
+fn ffmpeg_command<const N: usize>(args: [&str; N]) -> std::io::Result<Command> {
+    let (path, _) = ffmpeg_binary()?;
+
+    let mut command = Command::new(path);
+    command.args(args).stdin(Stdio::null());
+
+    #[cfg(target_os = "windows")]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    Ok(command)
+}
+
 fn relative_command_path(command: impl AsRef<Path>) -> Result<PathBuf, tauri_plugin_shell::Error> {
     match platform::current_exe()?.parent() {
         #[cfg(windows)]
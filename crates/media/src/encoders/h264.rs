@@ -53,8 +53,13 @@ impl H264Encoder {
         let mut output_stream = output_ctx.add_stream(codec)?;
         output_stream.set_time_base(config.frame_rate.invert());
         output_stream.set_parameters(&video_encoder);
+        // Fragmented output keeps everything written so far playable if we never get to
+        // write the trailer (eg. the app crashes mid-recording)
+        let mut muxer_options = Dictionary::new();
+        muxer_options.set("movflags", "frag_keyframe+empty_moov+default_base_moof");
+
         // TODO: Move this to after pipeline start maybe?
-        output_ctx.write_header()?;
+        output_ctx.write_header_with(muxer_options)?;
 
         Ok(Self {
             tag,
@@ -101,7 +106,7 @@ impl PipelineSinkTask for H264Encoder {
         ready_signal: crate::pipeline::task::PipelineReadySignal,
         input: flume::Receiver<Self::Input>,
     ) {
-        println!("Starting {} video encoding thread", self.tag);
+        tracing::info!("Starting {} video encoding thread", self.tag);
         ready_signal.send(Ok(())).unwrap();
 
         while let Ok(frame) = input.recv() {
@@ -109,10 +114,10 @@ impl PipelineSinkTask for H264Encoder {
             self.process_frame();
         }
 
-        println!("Received last {} frame. Finishing up encoding.", self.tag);
+        tracing::info!("Received last {} frame. Finishing up encoding.", self.tag);
         self.finish();
 
-        println!("Shutting down {} video encoding thread", self.tag);
+        tracing::info!("Shutting down {} video encoding thread", self.tag);
     }
 }
 
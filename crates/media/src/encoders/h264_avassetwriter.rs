@@ -120,7 +120,7 @@ impl PipelineSinkTask for H264AVAssetWriterEncoder {
         ready_signal: crate::pipeline::task::PipelineReadySignal,
         input: flume::Receiver<Self::Input>,
     ) {
-        println!("Starting {} video encoding thread", self.tag);
+        tracing::info!("Starting {} video encoding thread", self.tag);
         ready_signal.send(Ok(())).ok();
 
         while let Ok(frame) = input.recv() {
@@ -128,10 +128,10 @@ impl PipelineSinkTask for H264AVAssetWriterEncoder {
             self.process_frame();
         }
 
-        println!("Received last {} frame. Finishing up encoding.", self.tag);
+        tracing::info!("Received last {} frame. Finishing up encoding.", self.tag);
         self.finish();
 
-        println!("Shutting down {} video encoding thread", self.tag);
+        tracing::info!("Shutting down {} video encoding thread", self.tag);
     }
 }
 
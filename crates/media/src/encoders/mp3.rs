@@ -106,17 +106,17 @@ impl PipelineSinkTask for MP3Encoder {
         ready_signal: crate::pipeline::task::PipelineReadySignal,
         input: flume::Receiver<Self::Input>,
     ) {
-        println!("Starting {} audio encoding thread", self.tag);
+        tracing::info!("Starting {} audio encoding thread", self.tag);
         ready_signal.send(Ok(())).unwrap();
 
         while let Ok(frame) = input.recv() {
             self.queue_frame(frame);
         }
 
-        println!("Received last {} sample. Finishing up encoding.", self.tag);
+        tracing::info!("Received last {} sample. Finishing up encoding.", self.tag);
         self.finish();
 
-        println!("Shutting down {} audio encoding thread", self.tag);
+        tracing::info!("Shutting down {} audio encoding thread", self.tag);
     }
 }
 
@@ -1,6 +1,7 @@
 use cap_project::TimelineConfiguration;
 use ffmpeg::{
     codec::{context, decoder},
+    filter,
     format::sample::{Sample, Type},
     software::resampling,
 };
@@ -25,6 +26,92 @@ impl AudioData {
     pub const FORMAT: Sample = Sample::F64(Type::Packed);
 
     pub fn from_file(path: PathBuf) -> Result<Self, MediaError> {
+        Self::decode(path, None)
+    }
+
+    /// Decodes each track and sums them together, resampling to the first track's format
+    pub fn mixed(paths: Vec<PathBuf>) -> Result<Self, MediaError> {
+        let mut paths = paths.into_iter();
+        let first = Self::from_file(paths.next().ok_or(MediaError::MissingMedia("audio"))?)?;
+
+        let to_samples = |buffer: &[u8]| {
+            buffer
+                .chunks_exact(8)
+                .map(|bytes| f64::from_ne_bytes(bytes.try_into().unwrap()))
+                .collect::<Vec<_>>()
+        };
+
+        let mut samples = to_samples(&first.buffer);
+
+        for path in paths {
+            let track = Self::decode(path, Some(first.info))?;
+            let track = to_samples(&track.buffer);
+
+            if track.len() > samples.len() {
+                samples.resize(track.len(), 0.0);
+            }
+
+            for (mixed, sample) in samples.iter_mut().zip(track) {
+                *mixed = (*mixed + sample).clamp(-1.0, 1.0);
+            }
+        }
+
+        Ok(Self {
+            buffer: Arc::new(samples.into_iter().flat_map(f64::to_ne_bytes).collect()),
+            info: first.info,
+        })
+    }
+
+    /// Lays out the audio of each recording segment along `timeline`, so it can be played
+    /// back without one. Sped up or slowed down sections are stretched to keep their pitch,
+    /// and are silent if sped up past `MAX_AUDIO_SPEED`.
+    pub fn retimed(
+        segments: &[Self],
+        timeline: &TimelineConfiguration,
+    ) -> Result<Self, MediaError> {
+        let info = segments
+            .first()
+            .ok_or(MediaError::MissingMedia("audio"))?
+            .info;
+        let sample_size = info.channels * info.sample_format.bytes();
+        let rate = f64::from(info.sample_rate);
+
+        let mut buffer = vec![];
+        for segment in &timeline.segments {
+            let data = &segments
+                .get(segment.recording_segment.unwrap_or(0) as usize)
+                .ok_or(MediaError::MissingMedia("audio"))?
+                .buffer;
+
+            let to_offset =
+                |secs: f64| ((secs.max(0.0) * rate) as usize * sample_size).min(data.len());
+            let source = &data[to_offset(segment.start)..to_offset(segment.end)];
+
+            if segment.timescale == 1.0 {
+                buffer.extend_from_slice(source);
+                continue;
+            }
+
+            let mut stretched = if segment.timescale <= MAX_AUDIO_SPEED {
+                time_stretch(source, info, segment.timescale)?
+            } else {
+                vec![]
+            };
+
+            // atempo's output length is approximate, keep it in sync with the video
+            let output_samples =
+                ((segment.end - segment.start) / segment.timescale * rate) as usize;
+            stretched.resize(output_samples * sample_size, 0);
+            buffer.extend(stretched);
+        }
+
+        Ok(Self {
+            buffer: Arc::new(buffer),
+            info,
+        })
+    }
+
+    fn decode(path: PathBuf, output_info: Option<AudioInfo>) -> Result<Self, MediaError> {
         let input_ctx = ffmpeg::format::input(&path)?;
         let input_stream = input_ctx
             .streams()
@@ -37,8 +124,10 @@ impl AudioData {
         decoder.set_packet_time_base(input_stream.time_base());
 
         let input_info = AudioInfo::from_decoder(&decoder)?;
-        let mut output_info = input_info;
-        output_info.sample_format = Self::FORMAT;
+        let output_info = output_info.unwrap_or(AudioInfo {
+            sample_format: Self::FORMAT,
+            ..input_info
+        });
 
         let resampler = AudioResampler::new(input_info, output_info)?;
 
@@ -54,6 +143,75 @@ impl AudioData {
     }
 }
 
+/// Faster audio is hard to follow and stretching it produces noticeable artifacts
+pub const MAX_AUDIO_SPEED: f64 = 4.0;
+
+/// Changes the duration of packed `samples` by `speed` without changing their pitch
+fn time_stretch(samples: &[u8], info: AudioInfo, speed: f64) -> Result<Vec<u8>, MediaError> {
+    const CHUNK_SAMPLES: usize = 4096;
+
+    let mut filter_graph = filter::Graph::new();
+
+    let input_args = format!(
+        "time_base=1/{}:sample_rate={}:sample_fmt={}:channel_layout=0x{:x}",
+        info.rate(),
+        info.rate(),
+        info.sample_format.name(),
+        info.channel_layout().bits(),
+    );
+    filter_graph.add(&filter::find("abuffer").unwrap(), "in", &input_args)?;
+    filter_graph.add(&filter::find("abuffersink").unwrap(), "out", "")?;
+
+    // Older ffmpeg versions only support factors between 0.5 and 2 per atempo filter
+    let mut tempos = vec![];
+    let mut remaining = speed;
+    while remaining > 2.0 {
+        tempos.push("atempo=2.0".to_string());
+        remaining /= 2.0;
+    }
+    while remaining < 0.5 {
+        tempos.push("atempo=0.5".to_string());
+        remaining /= 0.5;
+    }
+    tempos.push(format!("atempo={remaining}"));
+
+    filter_graph
+        .output("in", 0)?
+        .input("out", 0)?
+        .parse(&tempos.join(","))?;
+    filter_graph.validate()?;
+
+    let sample_size = info.channels * info.sample_format.bytes();
+    let mut output = vec![];
+
+    let mut drain = |filter_graph: &mut filter::Graph| {
+        let mut frame = FFAudio::empty();
+        while filter_graph
+            .get("out")
+            .unwrap()
+            .sink()
+            .frame(&mut frame)
+            .is_ok()
+        {
+            output.extend_from_slice(&frame.data(0)[..frame.samples() * sample_size]);
+        }
+    };
+
+    for (i, chunk) in samples.chunks(CHUNK_SAMPLES * sample_size).enumerate() {
+        let mut frame = info.empty_frame(chunk.len() / sample_size);
+        frame.set_pts(Some((i * CHUNK_SAMPLES) as i64));
+        frame.data_mut(0)[..chunk.len()].copy_from_slice(chunk);
+
+        filter_graph.get("in").unwrap().source().add(&frame)?;
+        drain(&mut filter_graph);
+    }
+
+    filter_graph.get("in").unwrap().source().flush()?;
+    drain(&mut filter_graph);
+
+    Ok(output)
+}
+
 pub struct AudioFrameBuffer {
     data: Vec<AudioData>,
     cursor: (usize, usize),
@@ -185,8 +343,8 @@ impl<T: FromSampleBytes> AudioPlaybackBuffer<T> {
     const PROCESSING_SAMPLES_COUNT: u32 = 1024;
 
     pub fn new(data: Vec<AudioData>, output_info: AudioInfo) -> Self {
-        println!("Input info: {:?}", data[0].info);
-        println!("Output info: {:?}", output_info);
+        tracing::info!("Input info: {:?}", data[0].info);
+        tracing::info!("Output info: {:?}", output_info);
 
         let resampler = AudioResampler::new(data[0].info, output_info).unwrap();
 
@@ -210,7 +368,7 @@ impl<T: FromSampleBytes> AudioPlaybackBuffer<T> {
         self.resampled_buffer.clear();
         self.frame_buffer.set_playhead(playhead, maybe_timeline);
 
-        println!("Successful seek to sample {:?}", self.frame_buffer.cursor);
+        tracing::info!("Successful seek to sample {:?}", self.frame_buffer.cursor);
     }
 
     pub fn buffer_reaching_limit(&self) -> bool {
@@ -288,7 +446,7 @@ impl AudioFileReader {
         while self.decoder.receive_frame(&mut decoded_frame).is_ok() {
             let timestamp = decoded_frame.timestamp();
             if self.first {
-                println!(
+                tracing::info!(
                     "First timestamp: {timestamp:?}, time base {}",
                     self.decoder.time_base()
                 );
@@ -42,6 +42,17 @@ pub type AudioInputSamplesReceiver = Receiver<AudioInputSamples>;
 
 pub type AudioInputDeviceMap = IndexMap<String, (Device, SupportedStreamConfig)>;
 
+/// Virtual devices that route system output back in as an input. cpal can only capture
+/// input devices, so recording system audio requires one of these to be installed
+/// (eg. BlackHole on macOS, Stereo Mix or VB-Cable on Windows).
+const LOOPBACK_DEVICE_NAMES: &[&str] = &[
+    "BlackHole",
+    "Soundflower",
+    "Loopback Audio",
+    "Stereo Mix",
+    "CABLE Output",
+];
+
 #[derive(Clone)]
 pub struct AudioInputFeed {
     control_tx: Sender<AudioInputControl>,
@@ -58,7 +69,7 @@ impl AudioInputFeed {
         let (device, config) = Self::list_devices()
             .swap_remove_entry(selected_input)
             .map(|(device_name, (device, config))| {
-                println!("Using audio device: {}", device_name);
+                tracing::info!("Using audio device: {}", device_name);
                 (device, config)
             })
             .unwrap();
@@ -82,7 +93,7 @@ impl AudioInputFeed {
         let get_usable_device = |device: Device| {
             device
                 .supported_input_configs()
-                .map_err(|error| eprintln!("Error: {error}"))
+                .map_err(|error| tracing::error!("Error: {error}"))
                 .ok()
                 .and_then(|configs| {
                     let mut configs = configs.collect::<Vec<_>>();
@@ -120,14 +131,22 @@ impl AudioInputFeed {
                 }
             }
             Err(error) => {
-                eprintln!("Could not access audio input devices");
-                eprintln!("{error}");
+                tracing::error!("Could not access audio input devices");
+                tracing::error!("{error}");
             }
         }
 
         device_map
     }
 
+    pub fn loopback_device_name() -> Option<String> {
+        Self::list_devices().into_keys().find(|name| {
+            LOOPBACK_DEVICE_NAMES
+                .iter()
+                .any(|loopback| name.contains(loopback))
+        })
+    }
+
     pub async fn switch_input(&mut self, name: &str) -> Result<(), MediaError> {
         let (tx, rx) = flume::bounded(1);
 
@@ -135,16 +154,16 @@ impl AudioInputFeed {
             .send_async(AudioInputControl::Switch(name.to_string(), tx))
             .await
             .map_err(|error| {
-                eprintln!("Error while switching audio input: {error}");
+                tracing::error!("Error while switching audio input: {error}");
                 MediaError::TaskLaunch("Failed to switch audio input".into())
             })?;
 
         let config = rx.recv_async().await.map_err(|error| {
-            eprintln!("Error while switching audio input: {error}");
+            tracing::error!("Error while switching audio input: {error}");
             MediaError::TaskLaunch("Failed to switch audio input".into())
         })??;
 
-        dbg!(&config);
+        tracing::debug!("Switched audio input config: {config:?}");
 
         self.audio_info = AudioInfo::from_stream_config(&config)?;
 
@@ -156,7 +175,7 @@ impl AudioInputFeed {
             .send_async(AudioInputControl::AttachSender(sender))
             .await
             .map_err(|error| {
-                eprintln!("Error while attaching audio input sender: {error}");
+                tracing::error!("Error while attaching audio input sender: {error}");
                 MediaError::TaskLaunch("Failed to attach audio input sender".into())
             })?;
 
@@ -201,7 +220,7 @@ fn start_capturing(
                 None,
             )
             .map_err(|error| {
-                eprintln!("Error while preparing audio capture: {error}");
+                tracing::error!("Error while preparing audio capture: {error}");
                 MediaError::TaskLaunch("Failed to start audio capture".into())
             });
 
@@ -212,7 +231,7 @@ fn start_capturing(
                     drop(stream);
                     let Some(items) = AudioInputFeed::list_devices().swap_remove_entry(&name).map(
                         |(device_name, (device, config))| {
-                            println!("Using audio device: {}", device_name);
+                            tracing::info!("Using audio device: {}", device_name);
                             (device, config)
                         },
                     ) else {
@@ -235,7 +254,7 @@ fn start_capturing(
                     senders.push(sender);
                 }
                 Err(flume::TryRecvError::Disconnected) => {
-                    println!("Control receiver is unreachable! Shutting down");
+                    tracing::info!("Control receiver is unreachable! Shutting down");
                     return;
                 }
                 Err(flume::TryRecvError::Empty) => {
@@ -2,9 +2,11 @@ use cap_gpu_converters::{NV12Input, NV12ToRGBA, UYVYToRGBA};
 use ffmpeg::software::scaling;
 use flume::{Receiver, Sender, TryRecvError};
 use nokhwa::{utils::*, Camera};
+use serde::Serialize;
+use specta::Type;
 use std::{
     thread::{self, JoinHandle},
-    time::Instant,
+    time::{Duration, Instant},
 };
 use tracing::{error, info, warn};
 
@@ -15,8 +17,11 @@ use crate::{
 
 type CameraSwitchResult = Result<(CameraInfo, VideoInfo), MediaError>;
 
+/// How long a camera can fail to produce frames before it's considered disconnected
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
 enum CameraControl {
-    Switch(String, Sender<CameraSwitchResult>),
+    Switch(String, Option<(u32, u32)>, Sender<CameraSwitchResult>),
     AttachRawConsumer(Sender<RawCameraFrame>),
     Shutdown,
 }
@@ -34,6 +39,15 @@ pub struct CameraConnection {
 }
 
 impl CameraConnection {
+    /// A connection to a camera that disconnects as soon as it's attached to
+    #[cfg(test)]
+    pub(crate) fn disconnected() -> Self {
+        let (control, control_rx) = flume::unbounded();
+        std::thread::spawn(move || while control_rx.recv().is_ok() {});
+
+        Self { control }
+    }
+
     pub fn attach(&self) -> Receiver<RawCameraFrame> {
         let (sender, receiver) = flume::bounded(60);
         self.control
@@ -44,11 +58,27 @@ impl CameraConnection {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CameraFormatInfo {
+    pub width: u32,
+    pub height: u32,
+    pub frame_rate: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct CameraDeviceInfo {
+    pub label: String,
+    pub formats: Vec<CameraFormatInfo>,
+}
+
 // #[derive(Clone)]
 pub struct CameraFeed {
     camera_info: CameraInfo,
+    resolution: Option<(u32, u32)>,
     video_info: VideoInfo,
     control: Sender<CameraControl>,
+    disconnected: Receiver<String>,
     // join_handle: JoinHandle<()>,
 }
 
@@ -57,22 +87,33 @@ impl CameraFeed {
         flume::bounded(60)
     }
 
+    /// Opens the camera, requesting `resolution` from the device if set
     pub async fn init(
         selected_camera: &str,
+        resolution: Option<(u32, u32)>,
         rgba_data: Sender<Vec<u8>>,
     ) -> Result<CameraFeed, MediaError> {
-        println!("Selected camera: {:?}", selected_camera);
+        tracing::info!("Selected camera: {:?}", selected_camera);
 
         let camera_info = find_camera(selected_camera)?;
         let (control, control_receiver) = flume::bounded(1);
-
-        let (video_info, join_handle) =
-            start_capturing(camera_info.clone(), control_receiver, rgba_data).await?;
+        let (disconnected_tx, disconnected) = flume::bounded(1);
+
+        let (video_info, join_handle) = start_capturing(
+            camera_info.clone(),
+            resolution,
+            control_receiver,
+            rgba_data,
+            disconnected_tx,
+        )
+        .await?;
 
         let camera_feed = Self {
             camera_info,
+            resolution,
             video_info,
             control,
+            disconnected,
             // join_handle,
         };
 
@@ -86,24 +127,68 @@ impl CameraFeed {
                 .map(|i| i.human_name().to_string())
                 .collect::<Vec<String>>(),
             Err(e) => {
-                eprintln!("Failed to query cameras: {}", e);
+                tracing::error!("Failed to query cameras: {}", e);
                 Vec::new()
             }
         }
     }
 
+    /// Like `list_cameras`, but also opens each device to query the formats it supports
+    pub fn list_cameras_with_formats() -> Vec<CameraDeviceInfo> {
+        let cameras = match nokhwa::query(ApiBackend::Auto) {
+            Ok(cameras) => cameras,
+            Err(e) => {
+                tracing::error!("Failed to query cameras: {}", e);
+                return Vec::new();
+            }
+        };
+
+        cameras
+            .into_iter()
+            .map(|info| {
+                let formats = create_camera(&info, None)
+                    .and_then(|mut camera| supported_formats(&mut camera))
+                    .unwrap_or_else(|e| {
+                        tracing::error!("Failed to query formats for {}: {}", info.human_name(), e);
+                        Vec::new()
+                    });
+
+                CameraDeviceInfo {
+                    label: info.human_name(),
+                    formats,
+                }
+            })
+            .collect()
+    }
+
+    /// Resolutions the camera can capture at, largest first
+    pub fn list_resolutions(camera_name: &str) -> Result<Vec<(u32, u32)>, MediaError> {
+        let info = find_camera(camera_name)?;
+        let mut camera = create_camera(&info, None)?;
+
+        supported_resolutions(&mut camera)
+    }
+
     pub fn video_info(&self) -> VideoInfo {
         self.video_info
     }
 
-    pub async fn switch_cameras(&mut self, camera_name: &str) -> Result<(), MediaError> {
+    pub async fn switch_cameras(
+        &mut self,
+        camera_name: &str,
+        resolution: Option<(u32, u32)>,
+    ) -> Result<(), MediaError> {
         let current_camera_name = self.camera_info.human_name();
-        if camera_name != &current_camera_name {
+        if camera_name != &current_camera_name || resolution != self.resolution {
             let (result_tx, result_rx) = flume::bounded::<CameraSwitchResult>(1);
 
             let _ = self
                 .control
-                .send_async(CameraControl::Switch(camera_name.to_string(), result_tx))
+                .send_async(CameraControl::Switch(
+                    camera_name.to_string(),
+                    resolution,
+                    result_tx,
+                ))
                 .await;
 
             let (camera_info, video_info) = result_rx
@@ -112,12 +197,19 @@ impl CameraFeed {
                 .map_err(|_| MediaError::Any("Failed to prepare camera feed"))??;
 
             self.camera_info = camera_info;
+            self.resolution = resolution;
             self.video_info = video_info;
         }
 
         Ok(())
     }
 
+    /// Receives the name of the camera if it stops producing frames (eg. it was unplugged),
+    /// after which the feed shuts down
+    pub fn disconnected(&self) -> Receiver<String> {
+        self.disconnected.clone()
+    }
+
     pub fn create_connection(&self) -> CameraConnection {
         CameraConnection {
             control: self.control.clone(),
@@ -140,17 +232,16 @@ fn find_camera(selected_camera: &str) -> Result<CameraInfo, MediaError> {
         .ok_or(MediaError::DeviceUnreachable(selected_camera.to_string()))
 }
 
-fn create_camera(info: &CameraInfo) -> Result<Camera, MediaError> {
-    dbg!(info);
+fn create_camera(info: &CameraInfo, resolution: Option<(u32, u32)>) -> Result<Camera, MediaError> {
+    tracing::debug!("Creating camera: {info:?}");
+
+    let (width_x, height_y) = resolution.unwrap_or((1920, 1080));
 
     // TODO: Make selected format more flexible
     // let format = RequestedFormat::new::<RgbAFormat>(RequestedFormatType::AbsoluteHighestResolution);
     let format = RequestedFormat::with_formats(
         RequestedFormatType::ClosestIgnoringFormat {
-            resolution: Resolution {
-                width_x: 1920,
-                height_y: 1080,
-            },
+            resolution: Resolution { width_x, height_y },
             frame_rate: 30,
         },
         &[FrameFormat::NV12],
@@ -162,30 +253,93 @@ fn create_camera(info: &CameraInfo) -> Result<Camera, MediaError> {
     {
         let device = nokhwa_bindings_macos::AVCaptureDevice::new(&index).unwrap();
         let formats = device.supported_formats()?;
-        dbg!(formats);
+        tracing::debug!("Supported camera formats: {formats:?}");
     }
 
-    Ok(Camera::new(index, format)?)
+    let mut camera = Camera::new(index, format)?;
+
+    // The closest format is used otherwise, so check the device actually supports it
+    if let Some((width, height)) = resolution {
+        let supported = supported_resolutions(&mut camera)?;
+
+        if !supported.contains(&(width, height)) {
+            let supported = supported
+                .iter()
+                .map(|(width, height)| format!("{width}x{height}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            return Err(MediaError::UnsupportedResolution(format!(
+                "{width}x{height} isn't supported by {}, available resolutions are {supported}",
+                info.human_name()
+            )));
+        }
+    }
+
+    Ok(camera)
+}
+
+/// Resolution and frame rate combinations, largest first.
+/// Pixel formats are converted on capture so they aren't distinguished.
+fn supported_formats(camera: &mut Camera) -> Result<Vec<CameraFormatInfo>, MediaError> {
+    let mut formats = camera
+        .compatible_camera_formats()?
+        .into_iter()
+        .map(|format| CameraFormatInfo {
+            width: format.width(),
+            height: format.height(),
+            frame_rate: format.frame_rate(),
+        })
+        .collect::<Vec<_>>();
+
+    formats.sort_unstable_by(|a, b| {
+        (b.width, b.height, b.frame_rate).cmp(&(a.width, a.height, a.frame_rate))
+    });
+    formats.dedup();
+
+    Ok(formats)
 }
 
-fn find_and_create_camera(selected_camera: &String) -> Result<(CameraInfo, Camera), MediaError> {
+fn supported_resolutions(camera: &mut Camera) -> Result<Vec<(u32, u32)>, MediaError> {
+    let mut resolutions = supported_formats(camera)?
+        .into_iter()
+        .map(|format| (format.width, format.height))
+        .collect::<Vec<_>>();
+    resolutions.dedup();
+
+    Ok(resolutions)
+}
+
+fn find_and_create_camera(
+    selected_camera: &String,
+    resolution: Option<(u32, u32)>,
+) -> Result<(CameraInfo, Camera), MediaError> {
     let info = find_camera(selected_camera)?;
-    let camera = create_camera(&info)?;
+    let camera = create_camera(&info, resolution)?;
 
-    dbg!(camera.camera_format());
+    tracing::debug!("Camera format: {:?}", camera.camera_format());
 
     Ok((info, camera))
 }
 
 async fn start_capturing(
     camera_info: CameraInfo,
+    resolution: Option<(u32, u32)>,
     control: Receiver<CameraControl>,
     rgba_data: Sender<Vec<u8>>,
+    disconnected: Sender<String>,
 ) -> Result<(VideoInfo, JoinHandle<()>), MediaError> {
     let (ready_tx, ready_rx) = flume::bounded::<Result<VideoInfo, MediaError>>(1);
 
     let join_handle = thread::spawn(move || {
-        run_camera_feed(camera_info, control, rgba_data, ready_tx);
+        run_camera_feed(
+            camera_info,
+            resolution,
+            control,
+            rgba_data,
+            ready_tx,
+            disconnected,
+        );
     });
 
     let video_info = ready_rx
@@ -199,13 +353,15 @@ async fn start_capturing(
 // #[tracing::instrument(skip_all)]
 fn run_camera_feed(
     camera_info: CameraInfo,
+    resolution: Option<(u32, u32)>,
     control: Receiver<CameraControl>,
     rgba_data: Sender<Vec<u8>>,
     ready_signal: Sender<Result<VideoInfo, MediaError>>,
+    disconnected: Sender<String>,
 ) {
     let mut maybe_raw_data: Option<Sender<RawCameraFrame>> = None;
 
-    let mut camera = match create_camera(&camera_info) {
+    let mut camera = match create_camera(&camera_info, resolution) {
         Ok(cam) => cam,
         Err(error) => {
             error!("Failed to create camera: {:?}", error);
@@ -224,33 +380,34 @@ fn run_camera_feed(
 
     let mut converter = None;
     let mut ready_signal = Some(ready_signal);
+    let mut last_frame_at = Instant::now();
 
     loop {
         match control.try_recv() {
             Err(TryRecvError::Disconnected) => {
-                println!("Control receiver is unreachable! Shutting down");
+                tracing::info!("Control receiver is unreachable! Shutting down");
                 break;
             }
             Err(TryRecvError::Empty) => {
                 // No signal received, nothing to do
             }
             Ok(CameraControl::Shutdown) => {
-                println!("Shutdown request received.");
+                tracing::info!("Shutdown request received.");
                 break;
             }
             Ok(CameraControl::AttachRawConsumer(rgba_sender)) => {
-                eprintln!("Attaching to a new pipeline consumer. Any previously attached consumer will be dropped");
+                tracing::warn!("Attaching to a new pipeline consumer. Any previously attached consumer will be dropped");
                 maybe_raw_data = Some(rgba_sender);
             }
-            Ok(CameraControl::Switch(camera_name, switch_result)) => {
+            Ok(CameraControl::Switch(camera_name, resolution, switch_result)) => {
                 if maybe_raw_data.is_some() {
                     switch_result.send(Err(MediaError::Any("Cannot switch cameras while the feed is attached to a running pipeline"))).unwrap();
                 } else {
-                    println!("Switching camera to {camera_name}");
+                    tracing::info!("Switching camera to {camera_name}");
 
-                    match find_and_create_camera(&camera_name) {
+                    match find_and_create_camera(&camera_name, resolution) {
                         Err(error) => {
-                            eprintln!("{error}");
+                            tracing::error!("{error}");
                             switch_result.send(Err(error)).unwrap();
                         }
                         Ok((new_info, mut new_camera)) => {
@@ -258,15 +415,16 @@ fn run_camera_feed(
                             let new_converter = FrameConverter::build(new_format);
 
                             if new_camera.open_stream().is_ok() {
-                                println!("Now using {camera_name}");
+                                tracing::info!("Now using {camera_name}");
                                 let _ = camera.stop_stream();
                                 switch_result
                                     .send(Ok((new_info, new_converter.video_info)))
                                     .unwrap();
                                 camera = new_camera;
-                                // converter = new_converter;
+                                converter = Some(new_converter);
+                                last_frame_at = Instant::now();
                             } else {
-                                eprintln!(
+                                tracing::error!(
                                     "Unable to switch to {camera_name}. Still using previous camera"
                                 );
                                 switch_result
@@ -282,6 +440,8 @@ fn run_camera_feed(
         // Actual data capture
         match camera.frame() {
             Ok(raw_buffer) => {
+                last_frame_at = Instant::now();
+
                 let raw_buffer = if let FrameFormat::MJPEG = raw_buffer.source_frame_format() {
                     let rgba_buffer = raw_buffer
                         .decode_image::<nokhwa::pixel_format::RgbAFormat>()
@@ -305,7 +465,11 @@ fn run_camera_feed(
                     converter
                 });
 
-                if converter.format != raw_buffer.source_frame_format() {
+                let frame_resolution = raw_buffer.resolution();
+                if converter.format != raw_buffer.source_frame_format()
+                    || converter.video_info.width != frame_resolution.width()
+                    || converter.video_info.height != frame_resolution.height()
+                {
                     let mut format = camera.camera_format();
                     format.set_format(raw_buffer.source_frame_format());
                     *converter = FrameConverter::build(format);
@@ -318,7 +482,7 @@ fn run_camera_feed(
 
                 if dropping_send(&rgba_data, rgba_frame).is_err() {
                     // TODO: Also allow changing the connection?
-                    eprintln!("Camera preview has been disconnected. Shutting down feed");
+                    tracing::warn!("Camera preview has been disconnected. Shutting down feed");
                     break;
                 }
 
@@ -328,11 +492,23 @@ fn run_camera_feed(
                         captured_at,
                     };
                     if dropping_send(raw_data, frame).is_err() {
-                        eprintln!("Raw data consumer has been disconnected.");
+                        tracing::warn!("Raw data consumer has been disconnected.");
                         maybe_raw_data = None;
                     }
                 }
             }
+            Err(error) if last_frame_at.elapsed() > DISCONNECT_TIMEOUT => {
+                let camera_name = camera.info().human_name();
+                error!("Camera {camera_name} stopped producing frames: {:?}", error);
+
+                if let Some(ready_signal) = ready_signal.take() {
+                    ready_signal
+                        .send(Err(MediaError::DeviceUnreachable(camera_name.clone())))
+                        .ok();
+                }
+                disconnected.try_send(camera_name).ok();
+                break;
+            }
             Err(error) => {
                 warn!("Failed to capture frame: {:?}", error);
                 // Optionally, add a small delay to avoid busy-waiting
@@ -343,7 +519,7 @@ fn run_camera_feed(
     }
 
     let _ = camera.stop_stream();
-    println!("Closed {} stream", camera.info().human_name());
+    tracing::info!("Closed {} stream", camera.info().human_name());
 }
 
 struct FrameConverter {
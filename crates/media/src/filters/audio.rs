@@ -96,7 +96,7 @@ impl PipelinePipeTask for AudioFilter {
         input: Receiver<Self::Input>,
         output: Sender<Self::Output>,
     ) {
-        println!("Starting {} audio filtering thread", self.tag);
+        tracing::info!("Starting {} audio filtering thread", self.tag);
         ready_signal.send(Ok(())).unwrap();
 
         while let Ok(raw_frame) = input.recv() {
@@ -104,12 +104,12 @@ impl PipelinePipeTask for AudioFilter {
             self.process_frame(&output);
         }
 
-        println!(
+        tracing::info!(
             "Received last raw {} sample. Finishing up filtering.",
             self.tag
         );
         self.finish(&output);
 
-        println!("Shutting down {} audio filtering thread", self.tag);
+        tracing::info!("Shutting down {} audio filtering thread", self.tag);
     }
 }
@@ -29,6 +29,10 @@ impl<T> PipelineBuilder<T> {
         }
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
     pub fn source<O: Send + 'static, C: CloneFrom<T> + Send + 'static>(
         mut self,
         name: impl Into<String>,
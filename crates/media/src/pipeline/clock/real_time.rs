@@ -112,7 +112,7 @@ impl<T: LocalTimestamp> RealTimeClock<T> {
             if self.local_start_time.is_none()
                 || self.local_start_time.unwrap() < *global_start_time
             {
-                println!("Just resumed, resetting local state");
+                tracing::info!("Just resumed, resetting local state");
                 self.local_start_time = Some(now);
                 self.first_local_timestamp = Some(local.clone());
             }
@@ -30,7 +30,7 @@ impl PipelineControlSignal {
                 // Only peek for a new signal, else relinquish control to the caller
                 match self.receiver.try_recv() {
                     Ok(control) => {
-                        println!("Received new signal: {control:?}");
+                        tracing::info!("Received new signal: {control:?}");
                         self.last_value = Some(control)
                     }
                     Err(TryRecvError::Empty) => {}
@@ -42,7 +42,7 @@ impl PipelineControlSignal {
             _ => {
                 // For all else, block until a signal is sent.
                 // TODO: Maybe also spin down until the signal is different from the last value we have?
-                println!("Waiting for play signal...");
+                tracing::info!("Waiting for play signal...");
                 self.last_value = self.receiver.recv().ok();
 
                 self.last_value
@@ -54,7 +54,7 @@ impl PipelineControlSignal {
 /// An extremely naive broadcast channel. Sends values synchronously to all receivers,
 /// might block if one receiver takes too long to receive value.
 #[derive(Debug, Default)]
-pub(super) struct ControlBroadcast {
+pub(crate) struct ControlBroadcast {
     listeners: IndexMap<String, Sender<Control>>,
 }
 
@@ -72,13 +72,13 @@ impl ControlBroadcast {
         let mut any_dropped = false;
 
         if self.listeners.is_empty() {
-            eprintln!("Attempting to broadcast value without any listeners");
+            tracing::error!("Attempting to broadcast value without any listeners");
         }
 
         for (name, listener) in self.listeners.iter() {
-            println!("Sending signal {value:?} to {name}");
+            tracing::info!("Sending signal {value:?} to {name}");
             if let Err(_) = listener.send_async(value).await {
-                eprintln!("{name} is unreachable!");
+                tracing::error!("{name} is unreachable!");
                 any_dropped = true;
             }
         }
@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+
+use super::{Bounds, Window};
+
+// Window enumeration and focus aren't available through a common API on Linux,
+// so these fall back to reporting no windows and whole-display capture.
+
+pub fn get_on_screen_windows() -> Vec<Window> {
+    vec![]
+}
+
+#[inline]
+pub fn bring_window_to_focus(_window_id: u32) {}
+
+pub fn display_names() -> HashMap<u32, String> {
+    HashMap::new()
+}
+
+pub fn monitor_bounds(_id: u32) -> Bounds {
+    Bounds::default()
+}
+
+pub fn display_scale_factor(_id: u32) -> Option<f64> {
+    None
+}
+
+pub fn display_color_space(_id: u32) -> Option<String> {
+    None
+}
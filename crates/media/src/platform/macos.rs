@@ -151,7 +151,7 @@ pub fn bring_window_to_focus(window_id: u32) {
     use std::process::Command;
     use tempfile::NamedTempFile;
 
-    println!("Attempting to bring window {} to focus", window_id);
+    tracing::info!("Attempting to bring window {} to focus", window_id);
 
     // Get the window information associated with the window id
     let windows = get_on_screen_windows();
@@ -236,21 +236,54 @@ pub fn bring_window_to_focus(window_id: u32) {
         match output {
             Ok(output) => {
                 if output.status.success() {
-                    println!("Successfully executed AppleScript");
+                    tracing::info!("Successfully executed AppleScript");
                 } else {
                     let error_message = String::from_utf8_lossy(&output.stderr);
-                    eprintln!("AppleScript execution failed: {}", error_message);
+                    tracing::error!("AppleScript execution failed: {}", error_message);
                 }
             }
-            Err(e) => eprintln!("Failed to execute AppleScript: {}", e),
+            Err(e) => tracing::error!("Failed to execute AppleScript: {}", e),
         }
 
-        println!("Finished attempt to bring window {} to focus", window_id);
+        tracing::info!("Finished attempt to bring window {} to focus", window_id);
     } else {
-        eprintln!("Window with id {} not found", window_id);
+        tracing::warn!("Window with id {} not found", window_id);
     }
 }
 
+/// The bundle identifier of the application running as `process_id`, eg. `com.apple.Safari`
+pub fn bundle_identifier(process_id: u32) -> Option<String> {
+    use cocoa::base::nil;
+    use cocoa::foundation::NSString;
+    use objc::{msg_send, *};
+    use std::ffi::CStr;
+
+    unsafe {
+        let application: id = msg_send![
+            class!(NSRunningApplication),
+            runningApplicationWithProcessIdentifier: process_id as i32
+        ];
+        if application == nil {
+            return None;
+        }
+
+        let bundle_id: id = msg_send![application, bundleIdentifier];
+        if bundle_id == nil {
+            return None;
+        }
+
+        Some(
+            CStr::from_ptr(NSString::UTF8String(bundle_id))
+                .to_string_lossy()
+                .to_string(),
+        )
+    }
+}
+
+pub fn main_display_id() -> u32 {
+    core_graphics::display::CGDisplay::main().id
+}
+
 pub fn display_names() -> HashMap<u32, String> {
     use cocoa::appkit::NSScreen;
     use cocoa::base::nil;
@@ -288,44 +321,82 @@ pub fn display_names() -> HashMap<u32, String> {
     }
 }
 
-pub fn monitor_bounds(id: u32) -> Bounds {
+/// Finds the `NSScreen` of a display
+unsafe fn ns_screen(id: u32) -> Option<id> {
     use cocoa::appkit::NSScreen;
     use cocoa::base::nil;
     use cocoa::foundation::{NSArray, NSDictionary, NSString};
 
+    let screens = NSScreen::screens(nil);
+    let screen_count = NSArray::count(screens);
+
+    for i in 0..screen_count {
+        let screen: *mut objc::runtime::Object = screens.objectAtIndex(i);
+
+        let device_description = NSScreen::deviceDescription(screen);
+        let num = NSDictionary::valueForKey_(
+            device_description,
+            NSString::alloc(nil).init_str("NSScreenNumber"),
+        ) as id;
+        let num: *const objc2_foundation::NSNumber = num.cast();
+        let num = { &*num };
+
+        if num.as_u32() == id {
+            return Some(screen);
+        }
+    }
+
+    None
+}
+
+pub fn monitor_bounds(id: u32) -> Bounds {
+    use cocoa::appkit::NSScreen;
+
     unsafe {
-        let screens = NSScreen::screens(nil);
-        let screen_count = NSArray::count(screens);
+        let Some(screen) = ns_screen(id) else {
+            return Bounds::default();
+        };
 
-        for i in 0..screen_count {
-            let screen: *mut objc::runtime::Object = screens.objectAtIndex(i);
+        let frame = NSScreen::frame(screen);
 
-            let device_description = NSScreen::deviceDescription(screen);
-            let num = NSDictionary::valueForKey_(
-                device_description,
-                NSString::alloc(nil).init_str("NSScreenNumber"),
-            ) as id;
-            let num: *const objc2_foundation::NSNumber = num.cast();
-            let num = { &*num };
-            let num = num.as_u32();
+        Bounds {
+            x: frame.origin.x,
+            y: frame.origin.y,
+            width: frame.size.width,
+            height: frame.size.height,
+        }
+    }
+}
 
-            if num == id {
-                let frame = NSScreen::frame(screen);
+pub fn display_scale_factor(id: u32) -> Option<f64> {
+    use cocoa::appkit::NSScreen;
 
-                return Bounds {
-                    x: frame.origin.x,
-                    y: frame.origin.y,
-                    width: frame.size.width,
-                    height: frame.size.height,
-                };
-            }
+    unsafe { ns_screen(id).map(|screen| NSScreen::backingScaleFactor(screen)) }
+}
+
+pub fn display_color_space(id: u32) -> Option<String> {
+    use cocoa::base::nil;
+    use cocoa::foundation::NSString;
+    use objc::{msg_send, *};
+    use std::ffi::CStr;
+
+    unsafe {
+        let screen = ns_screen(id)?;
+
+        let color_space: id = msg_send![screen, colorSpace];
+        if color_space == nil {
+            return None;
         }
 
-        Bounds {
-            x: 0.0,
-            y: 0.0,
-            width: 0.0,
-            height: 0.0,
+        let name: id = msg_send![color_space, localizedName];
+        if name == nil {
+            return None;
         }
+
+        Some(
+            CStr::from_ptr(NSString::UTF8String(name))
+                .to_string_lossy()
+                .to_string(),
+        )
     }
 }
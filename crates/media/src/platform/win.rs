@@ -10,16 +10,16 @@ use windows::Win32::Devices::Display::{
     DisplayConfigGetDeviceInfo, DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
     DISPLAYCONFIG_DEVICE_INFO_HEADER, DISPLAYCONFIG_SOURCE_DEVICE_NAME,
 };
-use windows::Win32::Foundation::{CloseHandle, BOOL, FALSE, HWND, LPARAM, RECT, TRUE};
+use windows::Win32::Foundation::{CloseHandle, BOOL, FALSE, HWND, LPARAM, POINT, RECT, TRUE};
 use windows::Win32::Graphics::Dwm::{DwmGetWindowAttribute, DWMWA_CLOAKED};
 use windows::Win32::Graphics::Gdi::{
-    EnumDisplayDevicesW, EnumDisplayMonitors, GetMonitorInfoW, DISPLAY_DEVICEW, HDC, HMONITOR,
-    MONITORINFOEXW,
+    EnumDisplayDevicesW, EnumDisplayMonitors, GetMonitorInfoW, MonitorFromPoint, DISPLAY_DEVICEW,
+    HDC, HMONITOR, MONITORINFOEXW, MONITOR_DEFAULTTONULL,
 };
 use windows::Win32::System::Threading::{
     OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_FORMAT, PROCESS_QUERY_LIMITED_INFORMATION,
 };
-use windows::Win32::UI::HiDpi::GetDpiForWindow;
+use windows::Win32::UI::HiDpi::{GetDpiForMonitor, GetDpiForWindow, MDT_EFFECTIVE_DPI};
 use windows::Win32::UI::WindowsAndMessaging::{
     EnumWindows, GetCursorInfo, GetWindowRect, GetWindowTextLengthW, GetWindowTextW,
     GetWindowThreadProcessId, IsWindowVisible, LoadCursorW, SetForegroundWindow, CURSORINFO,
@@ -291,6 +291,37 @@ pub fn monitor_bounds(id: u32) -> Bounds {
     bounds.unwrap_or_default()
 }
 
+pub fn display_scale_factor(id: u32) -> Option<f64> {
+    const BASE_DPI: u32 = 96;
+
+    let bounds = monitor_bounds(id);
+    if bounds.width == 0.0 {
+        return None;
+    }
+
+    let center = POINT {
+        x: (bounds.x + bounds.width / 2.0) as i32,
+        y: (bounds.y + bounds.height / 2.0) as i32,
+    };
+
+    unsafe {
+        let monitor = MonitorFromPoint(center, MONITOR_DEFAULTTONULL);
+        if monitor.is_invalid() {
+            return None;
+        }
+
+        let (mut dpi_x, mut dpi_y) = (0, 0);
+        GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y).ok()?;
+
+        Some(dpi_x as f64 / BASE_DPI as f64)
+    }
+}
+
+/// Color profiles aren't exposed through Win32
+pub fn display_color_space(_id: u32) -> Option<String> {
+    None
+}
+
 pub fn display_names() -> HashMap<u32, String> {
     let mut names = HashMap::new();
 
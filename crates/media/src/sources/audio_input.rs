@@ -46,7 +46,7 @@ impl AudioInputSource {
     ) -> Result<(), MediaError> {
         match clock.timestamp_for(samples.info.timestamp().capture) {
             None => {
-                eprintln!("Clock is currently stopped. Dropping frames.");
+                tracing::warn!("Clock is currently stopped. Dropping frames.");
             }
             Some(timestamp) => {
                 let frame = self.audio_info.wrap_frame(&samples.data, timestamp);
@@ -70,7 +70,7 @@ impl AudioInputSource {
 
         for frame in frames {
             if let Err(error) = self.process_frame(clock, output, frame) {
-                eprintln!("{error}");
+                tracing::error!("{error}");
                 break;
             }
         }
@@ -90,7 +90,7 @@ impl PipelineSourceTask for AudioInputSource {
         mut control_signal: crate::pipeline::control::PipelineControlSignal,
         output: Sender<Self::Output>,
     ) {
-        println!("Preparing audio input source thread...");
+        tracing::info!("Preparing audio input source thread...");
 
         let mut samples_rx: Option<Receiver<AudioInputSamples>> = None;
         ready_signal.send(Ok(())).unwrap();
@@ -103,12 +103,12 @@ impl PipelineSourceTask for AudioInputSource {
                     match samples.recv() {
                         Ok(samples) => {
                             if let Err(error) = self.process_frame(&mut clock, &output, samples) {
-                                eprintln!("{error}");
+                                tracing::error!("{error}");
                                 break;
                             }
                         }
                         Err(_) => {
-                            eprintln!("Lost connection with the camera feed");
+                            tracing::error!("Lost connection with the camera feed");
                             break;
                         }
                     }
@@ -129,6 +129,6 @@ impl PipelineSourceTask for AudioInputSource {
             }
         }
 
-        println!("Shutting down audio input source thread.");
+        tracing::info!("Shutting down audio input source thread.");
     }
 }
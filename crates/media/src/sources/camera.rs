@@ -37,7 +37,7 @@ impl CameraSource {
         } = camera_frame;
         match clock.timestamp_for(captured_at) {
             None => {
-                eprintln!("Clock is currently stopped. Dropping frames.");
+                tracing::warn!("Clock is currently stopped. Dropping frames.");
             }
             Some(timestamp) => {
                 frame.set_pts(Some(timestamp));
@@ -61,7 +61,7 @@ impl CameraSource {
 
         for frame in frames {
             if let Err(error) = self.process_frame(clock, output, frame) {
-                eprintln!("{error}");
+                tracing::error!("{error}");
                 break;
             }
         }
@@ -81,26 +81,34 @@ impl PipelineSourceTask for CameraSource {
         mut control_signal: crate::pipeline::control::PipelineControlSignal,
         output: Sender<Self::Output>,
     ) {
-        println!("Preparing camera source thread...");
+        tracing::info!("Preparing camera source thread...");
 
         let mut frames_rx: Option<Receiver<RawCameraFrame>> = None;
+        let mut feed_lost = false;
         ready_signal.send(Ok(())).unwrap();
 
         loop {
             match control_signal.last() {
+                // Keep following the pipeline's signals so the rest of the recording carries on
+                Some(Control::Play) if feed_lost => {
+                    if let Some(Control::Shutdown) | None = control_signal.blocking_last() {
+                        break;
+                    }
+                }
                 Some(Control::Play) => {
                     let frames = frames_rx.get_or_insert_with(|| self.feed_connection.attach());
 
                     match frames.recv() {
                         Ok(frame) => {
                             if let Err(error) = self.process_frame(&mut clock, &output, frame) {
-                                eprintln!("{error}");
+                                tracing::error!("{error}");
                                 break;
                             }
                         }
                         Err(_) => {
-                            eprintln!("Lost connection with the camera feed");
-                            break;
+                            tracing::error!("Lost the camera feed, continuing without it");
+                            frames_rx = None;
+                            feed_lost = true;
                         }
                     }
                 }
@@ -120,6 +128,42 @@ impl PipelineSourceTask for CameraSource {
             }
         }
 
-        println!("Shutting down camera source thread.");
+        tracing::info!("Shutting down camera source thread.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{data::RawVideoFormat, pipeline::control::ControlBroadcast};
+
+    #[test]
+    fn shuts_down_after_losing_the_feed() {
+        let mut source = CameraSource {
+            feed_connection: CameraConnection::disconnected(),
+            video_info: VideoInfo::from_raw(RawVideoFormat::Bgra, 1280, 720, 30),
+        };
+
+        let mut control = ControlBroadcast::default();
+        let control_signal = control.add_listener("camera".to_string());
+        let (ready_tx, ready_rx) = flume::bounded(1);
+        let (output_tx, _output_rx) = flume::unbounded();
+        let (done_tx, done_rx) = flume::bounded(1);
+
+        std::thread::spawn(move || {
+            source.run(RealTimeClock::new(), ready_tx, control_signal, output_tx);
+            done_tx.send(()).ok();
+        });
+
+        ready_rx.recv().unwrap().unwrap();
+        futures::executor::block_on(control.broadcast(Control::Play)).unwrap();
+
+        // Give the source the chance to find out the feed is gone
+        std::thread::sleep(Duration::from_millis(100));
+        futures::executor::block_on(control.broadcast(Control::Shutdown)).unwrap();
+
+        assert!(done_rx.recv_timeout(Duration::from_secs(5)).is_ok());
     }
 }
@@ -1,4 +1,3 @@
-use cap_flags::FLAGS;
 use flume::Sender;
 use scap::{
     capturer::{get_output_frame_size, Area, Capturer, Options, Point, Resolution, Size},
@@ -7,7 +6,7 @@ use scap::{
 };
 use serde::{Deserialize, Serialize};
 use specta::Type;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::{
     data::{FFVideo, RawVideoFormat, VideoInfo},
@@ -36,11 +35,50 @@ pub struct CaptureScreen {
     pub name: String,
 }
 
+/// All of an application's on-screen windows
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CaptureApplication {
+    /// Process id, which identifies the application while it's running
+    pub id: u32,
+    /// Bundle identifier on macOS, the owner name of its windows elsewhere
+    pub bundle_id: String,
+    pub name: String,
+    pub windows: Vec<CaptureWindow>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase", tag = "variant")]
 pub enum ScreenCaptureTarget {
     Window(CaptureWindow),
     Screen(CaptureScreen),
+    Region {
+        bounds: Bounds,
+    },
+    /// Captures the main display with the windows of every other application hidden,
+    /// so the application can be followed across all of its windows
+    Application(CaptureApplication),
+}
+
+impl ScreenCaptureTarget {
+    pub fn get_bounds(&self) -> Bounds {
+        match self {
+            ScreenCaptureTarget::Window(capture_window) => capture_window.bounds,
+            ScreenCaptureTarget::Screen(capture_screen) => {
+                platform::monitor_bounds(capture_screen.id)
+            }
+            ScreenCaptureTarget::Region { bounds } => *bounds,
+            ScreenCaptureTarget::Application(_) => {
+                #[cfg(target_os = "macos")]
+                {
+                    platform::monitor_bounds(platform::main_display_id())
+                }
+                #[cfg(not(target_os = "macos"))]
+                {
+                    Bounds::default()
+                }
+            }
+        }
+    }
 }
 
 impl PartialEq<Target> for ScreenCaptureTarget {
@@ -52,6 +90,8 @@ impl PartialEq<Target> for ScreenCaptureTarget {
             (ScreenCaptureTarget::Screen(capture_screen), Target::Display(display)) => {
                 display.id == capture_screen.id
             }
+            (&ScreenCaptureTarget::Region { .. }, _)
+            | (&ScreenCaptureTarget::Application(_), _) => false,
             (&ScreenCaptureTarget::Window(_), &scap::Target::Display(_))
             | (&ScreenCaptureTarget::Screen(_), &scap::Target::Window(_)) => todo!(),
         }
@@ -63,6 +103,7 @@ pub struct ScreenCaptureSource<T> {
     fps: u32,
     resolution: Resolution,
     video_info: VideoInfo,
+    show_cursor: bool,
     phantom: std::marker::PhantomData<T>,
 }
 
@@ -73,6 +114,7 @@ impl<T> Clone for ScreenCaptureSource<T> {
             fps: self.fps,
             resolution: self.resolution,
             video_info: self.video_info,
+            show_cursor: self.show_cursor,
             phantom: Default::default(),
         }
     }
@@ -88,6 +130,7 @@ impl<T> ScreenCaptureSource<T> {
         capture_target: &ScreenCaptureTarget,
         fps: Option<u32>,
         resolution: Option<Resolution>,
+        show_cursor: bool,
     ) -> Self {
         let output_resolution = resolution.unwrap_or(Resolution::Captured);
         let fps = fps.unwrap_or(Self::DEFAULT_FPS);
@@ -97,6 +140,7 @@ impl<T> ScreenCaptureSource<T> {
             fps,
             resolution: output_resolution,
             video_info: VideoInfo::from_raw(RawVideoFormat::Bgra, 0, 0, fps),
+            show_cursor,
             phantom: Default::default(),
         };
 
@@ -109,23 +153,69 @@ impl<T> ScreenCaptureSource<T> {
         this
     }
 
+    pub fn target(&self) -> &ScreenCaptureTarget {
+        &self.target
+    }
+
     pub fn get_bounds(&self) -> Bounds {
+        self.target.get_bounds()
+    }
+
+    /// The display the target is on, going by the center of its bounds
+    pub fn display_id(&self) -> Option<u32> {
+        if let ScreenCaptureTarget::Screen(screen) = &self.target {
+            return Some(screen.id);
+        }
+
+        let bounds = self.get_bounds();
+        let center = (
+            bounds.x + bounds.width / 2.0,
+            bounds.y + bounds.height / 2.0,
+        );
+
+        Self::list_screens()
+            .into_iter()
+            .map(|screen| screen.id)
+            .find(|&id| {
+                let display = platform::monitor_bounds(id);
+                (display.x..display.x + display.width).contains(&center.0)
+                    && (display.y..display.y + display.height).contains(&center.1)
+            })
+    }
+
+    fn focus_window_id(&self) -> Option<u32> {
         match &self.target {
-            ScreenCaptureTarget::Window(capture_window) => capture_window.bounds,
-            ScreenCaptureTarget::Screen(capture_screen) => {
-                platform::monitor_bounds(capture_screen.id)
+            ScreenCaptureTarget::Window(window) => Some(window.id),
+            ScreenCaptureTarget::Application(application) => {
+                application.windows.first().map(|w| w.id)
             }
+            _ => None,
         }
     }
 
     fn create_options(&self) -> Options {
-        let targets = dbg!(scap::get_all_targets());
+        let targets = scap::get_all_targets();
+        tracing::debug!("Capture targets: {targets:?}");
+
+        // Looked up when capture starts so windows opened since the application was picked
+        // are accounted for
+        let other_application_windows = match &self.target {
+            ScreenCaptureTarget::Application(application) => {
+                crate::platform::get_on_screen_windows()
+                    .into_iter()
+                    .filter(|window| application_id(window) != application.bundle_id)
+                    .map(|window| window.window_id)
+                    .collect()
+            }
+            _ => HashSet::new(),
+        };
 
         let excluded_targets: Vec<scap::Target> = targets
             .iter()
             .filter(|target| {
                 matches!(target, Target::Window(scap_window)
-                if EXCLUDED_WINDOWS.contains(&scap_window.title.as_str()))
+                if EXCLUDED_WINDOWS.contains(&scap_window.title.as_str())
+                    || other_application_windows.contains(&scap_window.id))
             })
             .cloned()
             .collect();
@@ -141,11 +231,23 @@ impl<T> ScreenCaptureSource<T> {
                     y: capture_window.bounds.y,
                 },
             }),
-            ScreenCaptureTarget::Screen(_) => None,
+            ScreenCaptureTarget::Region { bounds } => Some(Area {
+                size: Size {
+                    width: bounds.width,
+                    height: bounds.height,
+                },
+                origin: Point {
+                    x: bounds.x,
+                    y: bounds.y,
+                },
+            }),
+            ScreenCaptureTarget::Screen(_) | ScreenCaptureTarget::Application(_) => None,
         };
 
         let target = match &self.target {
-            ScreenCaptureTarget::Window(w) => None,
+            ScreenCaptureTarget::Window(_)
+            | ScreenCaptureTarget::Region { .. }
+            | ScreenCaptureTarget::Application(_) => None,
             ScreenCaptureTarget::Screen(capture_screen) => targets
                 .iter()
                 .find(|t| match t {
@@ -157,7 +259,7 @@ impl<T> ScreenCaptureSource<T> {
 
         Options {
             fps: self.fps,
-            show_cursor: !FLAGS.record_mouse,
+            show_cursor: self.show_cursor,
             show_highlight: true,
             excluded_targets: Some(excluded_targets),
             output_type: if cfg!(windows) {
@@ -230,9 +332,56 @@ impl<T> ScreenCaptureSource<T> {
             .collect()
     }
 
+    /// Groups the windows from `list_windows` by the application that owns them
+    pub fn list_applications() -> Vec<CaptureApplication> {
+        let platform_windows: HashMap<u32, Window> = crate::platform::get_on_screen_windows()
+            .into_iter()
+            .map(|window| (window.window_id, window))
+            .collect();
+
+        let mut applications = Vec::<CaptureApplication>::new();
+
+        for window in Self::list_windows() {
+            if EXCLUDED_WINDOWS.contains(&window.name.as_str()) {
+                continue;
+            }
+
+            let Some(platform_window) = platform_windows.get(&window.id) else {
+                continue;
+            };
+
+            let bundle_id = application_id(platform_window);
+
+            match applications.iter_mut().find(|a| a.bundle_id == bundle_id) {
+                Some(application) => application.windows.push(window),
+                None => applications.push(CaptureApplication {
+                    id: platform_window.process_id,
+                    bundle_id,
+                    name: platform_window.owner_name.clone(),
+                    windows: vec![window],
+                }),
+            }
+        }
+
+        applications
+    }
+
     pub fn info(&self) -> VideoInfo {
         self.video_info
     }
+
+    pub fn fps(&self) -> u32 {
+        self.fps
+    }
+}
+
+fn application_id(window: &Window) -> String {
+    #[cfg(target_os = "macos")]
+    if let Some(bundle_id) = platform::bundle_identifier(window.process_id) {
+        return bundle_id;
+    }
+
+    window.owner_name.clone()
 }
 
 pub struct AVFrameCapture;
@@ -248,15 +397,13 @@ impl PipelineSourceTask for ScreenCaptureSource<AVFrameCapture> {
         mut control_signal: crate::pipeline::control::PipelineControlSignal,
         output: Sender<Self::Output>,
     ) {
-        println!("Preparing screen capture source thread...");
+        tracing::info!("Preparing screen capture source thread...");
 
         let options = self.create_options();
 
-        let maybe_capture_window_id = match &self.target {
-            ScreenCaptureTarget::Window(window) => Some(window.id),
-            _ => None,
-        };
-        let mut capturer = Capturer::new(dbg!(options));
+        let maybe_capture_window_id = self.focus_window_id();
+        tracing::debug!("Capture options: {options:?}");
+        let mut capturer = Capturer::new(options);
         let mut capturing = false;
         ready_signal.send(Ok(())).unwrap();
 
@@ -270,7 +417,7 @@ impl PipelineSourceTask for ScreenCaptureSource<AVFrameCapture> {
                         capturer.start_capture();
                         capturing = true;
 
-                        println!("Screen recording started.");
+                        tracing::info!("Screen recording started.");
                     }
 
                     match capturer.get_next_frame() {
@@ -282,7 +429,7 @@ impl PipelineSourceTask for ScreenCaptureSource<AVFrameCapture> {
                             let raw_timestamp = RawNanoseconds(frame.display_time);
                             match clock.timestamp_for(raw_timestamp) {
                                 None => {
-                                    eprintln!("Clock is currently stopped. Dropping frames.");
+                                    tracing::warn!("Clock is currently stopped. Dropping frames.");
                                 }
                                 Some(timestamp) => {
                                     let mut buffer = FFVideo::new(
@@ -302,19 +449,19 @@ impl PipelineSourceTask for ScreenCaptureSource<AVFrameCapture> {
                                     let dst_stride = buffer.stride(0);
 
                                     if src_data.len() < src_stride * height {
-                                        eprintln!("Frame data size mismatch.");
+                                        tracing::error!("Frame data size mismatch.");
                                         continue;
                                     }
 
                                     if src_stride < width_in_bytes {
-                                        eprintln!(
+                                        tracing::error!(
                                             "Source stride is less than expected width in bytes."
                                         );
                                         continue;
                                     }
 
                                     if buffer.data(0).len() < dst_stride * height {
-                                        eprintln!("Destination data size mismatch.");
+                                        tracing::error!("Destination data size mismatch.");
                                         continue;
                                     }
 
@@ -333,7 +480,7 @@ impl PipelineSourceTask for ScreenCaptureSource<AVFrameCapture> {
                                     }
 
                                     if let Err(_) = output.send(buffer) {
-                                        eprintln!(
+                                        tracing::error!(
                                             "Pipeline is unreachable. Shutting down recording."
                                         );
                                         break;
@@ -343,20 +490,20 @@ impl PipelineSourceTask for ScreenCaptureSource<AVFrameCapture> {
                         }
                         Ok(_) => unreachable!(),
                         Err(error) => {
-                            eprintln!("Capture error: {error}");
+                            tracing::error!("Capture error: {error}");
                             break;
                         }
                     }
                 }
                 Some(Control::Pause) => {
-                    println!("Received pause signal");
+                    tracing::info!("Received pause signal");
                     if capturing {
                         capturer.stop_capture();
                         capturing = false;
                     }
                 }
                 Some(Control::Shutdown) | None => {
-                    println!("Received shutdown signal");
+                    tracing::info!("Received shutdown signal");
                     if capturing {
                         capturer.stop_capture();
                     }
@@ -365,7 +512,7 @@ impl PipelineSourceTask for ScreenCaptureSource<AVFrameCapture> {
             }
         }
 
-        println!("Shutting down screen capture source thread.");
+        tracing::info!("Shutting down screen capture source thread.");
     }
 }
 
@@ -383,13 +530,12 @@ impl PipelineSourceTask for ScreenCaptureSource<CMSampleBufferCapture> {
         mut control_signal: crate::pipeline::control::PipelineControlSignal,
         output: Sender<Self::Output>,
     ) {
-        println!("Preparing screen capture source thread...");
+        tracing::info!("Preparing screen capture source thread...");
 
-        let maybe_capture_window_id = match &self.target {
-            ScreenCaptureTarget::Window(window) => Some(window.id),
-            _ => None,
-        };
-        let mut capturer = Capturer::new(dbg!(self.create_options()));
+        let maybe_capture_window_id = self.focus_window_id();
+        let options = self.create_options();
+        tracing::debug!("Capture options: {options:?}");
+        let mut capturer = Capturer::new(options);
         let mut capturing = false;
         ready_signal.send(Ok(())).ok();
 
@@ -403,7 +549,7 @@ impl PipelineSourceTask for ScreenCaptureSource<CMSampleBufferCapture> {
                         capturer.start_capture();
                         capturing = true;
 
-                        println!("Screen recording started.");
+                        tracing::info!("Screen recording started.");
                     }
 
                     match capturer.raw().get_next_pixel_buffer() {
@@ -413,25 +559,27 @@ impl PipelineSourceTask for ScreenCaptureSource<CMSampleBufferCapture> {
                             }
 
                             if let Err(_) = output.send(pixel_buffer.into()) {
-                                eprintln!("Pipeline is unreachable. Shutting down recording.");
+                                tracing::error!(
+                                    "Pipeline is unreachable. Shutting down recording."
+                                );
                                 break;
                             }
                         }
                         Err(error) => {
-                            eprintln!("Capture error: {error}");
+                            tracing::error!("Capture error: {error}");
                             break;
                         }
                     }
                 }
                 Some(Control::Pause) => {
-                    println!("Received pause signal");
+                    tracing::info!("Received pause signal");
                     if capturing {
                         capturer.stop_capture();
                         capturing = false;
                     }
                 }
                 Some(Control::Shutdown) | None => {
-                    println!("Received shutdown signal");
+                    tracing::info!("Received shutdown signal");
                     if capturing {
                         // capturer.stop_capture();
                     }
@@ -440,6 +588,6 @@ impl PipelineSourceTask for ScreenCaptureSource<CMSampleBufferCapture> {
             }
         }
 
-        println!("Shutting down screen capture source thread.");
+        tracing::info!("Shutting down screen capture source thread.");
     }
 }
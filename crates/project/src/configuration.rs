@@ -1,11 +1,13 @@
 use std::{
     ops::{Add, Div, Mul, Sub},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
+use crate::Encoder;
+
 #[derive(Type, Serialize, Deserialize, Clone, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub enum AspectRatio {
@@ -15,6 +17,24 @@ pub enum AspectRatio {
     Square,
     Classic,
     Tall,
+    /// Width and height, eg. `(9, 16)` for vertical video
+    Custom(u32, u32),
+}
+
+impl AspectRatio {
+    /// Width divided by height, `None` if a custom ratio has a zero side.
+    /// Custom ratios are limited to 1:4-4:1 to keep the output within texture size limits.
+    pub fn ratio(&self) -> Option<f32> {
+        Some(match self {
+            Self::Wide => 16.0 / 9.0,
+            Self::Vertical => 9.0 / 16.0,
+            Self::Square => 1.0,
+            Self::Classic => 4.0 / 3.0,
+            Self::Tall => 3.0 / 4.0,
+            Self::Custom(0, _) | Self::Custom(_, 0) => return None,
+            Self::Custom(width, height) => (*width as f32 / *height as f32).clamp(0.25, 4.0),
+        })
+    }
 }
 
 pub type Color = [u16; 3];
@@ -155,8 +175,12 @@ impl Crop {
 pub struct BackgroundConfiguration {
     pub source: BackgroundSource,
     pub blur: u32,
+    /// Inset of the screen from the output edges, from 0 to 100
     pub padding: f64,
     pub rounding: f64,
+    /// Strength of the drop shadow behind the screen, from 0 to 100
+    #[serde(default)]
+    pub shadow: f64,
     pub inset: u32,
     pub crop: Option<Crop>,
 }
@@ -183,6 +207,9 @@ pub enum CameraYPosition {
 pub struct CameraPosition {
     pub x: CameraXPosition,
     pub y: CameraYPosition,
+    /// Normalized (0-1) position within the padded output area, overriding `x` and `y`
+    #[serde(default)]
+    pub custom: Option<XY<f32>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -194,6 +221,19 @@ pub struct Camera {
     pub zoom_size: Option<f32>,
     pub rounding: f32,
     pub shadow: f32,
+    /// Makes the camera's background transparent, so only the presenter shows over the screen
+    #[serde(default)]
+    pub background_removal: Option<BackgroundRemoval>,
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum BackgroundRemoval {
+    /// Removes colors close to `color`, eg. a green screen.
+    /// `similarity` from 0 to 1 is how different a color can be and still be removed.
+    ChromaKey { color: Color, similarity: f32 },
+    /// Removes a plain background, whose color is taken from the edges of the frame
+    Auto,
 }
 
 impl Default for Camera {
@@ -206,6 +246,7 @@ impl Default for Camera {
             zoom_size: None,
             rounding: 100.0,
             shadow: 0.0,
+            background_removal: None,
         }
     }
 }
@@ -215,6 +256,9 @@ impl Default for Camera {
 pub struct AudioConfiguration {
     mute: bool,
     improve: bool,
+    /// Evens out the loudness of the recording's audio when rendering, to EBU R128
+    #[serde(default)]
+    pub normalize: bool,
 }
 
 #[derive(Type, Serialize, Deserialize, Clone, Debug, Default)]
@@ -241,6 +285,10 @@ pub struct CursorConfiguration {
     pub size: u32,
     r#type: CursorType,
     pub animation_style: CursorAnimationStyle,
+    /// How much the recorded cursor path is smoothed, from 0 to 1.
+    /// At 0 the animation style's smoothing is used instead.
+    #[serde(default)]
+    pub smoothing: f32,
 }
 
 impl Default for CursorConfiguration {
@@ -250,14 +298,135 @@ impl Default for CursorConfiguration {
             size: 100,
             r#type: CursorType::default(),
             animation_style: CursorAnimationStyle::Regular,
+            smoothing: 0.0,
+        }
+    }
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ClickHighlightConfiguration {
+    /// Draws a ripple wherever the mouse was clicked while recording
+    pub show: bool,
+    pub color: Color,
+}
+
+impl Default for ClickHighlightConfiguration {
+    fn default() -> Self {
+        Self {
+            show: false,
+            color: [255, 214, 10],
         }
     }
 }
 
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+pub const WATERMARK_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp"];
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WatermarkConfiguration {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub position: Corner,
+    /// 0-1
+    pub opacity: f32,
+    /// Width of the watermark relative to the output width
+    pub scale: f32,
+    /// Distance from the edges of the output, relative to the output height
+    #[serde(default = "default_watermark_margin")]
+    pub margin: f32,
+}
+
+fn default_watermark_margin() -> f32 {
+    0.03
+}
+
+impl WatermarkConfiguration {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.path.is_file() {
+            return Err(format!(
+                "Watermark image {} doesn't exist",
+                self.path.display()
+            ));
+        }
+
+        let extension = self
+            .path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_lowercase)
+            .unwrap_or_default();
+        if !WATERMARK_EXTENSIONS.contains(&extension.as_str()) {
+            return Err(format!(
+                "Watermark images must be one of {}",
+                WATERMARK_EXTENSIONS.join(", ")
+            ));
+        }
+
+        if !(0.0..=1.0).contains(&self.opacity) || self.scale <= 0.0 || self.scale > 1.0 {
+            return Err("Watermark opacity and scale must be between 0 and 1".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RedactionKind {
+    #[default]
+    Blur,
+    Pixelate,
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Redaction {
+    /// In pixels of the screen recording, before cropping
+    pub bounds: Crop,
+    #[serde(default)]
+    pub kind: RedactionKind,
+    /// In recording time, so redactions stay on their content when the timeline changes
+    pub start_secs: f64,
+    pub end_secs: f64,
+}
+
+impl Redaction {
+    pub fn is_active(&self, time: f64) -> bool {
+        (self.start_secs..self.end_secs).contains(&time)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.start_secs < 0.0 || self.start_secs >= self.end_secs {
+            return Err(format!(
+                "Invalid redaction time range {}-{}s",
+                self.start_secs, self.end_secs
+            ));
+        }
+
+        if self.bounds.size.x == 0 || self.bounds.size.y == 0 {
+            return Err("Redacted areas can't be empty".to_string());
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Type, Serialize, Deserialize, Clone, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct HotkeysConfiguration {
-    show: bool,
+    /// Renders the keys pressed while recording over the video
+    pub show: bool,
 }
 
 #[derive(Type, Serialize, Deserialize, Clone, Debug, Default)]
@@ -283,13 +452,24 @@ impl TimelineSegment {
     }
 }
 
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum ZoomMode {
+    /// Zoom towards the cursor
+    #[default]
+    Auto,
+    /// Zoom towards a fixed point, normalized to the display
+    Manual { x: f32, y: f32 },
+}
+
 #[derive(Type, Serialize, Deserialize, Clone, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ZoomSegment {
     pub start: f64,
     pub end: f64,
     pub amount: f64,
-    // pub mode: Z
+    #[serde(default)]
+    pub mode: ZoomMode,
 }
 
 #[derive(Type, Serialize, Deserialize, Clone, Debug, Default)]
@@ -320,6 +500,98 @@ impl TimelineConfiguration {
     pub fn duration(&self) -> f64 {
         self.segments.iter().map(|s| s.duration()).sum()
     }
+
+    /// Keeps only the part of the timeline between `start` and `end` seconds of output time
+    pub fn trimmed(&self, start: f64, end: f64) -> Self {
+        let mut segments = vec![];
+        let mut accum_duration = 0.0;
+
+        for segment in &self.segments {
+            let segment_start = accum_duration;
+            let segment_end = accum_duration + segment.duration();
+            accum_duration = segment_end;
+
+            let from = start.max(segment_start);
+            let to = end.min(segment_end);
+            if to <= from {
+                continue;
+            }
+
+            segments.push(TimelineSegment {
+                recording_segment: segment.recording_segment,
+                timescale: segment.timescale,
+                start: segment.start + (from - segment_start) * segment.timescale,
+                end: segment.start + (to - segment_start) * segment.timescale,
+            });
+        }
+
+        let zoom_segments = self
+            .zoom_segments
+            .iter()
+            .filter_map(|zoom| {
+                let from = zoom.start.max(start) - start;
+                let to = zoom.end.min(end) - start;
+                (to > from).then(|| ZoomSegment {
+                    start: from,
+                    end: to,
+                    amount: zoom.amount,
+                    mode: zoom.mode,
+                })
+            })
+            .collect();
+
+        Self {
+            segments,
+            zoom_segments,
+        }
+    }
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TrimConfiguration {
+    pub start_secs: f64,
+    pub end_secs: f64,
+}
+
+pub const MAX_CRF: u8 = 51;
+
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum QualitySetting {
+    /// Constant quality from 0 (lossless) to `MAX_CRF`, lower values look better
+    /// but make bigger files
+    Crf { value: u8 },
+    /// Average video bitrate, which makes the file size predictable
+    Bitrate { kbps: u32 },
+}
+
+impl QualitySetting {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Crf { value } if *value > MAX_CRF => {
+                Err(format!("CRF must be between 0 and {MAX_CRF}, got {value}"))
+            }
+            Self::Bitrate { kbps: 0 } => Err("The bitrate can't be 0".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// How the screen and camera recordings are arranged in the output
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum Layout {
+    /// The camera is overlaid on the screen, as configured in `Camera`
+    #[default]
+    PictureInPicture,
+    /// The screen on the left and the camera on the right, each letterboxed in its pane.
+    /// `split` is the share of the output's width taken up by the screen.
+    SideBySide {
+        split: f32,
+    },
+    ScreenOnly,
+    CameraOnly,
 }
 
 #[derive(Type, Serialize, Deserialize, Clone, Debug)]
@@ -330,12 +602,56 @@ pub struct ProjectConfiguration {
     pub camera: Camera,
     pub audio: AudioConfiguration,
     pub cursor: CursorConfiguration,
+    #[serde(default)]
+    pub click_highlight: ClickHighlightConfiguration,
     pub hotkeys: HotkeysConfiguration,
     #[serde(default)]
     pub timeline: Option<TimelineConfiguration>,
     pub motion_blur: Option<f32>,
+    #[serde(default)]
+    pub trim: Option<TrimConfiguration>,
+    /// Factor applied to the rendered output size on export, eg. 0.5 renders 4K captures at 1080p
+    #[serde(default)]
+    pub output_scale: Option<f32>,
+    /// Preferred H.264 encoder, falls back to software if it isn't available
+    #[serde(default)]
+    pub encoder: Encoder,
+    /// Trades off the exported video's quality against its size, the encoder's own
+    /// defaults are used without it
+    #[serde(default)]
+    pub quality: Option<QualitySetting>,
+    /// Playback speed of the whole recording, on top of each timeline segment's `timescale`
+    #[serde(default = "default_speed")]
+    pub speed: f64,
+    #[serde(default)]
+    pub watermark: Option<WatermarkConfiguration>,
+    /// Areas of the screen recording that are blurred or pixelated for part of the recording
+    #[serde(default)]
+    pub redactions: Vec<Redaction>,
+    /// Fades from black at the start of the output
+    #[serde(default)]
+    pub fade_in_secs: f64,
+    /// Fades to black at the end of the output
+    #[serde(default)]
+    pub fade_out_secs: f64,
+    #[serde(default)]
+    pub layout: Layout,
+    /// Maps HDR (PQ or HLG) recordings to SDR when rendering, otherwise they look washed out
+    #[serde(default = "default_tonemap_hdr")]
+    pub tonemap_hdr: bool,
+}
+
+fn default_speed() -> f64 {
+    1.0
+}
+
+fn default_tonemap_hdr() -> bool {
+    true
 }
 
+pub const MIN_SPEED: f64 = 0.25;
+pub const MAX_SPEED: f64 = 10.0;
+
 impl ProjectConfiguration {
     pub fn load(project_path: impl AsRef<Path>) -> Result<Self, std::io::Error> {
         std::fs::read_to_string(project_path.as_ref().join("project-config.json"))
@@ -352,6 +668,106 @@ impl ProjectConfiguration {
     pub fn timeline(&self) -> Option<&TimelineConfiguration> {
         self.timeline.as_ref()
     }
+
+    /// Validates `trim` against the recording duration and folds it into the timeline
+    pub fn apply_trim(&mut self, duration: f64) -> Result<(), String> {
+        let Some(trim) = self.trim.take() else {
+            return Ok(());
+        };
+
+        let timeline = self
+            .timeline
+            .take()
+            .unwrap_or_else(|| TimelineConfiguration {
+                segments: vec![TimelineSegment {
+                    recording_segment: None,
+                    timescale: 1.0,
+                    start: 0.0,
+                    end: duration,
+                }],
+                zoom_segments: vec![],
+            });
+
+        let timeline_duration = timeline.duration();
+
+        if trim.start_secs < 0.0
+            || trim.start_secs >= trim.end_secs
+            || trim.end_secs > timeline_duration
+        {
+            self.timeline = Some(timeline);
+            self.trim = Some(trim);
+
+            return Err(format!(
+                "Invalid trim range {}-{}s for a {timeline_duration}s recording",
+                trim.start_secs, trim.end_secs
+            ));
+        }
+
+        self.timeline = Some(timeline.trimmed(trim.start_secs, trim.end_secs));
+
+        Ok(())
+    }
+
+    /// Fade in and out durations for an output of `duration` seconds.
+    /// Each is limited to half of the duration so they don't overlap on short clips.
+    pub fn fades(&self, duration: f64) -> (f64, f64) {
+        let max = (duration / 2.0).max(0.0);
+        (
+            self.fade_in_secs.clamp(0.0, max),
+            self.fade_out_secs.clamp(0.0, max),
+        )
+    }
+
+    /// Validates `speed` and the timeline speeds, and folds `speed` into the timeline.
+    /// Should be applied after `apply_trim`, as trim times don't include the speed.
+    pub fn apply_speed(&mut self, duration: f64) -> Result<(), String> {
+        let speed_range = MIN_SPEED..=MAX_SPEED;
+
+        if !speed_range.contains(&self.speed) {
+            return Err(format!(
+                "Speed {}x is outside of {MIN_SPEED}x-{MAX_SPEED}x",
+                self.speed
+            ));
+        }
+
+        if let Some(segment) = self.timeline().and_then(|t| {
+            t.segments
+                .iter()
+                .find(|s| !speed_range.contains(&s.timescale))
+        }) {
+            return Err(format!(
+                "Segment speed {}x is outside of {MIN_SPEED}x-{MAX_SPEED}x",
+                segment.timescale
+            ));
+        }
+
+        let speed = std::mem::replace(&mut self.speed, 1.0);
+        if speed == 1.0 {
+            return Ok(());
+        }
+
+        let timeline = self.timeline.get_or_insert_with(|| TimelineConfiguration {
+            segments: vec![TimelineSegment {
+                recording_segment: None,
+                timescale: 1.0,
+                start: 0.0,
+                end: duration,
+            }],
+            zoom_segments: vec![],
+        });
+
+        for segment in &mut timeline.segments {
+            // Keep combined speeds within the supported range too
+            segment.timescale = (segment.timescale * speed).clamp(MIN_SPEED, MAX_SPEED);
+        }
+
+        for zoom in &mut timeline.zoom_segments {
+            zoom.start /= speed;
+            zoom.end /= speed;
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for ProjectConfiguration {
@@ -365,9 +781,21 @@ impl Default for ProjectConfiguration {
             camera: Camera::default(),
             audio: AudioConfiguration::default(),
             cursor: CursorConfiguration::default(),
+            click_highlight: ClickHighlightConfiguration::default(),
             hotkeys: HotkeysConfiguration::default(),
             timeline: None,
             motion_blur: None,
+            trim: None,
+            output_scale: None,
+            encoder: Encoder::default(),
+            quality: None,
+            speed: 1.0,
+            watermark: None,
+            redactions: vec![],
+            fade_in_secs: 0.0,
+            fade_out_secs: 0.0,
+            layout: Layout::default(),
+            tonemap_hdr: true,
         }
     }
 }
@@ -379,3 +807,31 @@ pub const FAST_SMOOTHING_SAMPLES: usize = 10;
 pub const SLOW_VELOCITY_THRESHOLD: f64 = 0.003;
 pub const REGULAR_VELOCITY_THRESHOLD: f64 = 0.008;
 pub const FAST_VELOCITY_THRESHOLD: f64 = 0.015;
+
+#[cfg(test)]
+mod test {
+    use super::{QualitySetting, MAX_CRF};
+
+    #[test]
+    fn crf_accepts_its_whole_range() {
+        assert!(QualitySetting::Crf { value: 0 }.validate().is_ok());
+        assert!(QualitySetting::Crf { value: MAX_CRF }.validate().is_ok());
+    }
+
+    #[test]
+    fn crf_above_max_is_invalid() {
+        assert!(QualitySetting::Crf { value: MAX_CRF + 1 }
+            .validate()
+            .is_err());
+        assert!(QualitySetting::Crf { value: u8::MAX }.validate().is_err());
+    }
+
+    #[test]
+    fn bitrate_has_to_be_positive() {
+        assert!(QualitySetting::Bitrate { kbps: 0 }.validate().is_err());
+        assert!(QualitySetting::Bitrate { kbps: 1 }.validate().is_ok());
+        assert!(QualitySetting::Bitrate { kbps: u32::MAX }
+            .validate()
+            .is_ok());
+    }
+}
@@ -44,6 +44,18 @@ impl CursorData {
     }
 }
 
+#[derive(Default, Serialize, Deserialize, Debug, Clone)]
+pub struct ClickEvents {
+    pub clicks: Vec<CursorClickEvent>,
+}
+
+impl ClickEvents {
+    pub fn load_from_file(path: &Path) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("Failed to open clicks file: {}", e))?;
+        serde_json::from_reader(file).map_err(|e| format!("Failed to parse clicks: {}", e))
+    }
+}
+
 #[derive(Default, Serialize, Deserialize, Debug, Clone)]
 pub struct CursorEvents {
     pub clicks: Vec<CursorClickEvent>,
@@ -65,3 +77,24 @@ impl From<CursorData> for CursorEvents {
         }
     }
 }
+
+#[derive(Serialize, Deserialize, Clone, Type, Debug)]
+pub struct KeystrokeEvent {
+    /// The pressed key prefixed by the held modifiers, eg. "⌘⇧K"
+    pub keys: String,
+    pub process_time_ms: f64,
+    pub unix_time_ms: f64,
+}
+
+#[derive(Default, Serialize, Deserialize, Debug, Clone)]
+pub struct KeystrokeEvents {
+    pub presses: Vec<KeystrokeEvent>,
+}
+
+impl KeystrokeEvents {
+    pub fn load_from_file(path: &Path) -> Result<Self, String> {
+        let file =
+            File::open(path).map_err(|e| format!("Failed to open keystrokes file: {}", e))?;
+        serde_json::from_reader(file).map_err(|e| format!("Failed to parse keystrokes: {}", e))
+    }
+}
@@ -7,11 +7,19 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::{CursorData, CursorEvents, CursorImages, ProjectConfiguration};
+use crate::{
+    ClickEvents, CursorData, CursorEvents, CursorImages, KeystrokeEvents, ProjectConfiguration,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct Display {
     pub path: PathBuf,
+    /// Scale factor of the recorded display, eg. 2 for Retina displays
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale_factor: Option<f64>,
+    /// Name of the recorded display's color profile
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_space: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -30,29 +38,220 @@ pub struct SharingMeta {
     pub link: String,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportFormat {
+    #[default]
+    Mp4H264,
+    WebmVp9,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Mp4H264 => "mp4",
+            Self::WebmVp9 => "webm",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Encoder {
+    #[default]
+    Software,
+    VideotoolboxH264,
+    NvencH264,
+}
+
+impl Encoder {
+    pub const ALL: [Self; 3] = [Self::Software, Self::VideotoolboxH264, Self::NvencH264];
+
+    pub fn ffmpeg_codec(&self) -> &'static str {
+        match self {
+            Self::Software => "libx264",
+            Self::VideotoolboxH264 => "h264_videotoolbox",
+            Self::NvencH264 => "h264_nvenc",
+        }
+    }
+}
+
+/// Format recording screenshots and thumbnails are saved in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ThumbnailFormat {
+    #[default]
+    Jpeg,
+    Png,
+    Webp,
+}
+
+impl ThumbnailFormat {
+    pub const ALL: [Self; 3] = [Self::Jpeg, Self::Png, Self::Webp];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpg",
+            Self::Png => "png",
+            Self::Webp => "webp",
+        }
+    }
+
+    /// Where a recording's screenshot is saved in this format
+    pub fn path(&self, project_path: &Path) -> PathBuf {
+        project_path
+            .join("screenshots")
+            .join(format!("display.{}", self.extension()))
+    }
+}
+
+/// Same as the image crate's default JPEG quality
+pub const DEFAULT_THUMBNAIL_QUALITY: u8 = 75;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThumbnailOptions {
+    pub format: ThumbnailFormat,
+    /// JPEG quality from 1 to 100, PNG and WebP thumbnails are lossless
+    pub quality: u8,
+}
+
+impl Default for ThumbnailOptions {
+    fn default() -> Self {
+        Self {
+            format: ThumbnailFormat::default(),
+            quality: DEFAULT_THUMBNAIL_QUALITY,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct RecordingMeta {
     // this field is just for convenience, it shouldn't be persisted
     #[serde(skip_serializing, default)]
     pub project_path: PathBuf,
     pub pretty_name: String,
+    /// RFC 3339 time the recording started, absent for legacy recordings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
     #[serde(default)]
     pub sharing: Option<SharingMeta>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
     #[serde(flatten)]
     pub content: Content,
 }
 
+/// User editable fields of a recording, fields that are `None` are left as they are
+#[derive(Debug, Clone, Default, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingMetaPatch {
+    /// Replaces `pretty_name`
+    pub title: Option<String>,
+    /// An empty string removes the notes
+    pub notes: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+impl RecordingMetaPatch {
+    /// Applied to the raw json so fields from other versions of Cap are kept
+    fn apply(&self, meta: &mut serde_json::Value) -> Result<(), String> {
+        let meta = meta
+            .as_object_mut()
+            .ok_or("Recording meta is not an object")?;
+
+        if let Some(title) = &self.title {
+            let title = title.trim();
+            if title.is_empty() {
+                return Err("Recording title can't be empty".to_string());
+            }
+            meta.insert("pretty_name".to_string(), title.into());
+        }
+
+        if let Some(notes) = &self.notes {
+            if notes.trim().is_empty() {
+                meta.remove("notes");
+            } else {
+                meta.insert("notes".to_string(), notes.as_str().into());
+            }
+        }
+
+        if let Some(tags) = &self.tags {
+            let mut unique = vec![];
+            for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+                if !unique.contains(&tag) {
+                    unique.push(tag);
+                }
+            }
+
+            if unique.is_empty() {
+                meta.remove("tags");
+            } else {
+                meta.insert("tags".to_string(), unique.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl RecordingMeta {
     pub fn load_for_project(project_path: &PathBuf) -> Result<Self, String> {
         let meta_path = project_path.join("recording-meta.json");
-        let mut meta: Self =
-            serde_json::from_str(&std::fs::read_to_string(&meta_path).map_err(|e| e.to_string())?)
-                .map_err(|e| e.to_string())?;
+
+        let mut meta: Self = match std::fs::read_to_string(&meta_path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| format!("Failed to parse {}: {}", meta_path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Self::from_content_dir(project_path).ok_or_else(|| {
+                    format!(
+                        "{} is missing and no display recording was found",
+                        meta_path.display()
+                    )
+                })?
+            }
+            Err(e) => return Err(format!("Failed to read {}: {}", meta_path.display(), e)),
+        };
         meta.project_path = project_path.clone();
 
         Ok(meta)
     }
 
+    /// Rebuilds metadata for recordings that lost their `recording-meta.json`
+    /// from the files in `content/`. Dimensions are read from the videos when loaded.
+    fn from_content_dir(project_path: &Path) -> Option<Self> {
+        let existing = |path: &str| {
+            let path = PathBuf::from(path);
+            project_path.join(&path).exists().then_some(path)
+        };
+
+        Some(Self {
+            project_path: project_path.to_path_buf(),
+            pretty_name: project_path.file_stem()?.to_string_lossy().into_owned(),
+            created_at: None,
+            sharing: None,
+            notes: None,
+            tags: vec![],
+            content: Content::SingleSegment {
+                segment: SingleSegment {
+                    display: Display {
+                        path: existing("content/display.mp4")?,
+                        scale_factor: None,
+                        color_space: None,
+                    },
+                    camera: existing("content/camera.mp4").map(|path| CameraMeta { path }),
+                    audio: existing("content/audio-input.mp3").map(|path| AudioMeta { path }),
+                    system_audio: existing("content/system-audio.mp3")
+                        .map(|path| AudioMeta { path }),
+                    cursor: existing("content/cursor.json"),
+                    clicks: existing("content/clicks.json"),
+                    keystrokes: existing("content/keystrokes.json"),
+                },
+            },
+        })
+    }
+
     pub fn save_for_project(&self) -> Result<(), Either<serde_json::Error, std::io::Error>> {
         let meta_path = &self.project_path.join("recording-meta.json");
         let meta = serde_json::to_string_pretty(&self).map_err(Either::Left)?;
@@ -60,12 +259,51 @@ impl RecordingMeta {
         Ok(())
     }
 
+    /// Updates the `recording-meta.json` in `project_path` without rewriting fields
+    /// this version doesn't know about
+    pub fn patch_for_project(
+        project_path: &PathBuf,
+        patch: &RecordingMetaPatch,
+    ) -> Result<Self, String> {
+        let meta_path = project_path.join("recording-meta.json");
+
+        // Recordings that lost their meta get a fresh one first
+        if !meta_path.exists() {
+            Self::load_for_project(project_path)?
+                .save_for_project()
+                .map_err(|e| format!("Failed to save {}: {}", meta_path.display(), e))?;
+        }
+
+        let json = std::fs::read_to_string(&meta_path)
+            .map_err(|e| format!("Failed to read {}: {}", meta_path.display(), e))?;
+        let mut value: serde_json::Value = serde_json::from_str(&json)
+            .map_err(|e| format!("Failed to parse {}: {}", meta_path.display(), e))?;
+
+        patch.apply(&mut value)?;
+
+        let mut meta: Self = serde_json::from_value(value.clone()).map_err(|e| e.to_string())?;
+        meta.project_path = project_path.clone();
+
+        let json = serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?;
+        std::fs::write(&meta_path, json)
+            .map_err(|e| format!("Failed to save {}: {}", meta_path.display(), e))?;
+
+        Ok(meta)
+    }
+
     pub fn project_config(&self) -> ProjectConfiguration {
         ProjectConfiguration::load(&self.project_path).unwrap_or_default()
     }
 
     pub fn output_path(&self) -> PathBuf {
-        self.project_path.join("output").join("result.mp4")
+        self.output_path_for(ExportFormat::default())
+    }
+
+    pub fn output_path_for(&self, format: ExportFormat) -> PathBuf {
+        self.project_path
+            .join("output")
+            .join("result")
+            .with_extension(format.extension())
     }
 }
 
@@ -89,8 +327,17 @@ pub struct SingleSegment {
     pub camera: Option<CameraMeta>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub audio: Option<AudioMeta>,
+    /// Audio captured from a loopback device, mixed with `audio` when rendering
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_audio: Option<AudioMeta>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cursor: Option<PathBuf>,
+    /// Mouse clicks, recorded even when the cursor track isn't
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clicks: Option<PathBuf>,
+    /// Keys pressed while recording, only captured when accessibility access is granted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keystrokes: Option<PathBuf>,
 }
 
 impl SingleSegment {
@@ -104,13 +351,13 @@ impl SingleSegment {
         };
 
         let full_path = self.path(meta, cursor_path);
-        println!("Loading cursor data from: {:?}", full_path);
+        tracing::debug!("Loading cursor data from: {:?}", full_path);
 
         // Try to load the cursor data
         let mut data = match CursorData::load_from_file(&full_path) {
             Ok(data) => data,
             Err(e) => {
-                eprintln!("Failed to load cursor data: {}", e);
+                tracing::warn!("Failed to load cursor data: {}", e);
                 return CursorData::default();
             }
         };
@@ -118,7 +365,7 @@ impl SingleSegment {
         // If cursor_images is empty but cursor files exist, populate it
         let cursors_dir = self.path(meta, "cursors");
         if data.cursor_images.0.is_empty() && cursors_dir.exists() {
-            println!("Scanning cursors directory: {:?}", cursors_dir);
+            tracing::debug!("Scanning cursors directory: {:?}", cursors_dir);
             if let Ok(entries) = std::fs::read_dir(&cursors_dir) {
                 for entry in entries {
                     let Ok(entry) = entry else {
@@ -133,7 +380,7 @@ impl SingleSegment {
                             .strip_prefix("cursor_")
                             .and_then(|s| s.strip_suffix(".png"))
                         {
-                            println!("Found cursor image: {} -> {}", id, filename_str);
+                            tracing::debug!("Found cursor image: {} -> {}", id, filename_str);
                             data.cursor_images
                                 .0
                                 .insert(id.to_string(), filename.to_string_lossy().into_owned());
@@ -141,10 +388,32 @@ impl SingleSegment {
                     }
                 }
             }
-            println!("Found {} cursor images", data.cursor_images.0.len());
+            tracing::debug!("Found {} cursor images", data.cursor_images.0.len());
         }
         data
     }
+
+    pub fn click_events(&self, meta: &RecordingMeta) -> ClickEvents {
+        let Some(clicks_path) = &self.clicks else {
+            return ClickEvents::default();
+        };
+
+        ClickEvents::load_from_file(&self.path(meta, clicks_path)).unwrap_or_else(|e| {
+            tracing::warn!("Failed to load clicks: {}", e);
+            ClickEvents::default()
+        })
+    }
+
+    pub fn keystroke_events(&self, meta: &RecordingMeta) -> KeystrokeEvents {
+        let Some(keystrokes_path) = &self.keystrokes else {
+            return KeystrokeEvents::default();
+        };
+
+        KeystrokeEvents::load_from_file(&self.path(meta, keystrokes_path)).unwrap_or_else(|e| {
+            tracing::warn!("Failed to load keystrokes: {}", e);
+            KeystrokeEvents::default()
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -219,22 +488,47 @@ impl MultipleSegment {
         };
 
         let full_path = self.path(meta, cursor_path);
-        println!("Loading cursor data from: {:?}", full_path);
+        tracing::debug!("Loading cursor data from: {:?}", full_path);
 
         // Try to load the cursor data
         match CursorEvents::load_from_file(&full_path) {
             Ok(data) => data,
             Err(e) => {
-                eprintln!("Failed to load cursor data: {}", e);
+                tracing::warn!("Failed to load cursor data: {}", e);
                 CursorEvents::default()
             }
         }
     }
 }
 
+/// Where markers dropped while recording are saved, relative to the project
+pub const MARKERS_PATH: &str = "content/markers.json";
+
+/// A point marked while recording, so it can be jumped to later
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Marker {
+    /// Seconds since the recording started, excluding pauses
+    pub timestamp: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl Marker {
+    /// Returns no markers if none were dropped while recording
+    pub fn load_for_project(project_path: &Path) -> Result<Vec<Self>, String> {
+        let path = project_path.join(MARKERS_PATH);
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+
+        let file = File::open(path).map_err(|e| format!("Failed to open markers file: {}", e))?;
+        serde_json::from_reader(file).map_err(|e| format!("Failed to parse markers: {}", e))
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::RecordingMeta;
+    use super::{RecordingMeta, RecordingMetaPatch};
 
     fn test_meta_deserialize(s: &str) {
         let _: RecordingMeta = serde_json::from_str(s).unwrap();
@@ -341,4 +635,55 @@ mod test {
 		        }"#,
         );
     }
+
+    #[test]
+    fn patch_keeps_unknown_fields() {
+        let mut meta: serde_json::Value = serde_json::from_str(
+            r#"{
+              "pretty_name": "Cap 2024-11-15 at 16.35.36",
+              "sharing": null,
+              "display": {
+                "path": "content/display.mp4"
+              },
+              "notes": "old notes",
+              "from_newer_version": { "value": 1 }
+            }"#,
+        )
+        .unwrap();
+
+        RecordingMetaPatch {
+            title: Some("  Demo  ".to_string()),
+            notes: Some("".to_string()),
+            tags: Some(vec![
+                "work".into(),
+                " demo".into(),
+                "work".into(),
+                " ".into(),
+            ]),
+        }
+        .apply(&mut meta)
+        .unwrap();
+
+        assert_eq!(meta["pretty_name"], "Demo");
+        assert!(meta.get("notes").is_none());
+        assert_eq!(meta["tags"], serde_json::json!(["work", "demo"]));
+        assert_eq!(meta["from_newer_version"]["value"], 1);
+
+        let meta: RecordingMeta = serde_json::from_value(meta).unwrap();
+        assert_eq!(meta.tags, vec!["work", "demo"]);
+    }
+
+    #[test]
+    fn patch_rejects_empty_title() {
+        let mut meta = serde_json::json!({ "pretty_name": "Cap" });
+
+        let result = RecordingMetaPatch {
+            title: Some(" ".to_string()),
+            ..Default::default()
+        }
+        .apply(&mut meta);
+
+        assert!(result.is_err());
+        assert_eq!(meta["pretty_name"], "Cap");
+    }
 }
@@ -1,4 +1,3 @@
-use cap_flags::FLAGS;
 use cap_media::{
     encoders::{H264Encoder, MP3Encoder, Output},
     feeds::{AudioInputFeed, CameraFeed},
@@ -7,23 +6,34 @@ use cap_media::{
     sources::{AudioInputSource, CameraSource, ScreenCaptureSource, ScreenCaptureTarget},
     MediaError,
 };
-use cap_project::RecordingMeta;
+use cap_project::{Marker, RecordingMeta, MARKERS_PATH};
 use either::Either;
 use std::{
     path::PathBuf,
-    sync::{atomic::AtomicBool, Arc},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::{SystemTime, UNIX_EPOCH},
 };
 use thiserror::Error;
 use tokio::sync::{oneshot, Mutex};
 
 use crate::{
+    clicks::{spawn_click_recorder, ClickActor},
     cursor::{spawn_cursor_recorder, CursorActor},
+    keystrokes::{spawn_keystroke_recorder, KeystrokeActor},
     RecordingOptions,
 };
 
 pub enum ActorControlMessage {
+    Pause(oneshot::Sender<Result<(), RecordingError>>),
+    Resume(oneshot::Sender<Result<(), RecordingError>>),
     Stop(oneshot::Sender<Result<CompletedRecording, RecordingError>>),
+    AddMarker(
+        Option<String>,
+        oneshot::Sender<Result<Marker, RecordingError>>,
+    ),
 }
 
 pub struct Actor {
@@ -34,11 +44,20 @@ pub struct Actor {
     start_time: f64,
     stop_signal: Arc<AtomicBool>,
     cursor: Option<CursorActor>,
+    clicks: Option<ClickActor>,
+    keystrokes: Option<KeystrokeActor>,
+    is_paused: Arc<AtomicBool>,
+    pause_started_at: Option<f64>,
+    paused_duration: f64,
+    display_properties: DisplayProperties,
+    markers: Vec<Marker>,
 }
 
+#[derive(Clone)]
 pub struct ActorHandle {
     ctrl_tx: flume::Sender<ActorControlMessage>,
     pub options: RecordingOptions,
+    is_paused: Arc<AtomicBool>,
 }
 
 #[derive(Error, Debug)]
@@ -79,11 +98,24 @@ impl ActorHandle {
     }
 
     pub async fn pause(&self) -> Result<(), RecordingError> {
-        Ok(())
+        send_message!(self.ctrl_tx, ActorControlMessage::Pause)
     }
 
     pub async fn resume(&self) -> Result<(), RecordingError> {
-        Ok(())
+        send_message!(self.ctrl_tx, ActorControlMessage::Resume)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is_paused.load(Ordering::Relaxed)
+    }
+
+    /// Marks the current point in the recording, saving it to `content/markers.json`
+    pub async fn add_marker(&self, label: Option<String>) -> Result<Marker, RecordingError> {
+        let (tx, rx) = oneshot::channel();
+        self.ctrl_tx
+            .send(ActorControlMessage::AddMarker(label, tx))
+            .map_err(ActorError::from)?;
+        rx.await.map_err(|_| ActorError::ActorStopped)?
     }
 }
 
@@ -99,13 +131,23 @@ pub async fn spawn_recording_actor(
     let content_dir = ensure_dir(recording_dir.join("content"))?;
     let cursors_dir = ensure_dir(content_dir.join("cursors"))?;
 
-    let screen_source = create_screen_capture(&options);
+    if options.camera_only && camera_feed.is_none() {
+        return Err(MediaError::Any("Camera-only recording requires a camera").into());
+    }
+
+    let screen_source = (!options.camera_only).then(|| create_screen_capture(&options));
+    let display_properties = screen_source
+        .as_ref()
+        .map(DisplayProperties::of)
+        .unwrap_or_default();
 
     let pipeline = create_pipeline(
         &content_dir,
         screen_source.clone(),
         camera_feed.as_deref(),
         audio_input_feed.as_ref(),
+        options.capture_system_audio,
+        options.fps(),
     )
     .await?;
 
@@ -114,44 +156,151 @@ pub async fn spawn_recording_actor(
     let (ctrl_tx, ctrl_rx) = flume::bounded(1);
 
     let stop_signal = Arc::new(AtomicBool::new(false));
+    let is_paused = Arc::new(AtomicBool::new(false));
+
+    let clicks = screen_source
+        .as_ref()
+        .map(|screen_source| spawn_click_recorder(screen_source.get_bounds()));
 
     // Initialize default values for cursor channels
-    let cursor = FLAGS.record_mouse.then(|| {
-        spawn_cursor_recorder(
-            screen_source.get_bounds(),
-            cursors_dir,
-            Default::default(),
-            0,
-        )
-    });
+    let cursor = screen_source
+        .filter(|_| options.records_cursor())
+        .map(|screen_source| {
+            spawn_cursor_recorder(
+                screen_source.get_bounds(),
+                cursors_dir,
+                Default::default(),
+                0,
+                screen_source.fps(),
+            )
+        });
+
+    let keystrokes = if options.camera_only {
+        None
+    } else {
+        spawn_keystroke_recorder()
+    };
 
     tokio::spawn({
         let options = options.clone();
+        let is_paused = is_paused.clone();
         async move {
-            let actor = Actor {
+            let mut actor = Actor {
                 recording_dir,
                 options,
                 pipeline,
                 start_time,
                 cursor,
+                clicks,
+                keystrokes,
                 stop_signal,
                 id,
+                is_paused,
+                pause_started_at: None,
+                paused_duration: 0.0,
+                display_properties,
+                markers: vec![],
             };
 
             loop {
-                let Ok(ActorControlMessage::Stop(tx)) = ctrl_rx.recv_async().await else {
+                let Ok(msg) = ctrl_rx.recv_async().await else {
                     return;
                 };
 
-                let resp = stop_recording(actor).await;
-
-                tx.send(resp).ok();
-                return;
+                match msg {
+                    ActorControlMessage::Pause(tx) => {
+                        tx.send(actor.set_paused(true).await).ok();
+                    }
+                    ActorControlMessage::Resume(tx) => {
+                        tx.send(actor.set_paused(false).await).ok();
+                    }
+                    ActorControlMessage::Stop(tx) => {
+                        tx.send(stop_recording(actor).await).ok();
+                        return;
+                    }
+                    ActorControlMessage::AddMarker(label, tx) => {
+                        tx.send(actor.add_marker(label)).ok();
+                    }
+                }
             }
         }
     });
 
-    Ok(ActorHandle { ctrl_tx, options })
+    Ok(ActorHandle {
+        ctrl_tx,
+        options,
+        is_paused,
+    })
+}
+
+impl Actor {
+    /// Time recorded so far, which lines up with the recording's timeline
+    fn elapsed(&self) -> f64 {
+        let now = current_time_f64();
+        let current_pause = self
+            .pause_started_at
+            .map(|pause_start| now - pause_start)
+            .unwrap_or(0.0);
+        now - self.start_time - self.paused_duration - current_pause
+    }
+
+    fn add_marker(&mut self, label: Option<String>) -> Result<Marker, RecordingError> {
+        let marker = Marker {
+            timestamp: self.elapsed().max(0.0),
+            label: label.filter(|label| !label.trim().is_empty()),
+        };
+        self.markers.push(marker.clone());
+
+        std::fs::write(
+            self.recording_dir.join(MARKERS_PATH),
+            serde_json::to_string_pretty(&self.markers)?,
+        )?;
+
+        Ok(marker)
+    }
+
+    /// Suspends or resumes capture without finalizing any output files.
+    /// The pipeline clock skips the paused duration so the recording has no gap.
+    async fn set_paused(&mut self, paused: bool) -> Result<(), RecordingError> {
+        if self.is_paused.load(Ordering::Relaxed) == paused {
+            return Ok(());
+        }
+
+        #[cfg(target_os = "linux")]
+        if self.pipeline.linux_screen_capture.is_some() {
+            return Err(MediaError::Any("Pausing is not supported by Linux screen capture").into());
+        }
+
+        if let Some(pipeline) = &mut self.pipeline.inner {
+            if paused {
+                pipeline.pause().await?;
+            } else {
+                pipeline.play().await?;
+            }
+        }
+
+        if let Some(cursor) = &self.cursor {
+            cursor.set_paused(paused);
+        }
+
+        if let Some(clicks) = &self.clicks {
+            clicks.set_paused(paused);
+        }
+
+        if let Some(keystrokes) = &self.keystrokes {
+            keystrokes.set_paused(paused);
+        }
+
+        if paused {
+            self.pause_started_at = Some(current_time_f64());
+        } else if let Some(pause_start) = self.pause_started_at.take() {
+            self.paused_duration += current_time_f64() - pause_start;
+        }
+
+        self.is_paused.store(paused, Ordering::Relaxed);
+
+        Ok(())
+    }
 }
 
 pub struct CompletedRecording {
@@ -164,17 +313,28 @@ pub struct CompletedRecording {
 }
 
 async fn stop_recording(mut actor: Actor) -> Result<CompletedRecording, RecordingError> {
-    let segment = (actor.start_time, current_time_f64());
+    let now = current_time_f64();
+    // Stopping while paused still counts the final pause towards the skipped time
+    let paused_duration = actor.paused_duration
+        + actor
+            .pause_started_at
+            .map(|pause_start| now - pause_start)
+            .unwrap_or(0.0);
+    let segment = (actor.start_time, now - paused_duration);
 
     use cap_project::*;
 
     let meta = RecordingMeta {
         project_path: actor.recording_dir.clone(),
         sharing: None,
+        notes: None,
+        tags: vec![],
         pretty_name: format!(
             "Cap {}",
             chrono::Local::now().format("%Y-%m-%d at %H.%M.%S")
         ),
+        created_at: chrono::DateTime::from_timestamp_millis((actor.start_time * 1000.0) as i64)
+            .map(|time| time.to_rfc3339()),
         content: Content::SingleSegment {
             segment: SingleSegment {
                 display: Display {
@@ -184,6 +344,8 @@ async fn stop_recording(mut actor: Actor) -> Result<CompletedRecording, Recordin
                         .strip_prefix(&actor.recording_dir)
                         .unwrap()
                         .to_owned(),
+                    scale_factor: actor.display_properties.scale_factor,
+                    color_space: actor.display_properties.color_space.clone(),
                 },
                 camera: actor
                     .pipeline
@@ -199,12 +361,34 @@ async fn stop_recording(mut actor: Actor) -> Result<CompletedRecording, Recordin
                     .map(|path| AudioMeta {
                         path: path.strip_prefix(&actor.recording_dir).unwrap().to_owned(),
                     }),
+                system_audio: actor
+                    .pipeline
+                    .system_audio_output_path
+                    .as_ref()
+                    .map(|path| AudioMeta {
+                        path: path.strip_prefix(&actor.recording_dir).unwrap().to_owned(),
+                    }),
                 cursor: Some(PathBuf::from("content/cursor.json")),
+                clicks: actor
+                    .clicks
+                    .is_some()
+                    .then(|| PathBuf::from("content/clicks.json")),
+                keystrokes: actor
+                    .keystrokes
+                    .is_some()
+                    .then(|| PathBuf::from("content/keystrokes.json")),
             },
         },
     };
 
-    actor.pipeline.inner.shutdown().await?;
+    if let Some(mut pipeline) = actor.pipeline.inner {
+        pipeline.shutdown().await?;
+    }
+
+    #[cfg(target_os = "linux")]
+    if let Some(screen_capture) = actor.pipeline.linux_screen_capture {
+        screen_capture.stop().await?;
+    }
 
     actor
         .stop_signal
@@ -231,6 +415,22 @@ async fn stop_recording(mut actor: Actor) -> Result<CompletedRecording, Recordin
         serde_json::to_string_pretty(&cursor_data)?,
     )?;
 
+    if let Some(clicks) = actor.clicks {
+        let clicks = clicks.stop().await;
+        std::fs::write(
+            actor.recording_dir.join("content/clicks.json"),
+            serde_json::to_string_pretty(&ClickEvents { clicks })?,
+        )?;
+    }
+
+    if let Some(keystrokes) = actor.keystrokes {
+        let presses = keystrokes.stop().await;
+        std::fs::write(
+            actor.recording_dir.join("content/keystrokes.json"),
+            serde_json::to_string_pretty(&KeystrokeEvents { presses })?,
+        )?;
+    }
+
     meta.save_for_project()
         .map_err(Either::either_into::<RecordingError>)?;
 
@@ -247,36 +447,45 @@ async fn stop_recording(mut actor: Actor) -> Result<CompletedRecording, Recordin
 fn create_screen_capture(
     recording_options: &RecordingOptions,
 ) -> ScreenCaptureSource<impl MakeCapturePipeline> {
+    tracing::debug!("Capture target: {:?}", recording_options.capture_target);
+
     #[cfg(target_os = "macos")]
     {
         ScreenCaptureSource::<cap_media::sources::CMSampleBufferCapture>::init(
-            dbg!(&recording_options.capture_target),
-            None,
+            &recording_options.capture_target,
+            Some(recording_options.fps()),
             None,
+            recording_options.captures_os_cursor(),
         )
     }
     #[cfg(not(target_os = "macos"))]
     {
         ScreenCaptureSource::<AVFrameCapture>::init(
-            dbg!(&recording_options.capture_target),
-            None,
+            &recording_options.capture_target,
+            Some(recording_options.fps()),
             None,
+            recording_options.captures_os_cursor(),
         )
     }
 }
 
 struct RecordingPipeline {
-    pub inner: Pipeline<RealTimeClock<()>>,
+    pub inner: Option<Pipeline<RealTimeClock<()>>>,
+    #[cfg(target_os = "linux")]
+    pub linux_screen_capture: Option<crate::linux_capture::LinuxScreenCapture>,
     pub display_output_path: PathBuf,
     pub audio_output_path: Option<PathBuf>,
+    pub system_audio_output_path: Option<PathBuf>,
     pub camera_output_path: Option<PathBuf>,
 }
 
 async fn create_pipeline<TCaptureFormat: MakeCapturePipeline>(
     content_dir: &PathBuf,
-    screen_source: ScreenCaptureSource<TCaptureFormat>,
+    screen_source: Option<ScreenCaptureSource<TCaptureFormat>>,
     camera_feed: Option<&Mutex<CameraFeed>>,
     audio_input_feed: Option<&AudioInputFeed>,
+    capture_system_audio: bool,
+    fps: u32,
 ) -> Result<RecordingPipeline, MediaError> {
     let camera_feed = match camera_feed.as_ref() {
         Some(camera_feed) => Some(camera_feed.lock().await),
@@ -289,19 +498,26 @@ async fn create_pipeline<TCaptureFormat: MakeCapturePipeline>(
 
     let display_output_path = content_dir.join("display.mp4");
     let mut audio_output_path = None;
+    let mut system_audio_output_path = None;
     let mut camera_output_path = None;
 
     if let Some(camera_source) = camera_feed.map(CameraSource::init) {
         let camera_config = camera_source.info();
-        let output_config = camera_config.scaled(1920, 30);
-        camera_output_path = Some(content_dir.join("camera.mp4"));
+
+        // Without a screen capture the camera is recorded at its native size as the display
+        let (output_config, output_path) = if screen_source.is_some() {
+            let path = content_dir.join("camera.mp4");
+            camera_output_path = Some(path.clone());
+            (camera_config.scaled(1920, 30), path)
+        } else {
+            (
+                camera_config.scaled(camera_config.width, fps),
+                display_output_path.clone(),
+            )
+        };
 
         let camera_filter = VideoFilter::init("camera", camera_config, output_config)?;
-        let camera_encoder = H264Encoder::init(
-            "camera",
-            output_config,
-            Output::File(camera_output_path.clone().unwrap()),
-        )?;
+        let camera_encoder = H264Encoder::init("camera", output_config, Output::File(output_path))?;
 
         pipeline_builder = pipeline_builder
             .source("camera_capture", camera_source)
@@ -326,28 +542,92 @@ async fn create_pipeline<TCaptureFormat: MakeCapturePipeline>(
             .sink("microphone_encoder", mic_encoder);
     }
 
+    if capture_system_audio {
+        match AudioInputFeed::loopback_device_name() {
+            Some(device_name) => {
+                // The capture thread shuts down once the source's connection is dropped
+                let system_audio_feed = AudioInputFeed::init(&device_name).await?;
+                let system_audio_source = AudioInputSource::init(&system_audio_feed);
+                let output_path = content_dir.join("system-audio.mp3");
+                system_audio_output_path = Some(output_path.clone());
+
+                let system_audio_encoder = MP3Encoder::init(
+                    "system_audio",
+                    system_audio_source.info(),
+                    Output::File(output_path),
+                )?;
+
+                pipeline_builder = pipeline_builder
+                    .source("system_audio_capture", system_audio_source)
+                    .sink("system_audio_encoder", system_audio_encoder);
+            }
+            None => tracing::warn!("No loopback audio device found, skipping system audio capture"),
+        }
+    }
+
     // we do this last to frontload the majority of errors,
     // as the screen pipeline crashes the app if an error occurs afterwards for some reason
-    pipeline_builder = TCaptureFormat::make_capture_pipeline(
-        pipeline_builder,
-        screen_source,
-        &display_output_path,
-    )?;
-
-    //
-
-    let mut pipeline = pipeline_builder.build().await?;
+    #[cfg(not(target_os = "linux"))]
+    if let Some(screen_source) = screen_source {
+        pipeline_builder = TCaptureFormat::make_capture_pipeline(
+            pipeline_builder,
+            screen_source,
+            &display_output_path,
+        )?;
+    }
 
-    pipeline.play().await?;
+    // ffmpeg captures the screen directly on Linux, outside of the media pipeline
+    #[cfg(target_os = "linux")]
+    let linux_screen_capture = screen_source
+        .map(|screen_source| {
+            crate::linux_capture::LinuxScreenCapture::start(
+                screen_source.target(),
+                screen_source.get_bounds(),
+                screen_source.fps(),
+                &display_output_path,
+            )
+        })
+        .transpose()?;
+
+    let pipeline = if pipeline_builder.is_empty() {
+        None
+    } else {
+        let mut pipeline = pipeline_builder.build().await?;
+        pipeline.play().await?;
+        Some(pipeline)
+    };
 
     Ok(RecordingPipeline {
         inner: pipeline,
+        #[cfg(target_os = "linux")]
+        linux_screen_capture,
         display_output_path,
         audio_output_path,
+        system_audio_output_path,
         camera_output_path,
     })
 }
 
+/// Kept in the recording meta, as rendering can happen on a different machine
+#[derive(Default)]
+pub(crate) struct DisplayProperties {
+    pub scale_factor: Option<f64>,
+    pub color_space: Option<String>,
+}
+
+impl DisplayProperties {
+    pub fn of<T>(source: &ScreenCaptureSource<T>) -> Self {
+        let Some(id) = source.display_id() else {
+            return Self::default();
+        };
+
+        Self {
+            scale_factor: cap_media::platform::display_scale_factor(id),
+            color_space: cap_media::platform::display_color_space(id),
+        }
+    }
+}
+
 fn ensure_dir(path: PathBuf) -> Result<PathBuf, MediaError> {
     std::fs::create_dir_all(&path)?;
     Ok(path)
@@ -374,7 +654,7 @@ impl MakeCapturePipeline for cap_media::sources::CMSampleBufferCapture {
     ) -> Result<CapturePipelineBuilder, MediaError> {
         let screen_config = source.info();
 
-        let output_config = screen_config.scaled(1920, 30);
+        let output_config = screen_config.scaled(1920, source.fps());
         let screen_encoder = cap_media::encoders::H264AVAssetWriterEncoder::init(
             "screen",
             output_config,
@@ -399,7 +679,7 @@ impl MakeCapturePipeline for cap_media::sources::AVFrameCapture {
         let screen_config = source.info();
         // let screen_bounds = screen_source.bounds;
 
-        let output_config = screen_config.scaled(1920, 30);
+        let output_config = screen_config.scaled(1920, source.fps());
         let screen_filter = VideoFilter::init("screen", screen_config, output_config)?;
         let screen_encoder =
             H264Encoder::init("screen", output_config, Output::File(output_path.into()))?;
@@ -0,0 +1,106 @@
+use std::{
+    sync::{atomic::AtomicBool, Arc},
+    time::{Duration, Instant},
+};
+
+use cap_media::platform::Bounds;
+use cap_project::CursorClickEvent;
+use device_query::{DeviceQuery, DeviceState};
+use tokio::sync::oneshot;
+
+/// Often enough to catch quick clicks without holding the button
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+pub struct ClickActor {
+    stop_signal: Arc<AtomicBool>,
+    pause_signal: Arc<AtomicBool>,
+    rx: oneshot::Receiver<Vec<CursorClickEvent>>,
+}
+
+impl ClickActor {
+    /// While paused no clicks are recorded, and the paused time is excluded from timestamps
+    pub fn set_paused(&self, paused: bool) {
+        self.pause_signal
+            .store(paused, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub async fn stop(self) -> Vec<CursorClickEvent> {
+        self.stop_signal
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        self.rx.await.unwrap_or_else(|_| {
+            tracing::error!("Click recorder stopped unexpectedly");
+            vec![]
+        })
+    }
+}
+
+/// Records mouse button changes with positions relative to `screen_bounds`.
+/// Unlike the cursor recorder this doesn't track movement or cursor images.
+pub fn spawn_click_recorder(screen_bounds: Bounds) -> ClickActor {
+    let stop_signal = Arc::new(AtomicBool::new(false));
+    let pause_signal = Arc::new(AtomicBool::new(false));
+    let (tx, rx) = oneshot::channel();
+
+    tokio::spawn({
+        let stop_signal = stop_signal.clone();
+        let pause_signal = pause_signal.clone();
+        async move {
+            let device_state = DeviceState::new();
+            let mut last_mouse_state = device_state.get_mouse();
+            let start_time = Instant::now();
+            let mut paused_at: Option<Instant> = None;
+            let mut paused_duration = Duration::ZERO;
+            let mut clicks = vec![];
+
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            while !stop_signal.load(std::sync::atomic::Ordering::Relaxed) {
+                interval.tick().await;
+
+                if pause_signal.load(std::sync::atomic::Ordering::Relaxed) {
+                    paused_at.get_or_insert_with(Instant::now);
+                    continue;
+                } else if let Some(paused_at) = paused_at.take() {
+                    paused_duration += paused_at.elapsed();
+                    last_mouse_state = device_state.get_mouse();
+                }
+
+                let mouse_state = device_state.get_mouse();
+                let elapsed = (start_time.elapsed() - paused_duration).as_secs_f64() * 1000.0;
+                let unix_time = chrono::Utc::now().timestamp_millis() as f64;
+
+                for (num, &pressed) in mouse_state.button_pressed.iter().enumerate() {
+                    let Some(&prev) = last_mouse_state.button_pressed.get(num) else {
+                        continue;
+                    };
+
+                    if pressed == prev {
+                        continue;
+                    }
+
+                    clicks.push(CursorClickEvent {
+                        down: pressed,
+                        active_modifiers: vec![],
+                        cursor_num: num as u8,
+                        cursor_id: "default".to_string(),
+                        process_time_ms: elapsed,
+                        unix_time_ms: unix_time,
+                        x: (mouse_state.coords.0 as f64 - screen_bounds.x) / screen_bounds.width,
+                        y: (mouse_state.coords.1 as f64 - screen_bounds.y) / screen_bounds.height,
+                    });
+                }
+
+                last_mouse_state = mouse_state;
+            }
+
+            tx.send(clicks).ok();
+        }
+    });
+
+    ClickActor {
+        stop_signal,
+        pause_signal,
+        rx,
+    }
+}
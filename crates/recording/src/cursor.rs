@@ -23,14 +23,31 @@ pub struct CursorActorResponse {
 
 pub struct CursorActor {
     stop_signal: Arc<AtomicBool>,
+    pause_signal: Arc<AtomicBool>,
     rx: oneshot::Receiver<CursorActorResponse>,
 }
 
 impl CursorActor {
+    /// While paused no events are recorded, and the paused time is excluded from timestamps
+    pub fn set_paused(&self, paused: bool) {
+        self.pause_signal
+            .store(paused, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Stops sampling and returns everything recorded so far.
+    /// If the recorder task died, an empty track is returned so the recording can still be saved.
     pub async fn stop(self) -> CursorActorResponse {
         self.stop_signal
             .store(true, std::sync::atomic::Ordering::Relaxed);
-        self.rx.await.unwrap()
+        self.rx.await.unwrap_or_else(|_| {
+            tracing::error!("Cursor recorder stopped unexpectedly");
+            CursorActorResponse {
+                cursors: Default::default(),
+                next_cursor_id: 0,
+                moves: vec![],
+                clicks: vec![],
+            }
+        })
     }
 }
 
@@ -39,16 +56,26 @@ pub fn spawn_cursor_recorder(
     cursors_dir: PathBuf,
     prev_cursors: Cursors,
     next_cursor_id: i32,
+    fps: u32,
 ) -> CursorActor {
     let stop_signal = Arc::new(AtomicBool::new(false));
+    let pause_signal = Arc::new(AtomicBool::new(false));
     let (tx, rx) = oneshot::channel();
 
     tokio::spawn({
         let stop_signal = stop_signal.clone();
+        let pause_signal = pause_signal.clone();
         async move {
             let device_state = DeviceState::new();
             let mut last_mouse_state = device_state.get_mouse();
             let start_time = Instant::now();
+            let mut paused_at: Option<Instant> = None;
+            let mut paused_duration = Duration::ZERO;
+
+            // Sample once per captured frame so the track lines up with the video
+            let mut interval =
+                tokio::time::interval(Duration::from_secs_f64(1.0 / fps.max(1) as f64));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
             let mut response = CursorActorResponse {
                 cursors: prev_cursors,
@@ -58,11 +85,23 @@ pub fn spawn_cursor_recorder(
             };
 
             // Create cursors directory if it doesn't exist
-            std::fs::create_dir_all(&cursors_dir).unwrap();
+            if let Err(e) = std::fs::create_dir_all(&cursors_dir) {
+                tracing::error!("Failed to create cursors directory: {e}");
+            }
 
             while !stop_signal.load(std::sync::atomic::Ordering::Relaxed) {
+                interval.tick().await;
+
+                if pause_signal.load(std::sync::atomic::Ordering::Relaxed) {
+                    paused_at.get_or_insert_with(Instant::now);
+                    continue;
+                } else if let Some(paused_at) = paused_at.take() {
+                    paused_duration += paused_at.elapsed();
+                    last_mouse_state = device_state.get_mouse();
+                }
+
                 let mouse_state = device_state.get_mouse();
-                let elapsed = start_time.elapsed().as_secs_f64() * 1000.0;
+                let elapsed = (start_time.elapsed() - paused_duration).as_secs_f64() * 1000.0;
                 let unix_time = chrono::Utc::now().timestamp_millis() as f64;
 
                 let cursor_data = get_cursor_image_data();
@@ -80,16 +119,16 @@ pub fn spawn_cursor_recorder(
                         let filename = format!("cursor_{}.png", cursor_id);
                         let cursor_path = cursors_dir.join(&filename);
 
-                        println!("Saving new cursor image to: {:?}", cursor_path);
+                        tracing::info!("Saving new cursor image to: {:?}", cursor_path);
 
                         if let Ok(image) = image::load_from_memory(&data) {
                             // Convert to RGBA
                             let rgba_image = image.into_rgba8();
 
                             if let Err(e) = rgba_image.save(&cursor_path) {
-                                eprintln!("Failed to save cursor image: {}", e);
+                                tracing::error!("Failed to save cursor image: {}", e);
                             } else {
-                                println!("Successfully saved cursor image {}", cursor_id);
+                                tracing::info!("Successfully saved cursor image {}", cursor_id);
                                 response
                                     .cursors
                                     .insert(id, (filename.clone(), response.next_cursor_id));
@@ -138,14 +177,17 @@ pub fn spawn_cursor_recorder(
                 }
 
                 last_mouse_state = mouse_state;
-                tokio::time::sleep(Duration::from_millis(10)).await;
             }
 
             tx.send(response).ok();
         }
     });
 
-    CursorActor { rx, stop_signal }
+    CursorActor {
+        rx,
+        stop_signal,
+        pause_signal,
+    }
 }
 
 #[cfg(target_os = "macos")]
@@ -327,3 +369,8 @@ fn get_cursor_image_data() -> Option<Vec<u8>> {
     //     Some(png_data)
     // }
 }
+
+#[cfg(target_os = "linux")]
+fn get_cursor_image_data() -> Option<Vec<u8>> {
+    None
+}
@@ -0,0 +1,267 @@
+use std::sync::{atomic::AtomicBool, Arc};
+
+use cap_project::KeystrokeEvent;
+use tokio::sync::oneshot;
+
+pub struct KeystrokeActor {
+    stop_signal: Arc<AtomicBool>,
+    pause_signal: Arc<AtomicBool>,
+    rx: oneshot::Receiver<Vec<KeystrokeEvent>>,
+}
+
+impl KeystrokeActor {
+    /// While paused no keys are recorded, and the paused time is excluded from timestamps
+    pub fn set_paused(&self, paused: bool) {
+        self.pause_signal
+            .store(paused, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub async fn stop(self) -> Vec<KeystrokeEvent> {
+        self.stop_signal
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        self.rx.await.unwrap_or_else(|_| {
+            tracing::error!("Keystroke recorder stopped unexpectedly");
+            vec![]
+        })
+    }
+}
+
+/// Starts recording key presses, or returns `None` if keys can't be observed on this platform
+/// or accessibility access hasn't been granted.
+///
+/// To avoid recording typed text such as passwords, only shortcuts using ⌘, ⌃ or ⌥
+/// and keys that don't produce text (arrows, return, function keys, etc.) are recorded.
+#[cfg(target_os = "macos")]
+pub fn spawn_keystroke_recorder() -> Option<KeystrokeActor> {
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrusted() -> bool;
+    }
+
+    if !unsafe { AXIsProcessTrusted() } {
+        tracing::info!("Accessibility access not granted, not recording keystrokes");
+        return None;
+    }
+
+    let stop_signal = Arc::new(AtomicBool::new(false));
+    let pause_signal = Arc::new(AtomicBool::new(false));
+    let (tx, rx) = oneshot::channel();
+
+    std::thread::spawn({
+        let stop_signal = stop_signal.clone();
+        let pause_signal = pause_signal.clone();
+        move || {
+            let presses = macos::record_keystrokes(&stop_signal, &pause_signal);
+            tx.send(presses).ok();
+        }
+    });
+
+    Some(KeystrokeActor {
+        stop_signal,
+        pause_signal,
+        rx,
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn spawn_keystroke_recorder() -> Option<KeystrokeActor> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::{
+        cell::{Cell, RefCell},
+        sync::atomic::{AtomicBool, Ordering},
+        time::{Duration, Instant},
+    };
+
+    use cap_project::KeystrokeEvent;
+    use core_foundation::runloop::{kCFRunLoopCommonModes, kCFRunLoopDefaultMode, CFRunLoop};
+    use core_graphics::event::{
+        CGEventFlags, CGEventTap, CGEventTapLocation, CGEventTapOptions, CGEventTapPlacement,
+        CGEventType, EventField,
+    };
+
+    /// Runs an event tap on the current thread until `stop_signal` is set
+    pub fn record_keystrokes(
+        stop_signal: &AtomicBool,
+        pause_signal: &AtomicBool,
+    ) -> Vec<KeystrokeEvent> {
+        let start_time = Instant::now();
+        let paused_duration = Cell::new(Duration::ZERO);
+        let presses = RefCell::new(vec![]);
+
+        let tap = CGEventTap::new(
+            CGEventTapLocation::HID,
+            CGEventTapPlacement::TailAppendEventTap,
+            CGEventTapOptions::ListenOnly,
+            vec![CGEventType::KeyDown],
+            |_, _, event| {
+                if pause_signal.load(Ordering::Relaxed)
+                    || event.get_integer_value_field(EventField::KEYBOARD_EVENT_AUTOREPEAT) != 0
+                {
+                    return None;
+                }
+
+                let keycode = event.get_integer_value_field(EventField::KEYBOARD_EVENT_KEYCODE);
+                if let Some(keys) = key_combo(keycode, event.get_flags()) {
+                    presses.borrow_mut().push(KeystrokeEvent {
+                        keys,
+                        process_time_ms: (start_time.elapsed() - paused_duration.get())
+                            .as_secs_f64()
+                            * 1000.0,
+                        unix_time_ms: chrono::Utc::now().timestamp_millis() as f64,
+                    });
+                }
+
+                None
+            },
+        );
+
+        let Ok(tap) = tap else {
+            tracing::error!("Failed to create keystroke event tap");
+            return vec![];
+        };
+
+        let Ok(source) = tap.mach_port.create_runloop_source(0) else {
+            tracing::error!("Failed to create keystroke run loop source");
+            return vec![];
+        };
+
+        let run_loop = CFRunLoop::get_current();
+        run_loop.add_source(&source, unsafe { kCFRunLoopCommonModes });
+        tap.enable();
+
+        let mut paused_at: Option<Instant> = None;
+        while !stop_signal.load(Ordering::Relaxed) {
+            CFRunLoop::run_in_mode(
+                unsafe { kCFRunLoopDefaultMode },
+                Duration::from_millis(100),
+                false,
+            );
+
+            if pause_signal.load(Ordering::Relaxed) {
+                paused_at.get_or_insert_with(Instant::now);
+            } else if let Some(paused_at) = paused_at.take() {
+                paused_duration.set(paused_duration.get() + paused_at.elapsed());
+            }
+        }
+
+        run_loop.remove_source(&source, unsafe { kCFRunLoopCommonModes });
+        drop(tap);
+
+        presses.into_inner()
+    }
+
+    fn key_combo(keycode: i64, flags: CGEventFlags) -> Option<String> {
+        let (key, produces_text) = key_name(keycode)?;
+
+        let shortcut = flags.intersects(
+            CGEventFlags::CGEventFlagCommand
+                | CGEventFlags::CGEventFlagControl
+                | CGEventFlags::CGEventFlagAlternate,
+        );
+        if produces_text && !shortcut {
+            return None;
+        }
+
+        let mut combo = String::new();
+        for (flag, symbol) in [
+            (CGEventFlags::CGEventFlagControl, "⌃"),
+            (CGEventFlags::CGEventFlagAlternate, "⌥"),
+            (CGEventFlags::CGEventFlagShift, "⇧"),
+            (CGEventFlags::CGEventFlagCommand, "⌘"),
+        ] {
+            if flags.contains(flag) {
+                combo.push_str(symbol);
+            }
+        }
+        combo.push_str(key);
+
+        Some(combo)
+    }
+
+    /// Names of the ANSI virtual key codes, and whether the key types text
+    fn key_name(keycode: i64) -> Option<(&'static str, bool)> {
+        let text = |name| Some((name, true));
+        let special = |name| Some((name, false));
+
+        match keycode {
+            0 => text("A"),
+            1 => text("S"),
+            2 => text("D"),
+            3 => text("F"),
+            4 => text("H"),
+            5 => text("G"),
+            6 => text("Z"),
+            7 => text("X"),
+            8 => text("C"),
+            9 => text("V"),
+            11 => text("B"),
+            12 => text("Q"),
+            13 => text("W"),
+            14 => text("E"),
+            15 => text("R"),
+            16 => text("Y"),
+            17 => text("T"),
+            18 => text("1"),
+            19 => text("2"),
+            20 => text("3"),
+            21 => text("4"),
+            22 => text("6"),
+            23 => text("5"),
+            24 => text("="),
+            25 => text("9"),
+            26 => text("7"),
+            27 => text("-"),
+            28 => text("8"),
+            29 => text("0"),
+            30 => text("]"),
+            31 => text("O"),
+            32 => text("U"),
+            33 => text("["),
+            34 => text("I"),
+            35 => text("P"),
+            37 => text("L"),
+            38 => text("J"),
+            39 => text("'"),
+            40 => text("K"),
+            41 => text(";"),
+            42 => text("\\"),
+            43 => text(","),
+            44 => text("/"),
+            45 => text("N"),
+            46 => text("M"),
+            47 => text("."),
+            49 => text("Space"),
+            50 => text("`"),
+            36 => special("↩"),
+            48 => special("⇥"),
+            51 => special("⌫"),
+            53 => special("⎋"),
+            117 => special("⌦"),
+            115 => special("Home"),
+            119 => special("End"),
+            116 => special("Page Up"),
+            121 => special("Page Down"),
+            123 => special("←"),
+            124 => special("→"),
+            125 => special("↓"),
+            126 => special("↑"),
+            122 => special("F1"),
+            120 => special("F2"),
+            99 => special("F3"),
+            118 => special("F4"),
+            96 => special("F5"),
+            97 => special("F6"),
+            98 => special("F7"),
+            100 => special("F8"),
+            101 => special("F9"),
+            109 => special("F10"),
+            103 => special("F11"),
+            111 => special("F12"),
+            _ => None,
+        }
+    }
+}
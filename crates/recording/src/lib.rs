@@ -1,9 +1,14 @@
 pub mod actor;
+mod clicks;
 mod cursor;
+mod keystrokes;
+#[cfg(target_os = "linux")]
+mod linux_capture;
 pub mod segmented_actor;
 
 pub use actor::{spawn_recording_actor, ActorHandle, CompletedRecording, RecordingError};
 
+use cap_flags::FLAGS;
 use cap_media::sources::*;
 use serde::{Deserialize, Serialize};
 
@@ -13,9 +18,45 @@ pub struct RecordingOptions {
     pub capture_target: ScreenCaptureTarget,
     pub camera_label: Option<String>,
     pub audio_input_name: Option<String>,
+    #[serde(default)]
+    pub fps: Option<u32>,
+    /// Records only the camera, which is saved as the display track
+    #[serde(default)]
+    pub camera_only: bool,
+    /// Seconds to count down before capture begins
+    #[serde(default)]
+    pub countdown_secs: Option<u32>,
+    /// Records system output through a loopback input device, if one is installed
+    #[serde(default)]
+    pub capture_system_audio: bool,
+    /// Resolution to request from the camera, otherwise the closest to 1080p is used
+    #[serde(default)]
+    pub camera_resolution: Option<(u32, u32)>,
+    /// Stops the recording once this many seconds have been recorded, excluding pauses
+    #[serde(default)]
+    pub max_duration_secs: Option<u32>,
+    #[serde(default)]
+    pub capture_cursor: CursorMode,
+}
+
+/// How the pointer ends up in a recording
+#[derive(specta::Type, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum CursorMode {
+    /// The OS cursor is captured as part of the screen
+    #[default]
+    Baked,
+    /// The OS cursor is left out of the capture and its movements are recorded instead,
+    /// so it can be drawn smoothly when rendering
+    Overlay,
+    /// Nothing of the cursor is captured or recorded
+    Hidden,
 }
 
 impl RecordingOptions {
+    pub const DEFAULT_FPS: u32 = 30;
+    pub const FPS_RANGE: std::ops::RangeInclusive<u32> = 1..=120;
+
     pub fn camera_label(&self) -> Option<&str> {
         self.camera_label.as_deref()
     }
@@ -23,4 +64,22 @@ impl RecordingOptions {
     pub fn audio_input_name(&self) -> Option<&str> {
         self.audio_input_name.as_deref()
     }
+
+    pub fn fps(&self) -> u32 {
+        self.fps.unwrap_or(Self::DEFAULT_FPS)
+    }
+
+    /// Whether the OS cursor is left in the screen capture
+    pub fn captures_os_cursor(&self) -> bool {
+        self.capture_cursor == CursorMode::Baked && !FLAGS.record_mouse
+    }
+
+    /// Whether cursor movements are recorded to be drawn when rendering
+    pub fn records_cursor(&self) -> bool {
+        match self.capture_cursor {
+            CursorMode::Overlay => true,
+            CursorMode::Baked => FLAGS.record_mouse,
+            CursorMode::Hidden => false,
+        }
+    }
 }
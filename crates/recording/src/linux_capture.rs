@@ -0,0 +1,72 @@
+use std::path::Path;
+
+use cap_ffmpeg_cli::{FFmpeg, FFmpegProcess};
+use cap_media::{platform::Bounds, sources::ScreenCaptureTarget, MediaError};
+
+/// Records the screen on Linux by handing capture off to ffmpeg's x11grab device,
+/// which also covers Wayland sessions running XWayland.
+pub struct LinuxScreenCapture {
+    process: FFmpegProcess,
+}
+
+impl LinuxScreenCapture {
+    pub fn start(
+        target: &ScreenCaptureTarget,
+        bounds: Bounds,
+        fps: u32,
+        output_path: &Path,
+    ) -> Result<Self, MediaError> {
+        let display = std::env::var("DISPLAY")
+            .map_err(|_| MediaError::Any("No X11 display available for screen capture"))?;
+
+        let mut ffmpeg = FFmpeg::new();
+        ffmpeg
+            .command
+            .args(["-loglevel", "error", "-nostats"])
+            .args(["-f", "x11grab"])
+            .args(["-framerate", &fps.to_string()]);
+
+        let input = match target {
+            // Hiding other applications' windows isn't possible with x11grab
+            ScreenCaptureTarget::Screen(_) | ScreenCaptureTarget::Application(_) => display,
+            ScreenCaptureTarget::Window(_) | ScreenCaptureTarget::Region { .. } => {
+                // libx264 with yuv420p requires even dimensions
+                let width = (bounds.width as u32) & !1;
+                let height = (bounds.height as u32) & !1;
+                if width == 0 || height == 0 {
+                    return Err(MediaError::Any("Capture area is empty"));
+                }
+
+                ffmpeg
+                    .command
+                    .args(["-video_size", &format!("{width}x{height}")]);
+                format!("{display}+{},{}", bounds.x as i32, bounds.y as i32)
+            }
+        };
+
+        ffmpeg
+            .command
+            .args(["-i", &input])
+            .args(["-c:v", "libx264", "-preset", "ultrafast"])
+            .args(["-pix_fmt", "yuv420p"])
+            .arg("-y")
+            .arg(output_path);
+
+        Ok(Self {
+            process: ffmpeg.start()?,
+        })
+    }
+
+    pub async fn stop(mut self) -> Result<(), MediaError> {
+        self.process.stop().await;
+        let status = self.process.wait().await?;
+
+        if !status.success() {
+            let stderr = self.process.read_stderr().await.unwrap_or_default();
+            tracing::error!("Linux screen capture exited with {status}: {stderr}");
+            return Err(MediaError::Any("Screen capture process failed"));
+        }
+
+        Ok(())
+    }
+}
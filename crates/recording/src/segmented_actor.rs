@@ -4,7 +4,6 @@ use std::{
     time::{SystemTime, UNIX_EPOCH},
 };
 
-use cap_flags::FLAGS;
 use cap_media::{
     encoders::{H264Encoder, MP3Encoder, Output},
     feeds::{AudioInputFeed, CameraFeed},
@@ -19,6 +18,7 @@ use thiserror::Error;
 use tokio::sync::{oneshot, Mutex};
 
 use crate::{
+    actor::DisplayProperties,
     cursor::{spawn_cursor_recorder, CursorActor, Cursors},
     RecordingOptions,
 };
@@ -48,6 +48,7 @@ pub struct Actor {
     recording_dir: PathBuf,
     options: RecordingOptions,
     segments: Vec<RecordingSegment>,
+    display_properties: DisplayProperties,
 }
 
 pub struct RecordingSegment {
@@ -134,7 +135,15 @@ pub async fn spawn_recording_actor(
     let segments_dir = ensure_dir(content_dir.join("segments"))?;
     let cursors_dir = ensure_dir(content_dir.join("cursors"))?;
 
-    let screen_source = create_screen_capture(&options);
+    if options.camera_only && camera_feed.is_none() {
+        return Err(MediaError::Any("Camera-only recording requires a camera").into());
+    }
+
+    let screen_source = (!options.camera_only).then(|| create_screen_capture(&options));
+    let display_properties = screen_source
+        .as_ref()
+        .map(DisplayProperties::of)
+        .unwrap_or_default();
 
     let index = 0;
     let pipeline = create_segment_pipeline(
@@ -146,6 +155,8 @@ pub async fn spawn_recording_actor(
         audio_input_feed.as_ref(),
         Default::default(),
         0,
+        options.records_cursor(),
+        options.fps(),
     )
     .await?;
 
@@ -161,6 +172,7 @@ pub async fn spawn_recording_actor(
                 recording_dir,
                 options,
                 segments: Vec::new(),
+                display_properties,
             };
 
             let mut state = ActorState::Recording {
@@ -192,7 +204,7 @@ pub async fn spawn_recording_actor(
                             let cursors = if let Some(cursor) = pipeline.cursor.take() {
                                 let res = cursor.actor.stop().await;
 
-                                dbg!(&res.cursors);
+                                tracing::debug!("Recorded cursors: {:?}", res.cursors);
 
                                 std::fs::write(
                                     &cursor.output_path,
@@ -276,6 +288,8 @@ pub async fn spawn_recording_actor(
                                     audio_input_feed.as_ref(),
                                     cursors,
                                     next_cursor_id,
+                                    actor.options.records_cursor(),
+                                    actor.options.fps(),
                                 )
                                 .await
                                 {
@@ -324,10 +338,19 @@ async fn stop_recording(
     let meta = RecordingMeta {
         project_path: actor.recording_dir.clone(),
         sharing: None,
+        notes: None,
+        tags: vec![],
         pretty_name: format!(
             "Cap {}",
             chrono::Local::now().format("%Y-%m-%d at %H.%M.%S")
         ),
+        created_at: actor
+            .segments
+            .first()
+            .and_then(|segment| {
+                chrono::DateTime::from_timestamp_millis((segment.start * 1000.0) as i64)
+            })
+            .map(|time| time.to_rfc3339()),
         content: Content::MultipleSegments {
             inner: MultipleSegments {
                 segments: {
@@ -342,6 +365,8 @@ async fn stop_recording(
                                     .strip_prefix(&actor.recording_dir)
                                     .unwrap()
                                     .to_owned(),
+                                scale_factor: actor.display_properties.scale_factor,
+                                color_space: actor.display_properties.color_space.clone(),
                             },
                             camera: s
                                 .pipeline
@@ -389,20 +414,24 @@ async fn stop_recording(
 fn create_screen_capture(
     recording_options: &RecordingOptions,
 ) -> ScreenCaptureSource<impl MakeCapturePipeline> {
+    tracing::debug!("Capture target: {:?}", recording_options.capture_target);
+
     #[cfg(target_os = "macos")]
     {
         ScreenCaptureSource::<cap_media::sources::CMSampleBufferCapture>::init(
-            dbg!(&recording_options.capture_target),
-            None,
+            &recording_options.capture_target,
+            Some(recording_options.fps()),
             None,
+            recording_options.captures_os_cursor(),
         )
     }
     #[cfg(not(target_os = "macos"))]
     {
         ScreenCaptureSource::<AVFrameCapture>::init(
-            dbg!(&recording_options.capture_target),
-            None,
+            &recording_options.capture_target,
+            Some(recording_options.fps()),
             None,
+            recording_options.captures_os_cursor(),
         )
     }
 }
@@ -411,11 +440,13 @@ async fn create_segment_pipeline<TCaptureFormat: MakeCapturePipeline>(
     segments_dir: &PathBuf,
     cursors_dir: &PathBuf,
     index: u32,
-    screen_source: ScreenCaptureSource<TCaptureFormat>,
+    screen_source: Option<ScreenCaptureSource<TCaptureFormat>>,
     camera_feed: Option<&Mutex<CameraFeed>>,
     audio_input_feed: Option<&AudioInputFeed>,
     prev_cursors: Cursors,
     next_cursors_id: i32,
+    record_cursor: bool,
+    fps: u32,
 ) -> Result<RecordingPipeline, MediaError> {
     let camera_feed = match camera_feed.as_ref() {
         Some(camera_feed) => Some(camera_feed.lock().await),
@@ -432,12 +463,16 @@ async fn create_segment_pipeline<TCaptureFormat: MakeCapturePipeline>(
     let mut audio_output_path = None;
     let mut camera_output_path = None;
 
-    let screen_bounds = screen_source.get_bounds();
-    pipeline_builder = TCaptureFormat::make_capture_pipeline(
-        pipeline_builder,
-        screen_source,
-        &display_output_path,
-    )?;
+    let screen = screen_source
+        .as_ref()
+        .map(|screen_source| (screen_source.get_bounds(), screen_source.fps()));
+    if let Some(screen_source) = screen_source {
+        pipeline_builder = TCaptureFormat::make_capture_pipeline(
+            pipeline_builder,
+            screen_source,
+            &display_output_path,
+        )?;
+    }
 
     if let Some(mic_source) = audio_input_feed.map(AudioInputSource::init) {
         let mic_config = mic_source.info();
@@ -458,15 +493,21 @@ async fn create_segment_pipeline<TCaptureFormat: MakeCapturePipeline>(
 
     if let Some(camera_source) = camera_feed.map(CameraSource::init) {
         let camera_config = camera_source.info();
-        let output_config = camera_config.scaled(1920, 30);
-        camera_output_path = Some(dir.join("camera.mp4"));
+
+        // Without a screen capture the camera is recorded at its native size as the display
+        let (output_config, output_path) = if screen.is_some() {
+            let path = dir.join("camera.mp4");
+            camera_output_path = Some(path.clone());
+            (camera_config.scaled(1920, 30), path)
+        } else {
+            (
+                camera_config.scaled(camera_config.width, fps),
+                display_output_path.clone(),
+            )
+        };
 
         let camera_filter = VideoFilter::init("camera", camera_config, output_config)?;
-        let camera_encoder = H264Encoder::init(
-            "camera",
-            output_config,
-            Output::File(camera_output_path.clone().unwrap()),
-        )?;
+        let camera_encoder = H264Encoder::init("camera", output_config, Output::File(output_path))?;
 
         pipeline_builder = pipeline_builder
             .source("camera_capture", camera_source)
@@ -476,19 +517,22 @@ async fn create_segment_pipeline<TCaptureFormat: MakeCapturePipeline>(
 
     let mut pipeline = pipeline_builder.build().await?;
 
-    let cursor = FLAGS.record_mouse.then(|| {
-        let cursor = spawn_cursor_recorder(
-            screen_bounds,
-            cursors_dir.clone(),
-            prev_cursors,
-            next_cursors_id,
-        );
-
-        CursorPipeline {
-            output_path: dir.join("cursor.json"),
-            actor: cursor,
-        }
-    });
+    let cursor = screen
+        .filter(|_| record_cursor)
+        .map(|(screen_bounds, screen_fps)| {
+            let cursor = spawn_cursor_recorder(
+                screen_bounds,
+                cursors_dir.clone(),
+                prev_cursors,
+                next_cursors_id,
+                screen_fps,
+            );
+
+            CursorPipeline {
+                output_path: dir.join("cursor.json"),
+                actor: cursor,
+            }
+        });
 
     pipeline.play().await?;
 
@@ -527,7 +571,7 @@ impl MakeCapturePipeline for cap_media::sources::CMSampleBufferCapture {
     ) -> Result<CapturePipelineBuilder, MediaError> {
         let screen_config = source.info();
 
-        let output_config = screen_config.scaled(1920, 30);
+        let output_config = screen_config.scaled(1920, source.fps());
         let screen_encoder = cap_media::encoders::H264AVAssetWriterEncoder::init(
             "screen",
             output_config,
@@ -552,7 +596,7 @@ impl MakeCapturePipeline for cap_media::sources::AVFrameCapture {
         let screen_config = source.info();
         // let screen_bounds = screen_source.bounds;
 
-        let output_config = screen_config.scaled(1920, 30);
+        let output_config = screen_config.scaled(1920, source.fps());
         let screen_filter = VideoFilter::init("screen", screen_config, output_config)?;
         let screen_encoder =
             H264Encoder::init("screen", output_config, Output::File(output_path.into()))?;
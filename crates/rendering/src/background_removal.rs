@@ -0,0 +1,176 @@
+use std::borrow::Cow;
+
+use cap_project::{BackgroundRemoval, XY};
+
+/// How far past `similarity` colors fade out, instead of a hard edge
+const BLEND: f32 = 0.08;
+/// Share of the frame's height sampled along its top edge to find the background's color
+const AUTO_EDGE_SIZE: u32 = 20;
+/// The edges' colors can only differ this much from their average, otherwise the
+/// background isn't plain enough to remove and the frame is left as is
+const AUTO_MAX_SPREAD: f32 = 0.05;
+const AUTO_SIMILARITY: f32 = 0.1;
+
+/// Makes the camera frame's background transparent before it's composited.
+/// Returns the frame unchanged without `removal`, or if the background can't be told apart.
+pub fn remove_background<'a>(
+    frame: &'a [u8],
+    frame_size: XY<u32>,
+    removal: Option<&BackgroundRemoval>,
+) -> Cow<'a, [u8]> {
+    let key = match removal {
+        None => return Cow::Borrowed(frame),
+        Some(BackgroundRemoval::ChromaKey { color, similarity }) => Key {
+            color: YCbCr::from_rgb(color.map(|c| c.min(255) as u8)),
+            // Only the hue matters, so shadows on a green screen are removed as well
+            use_luma: false,
+            similarity: similarity.clamp(0.0, 1.0),
+        },
+        Some(BackgroundRemoval::Auto) => match auto_key(frame, frame_size) {
+            Some(key) => key,
+            None => return Cow::Borrowed(frame),
+        },
+    };
+
+    let mut frame = frame.to_vec();
+
+    for pixel in frame.chunks_exact_mut(4) {
+        let distance = key.distance(YCbCr::from_rgb([pixel[0], pixel[1], pixel[2]]));
+        let alpha = ((distance - key.similarity) / BLEND).clamp(0.0, 1.0);
+        pixel[3] = (pixel[3] as f32 * alpha).round() as u8;
+    }
+
+    Cow::Owned(frame)
+}
+
+struct Key {
+    color: YCbCr,
+    use_luma: bool,
+    similarity: f32,
+}
+
+impl Key {
+    /// From 0 for the key's color to 1 for the furthest color from it
+    fn distance(&self, color: YCbCr) -> f32 {
+        let cb = color.cb - self.color.cb;
+        let cr = color.cr - self.color.cr;
+
+        if self.use_luma {
+            let y = color.y - self.color.y;
+            ((y * y + cb * cb + cr * cr) / 3.0).sqrt()
+        } else {
+            ((cb * cb + cr * cr) / 2.0).sqrt()
+        }
+    }
+}
+
+/// Picks the background's color from the top edge and the upper half of the sides,
+/// which are least likely to contain the presenter
+fn auto_key(frame: &[u8], frame_size: XY<u32>) -> Option<Key> {
+    let (width, height) = (frame_size.x as usize, frame_size.y as usize);
+    let edge = (frame_size.y / AUTO_EDGE_SIZE).max(1) as usize;
+
+    let samples = (0..height / 2)
+        .flat_map(|y| {
+            let columns: Box<dyn Iterator<Item = usize>> = if y < edge {
+                Box::new(0..width)
+            } else {
+                Box::new((0..edge.min(width)).chain(width.saturating_sub(edge)..width))
+            };
+            columns.map(move |x| (y * width + x) * 4)
+        })
+        .filter_map(|offset| frame.get(offset..offset + 3))
+        .map(|p| YCbCr::from_rgb([p[0], p[1], p[2]]))
+        .collect::<Vec<_>>();
+
+    if samples.is_empty() {
+        return None;
+    }
+
+    let count = samples.len() as f32;
+    let key = Key {
+        color: YCbCr {
+            y: samples.iter().map(|c| c.y).sum::<f32>() / count,
+            cb: samples.iter().map(|c| c.cb).sum::<f32>() / count,
+            cr: samples.iter().map(|c| c.cr).sum::<f32>() / count,
+        },
+        use_luma: true,
+        similarity: AUTO_SIMILARITY,
+    };
+
+    let spread = samples.iter().map(|c| key.distance(*c)).sum::<f32>() / count;
+    (spread <= AUTO_MAX_SPREAD).then_some(key)
+}
+
+/// BT.601 luma and chroma, scaled to 0-1 and centered on 0 respectively
+#[derive(Clone, Copy)]
+struct YCbCr {
+    y: f32,
+    cb: f32,
+    cr: f32,
+}
+
+impl YCbCr {
+    fn from_rgb([r, g, b]: [u8; 3]) -> Self {
+        let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+        Self {
+            y: 0.299 * r + 0.587 * g + 0.114 * b,
+            cb: -0.168736 * r - 0.331264 * g + 0.5 * b,
+            cr: 0.5 * r - 0.418688 * g - 0.081312 * b,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(size: XY<u32>, pixel: impl Fn(u32, u32) -> [u8; 3]) -> Vec<u8> {
+        (0..size.y)
+            .flat_map(|y| (0..size.x).map(move |x| (x, y)))
+            .flat_map(|(x, y)| {
+                let [r, g, b] = pixel(x, y);
+                [r, g, b, 255]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn chroma_key_removes_only_the_key_color() {
+        let size = XY::new(2, 1);
+        let frame = frame(
+            size,
+            |x, _| if x == 0 { [0, 200, 0] } else { [200, 150, 120] },
+        );
+
+        let removal = BackgroundRemoval::ChromaKey {
+            color: [0, 255, 0],
+            similarity: 0.2,
+        };
+        let keyed = remove_background(&frame, size, Some(&removal));
+
+        assert_eq!(keyed[3], 0);
+        assert_eq!(keyed[7], 255);
+    }
+
+    #[test]
+    fn auto_leaves_busy_backgrounds() {
+        let size = XY::new(40, 40);
+        let noisy = frame(size, |x, y| [(x * 37 % 256) as u8, (y * 91 % 256) as u8, 0]);
+
+        let keyed = remove_background(&noisy, size, Some(&BackgroundRemoval::Auto));
+        assert!(matches!(keyed, Cow::Borrowed(_)));
+
+        let plain = frame(size, |x, y| {
+            if (10..30).contains(&x) && y >= 20 {
+                [200, 150, 120]
+            } else {
+                [240, 240, 240]
+            }
+        });
+
+        let keyed = remove_background(&plain, size, Some(&BackgroundRemoval::Auto));
+        assert_eq!(keyed[3], 0);
+        assert_eq!(keyed[((30 * 40 + 20) * 4 + 3) as usize], 255);
+    }
+}
@@ -0,0 +1,163 @@
+use bytemuck::{Pod, Zeroable};
+use cap_project::{Content, CursorClickEvent, RecordingMeta, XY};
+use wgpu::{util::DeviceExt, CommandEncoder};
+
+use crate::{
+    create_shader_render_pipeline, do_render_pass, srgb_to_linear, Coord, ProjectUniforms,
+    RawDisplayUVSpace, RenderVideoConstants,
+};
+
+const RIPPLE_DURATION_MS: f64 = 500.0;
+/// Ripple radius relative to the output height
+const RELATIVE_RADIUS: f32 = 0.04;
+/// Must match the array size in click-ripple.wgsl
+const MAX_RIPPLES: usize = 8;
+
+#[derive(Debug, Clone, Copy, Pod, Zeroable, Default)]
+#[repr(C)]
+struct Ripple {
+    position: [f32; 2],
+    progress: f32,
+    _padding: f32,
+}
+
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+struct ClickRippleUniforms {
+    color: [f32; 4],
+    radius: f32,
+    count: u32,
+    _padding: [f32; 2],
+    ripples: [Ripple; MAX_RIPPLES],
+}
+
+pub struct ClickRipples {
+    clicks: Vec<CursorClickEvent>,
+    bind_group_layout: wgpu::BindGroupLayout,
+    render_pipeline: wgpu::RenderPipeline,
+}
+
+impl ClickRipples {
+    /// Returns `None` if no clicks were recorded
+    pub fn new(device: &wgpu::Device, meta: &RecordingMeta) -> Option<Self> {
+        let clicks = match &meta.content {
+            Content::SingleSegment { segment } => segment.click_events(meta).clicks,
+            Content::MultipleSegments { .. } => return None,
+        };
+
+        let clicks = clicks
+            .into_iter()
+            .filter(|click| click.down)
+            .collect::<Vec<_>>();
+
+        if clicks.is_empty() {
+            return None;
+        }
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("click-ripple.wgsl Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let render_pipeline = create_shader_render_pipeline(
+            device,
+            &bind_group_layout,
+            include_str!("shaders/click-ripple.wgsl"),
+            wgpu::BlendState::ALPHA_BLENDING,
+        );
+
+        Some(Self {
+            clicks,
+            bind_group_layout,
+            render_pipeline,
+        })
+    }
+
+    pub fn draw(
+        &self,
+        constants: &RenderVideoConstants,
+        uniforms: &ProjectUniforms,
+        time: f32,
+        encoder: &mut CommandEncoder,
+        view: &wgpu::TextureView,
+    ) {
+        let time_ms = time as f64 * 1000.0;
+
+        let mut ripples = [Ripple::default(); MAX_RIPPLES];
+        let mut count = 0;
+
+        let active_clicks = self.clicks.iter().filter(|click| {
+            let age = time_ms - click.process_time_ms;
+            (0.0..RIPPLE_DURATION_MS).contains(&age)
+                && (0.0..=1.0).contains(&click.x)
+                && (0.0..=1.0).contains(&click.y)
+        });
+
+        for click in active_clicks.take(MAX_RIPPLES) {
+            let frame_position = Coord::<RawDisplayUVSpace>::new(XY::new(click.x, click.y))
+                .to_frame_space(&constants.options, &uniforms.project);
+            let position = uniforms.zoom.apply_scale(frame_position);
+
+            ripples[count] = Ripple {
+                position: [position.x as f32, position.y as f32],
+                progress: ((time_ms - click.process_time_ms) / RIPPLE_DURATION_MS) as f32,
+                _padding: 0.0,
+            };
+            count += 1;
+        }
+
+        if count == 0 {
+            return;
+        }
+
+        let color = uniforms.project.click_highlight.color;
+        let ripple_uniforms = ClickRippleUniforms {
+            color: [
+                srgb_to_linear(color[0]),
+                srgb_to_linear(color[1]),
+                srgb_to_linear(color[2]),
+                0.8,
+            ],
+            radius: uniforms.output_size.1 as f32 * RELATIVE_RADIUS,
+            count: count as u32,
+            _padding: [0.0; 2],
+            ripples,
+        };
+
+        let buffer = constants
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Click Ripple Uniform Buffer"),
+                contents: bytemuck::cast_slice(&[ripple_uniforms]),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+
+        let bind_group = constants
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &self.bind_group_layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                }],
+                label: Some("Click Ripple Bind Group"),
+            });
+
+        do_render_pass(
+            encoder,
+            view,
+            &self.render_pipeline,
+            bind_group,
+            wgpu::LoadOp::Load,
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use cap_project::{CursorMoveEvent, XY};
+
+/// Standard deviation of the averaging window at full smoothing
+const MAX_SIGMA_MS: f64 = 60.0;
+/// Moves between two samples further than this, relative to the screen, are treated as the
+/// cursor jumping (eg. to another monitor) and aren't smoothed across
+const TELEPORT_DISTANCE: f64 = 0.3;
+/// Points sampled from the curve to average over the window
+const WINDOW_SAMPLES: usize = 16;
+
+/// Position at `time_ms` on a Catmull-Rom curve through the recorded moves, averaged over a
+/// gaussian window that widens with `smoothing` (0 to 1)
+pub fn smoothed_position(
+    moves: &[CursorMoveEvent],
+    time_ms: f64,
+    smoothing: f32,
+) -> Option<XY<f64>> {
+    if moves.is_empty() {
+        return None;
+    }
+
+    let sigma = MAX_SIGMA_MS * smoothing.clamp(0.0, 1.0) as f64;
+    let index = moves
+        .partition_point(|m| m.process_time_ms <= time_ms)
+        .saturating_sub(1);
+    let section = continuous_section(moves, index, time_ms - sigma * 2.0, time_ms + sigma * 2.0);
+
+    // The window stops at teleports so positions from before a jump don't leak past it
+    let from = (time_ms - sigma * 2.0).max(section[0].process_time_ms);
+    let to = (time_ms + sigma * 2.0).min(section[section.len() - 1].process_time_ms);
+
+    if sigma <= 0.0 || to <= from {
+        return Some(clamp(curve_position(section, time_ms)));
+    }
+
+    let mut sum = XY::new(0.0, 0.0);
+    let mut total_weight = 0.0;
+
+    for i in 0..=WINDOW_SAMPLES {
+        let t = from + (to - from) * i as f64 / WINDOW_SAMPLES as f64;
+        let weight = (-0.5 * ((t - time_ms) / sigma).powi(2)).exp();
+        let position = curve_position(section, t);
+
+        sum.x += position.x * weight;
+        sum.y += position.y * weight;
+        total_weight += weight;
+    }
+
+    Some(clamp(XY::new(sum.x / total_weight, sum.y / total_weight)))
+}
+
+fn is_teleport(a: &CursorMoveEvent, b: &CursorMoveEvent) -> bool {
+    (b.x - a.x).hypot(b.y - a.y) > TELEPORT_DISTANCE
+}
+
+/// The moves around `index` that can be reached without teleporting,
+/// only looking a couple of samples past `from` and `to`
+fn continuous_section(
+    moves: &[CursorMoveEvent],
+    index: usize,
+    from: f64,
+    to: f64,
+) -> &[CursorMoveEvent] {
+    let mut start = index;
+    while start > 0
+        && moves[start].process_time_ms > from
+        && !is_teleport(&moves[start - 1], &moves[start])
+    {
+        start -= 1;
+    }
+
+    let mut end = index;
+    while end + 1 < moves.len()
+        && moves[end].process_time_ms < to
+        && !is_teleport(&moves[end], &moves[end + 1])
+    {
+        end += 1;
+    }
+
+    // One more move on each side keeps the curve's tangents the same at the section's ends
+    if start > 0 && !is_teleport(&moves[start - 1], &moves[start]) {
+        start -= 1;
+    }
+    if end + 1 < moves.len() && !is_teleport(&moves[end], &moves[end + 1]) {
+        end += 1;
+    }
+
+    &moves[start..=end]
+}
+
+fn curve_position(points: &[CursorMoveEvent], time_ms: f64) -> XY<f64> {
+    let i = points.partition_point(|m| m.process_time_ms <= time_ms);
+
+    if i == 0 {
+        return XY::new(points[0].x, points[0].y);
+    }
+    if i == points.len() {
+        let last = &points[i - 1];
+        return XY::new(last.x, last.y);
+    }
+
+    let (p1, p2) = (&points[i - 1], &points[i]);
+    let p0 = if i >= 2 { &points[i - 2] } else { p1 };
+    let p3 = points.get(i + 1).unwrap_or(p2);
+
+    let span = p2.process_time_ms - p1.process_time_ms;
+    let u = if span > 0.0 {
+        ((time_ms - p1.process_time_ms) / span).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+
+    XY::new(
+        catmull_rom(p0.x, p1.x, p2.x, p3.x, u),
+        catmull_rom(p0.y, p1.y, p2.y, p3.y, u),
+    )
+}
+
+fn catmull_rom(p0: f64, p1: f64, p2: f64, p3: f64, u: f64) -> f64 {
+    let u2 = u * u;
+    let u3 = u2 * u;
+
+    0.5 * (2.0 * p1
+        + (p2 - p0) * u
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * u2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * u3)
+}
+
+fn clamp(position: XY<f64>) -> XY<f64> {
+    XY::new(position.x.clamp(0.0, 1.0), position.y.clamp(0.0, 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn moves(points: &[(f64, f64, f64)]) -> Vec<CursorMoveEvent> {
+        points
+            .iter()
+            .map(|&(process_time_ms, x, y)| CursorMoveEvent {
+                active_modifiers: vec![],
+                cursor_id: "0".to_string(),
+                process_time_ms,
+                unix_time_ms: process_time_ms,
+                x,
+                y,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn passes_through_recorded_moves() {
+        let moves = moves(&[(0.0, 0.1, 0.1), (100.0, 0.2, 0.3), (200.0, 0.3, 0.2)]);
+
+        let position = smoothed_position(&moves, 100.0, 0.0).unwrap();
+        assert!((position.x - 0.2).abs() < 1e-9);
+        assert!((position.y - 0.3).abs() < 1e-9);
+
+        let position = smoothed_position(&moves, 50.0, 0.0).unwrap();
+        assert!(position.x > 0.1 && position.x < 0.2);
+    }
+
+    #[test]
+    fn does_not_smooth_across_teleports() {
+        let moves = moves(&[
+            (0.0, 0.1, 0.5),
+            (16.0, 0.11, 0.5),
+            (32.0, 0.12, 0.5),
+            (48.0, 0.9, 0.5),
+            (64.0, 0.91, 0.5),
+        ]);
+
+        let before = smoothed_position(&moves, 40.0, 1.0).unwrap();
+        assert!(before.x < 0.13);
+
+        let after = smoothed_position(&moves, 48.0, 1.0).unwrap();
+        assert!(after.x >= 0.9);
+    }
+}
@@ -12,12 +12,22 @@ use ffmpeg::{
     Codec, Rational, Rescale,
 };
 use ffmpeg_hw_device::{CodecContextExt, HwDevice};
-use ffmpeg_sys_next::{avcodec_find_decoder, AVHWDeviceType};
+use ffmpeg_sys_next::{
+    av_display_rotation_get, av_packet_side_data_get, avcodec_find_decoder, sws_getCoefficients,
+    sws_setColorspaceDetails, AVHWDeviceType, AVPacketSideDataType, SWS_CS_BT2020,
+};
+
+use crate::tonemap::{HdrTransfer, Tonemapper};
 
 pub type DecodedFrame = Arc<Vec<u8>>;
 
 enum VideoDecoderMessage {
-    GetFrame(u32, tokio::sync::oneshot::Sender<Option<Arc<Vec<u8>>>>),
+    /// The frame number, and whether HDR frames should be tonemapped
+    GetFrame(
+        u32,
+        bool,
+        tokio::sync::oneshot::Sender<Option<Arc<Vec<u8>>>>,
+    ),
 }
 
 fn pts_to_frame(pts: i64, time_base: Rational) -> u32 {
@@ -42,22 +52,15 @@ impl CachedFrame {
         scaler_input_format: &mut format::Pixel,
         scaler: &mut scaling::Context,
         decoder: &codec::decoder::Video,
+        rotation: u32,
+        tonemapper: Option<&Tonemapper>,
     ) -> Arc<Vec<u8>> {
         match &mut self.data {
             CachedFrameData::Raw(frame) => {
                 if frame.format() != *scaler_input_format {
                     // Reinitialize the scaler with the new input format
                     *scaler_input_format = frame.format();
-                    *scaler = software::scaling::Context::get(
-                        *scaler_input_format,
-                        decoder.width(),
-                        decoder.height(),
-                        format::Pixel::RGBA,
-                        decoder.width(),
-                        decoder.height(),
-                        software::scaling::Flags::BILINEAR,
-                    )
-                    .unwrap();
+                    *scaler = create_scaler(*scaler_input_format, decoder, tonemapper.is_some());
                 }
 
                 let mut rgb_frame = frame::Video::empty();
@@ -68,13 +71,23 @@ impl CachedFrame {
                 let stride = rgb_frame.stride(0);
                 let data = rgb_frame.data(0);
 
-                let expected_size = width * height * 4;
+                let mut frame_buffer = if let Some(tonemapper) = tonemapper {
+                    tonemapper.apply(data, width, height, stride)
+                } else {
+                    let expected_size = width * height * 4;
 
-                let mut frame_buffer = Vec::with_capacity(expected_size);
+                    let mut frame_buffer = Vec::with_capacity(expected_size);
 
-                // account for stride > width
-                for line_data in data.chunks_exact(stride) {
-                    frame_buffer.extend_from_slice(&line_data[0..width * 4]);
+                    // account for stride > width
+                    for line_data in data.chunks_exact(stride) {
+                        frame_buffer.extend_from_slice(&line_data[0..width * 4]);
+                    }
+
+                    frame_buffer
+                };
+
+                if rotation != 0 {
+                    frame_buffer = rotate_rgba(&frame_buffer, width, height, rotation);
                 }
 
                 let data = Arc::new(frame_buffer);
@@ -118,10 +131,15 @@ impl AsyncVideoDecoder {
             let input_stream_index = input_stream.index();
             let time_base = input_stream.time_base();
             let frame_rate = input_stream.rate();
+            let rotation = stream_rotation(&input_stream);
 
             // Create a decoder for the video stream
             let mut decoder = context.decoder().video().unwrap();
 
+            let hdr_tonemapper = HdrTransfer::from_ffmpeg(decoder.color_transfer_characteristic())
+                .map(Tonemapper::new);
+            let mut tonemapping = hdr_tonemapper.is_some();
+
             {
                 use codec::threading::{Config, Type};
 
@@ -151,24 +169,15 @@ impl AsyncVideoDecoder {
                 None
             };
 
+            #[cfg(target_os = "macos")]
             use ffmpeg::format::Pixel;
-            use ffmpeg::software::scaling::{context::Context, flag::Flags};
 
             let mut scaler_input_format = hw_device
                 .as_ref()
                 .map(|d| d.pix_fmt)
                 .unwrap_or(decoder.format());
 
-            let mut scaler = Context::get(
-                scaler_input_format,
-                decoder.width(),
-                decoder.height(),
-                Pixel::RGBA,
-                decoder.width(),
-                decoder.height(),
-                Flags::BILINEAR,
-            )
-            .unwrap();
+            let mut scaler = create_scaler(scaler_input_format, &decoder, tonemapping);
 
             let mut temp_frame = ffmpeg::frame::Video::empty();
 
@@ -186,10 +195,25 @@ impl AsyncVideoDecoder {
 
             while let Ok(r) = peekable_requests.recv() {
                 match r {
-                    VideoDecoderMessage::GetFrame(requested_frame, sender) => {
+                    VideoDecoderMessage::GetFrame(requested_frame, tonemap, sender) => {
+                        // Frames that were already converted have to be decoded again
+                        if hdr_tonemapper.is_some() && tonemap != tonemapping {
+                            tonemapping = tonemap;
+                            scaler = create_scaler(scaler_input_format, &decoder, tonemapping);
+                            cache.clear();
+                            last_decoded_frame = None;
+                            last_sent_frame = None;
+                        }
+                        let tonemapper = hdr_tonemapper.as_ref().filter(|_| tonemapping);
+
                         let mut sender = if let Some(cached) = cache.get_mut(&requested_frame) {
-                            let data =
-                                cached.process(&mut scaler_input_format, &mut scaler, &decoder);
+                            let data = cached.process(
+                                &mut scaler_input_format,
+                                &mut scaler,
+                                &decoder,
+                                rotation,
+                                tonemapper,
+                            );
 
                             sender.send(Some(data.clone())).ok();
                             last_sent_frame = Some((requested_frame, data));
@@ -216,10 +240,14 @@ impl AsyncVideoDecoder {
                                     * 1_000_000.0) as i64;
                             let position = timestamp_us.rescale((1, 1_000_000), rescale::TIME_BASE);
 
-                            println!("seeking to {position} for frame {requested_frame}");
+                            tracing::debug!("seeking to {position} for frame {requested_frame}");
 
                             decoder.flush();
-                            input.seek(position, ..position).unwrap();
+                            // Seeking past the end of a truncated file can fail,
+                            // which is treated the same as running out of frames
+                            if let Err(e) = input.seek(position, ..position) {
+                                tracing::warn!("Failed to seek to {position}: {e}");
+                            }
                             cache.clear();
                             last_decoded_frame = None;
                             last_sent_frame = None;
@@ -306,6 +334,8 @@ impl AsyncVideoDecoder {
                                                     &mut scaler_input_format,
                                                     &mut scaler,
                                                     &decoder,
+                                                    rotation,
+                                                    tonemapper,
                                                 );
                                                 last_sent_frame =
                                                     Some((current_frame, data.clone()));
@@ -369,11 +399,12 @@ pub struct AsyncVideoDecoderHandle {
 }
 
 impl AsyncVideoDecoderHandle {
-    pub async fn get_frame(&self, time: u32) -> Option<Arc<Vec<u8>>> {
+    /// `tonemap` converts HDR frames to SDR, it has no effect on SDR videos
+    pub async fn get_frame(&self, time: u32, tonemap: bool) -> Option<Arc<Vec<u8>>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.sender
-            .send(VideoDecoderMessage::GetFrame(time, tx))
-            .unwrap();
+            .send(VideoDecoderMessage::GetFrame(time, tonemap, tx))
+            .ok()?;
         let res = rx.await.ok().flatten();
         res
     }
@@ -400,7 +431,7 @@ impl<T> PeekableReceiver<T> {
     }
 
     fn try_recv(&mut self) -> Result<T, mpsc::TryRecvError> {
-        println!("try_recv");
+        tracing::trace!("try_recv");
         if let Some(value) = self.peeked.take() {
             Ok(value)
         } else {
@@ -417,6 +448,103 @@ impl<T> PeekableReceiver<T> {
     }
 }
 
+/// Converts decoded frames to RGBA, or to 16 bit RGBA in BT.2020 for HDR frames that are
+/// tonemapped afterwards
+fn create_scaler(
+    input_format: format::Pixel,
+    decoder: &codec::decoder::Video,
+    hdr: bool,
+) -> scaling::Context {
+    let output_format = if hdr {
+        format::Pixel::RGBA64LE
+    } else {
+        format::Pixel::RGBA
+    };
+
+    let mut scaler = software::scaling::Context::get(
+        input_format,
+        decoder.width(),
+        decoder.height(),
+        output_format,
+        decoder.width(),
+        decoder.height(),
+        software::scaling::Flags::BILINEAR,
+    )
+    .unwrap();
+
+    // The scaler assumes BT.601 YUV unless told otherwise
+    if hdr {
+        let full_range = decoder.color_range() == ffmpeg::color::Range::JPEG;
+        unsafe {
+            let coefficients = sws_getCoefficients(SWS_CS_BT2020 as i32);
+            sws_setColorspaceDetails(
+                scaler.as_mut_ptr(),
+                coefficients,
+                full_range as i32,
+                coefficients,
+                1,
+                0,
+                1 << 16,
+                1 << 16,
+            );
+        }
+    }
+
+    scaler
+}
+
+/// Clockwise rotation in degrees that turns the stream's frames upright, read from the
+/// display matrix in its metadata. Players apply it automatically, but decoded frames aren't.
+pub(crate) fn stream_rotation(stream: &format::stream::Stream) -> u32 {
+    unsafe {
+        let parameters = (*stream.as_ptr()).codecpar;
+        let side_data = av_packet_side_data_get(
+            (*parameters).coded_side_data,
+            (*parameters).nb_coded_side_data,
+            AVPacketSideDataType::AV_PKT_DATA_DISPLAYMATRIX,
+        );
+
+        // The display matrix is 9 32-bit values
+        if side_data.is_null() || (*side_data).size < 9 * 4 {
+            return 0;
+        }
+
+        rotation_from_angle(av_display_rotation_get((*side_data).data as *const i32))
+    }
+}
+
+/// Converts a counter-clockwise angle to the nearest clockwise quarter turn
+fn rotation_from_angle(angle: f64) -> u32 {
+    if !angle.is_finite() {
+        return 0;
+    }
+
+    ((-angle / 90.0).round() as i32).rem_euclid(4) as u32 * 90
+}
+
+/// Rotates an RGBA frame clockwise by a multiple of 90 degrees.
+/// Frames turned by 90 or 270 degrees have their width and height swapped.
+fn rotate_rgba(data: &[u8], width: usize, height: usize, rotation: u32) -> Vec<u8> {
+    let mut rotated = vec![0; data.len()];
+
+    for y in 0..height {
+        for x in 0..width {
+            let (to_x, to_y, to_width) = match rotation {
+                90 => (height - 1 - y, x, height),
+                180 => (width - 1 - x, height - 1 - y, width),
+                270 => (y, width - 1 - x, height),
+                _ => (x, y, width),
+            };
+
+            let from = (y * width + x) * 4;
+            let to = (to_y * to_width + to_x) * 4;
+            rotated[to..to + 4].copy_from_slice(&data[from..from + 4]);
+        }
+    }
+
+    rotated
+}
+
 fn ff_find_decoder(
     s: &format::context::Input,
     st: &format::stream::Stream,
@@ -445,3 +573,58 @@ fn ff_find_decoder(
         Some(Codec::wrap(found))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_from_display_matrix_angle() {
+        assert_eq!(rotation_from_angle(0.0), 0);
+        assert_eq!(rotation_from_angle(-90.0), 90);
+        assert_eq!(rotation_from_angle(90.0), 270);
+        assert_eq!(rotation_from_angle(180.0), 180);
+        assert_eq!(rotation_from_angle(-180.0), 180);
+        assert_eq!(rotation_from_angle(-89.9), 90);
+        assert_eq!(rotation_from_angle(f64::NAN), 0);
+    }
+
+    // 2x3 frame with each pixel's red channel set to its index
+    fn frame() -> Vec<u8> {
+        (0..6).flat_map(|i| [i, 0, 0, 255]).collect()
+    }
+
+    fn red(data: &[u8]) -> Vec<u8> {
+        data.chunks_exact(4).map(|pixel| pixel[0]).collect()
+    }
+
+    #[test]
+    fn rotate_rgba_quarter_turns() {
+        // 0 1
+        // 2 3
+        // 4 5
+        assert_eq!(red(&rotate_rgba(&frame(), 2, 3, 0)), [0, 1, 2, 3, 4, 5]);
+        assert_eq!(red(&rotate_rgba(&frame(), 2, 3, 90)), [4, 2, 0, 5, 3, 1]);
+        assert_eq!(red(&rotate_rgba(&frame(), 2, 3, 180)), [5, 4, 3, 2, 1, 0]);
+        assert_eq!(red(&rotate_rgba(&frame(), 2, 3, 270)), [1, 3, 5, 0, 2, 4]);
+    }
+
+    #[tokio::test]
+    async fn rotated_video_frames_are_turned_upright() {
+        // 64x32 with its top left 16x16 block bright, turned 90 degrees clockwise on display
+        let path = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/rotated.mp4"));
+        let (width, height) = (32, 64);
+
+        let frame = AsyncVideoDecoder::spawn(path)
+            .get_frame(0, false)
+            .await
+            .unwrap();
+
+        assert_eq!(frame.len(), width * height * 4);
+
+        let brightness = |x: usize, y: usize| frame[(y * width + x) * 4];
+        assert!(brightness(width - 1, 0) > 200);
+        assert!(brightness(0, 0) < 60);
+        assert!(brightness(width - 1, height - 1) < 60);
+    }
+}
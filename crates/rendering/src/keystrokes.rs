@@ -0,0 +1,185 @@
+use ab_glyph::{point, Font, FontVec, PxScale, ScaleFont};
+use cap_project::{Content, KeystrokeEvent, RecordingMeta};
+use std::collections::HashMap;
+use wgpu::CommandEncoder;
+
+use crate::{
+    create_rgba_texture, draw_with_cursor_pipeline, CursorUniforms, ProjectUniforms,
+    RenderVideoConstants,
+};
+
+/// How long each key press stays on screen
+const DISPLAY_DURATION_MS: f64 = 1500.0;
+const FONT_SIZE: f32 = 64.0;
+/// Height of the overlay relative to the output height
+const RELATIVE_HEIGHT: f32 = 0.07;
+
+#[cfg(target_os = "macos")]
+const FONT_PATHS: &[&str] = &[
+    "/System/Library/Fonts/SFNS.ttf",
+    "/System/Library/Fonts/Helvetica.ttc",
+];
+#[cfg(windows)]
+const FONT_PATHS: &[&str] = &[
+    "C:\\Windows\\Fonts\\segoeui.ttf",
+    "C:\\Windows\\Fonts\\arial.ttf",
+];
+#[cfg(target_os = "linux")]
+const FONT_PATHS: &[&str] = &[
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/TTF/DejaVuSans.ttf",
+];
+
+pub struct KeystrokeOverlay {
+    presses: Vec<KeystrokeEvent>,
+    textures: HashMap<String, wgpu::Texture>,
+}
+
+impl KeystrokeOverlay {
+    /// Returns `None` if no keystrokes were recorded or no font could be loaded
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue, meta: &RecordingMeta) -> Option<Self> {
+        let presses = match &meta.content {
+            Content::SingleSegment { segment } => segment.keystroke_events(meta).presses,
+            Content::MultipleSegments { .. } => return None,
+        };
+
+        if presses.is_empty() {
+            return None;
+        }
+
+        let Some(font) = load_font() else {
+            tracing::warn!("No font found for the keystroke overlay");
+            return None;
+        };
+
+        let mut textures = HashMap::new();
+        for press in &presses {
+            if textures.contains_key(&press.keys) {
+                continue;
+            }
+
+            let label = render_label(&font, &press.keys);
+            let texture = create_rgba_texture(
+                device,
+                queue,
+                &format!("Keystroke Texture {}", press.keys),
+                &label,
+            );
+            textures.insert(press.keys.clone(), texture);
+        }
+
+        Some(Self { presses, textures })
+    }
+
+    pub fn draw(
+        &self,
+        constants: &RenderVideoConstants,
+        uniforms: &ProjectUniforms,
+        time: f32,
+        encoder: &mut CommandEncoder,
+        view: &wgpu::TextureView,
+    ) {
+        let time_ms = time as f64 * 1000.0;
+
+        let Some(press) = self
+            .presses
+            .iter()
+            .take_while(|press| press.process_time_ms <= time_ms)
+            .last()
+            .filter(|press| time_ms - press.process_time_ms < DISPLAY_DURATION_MS)
+        else {
+            return;
+        };
+
+        let Some(texture) = self.textures.get(&press.keys) else {
+            return;
+        };
+
+        let (output_width, output_height) =
+            (uniforms.output_size.0 as f32, uniforms.output_size.1 as f32);
+        let height = output_height * RELATIVE_HEIGHT;
+        let width = height * texture.width() as f32 / texture.height() as f32;
+
+        let keystroke_uniforms = CursorUniforms::overlay(
+            uniforms,
+            [output_width / 2.0, output_height - height * 1.5],
+            [width, height],
+            1.0,
+        );
+
+        draw_with_cursor_pipeline(constants, keystroke_uniforms, texture, encoder, view);
+    }
+}
+
+fn load_font() -> Option<FontVec> {
+    FONT_PATHS.iter().find_map(|path| {
+        let data = std::fs::read(path).ok()?;
+        FontVec::try_from_vec_and_index(data, 0).ok()
+    })
+}
+
+/// Draws `text` in white on a translucent rounded rectangle
+fn render_label(font: &FontVec, text: &str) -> image::RgbaImage {
+    let scale = PxScale::from(FONT_SIZE);
+    let scaled_font = font.as_scaled(scale);
+    let padding = FONT_SIZE * 0.5;
+    let baseline = padding / 2.0 + scaled_font.ascent();
+
+    let mut glyphs = vec![];
+    let mut x = padding;
+    let mut previous = None;
+    for c in text.chars() {
+        let id = scaled_font.glyph_id(c);
+        if let Some(previous) = previous {
+            x += scaled_font.kern(previous, id);
+        }
+        glyphs.push(id.with_scale_and_position(scale, point(x, baseline)));
+        x += scaled_font.h_advance(id);
+        previous = Some(id);
+    }
+
+    let width = (x + padding).ceil() as u32;
+    let height = (scaled_font.height() + padding).ceil() as u32;
+    let radius = height as f32 / 4.0;
+
+    let mut image = image::RgbaImage::from_fn(width, height, |px, py| {
+        let (px, py) = (px as f32 + 0.5, py as f32 + 0.5);
+        let dx = (radius - px).max(px - (width as f32 - radius)).max(0.0);
+        let dy = (radius - py).max(py - (height as f32 - radius)).max(0.0);
+
+        if dx * dx + dy * dy <= radius * radius {
+            image::Rgba([0, 0, 0, 180])
+        } else {
+            image::Rgba([0, 0, 0, 0])
+        }
+    });
+
+    for glyph in glyphs {
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            continue;
+        };
+
+        let bounds = outlined.px_bounds();
+        outlined.draw(|gx, gy, coverage| {
+            let (px, py) = (
+                bounds.min.x as i32 + gx as i32,
+                bounds.min.y as i32 + gy as i32,
+            );
+            if px < 0 || py < 0 || px >= width as i32 || py >= height as i32 {
+                return;
+            }
+
+            let pixel = image.get_pixel_mut(px as u32, py as u32);
+            let alpha = pixel[3] as f32;
+            let value = (255.0 * coverage) as u8;
+            *pixel = image::Rgba([
+                value,
+                value,
+                value,
+                (alpha + (255.0 - alpha) * coverage) as u8,
+            ]);
+        });
+    }
+
+    image
+}
@@ -13,23 +13,32 @@ use wgpu::util::DeviceExt;
 use wgpu::{CommandEncoder, COPY_BYTES_PER_ROW_ALIGNMENT};
 
 use cap_project::{
-    AspectRatio, BackgroundSource, CameraXPosition, CameraYPosition, Content, Crop,
-    CursorAnimationStyle, CursorClickEvent, CursorData, CursorEvents, CursorMoveEvent,
-    ProjectConfiguration, RecordingMeta, FAST_SMOOTHING_SAMPLES, FAST_VELOCITY_THRESHOLD,
+    AspectRatio, BackgroundSource, Camera, CameraXPosition, CameraYPosition, Content, Crop,
+    CursorAnimationStyle, CursorConfiguration, CursorEvents, CursorMoveEvent, Layout,
+    ProjectConfiguration, RecordingMeta, ZoomMode, FAST_SMOOTHING_SAMPLES, FAST_VELOCITY_THRESHOLD,
     REGULAR_SMOOTHING_SAMPLES, REGULAR_VELOCITY_THRESHOLD, SLOW_SMOOTHING_SAMPLES,
     SLOW_VELOCITY_THRESHOLD, XY,
 };
 
 use image::GenericImageView;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
+mod background_removal;
+mod clicks;
+mod cursor_smoothing;
 pub mod decoder;
+mod keystrokes;
 mod project_recordings;
+mod redaction;
+pub mod tonemap;
+mod watermark;
 pub use decoder::DecodedFrame;
 pub use project_recordings::{ProjectRecordings, SegmentRecordings};
 
 const STANDARD_CURSOR_HEIGHT: f32 = 75.0;
+/// Display scale factor `STANDARD_CURSOR_HEIGHT` is tuned for, ie. Retina displays
+const STANDARD_CURSOR_SCALE_FACTOR: f64 = 2.0;
 
 #[derive(Debug, Clone, Copy, Type)]
 pub struct RenderOptions {
@@ -37,7 +46,7 @@ pub struct RenderOptions {
     pub screen_size: XY<u32>,
 }
 
-#[derive(Debug, Clone, Type)]
+#[derive(Debug, Clone, Copy, Type)]
 pub struct WebcamStyle {
     pub border_radius: f32,
     pub shadow_color: [f32; 4],
@@ -45,7 +54,30 @@ pub struct WebcamStyle {
     pub shadow_offset: (f32, f32),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Type, Copy)]
+const MAX_SHADOW_OPACITY: f32 = 0.6;
+
+impl WebcamStyle {
+    /// Pixel-space style for a webcam overlay of the given size.
+    pub fn new(camera: &Camera, size: f32) -> Self {
+        let shadow = (camera.shadow / 100.0).clamp(0.0, 1.0);
+        let shadow_blur = shadow * size * 0.15;
+
+        Self {
+            border_radius: rounding_px(camera.rounding, size),
+            shadow_color: [0.0, 0.0, 0.0, shadow * MAX_SHADOW_OPACITY],
+            shadow_blur,
+            shadow_offset: (0.0, shadow_blur * 0.25),
+        }
+    }
+}
+
+/// Converts a 0-100 rounding percentage to a corner radius that never exceeds
+/// half of `min_axis`, so opposing corners can't overlap
+fn rounding_px(rounding: f32, min_axis: f32) -> f32 {
+    rounding.clamp(0.0, 100.0) / 100.0 * 0.5 * min_axis
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub enum Background {
     Color([f32; 4]),
     Gradient {
@@ -53,6 +85,9 @@ pub enum Background {
         end: [f32; 4],
         angle: f32,
     },
+    Image {
+        path: PathBuf,
+    },
 }
 
 impl From<BackgroundSource> for Background {
@@ -79,7 +114,12 @@ impl From<BackgroundSource> for Background {
                 ],
                 angle: angle as f32,
             },
-            _ => unimplemented!(),
+            BackgroundSource::Image { path: Some(path) } => Background::Image {
+                path: PathBuf::from(path),
+            },
+            BackgroundSource::Image { path: None } | BackgroundSource::Wallpaper { .. } => {
+                Background::from(BackgroundSource::default())
+            }
         }
     }
 }
@@ -100,7 +140,9 @@ impl RecordingSegmentDecoders {
         let screen = AsyncVideoDecoder::spawn(meta.project_path.join(segment.display));
         let camera = segment
             .camera
-            .map(|camera| AsyncVideoDecoder::spawn(meta.project_path.join(camera)));
+            .map(|camera| meta.project_path.join(camera))
+            .filter(|camera| project_recordings::Video::camera(camera).is_some())
+            .map(AsyncVideoDecoder::spawn);
 
         Self { screen, camera }
     }
@@ -108,10 +150,15 @@ impl RecordingSegmentDecoders {
     pub async fn get_frames(
         &self,
         frame_number: u32,
+        tonemap: bool,
     ) -> Option<(DecodedFrame, Option<DecodedFrame>)> {
         let (screen_frame, camera_frame) = tokio::join!(
-            self.screen.get_frame(frame_number),
-            OptionFuture::from(self.camera.as_ref().map(|d| d.get_frame(frame_number)))
+            self.screen.get_frame(frame_number, tonemap),
+            OptionFuture::from(
+                self.camera
+                    .as_ref()
+                    .map(|d| d.get_frame(frame_number, tonemap))
+            )
         );
 
         screen_frame.map(|f| (f, camera_frame.flatten()))
@@ -130,6 +177,11 @@ pub enum RenderingError {
     BufferMapFailed(#[from] wgpu::BufferAsyncError),
     #[error("Sending frame to channel failed")]
     ChannelSendFrameFailed(#[from] mpsc::error::SendError<Vec<u8>>),
+    #[error("Failed to load background image {path:?}: {source}")]
+    BackgroundImage {
+        path: PathBuf,
+        source: image::ImageError,
+    },
 }
 
 pub struct RenderSegment {
@@ -147,7 +199,7 @@ pub async fn render_video_to_channel(
     let constants = RenderVideoConstants::new(options, meta).await?;
     let recordings = ProjectRecordings::new(meta);
 
-    println!("Setting up FFmpeg input for screen recording...");
+    tracing::debug!("Setting up FFmpeg input for screen recording...");
 
     ffmpeg::init().unwrap();
 
@@ -171,7 +223,7 @@ pub async fn render_video_to_channel(
             match timeline.get_recording_time(frame_number as f64 / 30_f64) {
                 Some(value) => value,
                 None => {
-                    println!("no time");
+                    tracing::debug!("no time");
                     break;
                 }
             }
@@ -183,14 +235,17 @@ pub async fn render_video_to_channel(
 
         let uniforms = ProjectUniforms::new(&constants, &project, time as f32);
 
-        if let Some((screen_frame, camera_frame)) =
-            segment.decoders.get_frames((time * 30.0) as u32).await
+        if let Some((screen_frame, camera_frame)) = segment
+            .decoders
+            .get_frames((time * 30.0) as u32, project.tonemap_hdr)
+            .await
         {
             let frame = produce_frame(
                 &constants,
                 &screen_frame,
                 &camera_frame,
-                background,
+                &segment.cursor,
+                &background,
                 &uniforms,
                 time as f32,
             )
@@ -198,13 +253,13 @@ pub async fn render_video_to_channel(
 
             sender.send(frame).await?;
         } else {
-            println!("no decoder frames: {:?}", (time, segment_i));
+            tracing::warn!("no decoder frames: {:?}", (time, segment_i));
         };
 
         frame_number += 1;
         if frame_number % 60 == 0 {
             let elapsed = start_time.elapsed();
-            println!(
+            tracing::info!(
                 "Rendered {} frames in {:?} seconds",
                 frame_number,
                 elapsed.as_secs_f32()
@@ -212,12 +267,12 @@ pub async fn render_video_to_channel(
         }
     }
 
-    println!("Render loop exited");
+    tracing::info!("Render loop exited");
 
     let total_frames = frame_number;
 
     let total_time = start_time.elapsed();
-    println!(
+    tracing::info!(
         "Render complete. Processed {} frames in {:?} seconds",
         total_frames,
         total_time.as_secs_f32()
@@ -236,11 +291,17 @@ pub struct RenderVideoConstants {
     gradient_or_color_pipeline: GradientOrColorPipeline,
     pub cursor_textures: HashMap<String, wgpu::Texture>,
     cursor_pipeline: CursorPipeline,
+    click_ripples: Option<clicks::ClickRipples>,
+    keystroke_overlay: Option<keystrokes::KeystrokeOverlay>,
+    watermark_textures: watermark::WatermarkTextures,
+    background_textures: std::sync::Mutex<HashMap<PathBuf, Arc<wgpu::Texture>>>,
+    /// `None` for recordings made before it was recorded
+    display_scale_factor: Option<f64>,
 }
 
 impl RenderVideoConstants {
     pub async fn new(options: RenderOptions, meta: &RecordingMeta) -> Result<Self, RenderingError> {
-        println!("Initializing wgpu...");
+        tracing::debug!("Initializing wgpu...");
         let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
         let adapter = instance
             .request_adapter(&wgpu::RequestAdapterOptions::default())
@@ -254,6 +315,16 @@ impl RenderVideoConstants {
         let cursor_textures = Self::load_cursor_textures(&device, &queue, meta);
 
         let cursor_pipeline = CursorPipeline::new(&device);
+        let click_ripples = clicks::ClickRipples::new(&device, meta);
+        let keystroke_overlay = keystrokes::KeystrokeOverlay::new(&device, &queue, meta);
+
+        let display_scale_factor = match &meta.content {
+            Content::SingleSegment { segment } => segment.display.scale_factor,
+            Content::MultipleSegments { inner } => inner
+                .segments
+                .first()
+                .and_then(|segment| segment.display.scale_factor),
+        };
 
         Ok(Self {
             composite_video_frame_pipeline: CompositeVideoFramePipeline::new(&device),
@@ -265,23 +336,87 @@ impl RenderVideoConstants {
             options,
             cursor_textures,
             cursor_pipeline,
+            click_ripples,
+            keystroke_overlay,
+            watermark_textures: Default::default(),
+            background_textures: Default::default(),
+            display_scale_factor,
         })
     }
 
+    fn background_texture(&self, path: &Path) -> Result<Arc<wgpu::Texture>, RenderingError> {
+        let mut textures = self.background_textures.lock().unwrap();
+
+        if let Some(texture) = textures.get(path) {
+            return Ok(texture.clone());
+        }
+
+        let img = image::open(path).map_err(|source| {
+            tracing::warn!("Failed to load background image {:?}: {}", path, source);
+            RenderingError::BackgroundImage {
+                path: path.to_path_buf(),
+                source,
+            }
+        })?;
+
+        let dimensions = img.dimensions();
+        let rgba = img.into_rgba8();
+
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Background Image Texture"),
+            size: wgpu::Extent3d {
+                width: dimensions.0,
+                height: dimensions.1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        self.queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            &rgba,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * dimensions.0),
+                rows_per_image: None,
+            },
+            wgpu::Extent3d {
+                width: dimensions.0,
+                height: dimensions.1,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        let texture = Arc::new(texture);
+        textures.insert(path.to_path_buf(), texture.clone());
+
+        Ok(texture)
+    }
+
     fn load_cursor_textures(
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         meta: &RecordingMeta,
     ) -> HashMap<String, wgpu::Texture> {
-        println!("Starting to load cursor textures");
-        println!("Project path: {:?}", meta.project_path);
+        tracing::debug!("Starting to load cursor textures");
+        tracing::debug!("Project path: {:?}", meta.project_path);
         // println!("Cursor images to load: {:?}", cursor.cursor_images);
 
         let mut textures = HashMap::new();
 
         // Create the full path to the cursors directory
         let cursors_dir = meta.project_path.join("content").join("cursors");
-        println!("Cursors directory: {:?}", cursors_dir);
+        tracing::debug!("Cursors directory: {:?}", cursors_dir);
 
         let cursor_images = match &meta.content {
             Content::SingleSegment { segment } => segment.cursor_data(meta).cursor_images,
@@ -289,74 +424,44 @@ impl RenderVideoConstants {
         };
 
         for (cursor_id, filename) in &cursor_images.0 {
-            println!("Loading cursor image: {} -> {}", cursor_id, filename);
+            tracing::debug!("Loading cursor image: {} -> {}", cursor_id, filename);
 
             let cursor_path = cursors_dir.join(filename);
-            println!("Full cursor path: {:?}", cursor_path);
+            tracing::debug!("Full cursor path: {:?}", cursor_path);
 
             if !cursor_path.exists() {
-                println!("Cursor image file does not exist: {:?}", cursor_path);
+                tracing::warn!("Cursor image file does not exist: {:?}", cursor_path);
                 continue;
             }
 
             match image::open(&cursor_path) {
                 Ok(img) => {
                     let dimensions = img.dimensions();
-                    println!(
+                    tracing::info!(
                         "Loaded cursor image dimensions: {}x{}",
-                        dimensions.0, dimensions.1
+                        dimensions.0,
+                        dimensions.1
                     );
 
-                    let rgba = img.into_rgba8();
-
-                    // Create the texture
-                    let texture = device.create_texture(&wgpu::TextureDescriptor {
-                        label: Some(&format!("Cursor Texture {}", cursor_id)),
-                        size: wgpu::Extent3d {
-                            width: dimensions.0,
-                            height: dimensions.1,
-                            depth_or_array_layers: 1,
-                        },
-                        mip_level_count: 1,
-                        sample_count: 1,
-                        dimension: wgpu::TextureDimension::D2,
-                        format: wgpu::TextureFormat::Rgba8UnormSrgb,
-                        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-                        view_formats: &[],
-                    });
-
-                    queue.write_texture(
-                        wgpu::ImageCopyTexture {
-                            texture: &texture,
-                            mip_level: 0,
-                            origin: wgpu::Origin3d::ZERO,
-                            aspect: wgpu::TextureAspect::All,
-                        },
-                        &rgba,
-                        wgpu::ImageDataLayout {
-                            offset: 0,
-                            bytes_per_row: Some(4 * dimensions.0),
-                            rows_per_image: None,
-                        },
-                        wgpu::Extent3d {
-                            width: dimensions.0,
-                            height: dimensions.1,
-                            depth_or_array_layers: 1,
-                        },
+                    let texture = create_rgba_texture(
+                        device,
+                        queue,
+                        &format!("Cursor Texture {}", cursor_id),
+                        &img.into_rgba8(),
                     );
 
                     textures.insert(cursor_id.clone(), texture);
-                    println!("Successfully loaded cursor texture: {}", cursor_id);
+                    tracing::debug!("Successfully loaded cursor texture: {}", cursor_id);
                 }
                 Err(e) => {
-                    println!("Failed to load cursor image {}: {}", filename, e);
+                    tracing::warn!("Failed to load cursor image {}: {}", filename, e);
                     // Don't return error, just skip this cursor image
                     continue;
                 }
             }
         }
 
-        println!(
+        tracing::info!(
             "Completed loading cursor textures. Total loaded: {}",
             textures.len()
         );
@@ -364,6 +469,48 @@ impl RenderVideoConstants {
     }
 }
 
+fn create_rgba_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    label: &str,
+    image: &image::RgbaImage,
+) -> wgpu::Texture {
+    let size = wgpu::Extent3d {
+        width: image.width(),
+        height: image.height(),
+        depth_or_array_layers: 1,
+    };
+
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8UnormSrgb,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+
+    queue.write_texture(
+        wgpu::ImageCopyTexture {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        image,
+        wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: Some(4 * image.width()),
+            rows_per_image: None,
+        },
+        size,
+    );
+
+    texture
+}
+
 #[derive(Clone, Debug)]
 pub struct ProjectUniforms {
     pub output_size: (u32, u32),
@@ -372,6 +519,8 @@ pub struct ProjectUniforms {
     camera: Option<CompositeVideoFrameUniforms>,
     pub zoom: Zoom,
     pub project: ProjectConfiguration,
+    /// The project's layout, or `ScreenOnly` if there's no camera to show
+    pub layout: Layout,
 }
 
 #[derive(Debug, Clone)]
@@ -388,6 +537,9 @@ impl Zoom {
 
 const CAMERA_PADDING: f32 = 50.0;
 
+/// Neither pane of a side by side layout gets less than this share of the output's width
+const MIN_SIDE_BY_SIDE_SPLIT: f32 = 0.1;
+
 const SCREEN_MAX_PADDING: f64 = 0.4;
 
 impl ProjectUniforms {
@@ -405,50 +557,103 @@ impl ProjectUniforms {
         let crop = Self::get_crop(options, project);
 
         let basis = u32::max(crop.size.x, crop.size.y);
-        let padding_factor = project.background.padding / 100.0 * SCREEN_MAX_PADDING;
+        // Out of range values would push the screen off the output or collapse it entirely
+        let padding = project.background.padding.clamp(0.0, 100.0);
+        let padding_factor = padding / 100.0 * SCREEN_MAX_PADDING;
 
         basis as f64 * padding_factor
     }
 
-    pub fn get_output_size(options: &RenderOptions, project: &ProjectConfiguration) -> (u32, u32) {
+    pub fn get_layout(options: &RenderOptions, project: &ProjectConfiguration) -> Layout {
+        if options.camera_size.is_none() || project.camera.hide {
+            return Layout::ScreenOnly;
+        }
+
+        match project.layout {
+            Layout::SideBySide { split } => Layout::SideBySide {
+                split: split.clamp(MIN_SIDE_BY_SIDE_SPLIT, 1.0 - MIN_SIDE_BY_SIDE_SPLIT),
+            },
+            layout => layout,
+        }
+    }
+
+    /// Size of the content the output is built around, before padding is added
+    fn get_content_size(options: &RenderOptions, project: &ProjectConfiguration) -> XY<u32> {
         let crop = Self::get_crop(options, project);
 
-        let crop_aspect = crop.aspect_ratio();
+        match (Self::get_layout(options, project), options.camera_size) {
+            // The screen keeps its size in its pane, so the output is widened to fit the camera
+            (Layout::SideBySide { split }, _) => {
+                let padding = Self::get_padding(options, project) * 2.0;
+                let width = (crop.size.x as f64 + padding) / split as f64 - padding;
+                XY::new(width as u32, crop.size.y)
+            }
+            (Layout::CameraOnly, Some(camera_size)) => camera_size,
+            _ => crop.size,
+        }
+    }
+
+    pub fn get_output_size(options: &RenderOptions, project: &ProjectConfiguration) -> (u32, u32) {
+        let content = Self::get_content_size(options, project);
+
+        let content_aspect = content.x as f32 / content.y as f32;
 
         let padding = Self::get_padding(options, project) * 2.0;
 
-        let aspect = match &project.aspect_ratio {
-            None => {
-                let width = ((crop.size.x as f64 + padding) as u32 + 1) & !1;
-                let height = ((crop.size.y as f64 + padding) as u32 + 1) & !1;
-                return (width, height);
-            }
-            Some(AspectRatio::Square) => 1.0,
-            Some(AspectRatio::Wide) => 16.0 / 9.0,
-            Some(AspectRatio::Vertical) => 9.0 / 16.0,
-            Some(AspectRatio::Classic) => 4.0 / 3.0,
-            Some(AspectRatio::Tall) => 3.0 / 4.0,
+        // Fixed ratios fit the screen into the output instead of growing it by the padding,
+        // leaving the background around it
+        let Some(aspect) = project.aspect_ratio.as_ref().and_then(AspectRatio::ratio) else {
+            let width = ((content.x as f64 + padding) as u32 + 1) & !1;
+            let height = ((content.y as f64 + padding) as u32 + 1) & !1;
+            return (width, height);
         };
 
-        let (width, height) = if crop_aspect > aspect {
-            (crop.size.x, (crop.size.x as f32 / aspect) as u32)
-        } else if crop_aspect < aspect {
-            ((crop.size.y as f32 * aspect) as u32, crop.size.y)
+        let (width, height) = if content_aspect > aspect {
+            (content.x, (content.x as f32 / aspect) as u32)
+        } else if content_aspect < aspect {
+            ((content.y as f32 * aspect) as u32, content.y)
         } else {
-            (crop.size.x, crop.size.y)
+            (content.x, content.y)
         };
 
         // Ensure width and height are divisible by 2
         ((width + 1) & !1, (height + 1) & !1)
     }
 
-    pub fn get_display_offset(
+    /// The exported size after `output_scale` is applied, preserving aspect ratio
+    pub fn get_scaled_output_size(
         options: &RenderOptions,
         project: &ProjectConfiguration,
-    ) -> Coord<FrameSpace> {
+    ) -> (u32, u32) {
+        let (width, height) = Self::get_output_size(options, project);
+
+        let scale = match project.output_scale {
+            Some(scale) if scale > 0.0 && scale < 1.0 => scale as f64,
+            _ => return (width, height),
+        };
+
+        let scaled = |dimension: u32| (((dimension as f64 * scale).round() as u32).max(2) + 1) & !1;
+
+        (scaled(width), scaled(height))
+    }
+
+    /// The part of the output the screen is laid out in, starting at the top left
+    fn get_screen_pane_size(options: &RenderOptions, project: &ProjectConfiguration) -> XY<f64> {
         let output_size = Self::get_output_size(options, project);
         let output_size = XY::new(output_size.0 as f64, output_size.1 as f64);
 
+        match Self::get_layout(options, project) {
+            Layout::SideBySide { split } => XY::new(output_size.x * split as f64, output_size.y),
+            _ => output_size,
+        }
+    }
+
+    pub fn get_display_offset(
+        options: &RenderOptions,
+        project: &ProjectConfiguration,
+    ) -> Coord<FrameSpace> {
+        let output_size = Self::get_screen_pane_size(options, project);
+
         let output_aspect = output_size.x / output_size.y;
 
         let crop = Self::get_crop(options, project);
@@ -495,7 +700,7 @@ impl ProjectUniforms {
         let cursor_position = interpolate_cursor_position(
             &Default::default(), /*constants.cursor*/
             time,
-            &project.cursor.animation_style,
+            &project.cursor,
         );
 
         let zoom_keyframes = ZoomKeyframes::new(project);
@@ -527,7 +732,11 @@ impl ProjectUniforms {
 
         let crop = Self::get_crop(options, project);
 
-        let zoom_origin = if let Some(cursor_position) = cursor_position {
+        let zoom_origin = if let Some((x, y)) = zoom_keyframes.get_manual_origin(time as f64) {
+            Coord::<RawDisplayUVSpace>::new(XY::new(x as f64, y as f64))
+                .to_raw_display_space(options)
+                .to_cropped_display_space(options, project)
+        } else if let Some(cursor_position) = cursor_position {
             cursor_position
                 .to_raw_display_space(options)
                 .to_cropped_display_space(options, project)
@@ -554,7 +763,7 @@ impl ProjectUniforms {
 
             let display_offset = Self::get_display_offset(options, project);
 
-            let end = Coord::new(output_size) - display_offset;
+            let end = Coord::new(Self::get_screen_pane_size(options, project)) - display_offset;
 
             let screen_scale_origin = zoom_origin
                 .to_frame_space(options, project)
@@ -572,6 +781,9 @@ impl ProjectUniforms {
             let target_size = end - start;
             let min_target_axis = target_size.x.min(target_size.y);
 
+            let shadow = (project.background.shadow / 100.0).clamp(0.0, 1.0) as f32;
+            let shadow_blur = shadow * min_target_axis as f32 * 0.1;
+
             (
                 CompositeVideoFrameUniforms {
                     output_size: [output_size.x as f32, output_size.y as f32],
@@ -584,26 +796,54 @@ impl ProjectUniforms {
                     ],
                     target_bounds: [start.x as f32, start.y as f32, end.x as f32, end.y as f32],
                     target_size: [target_size.x as f32, target_size.y as f32],
-                    rounding_px: (project.background.rounding / 100.0 * 0.5 * min_target_axis)
-                        as f32,
-                    mirror_x: if project.camera.mirror { 1.0 } else { 0.0 },
+                    rounding_px: rounding_px(
+                        project.background.rounding as f32,
+                        min_target_axis as f32,
+                    ),
+                    mirror_x: 0.0,
                     velocity_uv: velocity,
                     motion_blur_amount,
                     camera_motion_blur_amount: 0.0,
-                    _padding: [0.0; 4],
+                    shadow_color: [0.0, 0.0, 0.0, shadow * MAX_SHADOW_OPACITY],
+                    shadow_offset: [0.0, shadow_blur * 0.25],
+                    shadow_blur,
+                    ..Default::default()
                 },
                 zoom,
             )
         };
 
+        let layout = Self::get_layout(options, project);
+
         let camera = options
             .camera_size
-            .filter(|_| !project.camera.hide)
+            .filter(|_| layout != Layout::ScreenOnly)
             .map(|camera_size| {
                 let output_size = [output_size.0 as f32, output_size.1 as f32];
                 let frame_size = [camera_size.x as f32, camera_size.y as f32];
                 let min_axis = output_size[0].min(output_size[1]);
 
+                let pane = match layout {
+                    Layout::SideBySide { split } => Some((
+                        [output_size[0] * split, 0.0],
+                        [output_size[0] * (1.0 - split), output_size[1]],
+                    )),
+                    Layout::CameraOnly => Some(([0.0, 0.0], output_size)),
+                    _ => None,
+                };
+
+                if let Some((pane_start, pane_size)) = pane {
+                    let padding = Self::get_padding(options, project) as f32;
+                    return letterboxed_camera(
+                        project,
+                        output_size,
+                        frame_size,
+                        pane_start,
+                        pane_size,
+                        padding,
+                    );
+                }
+
                 // Calculate camera size based on zoom
                 let base_size = project.camera.size / 100.0;
                 let zoom_amount = zoom_keyframes.get_amount(time as f64) as f32;
@@ -626,7 +866,17 @@ impl ProjectUniforms {
                     min_axis * zoomed_size + CAMERA_PADDING,
                 ];
 
-                let position = {
+                let position = if let Some(custom) = &project.camera.position.custom {
+                    let available = [
+                        output_size[0] - size[0] - CAMERA_PADDING * 2.0,
+                        output_size[1] - size[1] - CAMERA_PADDING * 2.0,
+                    ];
+
+                    [
+                        CAMERA_PADDING + available[0].max(0.0) * custom.x.clamp(0.0, 1.0),
+                        CAMERA_PADDING + available[1].max(0.0) * custom.y.clamp(0.0, 1.0),
+                    ]
+                } else {
                     let x = match &project.camera.position.x {
                         CameraXPosition::Left => CAMERA_PADDING,
                         CameraXPosition::Center => output_size[0] / 2.0 - (size[0]) / 2.0,
@@ -660,6 +910,8 @@ impl ProjectUniforms {
                     (base_blur * 2.0 * transition_factor).min(1.0)
                 };
 
+                let style = WebcamStyle::new(&project.camera, size[0]);
+
                 CompositeVideoFrameUniforms {
                     output_size,
                     frame_size,
@@ -674,12 +926,15 @@ impl ProjectUniforms {
                         target_bounds[2] - target_bounds[0],
                         target_bounds[3] - target_bounds[1],
                     ],
-                    rounding_px: project.camera.rounding / 100.0 * 0.5 * size[0],
+                    rounding_px: style.border_radius,
                     mirror_x: if project.camera.mirror { 1.0 } else { 0.0 },
                     velocity_uv: [0.0, 0.0],
                     motion_blur_amount,
                     camera_motion_blur_amount: camera_motion_blur,
-                    _padding: [0.0; 4],
+                    shadow_color: style.shadow_color,
+                    shadow_offset: [style.shadow_offset.0, style.shadow_offset.1],
+                    shadow_blur: style.shadow_blur,
+                    _padding: 0.0,
                 }
             });
 
@@ -690,14 +945,66 @@ impl ProjectUniforms {
             camera,
             zoom,
             project: project.clone(),
+            layout,
         }
     }
 }
 
+/// Fits the whole camera frame into a pane of the output, keeping its aspect ratio
+fn letterboxed_camera(
+    project: &ProjectConfiguration,
+    output_size: [f32; 2],
+    frame_size: [f32; 2],
+    pane_start: [f32; 2],
+    pane_size: [f32; 2],
+    padding: f32,
+) -> CompositeVideoFrameUniforms {
+    let available = [
+        (pane_size[0] - padding * 2.0).max(1.0),
+        (pane_size[1] - padding * 2.0).max(1.0),
+    ];
+    let frame_aspect = frame_size[0] / frame_size[1];
+
+    let target_size = if available[0] / available[1] > frame_aspect {
+        [available[1] * frame_aspect, available[1]]
+    } else {
+        [available[0], available[0] / frame_aspect]
+    };
+
+    let start = [
+        pane_start[0] + (pane_size[0] - target_size[0]) / 2.0,
+        pane_start[1] + (pane_size[1] - target_size[1]) / 2.0,
+    ];
+    let min_target_axis = target_size[0].min(target_size[1]);
+
+    let shadow = (project.background.shadow / 100.0).clamp(0.0, 1.0) as f32;
+    let shadow_blur = shadow * min_target_axis * 0.1;
+
+    CompositeVideoFrameUniforms {
+        output_size,
+        frame_size,
+        crop_bounds: [0.0, 0.0, frame_size[0], frame_size[1]],
+        target_bounds: [
+            start[0],
+            start[1],
+            start[0] + target_size[0],
+            start[1] + target_size[1],
+        ],
+        target_size,
+        rounding_px: rounding_px(project.background.rounding as f32, min_target_axis),
+        mirror_x: if project.camera.mirror { 1.0 } else { 0.0 },
+        shadow_color: [0.0, 0.0, 0.0, shadow * MAX_SHADOW_OPACITY],
+        shadow_offset: [0.0, shadow_blur * 0.25],
+        shadow_blur,
+        ..Default::default()
+    }
+}
+
 #[derive(Debug)]
 pub struct ZoomKeyframe {
     time: f64,
     amount: f64,
+    mode: ZoomMode,
 }
 #[derive(Debug)]
 pub struct ZoomKeyframes(Vec<ZoomKeyframe>);
@@ -720,18 +1027,22 @@ impl ZoomKeyframes {
             keyframes.push(ZoomKeyframe {
                 time: segment.start,
                 amount: 1.0,
+                mode: segment.mode,
             });
             keyframes.push(ZoomKeyframe {
                 time: segment.start + ZOOM_DURATION,
                 amount: segment.amount,
+                mode: segment.mode,
             });
             keyframes.push(ZoomKeyframe {
                 time: segment.end,
                 amount: segment.amount,
+                mode: segment.mode,
             });
             keyframes.push(ZoomKeyframe {
                 time: segment.end + ZOOM_DURATION,
                 amount: 1.0,
+                mode: segment.mode,
             });
         }
 
@@ -763,21 +1074,59 @@ impl ZoomKeyframes {
         let keyframe_length = next.time - prev.time;
         let delta_time = time - prev.time;
 
-        let t = delta_time / keyframe_length;
-        let t = t.powf(0.5);
+        // Ease in and out of the zoom rather than snapping at either end
+        let t = (delta_time / keyframe_length).clamp(0.0, 1.0);
+        let t = t * t * (3.0 - 2.0 * t);
 
         prev.amount + (next.amount - prev.amount) * t
     }
+
+    /// The fixed zoom target of the segment active at `time`, if it has one
+    pub fn get_manual_origin(&self, time: f64) -> Option<(f32, f32)> {
+        if self.get_amount(time) == 1.0 {
+            return None;
+        }
+
+        match self.0.iter().rev().find(|k| time >= k.time)?.mode {
+            ZoomMode::Manual { x, y } => Some((x, y)),
+            ZoomMode::Auto => None,
+        }
+    }
 }
 
 pub async fn produce_frame(
     constants: &RenderVideoConstants,
     screen_frame: &Vec<u8>,
     camera_frame: &Option<DecodedFrame>,
-    background: Background,
+    cursor: &CursorEvents,
+    background: &Background,
     uniforms: &ProjectUniforms,
     time: f32,
 ) -> Result<Vec<u8>, RenderingError> {
+    let frame = render_frame(
+        constants,
+        screen_frame,
+        camera_frame,
+        cursor,
+        background,
+        uniforms,
+        time,
+    )?;
+
+    read_frame(constants, &frame, uniforms.output_size).await
+}
+
+/// Composites a frame into a texture the size of `uniforms.output_size`, which can be read
+/// back with `read_frame`
+pub fn render_frame(
+    constants: &RenderVideoConstants,
+    screen_frame: &Vec<u8>,
+    camera_frame: &Option<DecodedFrame>,
+    cursor: &CursorEvents,
+    background: &Background,
+    uniforms: &ProjectUniforms,
+    time: f32,
+) -> Result<wgpu::Texture, RenderingError> {
     let mut encoder = constants.device.create_command_encoder(
         &(wgpu::CommandEncoderDescriptor {
             label: Some("Render Encoder"),
@@ -801,12 +1150,12 @@ pub async fn produce_frame(
         view_formats: &[],
     };
 
-    let textures = (
+    let owned_textures = (
         constants.device.create_texture(&output_texture_desc),
         constants.device.create_texture(&output_texture_desc),
     );
 
-    let textures = (&textures.0, &textures.1);
+    let textures = (&owned_textures.0, &owned_textures.1);
 
     let texture_views = (
         textures
@@ -823,24 +1172,50 @@ pub async fn produce_frame(
 
     // First, clear the background
     {
-        let bind_group = constants.gradient_or_color_pipeline.bind_group(
-            &constants.device,
-            &GradientOrColorUniforms::from(background).to_buffer(&constants.device),
-        );
+        let background_texture = match background {
+            Background::Image { path } => Some(constants.background_texture(path)?),
+            _ => None,
+        };
 
-        do_render_pass(
-            &mut encoder,
-            get_either(texture_views, output_is_left),
-            &constants.gradient_or_color_pipeline.render_pipeline,
-            bind_group,
-            wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-        );
+        if let Some(texture) = background_texture {
+            let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+            do_render_pass(
+                &mut encoder,
+                get_either(texture_views, output_is_left),
+                &constants.composite_video_frame_pipeline.render_pipeline,
+                constants.composite_video_frame_pipeline.bind_group(
+                    &constants.device,
+                    &CompositeVideoFrameUniforms::background_image(
+                        uniforms.output_size,
+                        (texture.width(), texture.height()),
+                    )
+                    .to_buffer(&constants.device),
+                    &texture_view,
+                    get_either(texture_views, !output_is_left),
+                ),
+                wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+            );
+        } else {
+            let bind_group = constants.gradient_or_color_pipeline.bind_group(
+                &constants.device,
+                &GradientOrColorUniforms::from(background).to_buffer(&constants.device),
+            );
+
+            do_render_pass(
+                &mut encoder,
+                get_either(texture_views, output_is_left),
+                &constants.gradient_or_color_pipeline.render_pipeline,
+                bind_group,
+                wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+            );
+        }
 
         output_is_left = !output_is_left;
     }
 
     // Then render the screen frame
-    {
+    if uniforms.layout != Layout::CameraOnly {
         let frame_size = constants.options.screen_size;
 
         let texture = constants.device.create_texture(
@@ -864,6 +1239,13 @@ pub async fn produce_frame(
 
         let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
 
+        let screen_frame = redaction::apply_redactions(
+            screen_frame,
+            frame_size,
+            &uniforms.project.redactions,
+            time,
+        );
+
         constants.queue.write_texture(
             wgpu::ImageCopyTexture {
                 texture: &texture,
@@ -871,7 +1253,7 @@ pub async fn produce_frame(
                 origin: wgpu::Origin3d::ZERO,
                 aspect: wgpu::TextureAspect::All,
             },
-            screen_frame,
+            &screen_frame,
             wgpu::ImageDataLayout {
                 offset: 0,
                 bytes_per_row: Some(constants.options.screen_size.x * 4),
@@ -902,15 +1284,34 @@ pub async fn produce_frame(
 
     // if FLAGS.zoom {
     // Then render the cursor
-    draw_cursor(
-        constants,
-        uniforms,
-        time,
-        &mut encoder,
-        get_either(texture_views, !output_is_left),
-    );
+    if uniforms.layout != Layout::CameraOnly {
+        draw_cursor(
+            constants,
+            uniforms,
+            cursor,
+            time,
+            &mut encoder,
+            get_either(texture_views, !output_is_left),
+        );
+    }
     // }
 
+    if let Some(click_ripples) = constants
+        .click_ripples
+        .as_ref()
+        .filter(|_| uniforms.project.click_highlight.show && uniforms.layout != Layout::CameraOnly)
+    {
+        click_ripples.draw(
+            constants,
+            uniforms,
+            time,
+            &mut encoder,
+            get_either(texture_views, !output_is_left),
+        );
+    }
+
+    let background_removal = uniforms.project.camera.background_removal.as_ref();
+
     // camera
     if let (Some(camera_size), Some(camera_frame), Some(uniforms)) = (
         constants.options.camera_size,
@@ -938,6 +1339,9 @@ pub async fn produce_frame(
 
         let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
 
+        let camera_frame =
+            background_removal::remove_background(camera_frame, camera_size, background_removal);
+
         constants.queue.write_texture(
             wgpu::ImageCopyTexture {
                 texture: &texture,
@@ -945,7 +1349,7 @@ pub async fn produce_frame(
                 origin: wgpu::Origin3d::ZERO,
                 aspect: wgpu::TextureAspect::All,
             },
-            camera_frame,
+            &camera_frame,
             wgpu::ImageDataLayout {
                 offset: 0,
                 bytes_per_row: Some(camera_size.x * 4),
@@ -974,25 +1378,114 @@ pub async fn produce_frame(
         output_is_left = !output_is_left;
     }
 
+    if let Some(keystroke_overlay) = constants
+        .keystroke_overlay
+        .as_ref()
+        .filter(|_| uniforms.project.hotkeys.show)
+    {
+        keystroke_overlay.draw(
+            constants,
+            uniforms,
+            time,
+            &mut encoder,
+            get_either(texture_views, !output_is_left),
+        );
+    }
+
+    constants.watermark_textures.draw(
+        constants,
+        uniforms,
+        &mut encoder,
+        get_either(texture_views, !output_is_left),
+    );
+
     // Now submit the encoder
     constants.queue.submit(std::iter::once(encoder.finish()));
 
+    Ok(get_either(owned_textures, !output_is_left))
+}
+
+/// Reads a frame from `render_frame` back as RGBA. Frames are scaled to `size` on the GPU
+/// first, halving at most each pass so detail isn't skipped over when sampling.
+pub async fn read_frame(
+    constants: &RenderVideoConstants,
+    frame: &wgpu::Texture,
+    size: (u32, u32),
+) -> Result<Vec<u8>, RenderingError> {
+    let mut scaled = None::<wgpu::Texture>;
+
+    loop {
+        let source = scaled.as_ref().unwrap_or(frame);
+        let source_size = (source.width(), source.height());
+        if source_size == size {
+            break;
+        }
+
+        let pass_size = (
+            size.0.max(source_size.0 / 2).max(1),
+            size.1.max(source_size.1 / 2).max(1),
+        );
+
+        let target = constants.device.create_texture(&wgpu::TextureDescriptor {
+            size: wgpu::Extent3d {
+                width: pass_size.0,
+                height: pass_size.1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_SRC,
+            label: Some("Scaled Texture"),
+            view_formats: &[],
+        });
+
+        let source_view = source.create_view(&wgpu::TextureViewDescriptor::default());
+        let mut encoder = constants.device.create_command_encoder(
+            &(wgpu::CommandEncoderDescriptor {
+                label: Some("Scale Encoder"),
+            }),
+        );
+
+        do_render_pass(
+            &mut encoder,
+            &target.create_view(&wgpu::TextureViewDescriptor::default()),
+            &constants.composite_video_frame_pipeline.render_pipeline,
+            constants.composite_video_frame_pipeline.bind_group(
+                &constants.device,
+                &CompositeVideoFrameUniforms::background_image(pass_size, source_size)
+                    .to_buffer(&constants.device),
+                &source_view,
+                &source_view,
+            ),
+            wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+        );
+
+        constants.queue.submit(std::iter::once(encoder.finish()));
+        scaled = Some(target);
+    }
+
+    let frame = scaled.as_ref().unwrap_or(frame);
+
     let output_texture_size = wgpu::Extent3d {
-        width: uniforms.output_size.0,
-        height: uniforms.output_size.1,
+        width: size.0,
+        height: size.1,
         depth_or_array_layers: 1,
     };
 
     // Calculate the aligned bytes per row
     let align = COPY_BYTES_PER_ROW_ALIGNMENT;
-    let unpadded_bytes_per_row = uniforms.output_size.0 * 4;
+    let unpadded_bytes_per_row = size.0 * 4;
     let padding = (align - (unpadded_bytes_per_row % align)) % align;
     let padded_bytes_per_row = unpadded_bytes_per_row + padding;
 
     // Ensure the padded_bytes_per_row is a multiple of 4 (32 bits)
     let padded_bytes_per_row = (padded_bytes_per_row + 3) & !3;
 
-    let output_buffer_size = (padded_bytes_per_row * uniforms.output_size.1) as u64;
+    let output_buffer_size = (padded_bytes_per_row * size.1) as u64;
 
     let output_buffer = constants.device.create_buffer(&wgpu::BufferDescriptor {
         size: output_buffer_size,
@@ -1010,7 +1503,7 @@ pub async fn produce_frame(
 
         encoder.copy_texture_to_buffer(
             wgpu::ImageCopyTexture {
-                texture: get_either(textures, !output_is_left),
+                texture: frame,
                 mip_level: 0,
                 origin: wgpu::Origin3d::ZERO,
                 aspect: wgpu::TextureAspect::All,
@@ -1020,7 +1513,7 @@ pub async fn produce_frame(
                 layout: wgpu::ImageDataLayout {
                     offset: 0,
                     bytes_per_row: Some(padded_bytes_per_row),
-                    rows_per_image: Some(uniforms.output_size.1),
+                    rows_per_image: Some(size.1),
                 },
             },
             output_texture_size,
@@ -1042,8 +1535,7 @@ pub async fn produce_frame(
 
     let data = buffer_slice.get_mapped_range();
     let padded_data: Vec<u8> = data.to_vec(); // Ensure the type is Vec<u8>
-    let mut image_data =
-        Vec::with_capacity((uniforms.output_size.0 * uniforms.output_size.1 * 4) as usize);
+    let mut image_data = Vec::with_capacity((size.0 * size.1 * 4) as usize);
     for chunk in padded_data.chunks(padded_bytes_per_row as usize) {
         image_data.extend_from_slice(&chunk[..unpadded_bytes_per_row as usize]);
     }
@@ -1055,27 +1547,24 @@ pub async fn produce_frame(
     Ok(image_data)
 }
 
+/// Draws the cursor recorded alongside the screen. Only recordings that didn't capture the
+/// OS cursor have cursor data, so this doesn't draw a second cursor over a captured one.
 fn draw_cursor(
     constants: &RenderVideoConstants,
     uniforms: &ProjectUniforms,
+    cursor: &CursorEvents,
     time: f32,
     encoder: &mut CommandEncoder,
     view: &wgpu::TextureView,
 ) {
-    let Some(cursor_position) = interpolate_cursor_position(
-        &Default::default(), // constants.cursor,
-        time,
-        &uniforms.project.cursor.animation_style,
-    ) else {
+    let Some(cursor_position) = interpolate_cursor_position(cursor, time, &uniforms.project.cursor)
+    else {
         return;
     };
 
     // Calculate previous position for velocity
-    let prev_position = interpolate_cursor_position(
-        &Default::default(), // constants.cursor,
-        time - 1.0 / 30.0,
-        &uniforms.project.cursor.animation_style,
-    );
+    let prev_position =
+        interpolate_cursor_position(cursor, time - 1.0 / 30.0, &uniforms.project.cursor);
 
     // Calculate velocity in screen space
     let velocity = if let Some(prev_pos) = prev_position {
@@ -1093,12 +1582,10 @@ fn draw_cursor(
     let speed = (velocity[0] * velocity[0] + velocity[1] * velocity[1]).sqrt();
     let motion_blur_amount = (speed * 0.3).min(1.0) * uniforms.project.motion_blur.unwrap_or(0.8);
 
-    let cursor = Default::default();
-    let cursor_event = find_cursor_event(&cursor /* constants.cursor */, time);
+    let cursor_event = find_cursor_event(cursor, time);
 
-    let last_click_time =  /* constants
-        .cursor
-        .clicks */ Vec::<CursorClickEvent>::new()
+    let last_click_time = cursor
+        .clicks
         .iter()
         .filter(|click| click.down && click.process_time_ms <= (time as f64) * 1000.0)
         .max_by_key(|click| click.process_time_ms as i64)
@@ -1118,9 +1605,18 @@ fn draw_cursor(
         uniforms.cursor_size / 100.0
     };
 
+    // Displays draw the cursor with as many pixels as their scale factor, so this keeps it
+    // the same size relative to the recorded content
+    let cursor_height = match constants.display_scale_factor {
+        Some(scale_factor) if scale_factor > 0.0 => {
+            STANDARD_CURSOR_HEIGHT * (scale_factor / STANDARD_CURSOR_SCALE_FACTOR) as f32
+        }
+        _ => STANDARD_CURSOR_HEIGHT,
+    };
+
     let normalized_size = [
-        STANDARD_CURSOR_HEIGHT * aspect_ratio * cursor_size_percentage,
-        STANDARD_CURSOR_HEIGHT * cursor_size_percentage,
+        cursor_height * aspect_ratio * cursor_size_percentage,
+        cursor_height * cursor_size_percentage,
     ];
 
     let frame_position = cursor_position.to_frame_space(&constants.options, &uniforms.project);
@@ -1141,15 +1637,27 @@ fn draw_cursor(
         last_click_time,
         velocity,
         motion_blur_amount,
-        _alignment: [0.0; 7],
+        opacity: 1.0,
+        _alignment: [0.0; 6],
     };
 
+    draw_with_cursor_pipeline(constants, cursor_uniforms, cursor_texture, encoder, view);
+}
+
+/// Draws `texture` as a quad described by `uniforms`
+fn draw_with_cursor_pipeline(
+    constants: &RenderVideoConstants,
+    uniforms: CursorUniforms,
+    texture: &wgpu::Texture,
+    encoder: &mut CommandEncoder,
+    view: &wgpu::TextureView,
+) {
     let cursor_uniform_buffer =
         constants
             .device
             .create_buffer_init(&wgpu::util::BufferInitDescriptor {
                 label: Some("Cursor Uniform Buffer"),
-                contents: bytemuck::cast_slice(&[cursor_uniforms]),
+                contents: bytemuck::cast_slice(&[uniforms]),
                 usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
             });
 
@@ -1165,7 +1673,7 @@ fn draw_cursor(
                 wgpu::BindGroupEntry {
                     binding: 1,
                     resource: wgpu::BindingResource::TextureView(
-                        &cursor_texture.create_view(&wgpu::TextureViewDescriptor::default()),
+                        &texture.create_view(&wgpu::TextureViewDescriptor::default()),
                     ),
                 },
                 wgpu::BindGroupEntry {
@@ -1218,10 +1726,40 @@ struct CompositeVideoFrameUniforms {
     pub mirror_x: f32,
     pub motion_blur_amount: f32,
     pub camera_motion_blur_amount: f32,
-    _padding: [f32; 4],
+    pub shadow_color: [f32; 4],
+    pub shadow_offset: [f32; 2],
+    pub shadow_blur: f32,
+    _padding: f32,
 }
 
 impl CompositeVideoFrameUniforms {
+    /// Covers the whole output with the image, cropping to preserve its aspect ratio
+    fn background_image(output_size: (u32, u32), image_size: (u32, u32)) -> Self {
+        let output_size = [output_size.0 as f32, output_size.1 as f32];
+        let frame_size = [image_size.0 as f32, image_size.1 as f32];
+
+        let scale = (output_size[0] / frame_size[0]).max(output_size[1] / frame_size[1]);
+        let crop_size = [output_size[0] / scale, output_size[1] / scale];
+        let crop_start = [
+            (frame_size[0] - crop_size[0]) / 2.0,
+            (frame_size[1] - crop_size[1]) / 2.0,
+        ];
+
+        Self {
+            crop_bounds: [
+                crop_start[0],
+                crop_start[1],
+                crop_start[0] + crop_size[0],
+                crop_start[1] + crop_size[1],
+            ],
+            target_bounds: [0.0, 0.0, output_size[0], output_size[1]],
+            output_size,
+            frame_size,
+            target_size: output_size,
+            ..Default::default()
+        }
+    }
+
     fn to_buffer(self, device: &wgpu::Device) -> wgpu::Buffer {
         device.create_buffer_init(
             &(wgpu::util::BufferInitDescriptor {
@@ -1236,8 +1774,12 @@ impl CompositeVideoFrameUniforms {
 impl CompositeVideoFramePipeline {
     pub fn new(device: &wgpu::Device) -> Self {
         let bind_group_layout = Self::bind_group_layout(device);
-        let render_pipeline =
-            create_shader_render_pipeline(device, &bind_group_layout, Self::shader());
+        let render_pipeline = create_shader_render_pipeline(
+            device,
+            &bind_group_layout,
+            Self::shader(),
+            wgpu::BlendState::REPLACE,
+        );
 
         Self {
             bind_group_layout,
@@ -1367,9 +1909,9 @@ impl GradientOrColorUniforms {
     }
 }
 
-impl From<Background> for GradientOrColorUniforms {
-    fn from(value: Background) -> Self {
-        match value {
+impl From<&Background> for GradientOrColorUniforms {
+    fn from(value: &Background) -> Self {
+        match *value {
             Background::Color(color) => Self {
                 start: color,
                 end: color,
@@ -1382,6 +1924,12 @@ impl From<Background> for GradientOrColorUniforms {
                 angle,
                 _padding: [0.0; 3],
             },
+            Background::Image { .. } => Self {
+                start: [0.0, 0.0, 0.0, 1.0],
+                end: [0.0, 0.0, 0.0, 1.0],
+                angle: 0.0,
+                _padding: [0.0; 3],
+            },
         }
     }
 }
@@ -1389,8 +1937,12 @@ impl From<Background> for GradientOrColorUniforms {
 impl GradientOrColorPipeline {
     pub fn new(device: &wgpu::Device) -> Self {
         let bind_group_layout = Self::bind_group_layout(device);
-        let render_pipeline =
-            create_shader_render_pipeline(device, &bind_group_layout, Self::shader());
+        let render_pipeline = create_shader_render_pipeline(
+            device,
+            &bind_group_layout,
+            Self::shader(),
+            wgpu::BlendState::REPLACE,
+        );
 
         Self {
             bind_group_layout,
@@ -1467,6 +2019,7 @@ fn create_shader_render_pipeline(
     device: &wgpu::Device,
     bind_group_layout: &wgpu::BindGroupLayout,
     wgsl_shader: &str,
+    blend: wgpu::BlendState,
 ) -> wgpu::RenderPipeline {
     let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
         label: Some("Shader"),
@@ -1499,7 +2052,7 @@ fn create_shader_render_pipeline(
             entry_point: "fs_main",
             targets: &[Some(wgpu::ColorTargetState {
                 format: wgpu::TextureFormat::Rgba8UnormSrgb,
-                blend: Some(wgpu::BlendState::REPLACE),
+                blend: Some(blend),
                 write_mask: wgpu::ColorWrites::ALL,
             })],
             compilation_options: wgpu::PipelineCompilationOptions {
@@ -1546,9 +2099,9 @@ fn get_either<T>((a, b): (T, T), left: bool) -> T {
 }
 
 fn interpolate_cursor_position(
-    cursor: &CursorData,
+    cursor: &CursorEvents,
     time_secs: f32,
-    animation_style: &CursorAnimationStyle,
+    config: &CursorConfiguration,
 ) -> Option<Coord<RawDisplayUVSpace>> {
     let time_ms = (time_secs * 1000.0) as f64;
 
@@ -1556,6 +2109,13 @@ fn interpolate_cursor_position(
         return None;
     }
 
+    if config.smoothing > 0.0 {
+        return cursor_smoothing::smoothed_position(&cursor.moves, time_ms, config.smoothing)
+            .map(Coord::new);
+    }
+
+    let animation_style = &config.animation_style;
+
     // Get style-specific parameters
     let (num_samples, velocity_threshold) = match animation_style {
         CursorAnimationStyle::Slow => (SLOW_SMOOTHING_SAMPLES, SLOW_VELOCITY_THRESHOLD),
@@ -1678,10 +2238,41 @@ struct CursorUniforms {
     last_click_time: f32,
     velocity: [f32; 2],
     motion_blur_amount: f32,
-    _alignment: [f32; 7],
+    opacity: f32,
+    _alignment: [f32; 6],
+}
+
+impl CursorUniforms {
+    /// Draws a texture centered on `position` as a plain overlay,
+    /// without the cursor's click animation or motion blur
+    fn overlay(
+        uniforms: &ProjectUniforms,
+        position: [f32; 2],
+        size: [f32; 2],
+        opacity: f32,
+    ) -> Self {
+        Self {
+            position: [position[0], position[1], 0.0, 0.0],
+            size: [size[0], size[1], 0.0, 0.0],
+            output_size: [
+                uniforms.output_size.0 as f32,
+                uniforms.output_size.1 as f32,
+                0.0,
+                0.0,
+            ],
+            screen_bounds: uniforms.display.target_bounds,
+            cursor_size: 1.0,
+            // Anything past the click animation's duration
+            last_click_time: 1.0,
+            velocity: [0.0, 0.0],
+            motion_blur_amount: 0.0,
+            opacity,
+            _alignment: [0.0; 6],
+        }
+    }
 }
 
-fn find_cursor_event(cursor: &CursorData, time: f32) -> &CursorMoveEvent {
+fn find_cursor_event(cursor: &CursorEvents, time: f32) -> &CursorMoveEvent {
     let time_ms = time * 1000.0;
 
     let event = cursor
@@ -1,31 +1,69 @@
 use std::path::PathBuf;
 
-use crate::RecordingMeta;
+use crate::{decoder::stream_rotation, RecordingMeta, RenderOptions, XY};
 use serde::Serialize;
 use specta::Type;
 
 #[derive(Debug, Clone, Copy, Serialize, Type)]
 pub struct Video {
     pub duration: f64,
+    /// Size of the decoded frames once they've been turned upright
     pub width: u32,
     pub height: u32,
+    /// Clockwise rotation applied to decoded frames, from the video's metadata
+    pub rotation: u32,
 }
 
 impl Video {
     pub fn new(path: &PathBuf) -> Self {
-        let input = ffmpeg::format::input(path).unwrap();
-        let stream = input.streams().best(ffmpeg::media::Type::Video).unwrap();
+        Self::try_new(path).unwrap()
+    }
 
-        let video_decoder = ffmpeg::codec::Context::from_parameters(stream.parameters())
-            .unwrap()
+    pub fn try_new(path: &PathBuf) -> Result<Self, ffmpeg::Error> {
+        let input = ffmpeg::format::input(path)?;
+        let stream = input
+            .streams()
+            .best(ffmpeg::media::Type::Video)
+            .ok_or(ffmpeg::Error::StreamNotFound)?;
+
+        let video_decoder = ffmpeg::codec::Context::from_parameters(stream.parameters())?
             .decoder()
-            .video()
-            .unwrap();
+            .video()?;
+
+        let rotation = stream_rotation(&stream);
+        if rotation != 0 {
+            tracing::info!(
+                "{:?} is rotated by {rotation} degrees, frames will be turned upright",
+                path
+            );
+        }
 
-        Video {
-            width: video_decoder.width(),
-            height: video_decoder.height(),
+        let (width, height) = match rotation {
+            90 | 270 => (video_decoder.height(), video_decoder.width()),
+            _ => (video_decoder.width(), video_decoder.height()),
+        };
+
+        Ok(Video {
+            width,
+            height,
+            rotation,
             duration: input.duration() as f64 / 1_000_000.0,
+        })
+    }
+
+    /// Camera recordings can be cut short or left empty if the camera was disconnected,
+    /// in which case the segment is treated as not having one
+    pub(crate) fn camera(path: &PathBuf) -> Option<Self> {
+        match Self::try_new(path) {
+            Ok(video) if video.duration > 0.0 => Some(video),
+            Ok(_) => {
+                tracing::warn!("Camera recording {:?} is empty, ignoring it", path);
+                None
+            }
+            Err(e) => {
+                tracing::error!("Failed to read camera recording {:?}: {}", path, e);
+                None
+            }
         }
     }
 }
@@ -63,16 +101,28 @@ pub struct ProjectRecordings {
 
 impl ProjectRecordings {
     pub fn new(meta: &RecordingMeta) -> Self {
+        Self::try_new(meta).unwrap()
+    }
+
+    /// Errors instead of panicking if a display track is missing or unreadable,
+    /// such as once a recording's raw tracks have been removed after rendering
+    pub fn try_new(meta: &RecordingMeta) -> Result<Self, String> {
+        let display = |path: &PathBuf| {
+            let path = meta.project_path.join(path);
+            Video::try_new(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+        };
+
         let segments = match &meta.content {
             crate::Content::SingleSegment { segment } => {
-                let display = Video::new(&meta.project_path.join(&segment.display.path));
+                let display = display(&segment.display.path)?;
                 let camera = segment
                     .camera
                     .as_ref()
-                    .map(|camera| Video::new(&meta.project_path.join(&camera.path)));
+                    .and_then(|camera| Video::camera(&meta.project_path.join(&camera.path)));
                 let audio = segment
                     .audio
                     .as_ref()
+                    .or(segment.system_audio.as_ref())
                     .map(|audio| Audio::new(&meta.project_path.join(&audio.path)));
 
                 vec![SegmentRecordings {
@@ -85,31 +135,45 @@ impl ProjectRecordings {
                 .segments
                 .iter()
                 .map(|s| {
-                    let display = Video::new(&meta.project_path.join(&s.display.path));
+                    let display = display(&s.display.path)?;
                     let camera = s
                         .camera
                         .as_ref()
-                        .map(|camera| Video::new(&meta.project_path.join(&camera.path)));
+                        .and_then(|camera| Video::camera(&meta.project_path.join(&camera.path)));
                     let audio = s
                         .audio
                         .as_ref()
                         .map(|audio| Audio::new(&meta.project_path.join(&audio.path)));
 
-                    SegmentRecordings {
+                    Ok(SegmentRecordings {
                         display,
                         camera,
                         audio,
-                    }
+                    })
                 })
-                .collect(),
+                .collect::<Result<_, String>>()?,
         };
 
-        Self { segments }
+        Ok(Self { segments })
     }
 
     pub fn duration(&self) -> f64 {
         self.segments.iter().map(|s| s.duration()).sum()
     }
+
+    /// Sized from the first segment, which every other segment matches
+    pub fn render_options(&self) -> RenderOptions {
+        RenderOptions {
+            screen_size: XY::new(
+                self.segments[0].display.width,
+                self.segments[0].display.height,
+            ),
+            camera_size: self.segments[0]
+                .camera
+                .as_ref()
+                .map(|c| XY::new(c.width, c.height)),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Type)]
@@ -133,3 +197,20 @@ impl SegmentRecordings {
         duration_ns[0]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotated_video_is_sized_upright() {
+        // Stored as 64x32 with a display matrix turning it 90 degrees clockwise,
+        // like portrait recordings from phones
+        let path = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/rotated.mp4"));
+
+        let video = Video::try_new(&path).unwrap();
+
+        assert_eq!(video.rotation, 90);
+        assert_eq!((video.width, video.height), (32, 64));
+    }
+}
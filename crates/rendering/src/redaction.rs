@@ -0,0 +1,175 @@
+use std::borrow::Cow;
+
+use cap_project::{Redaction, RedactionKind, XY};
+
+/// Blurring is repeated, as a few box blurs look close to a gaussian blur
+const BLUR_PASSES: usize = 3;
+
+/// Redacts the screen frame before it's composited, so redactions are affected
+/// by cropping, padding and zoom like the rest of the recording.
+/// Returns the frame unchanged if no redactions are active at `time`.
+pub fn apply_redactions<'a>(
+    frame: &'a [u8],
+    frame_size: XY<u32>,
+    redactions: &[Redaction],
+    time: f32,
+) -> Cow<'a, [u8]> {
+    let mut active = redactions
+        .iter()
+        .filter(|redaction| redaction.is_active(time as f64))
+        .peekable();
+
+    if active.peek().is_none() {
+        return Cow::Borrowed(frame);
+    }
+
+    let mut frame = frame.to_vec();
+
+    for redaction in active {
+        let Some(region) = Region::new(redaction, frame_size) else {
+            continue;
+        };
+
+        match redaction.kind {
+            RedactionKind::Blur => {
+                let radius = (frame_size.y / 90).max(6) as usize;
+                for _ in 0..BLUR_PASSES {
+                    box_blur(&mut frame, &region, radius);
+                }
+            }
+            RedactionKind::Pixelate => {
+                pixelate(&mut frame, &region, (frame_size.y / 60).max(8) as usize);
+            }
+        }
+    }
+
+    Cow::Owned(frame)
+}
+
+struct Region {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    stride: usize,
+}
+
+impl Region {
+    /// Clamps the redaction's bounds to the frame, returning `None` if they're outside of it
+    fn new(redaction: &Redaction, frame_size: XY<u32>) -> Option<Self> {
+        let bounds = redaction.bounds;
+        let x = bounds.position.x.min(frame_size.x);
+        let y = bounds.position.y.min(frame_size.y);
+        let width = bounds.size.x.min(frame_size.x - x);
+        let height = bounds.size.y.min(frame_size.y - y);
+
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        Some(Self {
+            x: x as usize,
+            y: y as usize,
+            width: width as usize,
+            height: height as usize,
+            stride: frame_size.x as usize * 4,
+        })
+    }
+
+    fn offset(&self, x: usize, y: usize) -> usize {
+        (self.y + y) * self.stride + (self.x + x) * 4
+    }
+}
+
+/// Separable box blur that only samples pixels inside the region,
+/// so nothing around it bleeds in and nothing inside it bleeds out
+fn box_blur(frame: &mut [u8], region: &Region, radius: usize) {
+    let mut line = vec![];
+
+    for y in 0..region.height {
+        line.clear();
+        line.extend((0..region.width).map(|x| pixel(frame, region.offset(x, y))));
+        for (x, value) in blur_line(&line, radius).into_iter().enumerate() {
+            set_pixel(frame, region.offset(x, y), value);
+        }
+    }
+
+    for x in 0..region.width {
+        line.clear();
+        line.extend((0..region.height).map(|y| pixel(frame, region.offset(x, y))));
+        for (y, value) in blur_line(&line, radius).into_iter().enumerate() {
+            set_pixel(frame, region.offset(x, y), value);
+        }
+    }
+}
+
+fn blur_line(line: &[[u32; 4]], radius: usize) -> Vec<[u32; 4]> {
+    let mut sum = [0u32; 4];
+    let mut window = 0u32;
+    let mut blurred = Vec::with_capacity(line.len());
+
+    for value in &line[..radius.min(line.len())] {
+        add(&mut sum, value);
+        window += 1;
+    }
+
+    for i in 0..line.len() {
+        if let Some(value) = line.get(i + radius) {
+            add(&mut sum, value);
+            window += 1;
+        }
+
+        if let Some(value) = i.checked_sub(radius + 1).map(|j| &line[j]) {
+            for (s, v) in sum.iter_mut().zip(value) {
+                *s -= v;
+            }
+            window -= 1;
+        }
+
+        blurred.push(sum.map(|c| c / window));
+    }
+
+    blurred
+}
+
+fn pixelate(frame: &mut [u8], region: &Region, block_size: usize) {
+    for block_y in (0..region.height).step_by(block_size) {
+        for block_x in (0..region.width).step_by(block_size) {
+            let ys = block_y..(block_y + block_size).min(region.height);
+            let xs = block_x..(block_x + block_size).min(region.width);
+
+            let mut sum = [0u32; 4];
+            for y in ys.clone() {
+                for x in xs.clone() {
+                    add(&mut sum, &pixel(frame, region.offset(x, y)));
+                }
+            }
+
+            let count = (ys.len() * xs.len()) as u32;
+            let average = sum.map(|c| c / count);
+
+            for y in ys.clone() {
+                for x in xs.clone() {
+                    set_pixel(frame, region.offset(x, y), average);
+                }
+            }
+        }
+    }
+}
+
+fn pixel(frame: &[u8], offset: usize) -> [u32; 4] {
+    let p = &frame[offset..offset + 4];
+    [p[0] as u32, p[1] as u32, p[2] as u32, p[3] as u32]
+}
+
+fn set_pixel(frame: &mut [u8], offset: usize, value: [u32; 4]) {
+    for (c, v) in frame[offset..offset + 4].iter_mut().zip(value) {
+        *c = v as u8;
+    }
+}
+
+fn add(sum: &mut [u32; 4], value: &[u32; 4]) {
+    for (s, v) in sum.iter_mut().zip(value) {
+        *s += v;
+    }
+}
@@ -0,0 +1,40 @@
+struct Ripple {
+    position: vec2<f32>,
+    progress: f32,
+    _padding: f32,
+};
+
+struct Uniforms {
+    color: vec4<f32>,
+    radius: f32,
+    count: u32,
+    _padding: vec2<f32>,
+    ripples: array<Ripple, 8>,
+};
+
+@group(0) @binding(0) var<uniform> u: Uniforms;
+
+@vertex
+fn vs_main(@builtin(vertex_index) in_vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let x = f32(i32(in_vertex_index & 1u) * 4 - 1);
+    let y = f32(i32(in_vertex_index & 2u) * 2 - 1);
+    return vec4<f32>(x, y, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) frag_coord: vec4<f32>) -> @location(0) vec4<f32> {
+    var alpha = 0.0;
+
+    for (var i = 0u; i < u.count; i++) {
+        let ripple = u.ripples[i];
+        let distance = length(frag_coord.xy - ripple.position) / u.radius;
+
+        // A ring expands from the click while fading out, around a lighter fill
+        let ring = 1.0 - smoothstep(0.0, 0.12, abs(distance - ripple.progress));
+        let fill = 0.35 * (1.0 - smoothstep(ripple.progress - 0.05, ripple.progress, distance));
+
+        alpha = max(alpha, max(ring, fill) * (1.0 - ripple.progress));
+    }
+
+    return vec4<f32>(u.color.rgb, u.color.a * alpha);
+}
@@ -9,7 +9,10 @@ struct Uniforms {
     mirror_x: f32,
     motion_blur_amount: f32,
     camera_motion_blur_amount: f32,
-    _padding: vec4<f32>,
+    shadow_color: vec4<f32>,
+    shadow_offset: vec2<f32>,
+    shadow_blur: f32,
+    _padding: f32,
 };
 
 @group(0) @binding(0) var<uniform> u: Uniforms;
@@ -29,8 +32,10 @@ fn main_image(frag_color: vec4<f32>, frag_coord: vec2<f32>) -> vec4<f32> {
 		let target_uv = (frag_coord - u.target_bounds.xy) / u.target_size;
 		let crop_bounds_uv = vec4<f32>(u.crop_bounds.xy / u.frame_size, u.crop_bounds.zw / u.frame_size);
 
+		let prev_color = apply_shadow(textureSample(prev_tex, sampler0, uv), frag_coord);
+
 		if target_uv.x < 0.0 || target_uv.x > 1.0 || target_uv.y < 0.0 || target_uv.y > 1.0 {
-				return textureSample(prev_tex, sampler0, uv);
+				return prev_color;
 		}
 
 		var base_color = sample_texture(target_uv, crop_bounds_uv);
@@ -39,7 +44,7 @@ fn main_image(frag_color: vec4<f32>, frag_coord: vec2<f32>) -> vec4<f32> {
 		let blur_amount = select(u.motion_blur_amount, u.camera_motion_blur_amount, u.camera_motion_blur_amount > 0.0);
 		
 		if blur_amount < 0.01 {
-				return mix(textureSample(prev_tex, sampler0, uv), base_color, base_color.a);
+				return mix(prev_color, base_color, base_color.a);
 		}
 
 		let center = vec2<f32>(0.5, 0.5);
@@ -87,7 +92,22 @@ fn main_image(frag_color: vec4<f32>, frag_coord: vec2<f32>) -> vec4<f32> {
 		
 		let blurred = vec4(final_color.rgb, base_color.a);
 		
-		return mix(textureSample(prev_tex, sampler0, uv), blurred, blurred.a);
+		return mix(prev_color, blurred, blurred.a);
+}
+
+fn apply_shadow(prev_color: vec4<f32>, frag_coord: vec2<f32>) -> vec4<f32> {
+		if u.shadow_color.a <= 0.0 {
+				return prev_color;
+		}
+
+		let half_size = u.target_size / 2.0;
+		let p = frag_coord - u.target_bounds.xy - half_size - u.shadow_offset;
+		let q = abs(p) - half_size + u.rounding_px;
+		let distance = length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0) - u.rounding_px;
+
+		let strength = 1.0 - smoothstep(0.0, max(u.shadow_blur, 1.0), distance);
+
+		return vec4(mix(prev_color.rgb, u.shadow_color.rgb, u.shadow_color.a * strength), prev_color.a);
 }
 
 fn sample_texture(uv: vec2<f32>, crop_bounds_uv: vec4<f32>) -> vec4<f32> {
@@ -95,7 +115,8 @@ fn sample_texture(uv: vec2<f32>, crop_bounds_uv: vec4<f32>) -> vec4<f32> {
 				var cropped_uv = uv * (crop_bounds_uv.zw - crop_bounds_uv.xy) + crop_bounds_uv.xy;
 
 				if u.mirror_x != 0.0 {
-						cropped_uv.x = (1.0 - uv.x + crop_bounds_uv.x) * (crop_bounds_uv.z - crop_bounds_uv.x);
+						// Flip within the crop so the mirrored image stays in the same bounds
+						cropped_uv.x = (1.0 - uv.x) * (crop_bounds_uv.z - crop_bounds_uv.x) + crop_bounds_uv.x;
 				}
 
 				return textureSample(frame_tex, sampler0, cropped_uv);
@@ -12,6 +12,7 @@ struct Uniforms {
     last_click_time: f32,
     velocity: vec2<f32>,
     motion_blur_amount: f32,
+    opacity: f32,
     _alignment: vec4<f32>,
 };
 
@@ -117,5 +118,5 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
         );
     }
     
-    return final_color;
+    return vec4<f32>(final_color.rgb, final_color.a * uniforms.opacity);
 }
@@ -0,0 +1,250 @@
+use std::path::Path;
+
+use ffmpeg::{color, format::Pixel};
+use ffmpeg_sys_next::{av_pix_fmt_desc_get, AVPixelFormat};
+
+/// Reference white of HDR video (BT.2408), which becomes SDR white
+const SDR_WHITE_NITS: f32 = 203.0;
+const PQ_MAX_NITS: f32 = 10000.0;
+/// Peak brightness assumed for HDR video, as mastering metadata is rarely set for captures
+const HDR_PEAK_NITS: f32 = 1000.0;
+/// Linear values below this, relative to SDR white, are left as they are
+const KNEE: f32 = 0.75;
+const ENCODE_LUT_SIZE: usize = 4096;
+
+/// Transfer functions of HDR video
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HdrTransfer {
+    /// Perceptual quantizer (SMPTE ST 2084), used by HDR10
+    Pq,
+    /// Hybrid log-gamma (ARIB STD-B67)
+    Hlg,
+}
+
+impl HdrTransfer {
+    pub fn from_ffmpeg(transfer: color::TransferCharacteristic) -> Option<Self> {
+        match transfer {
+            color::TransferCharacteristic::SMPTE2084 => Some(Self::Pq),
+            color::TransferCharacteristic::ARIB_STD_B67 => Some(Self::Hlg),
+            _ => None,
+        }
+    }
+}
+
+/// How a video's colors are encoded
+#[derive(Debug, Clone)]
+pub struct VideoColor {
+    pub primaries: color::Primaries,
+    pub transfer: color::TransferCharacteristic,
+    pub bit_depth: u32,
+}
+
+impl VideoColor {
+    pub fn hdr(&self) -> Option<HdrTransfer> {
+        HdrTransfer::from_ffmpeg(self.transfer)
+    }
+
+    /// eg. "BT2020 / SMPTE2084"
+    pub fn name(&self) -> String {
+        format!("{:?} / {:?}", self.primaries, self.transfer)
+    }
+}
+
+/// Reads the color encoding of the best video stream in a file
+pub fn probe_color(path: &Path) -> Option<VideoColor> {
+    let input = ffmpeg::format::input(&path).ok()?;
+    let stream = input.streams().best(ffmpeg::media::Type::Video)?;
+    let decoder = ffmpeg::codec::context::Context::from_parameters(stream.parameters())
+        .and_then(|c| c.decoder().video())
+        .ok()?;
+
+    Some(VideoColor {
+        primaries: decoder.color_primaries(),
+        transfer: decoder.color_transfer_characteristic(),
+        bit_depth: bit_depth(decoder.format()),
+    })
+}
+
+fn bit_depth(format: Pixel) -> u32 {
+    unsafe {
+        let descriptor = av_pix_fmt_desc_get(AVPixelFormat::from(format));
+        if descriptor.is_null() {
+            return 8;
+        }
+        (*descriptor).comp[0].depth as u32
+    }
+}
+
+/// Maps HDR frames to SDR so they don't look washed out once composited,
+/// keeping colors up to SDR white as they are and rolling off highlights above it
+pub struct Tonemapper {
+    transfer: HdrTransfer,
+    /// Linear light for each 16 bit code value, relative to SDR white for PQ
+    /// and scene light from 0 to 1 for HLG
+    linearize: Vec<f32>,
+    /// sRGB encoded values for linear light from 0 to 1
+    encode: Vec<u8>,
+}
+
+impl Tonemapper {
+    pub fn new(transfer: HdrTransfer) -> Self {
+        let linearize = (0..=u16::MAX)
+            .map(|code| {
+                let e = code as f32 / u16::MAX as f32;
+                match transfer {
+                    HdrTransfer::Pq => pq_eotf(e) / SDR_WHITE_NITS,
+                    HdrTransfer::Hlg => hlg_inverse_oetf(e),
+                }
+            })
+            .collect();
+
+        let encode = (0..ENCODE_LUT_SIZE)
+            .map(|i| {
+                let linear = i as f32 / (ENCODE_LUT_SIZE - 1) as f32;
+                (srgb_oetf(linear) * 255.0).round() as u8
+            })
+            .collect();
+
+        Self {
+            transfer,
+            linearize,
+            encode,
+        }
+    }
+
+    /// Converts a BT.2020 RGBA64LE frame to an sRGB RGBA frame without row padding
+    pub fn apply(&self, data: &[u8], width: usize, height: usize, stride: usize) -> Vec<u8> {
+        let mut output = Vec::with_capacity(width * height * 4);
+
+        for row in data.chunks(stride).take(height) {
+            for pixel in row[..width * 8].chunks_exact(8) {
+                let channel = |i: usize| {
+                    self.linearize[u16::from_le_bytes([pixel[i * 2], pixel[i * 2 + 1]]) as usize]
+                };
+                let mut rgb = [channel(0), channel(1), channel(2)];
+
+                if self.transfer == HdrTransfer::Hlg {
+                    rgb = hlg_ootf(rgb);
+                }
+
+                for value in tonemap(bt2020_to_bt709(rgb)) {
+                    let i = (value.clamp(0.0, 1.0) * (ENCODE_LUT_SIZE - 1) as f32) as usize;
+                    output.push(self.encode[i]);
+                }
+                output.push((u16::from_le_bytes([pixel[6], pixel[7]]) >> 8) as u8);
+            }
+        }
+
+        output
+    }
+}
+
+/// Absolute luminance in nits of a PQ encoded value
+fn pq_eotf(e: f32) -> f32 {
+    const M1: f32 = 2610.0 / 16384.0;
+    const M2: f32 = 2523.0 / 4096.0 * 128.0;
+    const C1: f32 = 3424.0 / 4096.0;
+    const C2: f32 = 2413.0 / 4096.0 * 32.0;
+    const C3: f32 = 2392.0 / 4096.0 * 32.0;
+
+    let p = e.powf(1.0 / M2);
+    ((p - C1).max(0.0) / (C2 - C3 * p)).powf(1.0 / M1) * PQ_MAX_NITS
+}
+
+/// Scene light from 0 to 1 of an HLG encoded value
+fn hlg_inverse_oetf(e: f32) -> f32 {
+    const A: f32 = 0.178_832_77;
+    const B: f32 = 0.284_668_92;
+    const C: f32 = 0.559_910_73;
+
+    if e <= 0.5 {
+        e * e / 3.0
+    } else {
+        (((e - C) / A).exp() + B) / 12.0
+    }
+}
+
+/// Displays HLG scene light as it would be on a `HDR_PEAK_NITS` display, relative to SDR white
+fn hlg_ootf(rgb: [f32; 3]) -> [f32; 3] {
+    let gamma = 1.2 + 0.42 * (HDR_PEAK_NITS / 1000.0).log10();
+    let luminance = 0.2627 * rgb[0] + 0.6780 * rgb[1] + 0.0593 * rgb[2];
+    let scale = HDR_PEAK_NITS * luminance.max(0.0).powf(gamma - 1.0) / SDR_WHITE_NITS;
+    rgb.map(|c| c * scale)
+}
+
+fn bt2020_to_bt709([r, g, b]: [f32; 3]) -> [f32; 3] {
+    [
+        1.6605 * r - 0.5876 * g - 0.0728 * b,
+        -0.1246 * r + 1.1329 * g - 0.0083 * b,
+        -0.0182 * r - 0.1006 * g + 1.1187 * b,
+    ]
+}
+
+/// Compresses everything above `KNEE` into the headroom left below SDR white,
+/// scaling the channels together so highlights keep their hue
+fn tonemap(rgb: [f32; 3]) -> [f32; 3] {
+    let max = rgb[0].max(rgb[1]).max(rgb[2]);
+    if max <= KNEE {
+        return rgb;
+    }
+
+    // Extended Reinhard over the range above the knee, reaching 1 at the peak
+    let peak = (HDR_PEAK_NITS / SDR_WHITE_NITS - KNEE) / (1.0 - KNEE);
+    let x = (max - KNEE) / (1.0 - KNEE);
+    let mapped = KNEE + (1.0 - KNEE) * x * (1.0 + x / (peak * peak)) / (1.0 + x);
+
+    rgb.map(|c| c * mapped / max)
+}
+
+fn srgb_oetf(linear: f32) -> f32 {
+    if linear <= 0.003_130_8 {
+        linear * 12.92
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(r: u16, g: u16, b: u16) -> Vec<u8> {
+        [r, g, b, u16::MAX]
+            .iter()
+            .flat_map(|c| c.to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn pq_reference_white_is_sdr_white() {
+        // 203 nits is encoded as roughly 58% in PQ
+        let white = pq_eotf(0.5806) / SDR_WHITE_NITS;
+        assert!((white - 1.0).abs() < 0.01, "{white}");
+    }
+
+    #[test]
+    fn keeps_shadows_and_rolls_off_highlights() {
+        let tonemapper = Tonemapper::new(HdrTransfer::Pq);
+
+        let black = tonemapper.apply(&pixel(0, 0, 0), 1, 1, 8);
+        assert_eq!(black, [0, 0, 0, 255]);
+
+        let peak = tonemapper.apply(&pixel(u16::MAX, u16::MAX, u16::MAX), 1, 1, 8);
+        assert_eq!(peak, [255, 255, 255, 255]);
+
+        // A 1000 nit highlight is brighter than reference white without clipping before it
+        let white = (0.5806 * u16::MAX as f32) as u16;
+        let highlight = (0.7518 * u16::MAX as f32) as u16;
+        let white = tonemapper.apply(&pixel(white, white, white), 1, 1, 8)[0];
+        let highlight = tonemapper.apply(&pixel(highlight, highlight, highlight), 1, 1, 8)[0];
+        assert!(white < highlight, "{white} {highlight}");
+        assert!(white > 200, "{white}");
+    }
+
+    #[test]
+    fn tonemap_is_continuous_at_the_knee() {
+        let below = tonemap([KNEE - 0.0001; 3])[0];
+        let above = tonemap([KNEE + 0.0001; 3])[0];
+        assert!((above - below).abs() < 0.001);
+    }
+}
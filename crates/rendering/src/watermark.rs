@@ -0,0 +1,90 @@
+use cap_project::{Corner, WatermarkConfiguration};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+use wgpu::CommandEncoder;
+
+use crate::{
+    create_rgba_texture, draw_with_cursor_pipeline, CursorUniforms, ProjectUniforms,
+    RenderVideoConstants,
+};
+
+/// Textures are created on first use, as the watermark can change while editing.
+/// The opacity is applied when drawing so changing it doesn't create another texture.
+#[derive(Default)]
+pub struct WatermarkTextures {
+    textures: Mutex<HashMap<PathBuf, Option<Arc<wgpu::Texture>>>>,
+}
+
+impl WatermarkTextures {
+    fn texture(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        config: &WatermarkConfiguration,
+    ) -> Option<Arc<wgpu::Texture>> {
+        let mut textures = self.textures.lock().unwrap();
+
+        textures
+            .entry(config.path.clone())
+            .or_insert_with(|| {
+                let image = match image::open(&config.path) {
+                    Ok(image) => image.into_rgba8(),
+                    Err(e) => {
+                        tracing::warn!("Failed to load watermark image {:?}: {}", config.path, e);
+                        return None;
+                    }
+                };
+
+                Some(Arc::new(create_rgba_texture(
+                    device,
+                    queue,
+                    "Watermark Texture",
+                    &image,
+                )))
+            })
+            .clone()
+    }
+
+    pub fn draw(
+        &self,
+        constants: &RenderVideoConstants,
+        uniforms: &ProjectUniforms,
+        encoder: &mut CommandEncoder,
+        view: &wgpu::TextureView,
+    ) {
+        let Some(config) = &uniforms.project.watermark else {
+            return;
+        };
+
+        let Some(texture) = self.texture(&constants.device, &constants.queue, config) else {
+            return;
+        };
+
+        let (output_width, output_height) =
+            (uniforms.output_size.0 as f32, uniforms.output_size.1 as f32);
+        let width = output_width * config.scale.clamp(0.0, 1.0);
+        let height = width * texture.height() as f32 / texture.width() as f32;
+        let margin = output_height * config.margin;
+
+        let x = match config.position {
+            Corner::TopLeft | Corner::BottomLeft => margin + width / 2.0,
+            Corner::TopRight | Corner::BottomRight => output_width - margin - width / 2.0,
+        };
+        let y = match config.position {
+            Corner::TopLeft | Corner::TopRight => margin + height / 2.0,
+            Corner::BottomLeft | Corner::BottomRight => output_height - margin - height / 2.0,
+        };
+
+        let watermark_uniforms = CursorUniforms::overlay(
+            uniforms,
+            [x, y],
+            [width, height],
+            config.opacity.clamp(0.0, 1.0),
+        );
+
+        draw_with_cursor_pipeline(constants, watermark_uniforms, &texture, encoder, view);
+    }
+}
@@ -57,13 +57,13 @@ pub fn create_channel_named_pipe(
                 .create(false)
                 .truncate(true)
                 .open(&path)?;
-            println!("video pipe opened");
+            tracing::debug!("video pipe opened");
 
             while let Some(bytes) = rx.recv().await {
                 file.write_all(&bytes)?;
             }
 
-            println!("done writing to video pipe");
+            tracing::debug!("done writing to video pipe");
             Ok::<(), std::io::Error>(())
         });
 
@@ -85,7 +85,7 @@ pub fn create_channel_named_pipe(
 
         tokio::spawn({
             async move {
-                println!("video pipe opened");
+                tracing::debug!("video pipe opened");
 
                 server.connect().await.unwrap();
 
@@ -93,7 +93,7 @@ pub fn create_channel_named_pipe(
                     server.write_all(&bytes).await.unwrap();
                 }
 
-                println!("done writing to video pipe");
+                tracing::debug!("done writing to video pipe");
             }
         });
 